@@ -1,13 +1,14 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+use std::collections::VecDeque;
 use std::io::Write;
 use std::sync::{Arc, Condvar, Mutex};
 
 use anyhow::ensure;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::zk::Matches;
+use sov_rollup_interface::zk::{Matches, Zkvm};
 
 /// A mock commitment to a particular zkVM program.
 #[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -94,6 +95,7 @@ impl Notifier {
 #[derive(Clone, Default)]
 pub struct MockZkvm {
     worker_thread_notifier: Notifier,
+    hints: Vec<Vec<u8>>,
 }
 
 impl MockZkvm {
@@ -122,6 +124,14 @@ impl sov_rollup_interface::zk::Zkvm for MockZkvm {
         Ok(proof.log)
     }
 
+    fn verify_and_deserialize_output<T: serde::de::DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+
     fn verify_and_extract_output<
         Add: sov_rollup_interface::RollupAddress,
         Da: sov_rollup_interface::da::DaSpec,
@@ -130,28 +140,59 @@ impl sov_rollup_interface::zk::Zkvm for MockZkvm {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Add, Root>, Self::Error> {
-        let output = Self::verify(serialized_proof, code_commitment)?;
-        Ok(bincode::deserialize(output)?)
+        Self::verify_and_deserialize_output(serialized_proof, code_commitment)
     }
 }
 
 impl sov_rollup_interface::zk::ZkvmHost for MockZkvm {
     type Guest = MockZkGuest;
 
-    fn add_hint<T: Serialize>(&mut self, _item: T) {}
+    fn add_hint<T: Serialize>(&mut self, item: T) {
+        let hint = bincode::serialize(&item).expect("Mock hint serialization must succeed");
+        self.hints.push(hint);
+    }
 
     fn simulate_with_hints(&mut self) -> Self::Guest {
-        MockZkGuest {}
+        MockZkGuest::with_hints(std::mem::take(&mut self.hints))
     }
 
-    fn run(&mut self, _with_proof: bool) -> Result<sov_rollup_interface::zk::Proof, anyhow::Error> {
+    fn run(&mut self, with_proof: bool) -> Result<sov_rollup_interface::zk::Proof, anyhow::Error> {
         self.worker_thread_notifier.wait();
-        Ok(sov_rollup_interface::zk::Proof::Empty)
+        if with_proof {
+            let proof = MockProof {
+                program_id: MockCodeCommitment([0; 32]),
+                is_valid: true,
+                log: &[],
+            };
+            Ok(sov_rollup_interface::zk::Proof::Data(proof.encode_to_vec()))
+        } else {
+            Ok(sov_rollup_interface::zk::Proof::Empty)
+        }
     }
 }
 
-/// A mock implementing the Guest.
-pub struct MockZkGuest {}
+/// A mock implementing the Guest. Hints and outputs are serialized with `bincode`,
+/// and proofs are verified like [`MockZkvm`] does.
+#[derive(Default)]
+pub struct MockZkGuest {
+    hints: Mutex<VecDeque<Vec<u8>>>,
+    commits: Mutex<Vec<u8>>,
+}
+
+impl MockZkGuest {
+    /// Constructs a new guest with the provided serialized hints.
+    pub fn with_hints(hints: Vec<Vec<u8>>) -> Self {
+        Self {
+            hints: Mutex::new(hints.into()),
+            commits: Default::default(),
+        }
+    }
+
+    /// Returns the outputs committed by the guest so far.
+    pub fn committed_outputs(&self) -> Vec<u8> {
+        self.commits.lock().unwrap().clone()
+    }
+}
 
 impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     type CodeCommitment = MockCodeCommitment;
@@ -159,10 +200,17 @@ impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     type Error = anyhow::Error;
 
     fn verify<'a>(
-        _serialized_proof: &'a [u8],
-        _code_commitment: &Self::CodeCommitment,
+        serialized_proof: &'a [u8],
+        code_commitment: &Self::CodeCommitment,
     ) -> Result<&'a [u8], Self::Error> {
-        unimplemented!()
+        MockZkvm::verify(serialized_proof, code_commitment)
+    }
+
+    fn verify_and_deserialize_output<T: serde::de::DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error> {
+        MockZkvm::verify_and_deserialize_output(serialized_proof, code_commitment)
     }
 
     fn verify_and_extract_output<
@@ -170,20 +218,28 @@ impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
         Da: sov_rollup_interface::da::DaSpec,
         Root: Serialize + serde::de::DeserializeOwned,
     >(
-        _serialized_proof: &[u8],
-        _code_commitment: &Self::CodeCommitment,
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Add, Root>, Self::Error> {
-        unimplemented!()
+        MockZkvm::verify_and_extract_output::<Add, Da, Root>(serialized_proof, code_commitment)
     }
 }
 
 impl sov_rollup_interface::zk::ZkvmGuest for MockZkGuest {
     fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
-        unimplemented!()
+        let hint = self
+            .hints
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("The host did not provide enough hints");
+        bincode::deserialize(&hint).expect("Mock hint deserialization must succeed")
     }
 
-    fn commit<T: Serialize>(&self, _item: &T) {
-        unimplemented!()
+    fn commit<T: Serialize>(&self, item: &T) {
+        let mut commits = self.commits.lock().unwrap();
+        bincode::serialize_into(&mut *commits, item)
+            .expect("Mock output serialization must succeed");
     }
 }
 
@@ -124,6 +124,14 @@ impl Zkvm for Risc0Guest {
         todo!("Implement once risc0 supports recursion: https://github.com/Sovereign-Labs/sovereign-sdk/issues/633")
     }
 
+    fn verify_and_deserialize_output<T: DeserializeOwned>(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error> {
+        // Verifying proofs inside the guest requires recursion: issue #633
+        Err(anyhow::anyhow!("Proofs cannot be verified inside the risc0 guest until risc0 supports recursion: https://github.com/Sovereign-Labs/sovereign-sdk/issues/633"))
+    }
+
     fn verify_and_extract_output<
         Add: sov_rollup_interface::RollupAddress,
         Da: sov_rollup_interface::da::DaSpec,
@@ -108,6 +108,14 @@ impl<'host> Zkvm for Risc0Host<'host> {
         verify_from_slice(serialized_proof, code_commitment)
     }
 
+    fn verify_and_deserialize_output<T: DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
+
     fn verify_and_extract_output<
         Add: sov_rollup_interface::RollupAddress,
         Da: sov_rollup_interface::da::DaSpec,
@@ -116,8 +124,7 @@ impl<'host> Zkvm for Risc0Host<'host> {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Add, Root>, Self::Error> {
-        let output = Self::verify(serialized_proof, code_commitment)?;
-        Ok(risc0_zkvm::serde::from_slice(output)?)
+        Self::verify_and_deserialize_output(serialized_proof, code_commitment)
     }
 }
 
@@ -136,6 +143,14 @@ impl Zkvm for Risc0Verifier {
         verify_from_slice(serialized_proof, code_commitment)
     }
 
+    fn verify_and_deserialize_output<T: DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
+
     fn verify_and_extract_output<
         Add: sov_rollup_interface::RollupAddress,
         Da: sov_rollup_interface::da::DaSpec,
@@ -144,8 +159,7 @@ impl Zkvm for Risc0Verifier {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Add, Root>, Self::Error> {
-        let output = Self::verify(serialized_proof, code_commitment)?;
-        Ok(risc0_zkvm::serde::from_slice(output)?)
+        Self::verify_and_deserialize_output(serialized_proof, code_commitment)
     }
 }
 
@@ -12,11 +12,12 @@ use sov_mock_da::{MockBlock, MockBlockHeader};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Verifier;
 use sov_rng_da_service::{RngDaService, RngDaSpec};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::storage::StorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_stf_runner::{from_toml_path, RollupConfig};
 use tempfile::TempDir;
 
@@ -46,14 +47,15 @@ fn rollup_bench(_bench: &mut Criterion) {
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path,
     };
-    let storage_manager = sov_state::storage_manager::ProverStorageManager::new(storage_config)
-        .expect("Failed to initialize prover storage manager");
+    let mut storage_manager =
+        ProverStorageManager::<RngDaSpec, sov_state::DefaultStorageSpec>::new(storage_config)
+            .expect("Failed to initialize prover storage manager");
     let stf = StfBlueprint::<
         DefaultContext,
         RngDaSpec,
         Risc0Verifier,
         Runtime<DefaultContext, RngDaSpec>,
-        BasicKernel<DefaultContext>,
+        BasicKernel<DefaultContext, RngDaSpec>,
     >::new();
 
     let demo_genesis_config = get_genesis_config(&GenesisPaths::from_dir(
@@ -61,20 +63,14 @@ fn rollup_bench(_bench: &mut Criterion) {
     ))
     .unwrap();
 
-    let (mut current_root, _) =
-        stf.init_chain(storage_manager.get_native_storage(), demo_genesis_config);
-
     // data generation
     let mut blobs = vec![];
     let mut blocks = vec![];
     for height in start_height..end_height {
-        let num_bytes = height.to_le_bytes();
-        let mut barray = [0u8; 32];
-        barray[..num_bytes.len()].copy_from_slice(&num_bytes);
         let filtered_block = MockBlock {
             header: MockBlockHeader {
-                hash: barray.into(),
-                prev_hash: [0u8; 32].into(),
+                hash: height_to_hash(height + 1).into(),
+                prev_hash: height_to_hash(height).into(),
                 height,
             },
             validity_cond: Default::default(),
@@ -86,6 +82,18 @@ fn rollup_bench(_bench: &mut Criterion) {
         blobs.push(blob_txs.clone());
     }
 
+    let genesis_header = &blocks[0].header;
+    let (mut current_root, change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(genesis_header)
+            .unwrap(),
+        demo_genesis_config,
+    );
+    storage_manager
+        .save_change_set(genesis_header, change_set)
+        .unwrap();
+    storage_manager.finalize(genesis_header).unwrap();
+
     let mut height = 0u64;
     c.bench_function("rollup main loop", |b| {
         b.iter(|| {
@@ -94,13 +102,19 @@ fn rollup_bench(_bench: &mut Criterion) {
             let mut data_to_commit = SlotCommit::new(filtered_block.clone());
             let apply_block_result = stf.apply_slot(
                 &current_root,
-                storage_manager.get_native_storage(),
+                storage_manager
+                    .get_native_storage_on(&filtered_block.header)
+                    .unwrap(),
                 Default::default(),
                 &filtered_block.header,
                 &filtered_block.validity_cond,
                 &mut blobs[height as usize],
             );
             current_root = apply_block_result.state_root;
+            storage_manager
+                .save_change_set(&filtered_block.header, apply_block_result.change_set)
+                .unwrap();
+            storage_manager.finalize(&filtered_block.header).unwrap();
             for receipts in apply_block_result.batch_receipts {
                 data_to_commit.add_batch(receipts);
             }
@@ -111,5 +125,12 @@ fn rollup_bench(_bench: &mut Criterion) {
     });
 }
 
+fn height_to_hash(height: u64) -> [u8; 32] {
+    let num_bytes = height.to_le_bytes();
+    let mut barray = [0u8; 32];
+    barray[..num_bytes.len()].copy_from_slice(&num_bytes);
+    barray
+}
+
 criterion_group!(benches, rollup_bench);
 criterion_main!(benches);
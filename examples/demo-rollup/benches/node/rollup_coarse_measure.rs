@@ -16,11 +16,12 @@ use sov_mock_da::{MockBlock, MockBlockHeader};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::{StfBlueprint, TxEffect};
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Verifier;
 use sov_rng_da_service::{RngDaService, RngDaSpec};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::storage::StorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_stf_runner::{from_toml_path, RollupConfig};
 use tempfile::TempDir;
 
@@ -50,6 +51,13 @@ fn print_times(
     table.printstd();
 }
 
+fn height_to_hash(height: u64) -> [u8; 32] {
+    let num_bytes = height.to_le_bytes();
+    let mut barray = [0u8; 32];
+    barray[..num_bytes.len()].copy_from_slice(&num_bytes);
+    barray
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     let registry = Registry::new();
@@ -101,14 +109,15 @@ async fn main() -> Result<(), anyhow::Error> {
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path,
     };
-    let storage_manager = sov_state::storage_manager::ProverStorageManager::new(storage_config)
-        .expect("ProverStorageManager initialization has failed");
+    let mut storage_manager =
+        ProverStorageManager::<RngDaSpec, sov_state::DefaultStorageSpec>::new(storage_config)
+            .expect("ProverStorageManager initialization has failed");
     let stf = StfBlueprint::<
         DefaultContext,
         RngDaSpec,
         Risc0Verifier,
         Runtime<DefaultContext, RngDaSpec>,
-        BasicKernel<DefaultContext>,
+        BasicKernel<DefaultContext, RngDaSpec>,
     >::new();
 
     let demo_genesis_config = get_genesis_config(&GenesisPaths::from_dir(
@@ -116,20 +125,14 @@ async fn main() -> Result<(), anyhow::Error> {
     ))
     .unwrap();
 
-    let (mut current_root, _) =
-        stf.init_chain(storage_manager.get_native_storage(), demo_genesis_config);
-
     // data generation
     let mut blobs = vec![];
     let mut blocks = vec![];
     for height in 0..=end_height {
-        let num_bytes = height.to_le_bytes();
-        let mut barray = [0u8; 32];
-        barray[..num_bytes.len()].copy_from_slice(&num_bytes);
         let filtered_block = MockBlock {
             header: MockBlockHeader {
-                prev_hash: [0u8; 32].into(),
-                hash: barray.into(),
+                prev_hash: height_to_hash(height).into(),
+                hash: height_to_hash(height + 1).into(),
                 height,
             },
             validity_cond: Default::default(),
@@ -143,16 +146,33 @@ async fn main() -> Result<(), anyhow::Error> {
 
     // Setup. Block 0 has a single txn that creates the token. Exclude from timers
     let filtered_block = &blocks[0usize];
+    let (mut current_root, change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(&filtered_block.header)
+            .unwrap(),
+        demo_genesis_config,
+    );
+    storage_manager
+        .save_change_set(&filtered_block.header, change_set)
+        .unwrap();
+    storage_manager.finalize(&filtered_block.header).unwrap();
+
     let mut data_to_commit = SlotCommit::new(filtered_block.clone());
     let apply_block_results = stf.apply_slot(
         &current_root,
-        storage_manager.get_native_storage(),
+        storage_manager
+            .get_native_storage_on(&filtered_block.header)
+            .unwrap(),
         Default::default(),
         &filtered_block.header,
         &filtered_block.validity_cond,
         &mut blobs[0usize],
     );
     current_root = apply_block_results.state_root;
+    storage_manager
+        .save_change_set(&filtered_block.header, apply_block_results.change_set)
+        .unwrap();
+    storage_manager.finalize(&filtered_block.header).unwrap();
     data_to_commit.add_batch(apply_block_results.batch_receipts[0].clone());
 
     ledger_db.commit_slot(data_to_commit).unwrap();
@@ -169,19 +189,25 @@ async fn main() -> Result<(), anyhow::Error> {
 
         let apply_block_results = stf.apply_slot(
             &current_root,
-            storage_manager.get_native_storage(),
+            storage_manager
+                .get_native_storage_on(&filtered_block.header)
+                .unwrap(),
             Default::default(),
             &filtered_block.header,
             &filtered_block.validity_cond,
             &mut blobs[height as usize],
         );
         current_root = apply_block_results.state_root;
+        storage_manager
+            .save_change_set(&filtered_block.header, apply_block_results.change_set)
+            .unwrap();
+        storage_manager.finalize(&filtered_block.header).unwrap();
 
         apply_block_time += now.elapsed();
         h_apply_block.observe(now.elapsed().as_secs_f64());
         for receipt in apply_block_results.batch_receipts {
             for t in &receipt.tx_receipts {
-                if t.receipt == TxEffect::Successful {
+                if matches!(t.receipt, TxEffect::Successful { .. }) {
                     num_success_txns += 1
                 }
             }
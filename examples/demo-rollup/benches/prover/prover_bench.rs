@@ -23,12 +23,13 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::SlotData;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
 #[cfg(feature = "bench")]
 use sov_risc0_adapter::metrics::GLOBAL_HASHMAP;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::storage::StorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_rollup_interface::zk::ZkvmHost;
 use sov_stf_runner::{from_toml_path, RollupConfig};
 use tempfile::TempDir;
@@ -119,7 +120,13 @@ fn print_cycle_averages(metric_map: HashMap<String, (u64, u64)>) {
     table.printstd();
 }
 
-fn chain_stats(num_blocks: usize, num_blocks_with_txns: usize, num_txns: usize, num_blobs: usize) {
+fn chain_stats(
+    num_blocks: usize,
+    num_blocks_with_txns: usize,
+    num_txns: usize,
+    num_blobs: usize,
+    witness_bytes: usize,
+) {
     let mut table = Table::new();
     table.add_row(row!["Total blocks", num_blocks]);
     table.add_row(row!["Blocks with transactions", num_blocks_with_txns]);
@@ -129,6 +136,11 @@ fn chain_stats(num_blocks: usize, num_blocks_with_txns: usize, num_txns: usize,
         "Average number of transactions per block",
         ((num_txns as f64) / (num_blocks_with_txns as f64)) as u64
     ]);
+    table.add_row(row!["Total witness size (bytes)", witness_bytes]);
+    table.add_row(row![
+        "Average witness size per block (bytes)",
+        ((witness_bytes as f64) / (num_blocks as f64)) as u64
+    ]);
     table.printstd();
 }
 
@@ -149,6 +161,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let mut num_blobs = 0;
     let mut num_blocks_with_txns = 0;
     let mut num_total_transactions = 0;
+    let mut witness_bytes = 0;
 
     let temp_dir = TempDir::new().expect("Unable to create temporary directory");
     rollup_config.storage.path = PathBuf::from(temp_dir.path());
@@ -157,26 +170,36 @@ async fn main() -> Result<(), anyhow::Error> {
         path: rollup_config.storage.path,
     };
 
-    let storage_manager = sov_state::storage_manager::ProverStorageManager::new(storage_config)
-        .expect("ProverStorageManager initialization has failed");
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, sov_state::DefaultStorageSpec>::new(storage_config)
+            .expect("ProverStorageManager initialization has failed");
     let stf = StfBlueprint::<
         DefaultContext,
         MockDaSpec,
         Risc0Host,
         Runtime<DefaultContext, MockDaSpec>,
-        BasicKernel<DefaultContext>,
+        BasicKernel<DefaultContext, MockDaSpec>,
     >::new();
 
     let genesis_config = get_genesis_config(&GenesisPaths::from_dir(
         "../test-data/genesis/integration-tests",
     ))
     .unwrap();
-    println!("Starting from empty storage, initialization chain");
-    let (mut prev_state_root, _) =
-        stf.init_chain(storage_manager.get_native_storage(), genesis_config);
-
     let blocks = get_bench_blocks().await;
 
+    println!("Starting from empty storage, initialization chain");
+    let genesis_header = &blocks[0].header;
+    let (mut prev_state_root, change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(genesis_header)
+            .unwrap(),
+        genesis_config,
+    );
+    storage_manager
+        .save_change_set(genesis_header, change_set)
+        .unwrap();
+    storage_manager.finalize(genesis_header).unwrap();
+
     for height in 0..(blocks.len() as u64) {
         num_blocks += 1;
         let mut host = Risc0Host::new(MOCK_DA_ELF);
@@ -201,7 +224,9 @@ async fn main() -> Result<(), anyhow::Error> {
 
         let result = stf.apply_slot(
             &prev_state_root,
-            storage_manager.get_native_storage(),
+            storage_manager
+                .get_native_storage_on(&filtered_block.header)
+                .unwrap(),
             Default::default(),
             &filtered_block.header,
             &filtered_block.validity_condition(),
@@ -216,7 +241,14 @@ async fn main() -> Result<(), anyhow::Error> {
             }
         }
 
+        witness_bytes += bincode::serialize(&result.witness)
+            .expect("Witness serialization should succeed")
+            .len();
         host.add_hint(&result.witness);
+        storage_manager
+            .save_change_set(&filtered_block.header, result.change_set)
+            .unwrap();
+        storage_manager.finalize(&filtered_block.header).unwrap();
 
         println!("Skipping prover at block {height} to capture cycle counts\n");
         let _receipt = host
@@ -237,6 +269,7 @@ async fn main() -> Result<(), anyhow::Error> {
             num_blocks_with_txns,
             num_total_transactions,
             num_blobs,
+            witness_bytes,
         );
         println!("\nCycle Metrics\n");
         print_cycle_averages(metric_map);
@@ -0,0 +1,97 @@
+#[macro_use]
+extern crate prettytable;
+
+use std::time::Instant;
+
+use prettytable::Table;
+use sov_mock_da::{MockBlockHeader, MockDaSpec};
+use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
+use sov_modules_api::storage::HierarchicalStorageManager;
+use sov_modules_api::{Context, StateMap, StateMapAccessor, WorkingSet};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_state::{ArrayWitness, DefaultStorageSpec, Prefix, Storage, ZkStorage};
+use tempfile::TempDir;
+
+/// Numbers of keys read by a single slot.
+const NUM_READS: [u64; 3] = [100, 1000, 5000];
+
+/// Reads every key of `0..num_reads`, half of which are in the state, and overwrites it.
+fn read_and_update<C: Context>(
+    state_map: &StateMap<u64, u64>,
+    num_reads: u64,
+    working_set: &mut WorkingSet<C>,
+) {
+    for key in 0..num_reads {
+        let value = state_map.get(&key, working_set).unwrap_or_default();
+        state_map.set(&key, &(value + 1), working_set);
+    }
+}
+
+/// Runs a slot reading `num_reads` keys natively, then in the zk storage, and returns the size of
+/// its witness along with the time it took to verify it.
+fn measure(num_reads: u64) -> (usize, u128) {
+    let temp_dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: temp_dir.path().to_path_buf(),
+        })
+        .unwrap();
+    let storage = storage_manager
+        .get_native_storage_on(&MockBlockHeader::default())
+        .unwrap();
+    let state_map = StateMap::<u64, u64>::new(Prefix::new(vec![0]));
+
+    {
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        for key in (0..num_reads).step_by(2) {
+            state_map.set(&key, &key, &mut working_set);
+        }
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+    }
+
+    let witness: ArrayWitness = {
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        read_and_update(&state_map, num_reads, &mut working_set);
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+        witness
+    };
+    let witness_size = bincode::serialize(&witness).unwrap().len();
+
+    let storage = ZkStorage::<DefaultStorageSpec>::new();
+    let mut working_set: WorkingSet<ZkDefaultContext> =
+        WorkingSet::with_witness(storage.clone(), witness);
+    read_and_update(&state_map, num_reads, &mut working_set);
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+
+    let start = Instant::now();
+    storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("ZK validation should succeed");
+    (witness_size, start.elapsed().as_micros())
+}
+
+fn main() {
+    let mut table = Table::new();
+    table.add_row(row![
+        "Reads per slot",
+        "Witness size (bytes)",
+        "Bytes per read",
+        "ZK validation (us)"
+    ]);
+    for num_reads in NUM_READS {
+        let (witness_size, validation_time) = measure(num_reads);
+        table.add_row(row![
+            num_reads,
+            witness_size,
+            witness_size as u64 / num_reads,
+            validation_time
+        ]);
+    }
+    table.printstd();
+}
@@ -6,7 +6,7 @@ use demo_stf::runtime::RuntimeCall;
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, TxFee};
 use sov_modules_api::PrivateKey;
 use sov_nft_module::utils::{
     get_collection_address, get_create_collection_message, get_mint_nft_message,
@@ -43,6 +43,9 @@ pub fn build_transaction(
     Transaction::<DefaultContext>::new_signed_tx(
         signer,
         runtime_encoded_message.try_to_vec().unwrap(),
+        0,
+        None,
+        TxFee::zero(),
         nonce,
     )
 }
@@ -0,0 +1,93 @@
+//! Exports the state of a finalized slot into a snapshot, or bootstraps the storage of a new
+//! node from one, so that it does not have to replay every DA block since genesis.
+//! The rollup must not be running while either command runs.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use serde::Deserialize;
+use sov_db::ledger_db::LedgerDB;
+use sov_modules_api::rpc::state_version_after_slot;
+use sov_prover_storage_manager::snapshot::{export_snapshot, import_snapshot};
+use sov_state::DefaultStorageSpec;
+use sov_stf_runner::{from_toml_path, StorageConfig};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The path to the rollup config. Only its storage section is used.
+    #[arg(long, default_value = "mock_rollup_config.toml")]
+    rollup_config_path: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Exports the state right after a finalized slot into a snapshot directory.
+    Export {
+        /// The number of the slot to export. Defaults to the latest finalized slot.
+        #[arg(long)]
+        slot: Option<u64>,
+        /// The directory to write the snapshot to.
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Imports a snapshot into the empty storage of a new node.
+    Import {
+        /// The directory holding the snapshot.
+        #[arg(long)]
+        snapshot: PathBuf,
+    },
+}
+
+/// The part of the rollup config used by this binary, which does not depend on the DA layer.
+#[derive(Deserialize)]
+struct StorageOnlyConfig {
+    storage: StorageConfig,
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    let args = Args::parse();
+    let StorageOnlyConfig { storage } =
+        from_toml_path(&args.rollup_config_path).context("Failed to read rollup configuration")?;
+    let ledger_db = LedgerDB::with_path(&storage.path).context("Ledger DB failed to open")?;
+
+    match args.command {
+        Command::Export { slot, out } => {
+            let slot_number = match slot {
+                Some(slot_number) => slot_number,
+                None => {
+                    let (head_slot_number, _) =
+                        ledger_db.get_head_slot()?.context("The ledger is empty")?;
+                    head_slot_number.into()
+                }
+            };
+            let manifest = export_snapshot::<DefaultStorageSpec>(
+                &storage.path,
+                &ledger_db,
+                slot_number,
+                state_version_after_slot(slot_number),
+                &out,
+            )?;
+            println!(
+                "Exported the state after slot {} into {} state and {} accessory chunks in {}",
+                manifest.slot_number,
+                manifest.state_chunks.len(),
+                manifest.accessory_chunks.len(),
+                out.display()
+            );
+        }
+        Command::Import { snapshot } => {
+            let manifest =
+                import_snapshot::<DefaultStorageSpec>(&snapshot, &storage.path, &ledger_db)?;
+            println!(
+                "Imported the state after slot {}, the rollup resumes from the next slot",
+                manifest.slot_number
+            );
+        }
+    }
+    Ok(())
+}
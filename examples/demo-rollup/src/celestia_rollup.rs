@@ -6,11 +6,13 @@ use sov_celestia_adapter::{CelestiaConfig, CelestiaService};
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
 use sov_modules_api::Spec;
 use sov_modules_rollup_blueprint::{RollupBlueprint, WalletBlueprint};
+use sov_modules_stf_blueprint::kernels::base_fee::get_base_fee_rpc_methods;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
+use sov_risc0_adapter::Risc0MethodId;
 use sov_rollup_interface::zk::ZkvmHost;
-use sov_state::storage_manager::ProverStorageManager;
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
 use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
 
@@ -29,13 +31,13 @@ impl RollupBlueprint for CelestiaDemoRollup {
     type ZkContext = ZkDefaultContext;
     type NativeContext = DefaultContext;
 
-    type StorageManager = ProverStorageManager<DefaultStorageSpec>;
+    type StorageManager = ProverStorageManager<CelestiaSpec, DefaultStorageSpec>;
     type ZkRuntime = Runtime<Self::ZkContext, Self::DaSpec>;
 
     type NativeRuntime = Runtime<Self::NativeContext, Self::DaSpec>;
 
-    type NativeKernel = BasicKernel<Self::NativeContext>;
-    type ZkKernel = BasicKernel<Self::ZkContext>;
+    type NativeKernel = BasicKernel<Self::NativeContext, Self::DaSpec>;
+    type ZkKernel = BasicKernel<Self::ZkContext, Self::DaSpec>;
 
     type ProverService = ParallelProverService<
         <<Self::NativeContext as Spec>::Storage as Storage>::Root,
@@ -51,18 +53,24 @@ impl RollupBlueprint for CelestiaDemoRollup {
         >,
     >;
 
+    // The DA service is only used by the experimental rpc methods.
+    #[allow(unused_variables)]
     fn create_rpc_methods(
         &self,
         storage: &<Self::NativeContext as sov_modules_api::Spec>::Storage,
         ledger_db: &sov_db::ledger_db::LedgerDB,
         da_service: &Self::DaService,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error> {
-        #[allow(unused_mut)]
         let mut rpc_methods = sov_modules_rollup_blueprint::register_rpc::<
             Self::NativeRuntime,
             Self::NativeContext,
             Self::DaService,
-        >(storage, ledger_db, da_service)?;
+        >(storage, ledger_db)?;
+
+        // The base fee is maintained by the kernel, so it isn't part of the runtime rpc.
+        rpc_methods.merge(get_base_fee_rpc_methods::<Self::NativeContext>(
+            storage.clone(),
+        )?)?;
 
         #[cfg(feature = "experimental")]
         crate::eth::register_ethereum::<Self::DaService>(
@@ -81,7 +89,12 @@ impl RollupBlueprint for CelestiaDemoRollup {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
         };
-        ProverStorageManager::new(storage_config)
+        let mut storage_manager = ProverStorageManager::new(storage_config)?;
+        let pruning = &rollup_config.storage.pruning;
+        if let Some(state_pruning) = &pruning.state {
+            storage_manager.spawn_state_pruner(state_pruning.clone(), pruning.interval())?;
+        }
+        Ok(storage_manager)
     }
 
     async fn create_da_service(
@@ -100,7 +113,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
     async fn create_prover_service(
         &self,
         prover_config: RollupProverConfig,
-        _da_service: &Self::DaService,
+        da_service: &Self::DaService,
     ) -> Self::ProverService {
         let vm = Risc0Host::new(risc0::ROLLUP_ELF);
         let zk_stf = StfBlueprint::new();
@@ -113,11 +126,17 @@ impl RollupBlueprint for CelestiaDemoRollup {
         ParallelProverService::new_with_default_workers(
             vm,
             zk_stf,
+            da_service.clone(),
             da_verifier,
+            self.code_commitment(),
             prover_config,
             zk_storage,
         )
     }
+
+    fn code_commitment(&self) -> Risc0MethodId {
+        Risc0MethodId::new(risc0::ROLLUP_ID)
+    }
 }
 
 impl WalletBlueprint for CelestiaDemoRollup {}
@@ -1,6 +1,9 @@
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
+use sov_modules_api::macros::config_constant;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{AccessoryWorkingSet, Context, Spec, WorkingSet};
+use sov_modules_api::{
+    AccessoryWorkingSet, Context, GasMeter, GasUnit, Spec, StateAccessGasConfig, WorkingSet,
+};
 use sov_modules_stf_blueprint::SequencerOutcome;
 #[cfg(feature = "experimental")]
 use sov_rollup_interface::da::BlockHeaderTrait;
@@ -11,26 +14,91 @@ use tracing::info;
 
 use crate::runtime::Runtime;
 
+/// Gas charged for every read of a value from the state.
+#[config_constant]
+pub const GAS_STATE_READ: [u64; 2];
+
+/// Gas charged for every write or deletion of a value in the state.
+#[config_constant]
+pub const GAS_STATE_WRITE: [u64; 2];
+
 impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
     type Context = C;
 
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
+        base_fee: &C::GasUnit,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<<Self::Context as Spec>::Address> {
         // Before executing a transaction, retrieve the sender's address from the accounts module
         // and check the nonce
-        self.accounts.pre_dispatch_tx_hook(tx, working_set)
+        let sender = self
+            .accounts
+            .pre_dispatch_tx_hook(tx, base_fee, working_set)?;
+
+        // Then reserve the maximum fee, which funds the gas meter of the transaction.
+        // Gas is priced at the base fee of the slot, plus the priority fee offered by the sender.
+        let fee = tx.fee();
+        self.bank.reserve_gas(fee.max_fee, &sender, working_set)?;
+        let gas_price = base_fee.saturating_add(&fee.priority_fee);
+        working_set.set_gas_meter(
+            GasMeter::new(fee.max_fee, gas_price)
+                .with_gas_limit(fee.gas_limit.clone())
+                .with_state_access_config(StateAccessGasConfig {
+                    read: C::GasUnit::from_arbitrary_dimensions(&GAS_STATE_READ),
+                    write: C::GasUnit::from_arbitrary_dimensions(&GAS_STATE_WRITE),
+                }),
+        );
+
+        Ok(sender)
     }
 
     fn post_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
+        ctx: &C,
+        gas_meter: &GasMeter<C::GasUnit>,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         // After executing each transaction, update the nonce
-        self.accounts.post_dispatch_tx_hook(tx, working_set)
+        self.accounts
+            .post_dispatch_tx_hook(tx, ctx, gas_meter, working_set)?;
+
+        // Burn the base fee, pay the priority fee to the sequencer which included the transaction,
+        // and refund the rest. The priority fee is burned too if the sequencer is no longer registered.
+        // The funds consumed by running out of gas are burned as well.
+        let priority_fee = gas_meter
+            .gas_used()
+            .value(&tx.fee().priority_fee)
+            .min(gas_meter.consumed_funds());
+        let reward_address = Da::Address::try_from(ctx.sequencer())
+            .ok()
+            .and_then(|sequencer| {
+                self.sequencer_registry
+                    .get_sequencer_rollup_address(&sequencer, working_set)
+            });
+        self.bank.settle_gas(
+            gas_meter.consumed_funds() - priority_fee,
+            priority_fee,
+            gas_meter.remaining_funds(),
+            ctx.sender(),
+            reward_address.as_ref(),
+            working_set,
+        )
+    }
+
+    fn refund_tx_hook(
+        &self,
+        tx: &Transaction<Self::Context>,
+        ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        self.accounts.refund_tx_hook(tx, ctx, working_set)?;
+
+        // Nothing is paid for a transaction whose fee couldn't be settled.
+        self.bank
+            .settle_gas(0, 0, tx.fee().max_fee, ctx.sender(), None, working_set)
     }
 }
 
@@ -55,7 +123,7 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
     ) -> anyhow::Result<()> {
         match result {
             SequencerOutcome::Rewarded(_reward) => {
-                // TODO: Process reward here or above.
+                // The reward has already been paid by the post dispatch hook of each transaction.
                 <SequencerRegistry<C, Da> as ApplyBlobHooks<Da::BlobTransaction>>::end_blob_hook(
                     &self.sequencer_registry,
                     sov_sequencer_registry::SequencerOutcome::Completed,
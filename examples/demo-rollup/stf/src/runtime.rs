@@ -29,6 +29,9 @@
 //! `#[derive(MessageCodec)` adds deserialization capabilities to the `Runtime` (implements `decode_call` method).
 //! `Runtime::decode_call` accepts serialized call message and returns a type that implements the `DispatchCall` trait.
 //!  The `DispatchCall` implementation (derived by a macro) forwards the message to the appropriate module and executes its `call` method.
+//!
+//! `#[derive(Event)]` generates the `RuntimeEvent` enum, with one variant per module, and implements
+//! `RuntimeEventProcessor::decode_event`, which decodes the events emitted by the modules into it.
 
 #![allow(unused_doc_comments)]
 #[cfg(feature = "native")]
@@ -50,7 +53,7 @@ use sov_modules_api::macros::{expose_rpc, CliWallet};
 use sov_modules_api::runtime::capabilities::{BlobRefOrOwned, BlobSelector};
 #[cfg(feature = "native")]
 use sov_modules_api::Spec;
-use sov_modules_api::{Context, DispatchCall, Genesis, MessageCodec};
+use sov_modules_api::{Context, DispatchCall, Event, Genesis, MessageCodec};
 #[cfg(feature = "native")]
 use sov_nft_module::{NonFungibleTokenRpcImpl, NonFungibleTokenRpcServer};
 use sov_rollup_interface::da::DaSpec;
@@ -64,7 +67,7 @@ use crate::genesis_config::GenesisPaths;
 
 /// The `demo-stf runtime`.
 #[cfg_attr(feature = "native", derive(CliWallet), expose_rpc)]
-#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[derive(Genesis, DispatchCall, Event, MessageCodec, DefaultRuntime)]
 #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
 #[cfg_attr(feature = "serde", serialization(serde::Serialize, serde::Deserialize))]
 pub struct Runtime<C: Context, Da: DaSpec> {
@@ -101,8 +104,11 @@ where
     type GenesisPaths = GenesisPaths;
 
     #[cfg(feature = "native")]
-    fn rpc_methods(storage: <C as Spec>::Storage) -> jsonrpsee::RpcModule<()> {
-        get_rpc_methods::<C, Da>(storage)
+    fn rpc_methods(
+        storage: <C as Spec>::Storage,
+        slot_resolver: std::sync::Arc<dyn sov_modules_api::rpc::SlotResolver>,
+    ) -> jsonrpsee::RpcModule<()> {
+        get_rpc_methods::<C, Da>(storage, slot_resolver)
     }
 
     #[cfg(feature = "native")]
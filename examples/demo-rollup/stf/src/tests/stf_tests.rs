@@ -4,13 +4,17 @@ pub mod test {
     use sov_cli::wallet_state::PrivateKeyAndAddress;
     use sov_data_generators::bank_data::get_default_token_address;
     use sov_data_generators::{has_tx_events, new_test_blob_from_batch};
-    use sov_mock_da::{MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
+    use sov_mock_da::{MockAddress, MockBlob, MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
     use sov_modules_api::default_context::DefaultContext;
     use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::rpc::state_version_after_slot;
     use sov_modules_api::{Context, PrivateKey, WorkingSet};
     use sov_modules_stf_blueprint::{Batch, SequencerOutcome, StfBlueprint};
     use sov_rollup_interface::stf::StateTransitionFunction;
-    use sov_rollup_interface::storage::StorageManager;
+    use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
+    use sov_rollup_interface::zk::PROOF_BLOB_PREFIX;
+    use sov_state::storage::NativeStorage;
+    use sov_state::Storage;
 
     use crate::runtime::Runtime;
     use crate::tests::da_simulation::simulate_da;
@@ -22,13 +26,23 @@ pub mod test {
     fn test_demo_values_in_db() {
         let tempdir = tempfile::tempdir().unwrap();
         let path = tempdir.path();
-        let storage_manager = create_storage_manager_for_tests(path);
+        let mut storage_manager = create_storage_manager_for_tests(path);
 
         let config = get_genesis_config_for_tests();
         {
             let stf: StfBlueprintTest = StfBlueprint::new();
 
-            let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+            let genesis_block = MockBlock::default();
+            let (genesis_root, change_set) = stf.init_chain(
+                storage_manager
+                    .get_native_storage_on(&genesis_block.header)
+                    .unwrap(),
+                config,
+            );
+            storage_manager
+                .save_change_set(&genesis_block.header, change_set)
+                .unwrap();
+            storage_manager.finalize(&genesis_block.header).unwrap();
 
             let priv_key = read_private_key::<DefaultContext>().private_key;
             let txs = simulate_da(priv_key);
@@ -40,7 +54,7 @@ pub mod test {
 
             let result = stf.apply_slot(
                 &genesis_root,
-                storage_manager.get_native_storage(),
+                storage_manager.get_native_storage_on(&data.header).unwrap(),
                 Default::default(),
                 &data.header,
                 &data.validity_cond,
@@ -59,6 +73,11 @@ pub mod test {
             );
 
             assert!(has_tx_events(&apply_blob_outcome),);
+
+            storage_manager
+                .save_change_set(&data.header, result.change_set)
+                .unwrap();
+            storage_manager.finalize(&data.header).unwrap();
         }
 
         // Generate a new storage instance after dumping data to the db.
@@ -78,17 +97,88 @@ pub mod test {
         }
     }
 
+    /// Checks that the genesis state is the state after slot 0, and that every slot writes the
+    /// next version of the state, as historical queries assume.
+    #[test]
+    fn test_state_version_after_slot() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+        let stf: StfBlueprintTest = StfBlueprint::new();
+
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            get_genesis_config_for_tests(),
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
+
+        let txs = simulate_da(read_private_key::<DefaultContext>().private_key);
+        let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+        let mut blobs = [blob];
+        let data = MockBlock::default();
+        let result = stf.apply_slot(
+            &genesis_root,
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
+            Default::default(),
+            &data.header,
+            &data.validity_cond,
+            &mut blobs,
+        );
+        storage_manager
+            .save_change_set(&data.header, result.change_set)
+            .unwrap();
+        storage_manager.finalize(&data.header).unwrap();
+
+        let storage = storage_manager.get_native_storage();
+        assert_eq!(state_version_after_slot(1), storage.latest_version());
+        assert_eq!(
+            genesis_root,
+            storage.get_root_hash(state_version_after_slot(0)).unwrap()
+        );
+        assert_eq!(
+            result.state_root,
+            storage.get_root_hash(state_version_after_slot(1)).unwrap()
+        );
+
+        // The value set by the first slot isn't visible in the genesis state.
+        let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
+        let mut working_set =
+            WorkingSet::new(storage.at_version(state_version_after_slot(0)).unwrap());
+        let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
+        assert_eq!(resp, sov_value_setter::Response { value: None });
+
+        let mut working_set =
+            WorkingSet::new(storage.at_version(state_version_after_slot(1)).unwrap());
+        let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
+        assert_eq!(resp, sov_value_setter::Response { value: Some(33) });
+    }
+
     #[test]
     fn test_demo_values_in_cache() {
         let tempdir = tempfile::tempdir().unwrap();
         let path = tempdir.path();
-        let storage_manager = create_storage_manager_for_tests(path);
+        let mut storage_manager = create_storage_manager_for_tests(path);
 
         let stf: StfBlueprintTest = StfBlueprint::new();
 
         let config = get_genesis_config_for_tests();
 
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let private_key = read_private_key::<DefaultContext>().private_key;
         let txs = simulate_da(private_key);
@@ -99,7 +189,7 @@ pub mod test {
 
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -117,8 +207,9 @@ pub mod test {
 
         assert!(has_tx_events(&apply_blob_outcome),);
 
+        // The change set has not been saved, so the values are only visible in its own snapshot.
         let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
-        let mut working_set = WorkingSet::new(storage_manager.get_native_storage());
+        let mut working_set = WorkingSet::new(apply_block_result.change_set);
 
         let resp = runtime
             .bank
@@ -136,14 +227,24 @@ pub mod test {
     fn test_demo_values_not_in_db() {
         let tempdir = tempfile::tempdir().unwrap();
         let path = tempdir.path();
-        let storage_manager = create_storage_manager_for_tests(path);
+        let mut storage_manager = create_storage_manager_for_tests(path);
 
         let value_setter_admin_private_key = DefaultPrivateKey::generate();
 
         let config = get_genesis_config_for_tests();
         {
             let stf: StfBlueprintTest = StfBlueprint::new();
-            let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+            let genesis_block = MockBlock::default();
+            let (genesis_root, change_set) = stf.init_chain(
+                storage_manager
+                    .get_native_storage_on(&genesis_block.header)
+                    .unwrap(),
+                config,
+            );
+            storage_manager
+                .save_change_set(&genesis_block.header, change_set)
+                .unwrap();
+            storage_manager.finalize(&genesis_block.header).unwrap();
 
             let txs = simulate_da(value_setter_admin_private_key);
             let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
@@ -152,7 +253,7 @@ pub mod test {
 
             let apply_block_result = stf.apply_slot(
                 &genesis_root,
-                storage_manager.get_native_storage(),
+                storage_manager.get_native_storage_on(&data.header).unwrap(),
                 Default::default(),
                 &data.header,
                 &data.validity_cond,
@@ -169,7 +270,7 @@ pub mod test {
             );
         }
 
-        // Generate a new storage instance, values are missing because we didn't call `end_slot()`;
+        // Generate a new storage instance, values are missing because the change set was not saved;
         {
             let runtime = &mut Runtime::<C, MockDaSpec>::default();
             let storage = storage_manager.get_native_storage();
@@ -195,9 +296,19 @@ pub mod test {
         let mut config = get_genesis_config_for_tests();
         config.sequencer_registry.is_preferred_sequencer = false;
 
-        let storage_manager = create_storage_manager_for_tests(path);
+        let mut storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let some_sequencer: [u8; 32] = [121; 32];
 
@@ -209,7 +320,7 @@ pub mod test {
 
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -229,6 +340,67 @@ pub mod test {
         assert!(!has_tx_events(&apply_blob_outcome));
     }
 
+    #[test]
+    fn test_proof_blob_from_unknown_sender() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path();
+
+        let mut config = get_genesis_config_for_tests();
+        config.sequencer_registry.is_preferred_sequencer = false;
+
+        let mut storage_manager = create_storage_manager_for_tests(path);
+        let stf: StfBlueprintTest = StfBlueprint::new();
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
+
+        let proof_data = [PROOF_BLOB_PREFIX, b"proof"].concat();
+        let some_sequencer: [u8; 32] = [121; 32];
+        let mut blobs = [
+            MockBlob::new(
+                proof_data.clone(),
+                MockAddress::from(some_sequencer),
+                [1; 32],
+            ),
+            MockBlob::new(
+                proof_data,
+                MockAddress::from(MOCK_SEQUENCER_DA_ADDRESS),
+                [2; 32],
+            ),
+        ];
+        let data = MockBlock::default();
+
+        let apply_block_result = stf.apply_slot(
+            &genesis_root,
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
+            Default::default(),
+            &data.header,
+            &data.validity_cond,
+            &mut blobs,
+        );
+
+        assert_eq!(2, apply_block_result.batch_receipts.len());
+        assert_eq!(
+            SequencerOutcome::Ignored,
+            apply_block_result.batch_receipts[0].inner,
+            "Proof should have been skipped due to unknown sender"
+        );
+        // Proofs of registered sequencers are accepted, but not executed.
+        assert_eq!(
+            SequencerOutcome::Rewarded(0),
+            apply_block_result.batch_receipts[1].inner
+        );
+        assert!(apply_block_result.batch_receipts[1].tx_receipts.is_empty());
+    }
+
     fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
         let token_deployer_data =
             std::fs::read_to_string("../../test-data/keys/token_deployer_private_key.json")
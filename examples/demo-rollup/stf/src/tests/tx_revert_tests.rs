@@ -7,8 +7,7 @@ use sov_modules_api::{PrivateKey, WorkingSet};
 use sov_modules_stf_blueprint::{Batch, SequencerOutcome, SlashingReason, StfBlueprint, TxEffect};
 use sov_rollup_interface::da::BlobReaderTrait;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::storage::StorageManager;
-use sov_state::ProverStorage;
+use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
 
 use super::{create_storage_manager_for_tests, get_genesis_config_for_tests, RuntimeTest};
 use crate::runtime::Runtime;
@@ -28,13 +27,21 @@ fn test_tx_revert() {
 
     let config = get_genesis_config_for_tests();
     let sequencer_rollup_address = config.sequencer_registry.seq_rollup_address;
+    let mut storage_manager = create_storage_manager_for_tests(path);
 
     {
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
-        // TODO: Maybe complete with actual block data
-        let _data = MockBlock::default();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let txs = simulate_da_with_revert_msg();
         let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
@@ -43,7 +50,7 @@ fn test_tx_revert() {
 
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -64,15 +71,20 @@ fn test_tx_revert() {
         // create 1000 tokens
         // transfer 15 tokens
         // transfer 5000 tokens // this should be reverted
-        assert_eq!(txn_receipts[0].receipt, TxEffect::Successful);
-        assert_eq!(txn_receipts[1].receipt, TxEffect::Successful);
-        assert_eq!(txn_receipts[2].receipt, TxEffect::Reverted);
+        assert_eq!(txn_receipts[0].receipt, TxEffect::Successful { fee: 0 });
+        assert_eq!(txn_receipts[1].receipt, TxEffect::Successful { fee: 0 });
+        assert_eq!(txn_receipts[2].receipt, TxEffect::Reverted { fee: 0 });
+
+        storage_manager
+            .save_change_set(&data.header, apply_block_result.change_set)
+            .unwrap();
+        storage_manager.finalize(&data.header).unwrap();
     }
 
     // Checks
     {
         let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
-        let storage = ProverStorage::with_path(path).unwrap();
+        let storage = storage_manager.get_native_storage();
         let mut working_set = WorkingSet::new(storage);
         let resp = runtime
             .bank
@@ -103,12 +115,20 @@ fn test_nonce_incremented_on_revert() {
     let path = tempdir.path();
 
     let config = get_genesis_config_for_tests();
+    let mut storage_manager = create_storage_manager_for_tests(path);
     {
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
-        // TODO: Maybe complete with actual block data
-        let _data = MockBlock::default();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let txs = simulate_da_with_revert_msg();
         let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
@@ -117,7 +137,7 @@ fn test_nonce_incremented_on_revert() {
 
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -138,9 +158,14 @@ fn test_nonce_incremented_on_revert() {
         // create 1000 tokens
         // transfer 15 tokens
         // transfer 5000 tokens // this should be reverted
-        assert_eq!(txn_receipts[0].receipt, TxEffect::Successful);
-        assert_eq!(txn_receipts[1].receipt, TxEffect::Successful);
-        assert_eq!(txn_receipts[2].receipt, TxEffect::Reverted);
+        assert_eq!(txn_receipts[0].receipt, TxEffect::Successful { fee: 0 });
+        assert_eq!(txn_receipts[1].receipt, TxEffect::Successful { fee: 0 });
+        assert_eq!(txn_receipts[2].receipt, TxEffect::Reverted { fee: 0 });
+
+        storage_manager
+            .save_change_set(&data.header, apply_block_result.change_set)
+            .unwrap();
+        storage_manager.finalize(&data.header).unwrap();
     }
 
     // with 3 transactions, the final nonce should be 3
@@ -149,7 +174,7 @@ fn test_nonce_incremented_on_revert() {
     // 2 -> 3
     {
         let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
-        let storage = ProverStorage::with_path(path).unwrap();
+        let storage = storage_manager.get_native_storage();
         let mut working_set = WorkingSet::new(storage);
         let nonce = match runtime
             .accounts
@@ -171,13 +196,21 @@ fn test_tx_bad_sig() {
     let path = tempdir.path();
 
     let config = get_genesis_config_for_tests();
+    let mut storage_manager = create_storage_manager_for_tests(path);
 
     {
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
-        // TODO: Maybe complete with actual block data
-        let _data = MockBlock::default();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let txs = simulate_da_with_bad_sig();
 
@@ -188,7 +221,7 @@ fn test_tx_bad_sig() {
         let data = MockBlock::default();
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -218,13 +251,21 @@ fn test_tx_bad_nonce() {
     let path = tempdir.path();
 
     let config = get_genesis_config_for_tests();
+    let mut storage_manager = create_storage_manager_for_tests(path);
 
     {
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
-        // TODO: Maybe complete with actual block data
-        let _data = MockBlock::default();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let txs = simulate_da_with_bad_nonce();
 
@@ -234,7 +275,7 @@ fn test_tx_bad_nonce() {
         let data = MockBlock::default();
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -244,7 +285,7 @@ fn test_tx_bad_nonce() {
         assert_eq!(1, apply_block_result.batch_receipts.len());
         let tx_receipts = apply_block_result.batch_receipts[0].tx_receipts.clone();
         // Bad nonce means that the transaction has to be reverted
-        assert_eq!(tx_receipts[0].receipt, TxEffect::Reverted);
+        assert_eq!(tx_receipts[0].receipt, TxEffect::Reverted { fee: 0 });
 
         // We don't expect the sequencer to be slashed for a bad nonce
         // The reason for this is that in cases such as based sequencing, the sequencer can
@@ -265,11 +306,21 @@ fn test_tx_bad_serialization() {
 
     let config = get_genesis_config_for_tests();
     let sequencer_rollup_address = config.sequencer_registry.seq_rollup_address;
+    let mut storage_manager = create_storage_manager_for_tests(path);
     let (genesis_root, sequencer_balance_before) = {
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
         let runtime: RuntimeTest = Runtime::default();
-        let (genesis_root, _) = stf.init_chain(storage_manager.get_native_storage(), config);
+        let genesis_block = MockBlock::default();
+        let (genesis_root, change_set) = stf.init_chain(
+            storage_manager
+                .get_native_storage_on(&genesis_block.header)
+                .unwrap(),
+            config,
+        );
+        storage_manager
+            .save_change_set(&genesis_block.header, change_set)
+            .unwrap();
+        storage_manager.finalize(&genesis_block.header).unwrap();
 
         let mut working_set = WorkingSet::new(storage_manager.get_native_storage());
         let coins = runtime
@@ -289,10 +340,6 @@ fn test_tx_bad_serialization() {
     };
 
     {
-        // TODO: Maybe complete with actual block data
-        let _data = MockBlock::default();
-
-        let storage_manager = create_storage_manager_for_tests(path);
         let stf: StfBlueprintTest = StfBlueprint::new();
 
         let txs = simulate_da_with_bad_serialization();
@@ -303,7 +350,7 @@ fn test_tx_bad_serialization() {
         let data = MockBlock::default();
         let apply_block_result = stf.apply_slot(
             &genesis_root,
-            storage_manager.get_native_storage(),
+            storage_manager.get_native_storage_on(&data.header).unwrap(),
             Default::default(),
             &data.header,
             &data.validity_cond,
@@ -324,11 +371,16 @@ fn test_tx_bad_serialization() {
 
         // The batch receipt contains no events.
         assert!(!has_tx_events(&apply_blob_outcome));
+
+        storage_manager
+            .save_change_set(&data.header, apply_block_result.change_set)
+            .unwrap();
+        storage_manager.finalize(&data.header).unwrap();
     }
 
     {
         let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
-        let storage = ProverStorage::with_path(path).unwrap();
+        let storage = storage_manager.get_native_storage();
         let mut working_set = WorkingSet::new(storage);
 
         // Sequencer is not in the list of allowed sequencers
@@ -8,7 +8,8 @@ use jsonrpsee::rpc_params;
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::rpc::SlotIdentifier;
+use sov_modules_api::transaction::{Transaction, TxFee};
 use sov_modules_api::{PrivateKey, Spec};
 use sov_sequencer::utils::SimpleClient;
 use sov_stf_runner::RollupProverConfig;
@@ -60,7 +61,14 @@ async fn send_test_create_token_tx(rpc_address: SocketAddr) -> Result<(), anyhow
         minter_address: user_address,
         authorized_minters: vec![],
     });
-    let tx = Transaction::<DefaultContext>::new_signed_tx(&key, msg.try_to_vec().unwrap(), 0);
+    let tx = Transaction::<DefaultContext>::new_signed_tx(
+        &key,
+        msg.try_to_vec().unwrap(),
+        0,
+        None,
+        TxFee::zero(),
+        0,
+    );
 
     let port = rpc_address.port();
     let client = SimpleClient::new("localhost", port).await?;
@@ -86,5 +94,15 @@ async fn send_test_create_token_tx(rpc_address: SocketAddr) -> Result<(), anyhow
     )
     .await?;
     assert_eq!(balance_response.amount.unwrap_or_default(), 1000);
+
+    // The token didn't exist yet in the genesis state.
+    let genesis_balance_response = sov_bank::BankRpcClient::<DefaultContext>::balance_of_at_slot(
+        client.http(),
+        user_address,
+        token_address,
+        SlotIdentifier::Number(0),
+    )
+    .await?;
+    assert_eq!(genesis_balance_response.amount, None);
     Ok(())
 }
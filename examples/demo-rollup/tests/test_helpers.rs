@@ -18,6 +18,7 @@ pub async fn start_rollup(
     let rollup_config = RollupConfig {
         storage: StorageConfig {
             path: temp_path.to_path_buf(),
+            pruning: Default::default(),
         },
         runner: RunnerConfig {
             start_height: 0,
@@ -25,10 +26,12 @@ pub async fn start_rollup(
                 bind_host: "127.0.0.1".into(),
                 bind_port: 0,
             },
+            max_proof_backlog: 16,
         },
         da: MockDaConfig {
             sender_address: MockAddress::from([0; 32]),
         },
+        sequencer: Default::default(),
     };
 
     let mock_demo_rollup = MockDemoRollup {};
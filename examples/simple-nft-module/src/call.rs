@@ -35,6 +35,39 @@ pub enum CallMessage<C: Context> {
     },
 }
 
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize),
+    derive(serde::Deserialize),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "Event")
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+/// An event emitted by the NFT module.
+pub enum Event<C: Context> {
+    /// A new token was minted
+    Minted {
+        /// The id of the new token
+        id: u64,
+        /// The owner of the new token
+        owner: C::Address,
+    },
+    /// A token was transferred to a new owner
+    Transferred {
+        /// The id of the transferred token
+        id: u64,
+        /// The previous owner of the token
+        from: C::Address,
+        /// The new owner of the token
+        to: C::Address,
+    },
+    /// A token was burned
+    Burned {
+        /// The id of the burned token
+        id: u64,
+    },
+}
+
 impl<C: Context> NonFungibleToken<C> {
     pub(crate) fn mint(
         &self,
@@ -48,7 +81,13 @@ impl<C: Context> NonFungibleToken<C> {
 
         self.owners.set(&id, context.sender(), working_set);
 
-        working_set.add_event("NFT mint", &format!("A token with id {id} was minted"));
+        working_set.emit_event(
+            self,
+            Event::Minted {
+                id,
+                owner: context.sender().clone(),
+            },
+        );
         Ok(CallResponse::default())
     }
 
@@ -69,9 +108,13 @@ impl<C: Context> NonFungibleToken<C> {
             bail!("Only token owner can transfer token");
         }
         self.owners.set(&id, &to, working_set);
-        working_set.add_event(
-            "NFT transfer",
-            &format!("A token with id {id} was transferred"),
+        working_set.emit_event(
+            self,
+            Event::Transferred {
+                id,
+                from: token_owner,
+                to,
+            },
         );
         Ok(CallResponse::default())
     }
@@ -93,7 +136,7 @@ impl<C: Context> NonFungibleToken<C> {
         }
         self.owners.remove(&id, working_set);
 
-        working_set.add_event("NFT burn", &format!("A token with id {id} was burned"));
+        working_set.emit_event(self, Event::Burned { id });
         Ok(CallResponse::default())
     }
 }
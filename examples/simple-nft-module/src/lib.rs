@@ -2,7 +2,7 @@
 #![doc = include_str!("../README.md")]
 
 mod call;
-pub use call::CallMessage;
+pub use call::{CallMessage, Event};
 mod genesis;
 pub use genesis::*;
 #[cfg(feature = "native")]
@@ -36,7 +36,7 @@ impl<C: Context> Module for NonFungibleToken<C> {
 
     type CallMessage = CallMessage<C>;
 
-    type Event = ();
+    type Event = call::Event<C>;
 
     fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
         Ok(self.init_module(config, working_set)?)
@@ -1,8 +1,9 @@
-use simple_nft_module::{CallMessage, NonFungibleToken, NonFungibleTokenConfig, OwnerResponse};
+use simple_nft_module::{
+    CallMessage, Event, NonFungibleToken, NonFungibleTokenConfig, OwnerResponse,
+};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address as gen_addr_generic;
-use sov_modules_api::{Address, Context, Module, WorkingSet};
-use sov_rollup_interface::stf::Event;
+use sov_modules_api::{module_event, Address, Context, Module, WorkingSet};
 use sov_state::{DefaultStorageSpec, ProverStorage};
 
 pub type C = DefaultContext;
@@ -38,13 +39,19 @@ fn genesis_and_mint() {
 
     // Mint, anybody can mint
     let mint_message = CallMessage::Mint { id: 1 };
-    let owner2_context = C::new(owner2, 1);
+    let owner2_context = C::new(owner2, Vec::new(), [0; 32], 1);
     nft.call(mint_message.clone(), &owner2_context, &mut working_set)
         .expect("Minting failed");
 
     assert_eq!(
         working_set.events()[0],
-        Event::new("NFT mint", "A token with id 1 was minted")
+        module_event(
+            &nft,
+            &Event::Minted {
+                id: 1,
+                owner: owner2
+            }
+        )
     );
     let query3: OwnerResponse<C> = nft.get_owner(1, &mut working_set).unwrap();
     assert_eq!(query3.owner, Some(owner2));
@@ -61,9 +68,9 @@ fn genesis_and_mint() {
 fn transfer() {
     // Preparation
     let admin = generate_address("admin");
-    let admin_context = C::new(admin, 1);
+    let admin_context = C::new(admin, Vec::new(), [0; 32], 1);
     let owner1 = generate_address("owner2");
-    let owner1_context = C::new(owner1, 1);
+    let owner1_context = C::new(owner1, Vec::new(), [0; 32], 1);
     let owner2 = generate_address("owner2");
     let config: NonFungibleTokenConfig<C> = NonFungibleTokenConfig {
         admin,
@@ -97,7 +104,14 @@ fn transfer() {
 
     assert_eq!(
         working_set.events()[0],
-        Event::new("NFT transfer", "A token with id 1 was transferred")
+        module_event(
+            &nft,
+            &Event::Transferred {
+                id: 1,
+                from: owner1,
+                to: owner2
+            }
+        )
     );
 
     let token1_owner = query_token_owner(1, &mut working_set);
@@ -117,9 +131,9 @@ fn transfer() {
 fn burn() {
     // Preparation
     let admin = generate_address("admin");
-    let admin_context = C::new(admin, 1);
+    let admin_context = C::new(admin, Vec::new(), [0; 32], 1);
     let owner1 = generate_address("owner2");
-    let owner1_context = C::new(owner1, 1);
+    let owner1_context = C::new(owner1, Vec::new(), [0; 32], 1);
     let config: NonFungibleTokenConfig<C> = NonFungibleTokenConfig {
         admin,
         owners: vec![(0, owner1)],
@@ -147,7 +161,7 @@ fn burn() {
 
     assert_eq!(
         working_set.events()[0],
-        Event::new("NFT burn", "A token with id 0 was burned")
+        module_event(&nft, &Event::Burned { id: 0 })
     );
     let query: OwnerResponse<C> = nft.get_owner(0, &mut working_set).unwrap();
 
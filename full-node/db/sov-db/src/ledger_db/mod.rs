@@ -1,40 +1,57 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use serde::Serialize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::{BatchReceipt, Event};
 use sov_schema_db::{Schema, SchemaBatch, SeekKeyEncoder, DB};
 
+use crate::pruning::{spawn_pruner, LedgerPruningConfig, PrunerHandle};
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber, TxByHash,
-    TxByNumber, LEDGER_TABLES,
+    BatchByHash, BatchByNumber, EventByKey, EventByModule, EventByNumber, LastFinalizedSlot,
+    LedgerPruningHorizon, LedgerSchemaVersion, ProofBySlotHash, ProverJobBySlotHash, SlotByHash,
+    SlotByNumber, TxByHash, TxByNumber, LEDGER_TABLES,
 };
 use crate::schema::types::{
-    split_tx_for_storage, BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot,
-    StoredTransaction, TxNumber,
+    BatchNumber, DbHash, EventNumber, SlotNumber, StoredBatch, StoredProof, StoredProofStatus,
+    StoredProverJob, StoredSlot, StoredTransaction, TxNumber,
 };
 
+mod numbered_slot;
 mod rpc;
 
+use numbered_slot::NumberedSlot;
+
 const LEDGER_DB_PATH_SUFFIX: &str = "ledger";
 
+/// The version of the layout of the ledger tables. Version 1 keys module events by module
+/// and event kind, and indexes them by module in [`EventByModule`]. Ledgers written with an
+/// older layout can't be migrated and must be resynced.
+const LEDGER_SCHEMA_VERSION: u64 = 1;
+
 #[derive(Clone, Debug)]
 /// A database which stores the ledger history (slots, transactions, events, etc).
-/// Ledger data is first ingested into an in-memory map before being fed to the state-transition function.
-/// Once the state-transition function has been executed and finalized, the results are committed to the final db
+/// Slots are committed as soon as they are executed, and reverted if the DA layer
+/// reorganizes before they are finalized.
 pub struct LedgerDB {
     /// The database which stores the committed ledger. Uses an optimized layout which
     /// requires transactions to be executed before being committed.
     db: Arc<DB>,
     next_item_numbers: Arc<Mutex<ItemNumbers>>,
+    /// The number of the last finalized slot. The slots after it can still be reverted.
+    last_finalized_slot: Arc<Mutex<u64>>,
     slot_subscriptions: tokio::sync::broadcast::Sender<u64>,
+    executed_slot_subscriptions: tokio::sync::broadcast::Sender<u64>,
 }
 
 /// A SlotNumber, BatchNumber, TxNumber, and EventNumber which are grouped together, typically representing
 /// the respective heights at the start or end of slot processing.
-#[derive(Default, Clone, Debug)]
+#[derive(
+    Default, Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct ItemNumbers {
     /// The slot number
@@ -48,7 +65,7 @@ pub struct ItemNumbers {
 }
 
 /// All of the data to be committed to the ledger db for a single slot.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SlotCommit<S: SlotData, B, T> {
     slot_data: S,
     batch_receipts: Vec<BatchReceipt<B, T>>,
@@ -88,6 +105,9 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
 impl LedgerDB {
     /// Open a [`LedgerDB`] (backed by RocksDB) at the specified path.
     /// The returned instance will be at the path `{path}/ledger-db`.
+    ///
+    /// Slots which were not finalized when the ledger was closed are reverted, since the state
+    /// they lead to is only persisted once they are finalized.
     pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
         let path = path.as_ref().join(LEDGER_DB_PATH_SUFFIX);
         let inner = DB::open(
@@ -96,21 +116,75 @@ impl LedgerDB {
             LEDGER_TABLES.iter().copied(),
             &gen_rocksdb_options(&Default::default(), false),
         )?;
+        Self::check_schema_version(&inner)?;
 
+        // Items before the pruning horizon may all have been deleted, so the numbering
+        // never restarts below it.
+        let horizon = inner.get::<LedgerPruningHorizon>(&())?.unwrap_or_default();
+        let next_number = |last: Option<u64>, first_retained: u64| {
+            (last.unwrap_or_default() + 1).max(first_retained)
+        };
         let next_item_numbers = ItemNumbers {
-            slot_number: Self::last_version_written(&inner, SlotByNumber)?.unwrap_or_default() + 1,
-            batch_number: Self::last_version_written(&inner, BatchByNumber)?.unwrap_or_default()
-                + 1,
-            tx_number: Self::last_version_written(&inner, TxByNumber)?.unwrap_or_default() + 1,
-            event_number: Self::last_version_written(&inner, EventByNumber)?.unwrap_or_default()
-                + 1,
+            slot_number: next_number(
+                Self::last_version_written(&inner, SlotByNumber)?,
+                horizon.slot_number,
+            ),
+            batch_number: next_number(
+                Self::last_version_written(&inner, BatchByNumber)?,
+                horizon.batch_number,
+            ),
+            tx_number: next_number(
+                Self::last_version_written(&inner, TxByNumber)?,
+                horizon.tx_number,
+            ),
+            event_number: next_number(
+                Self::last_version_written(&inner, EventByNumber)?,
+                horizon.event_number,
+            ),
         };
 
-        Ok(Self {
+        let last_finalized_slot = match inner.get::<LastFinalizedSlot>(&())? {
+            Some(slot_number) => slot_number.0,
+            None => next_item_numbers.slot_number - 1,
+        };
+
+        let ledger_db = Self {
             db: Arc::new(inner),
             next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
+            last_finalized_slot: Arc::new(Mutex::new(last_finalized_slot)),
             slot_subscriptions: tokio::sync::broadcast::channel(10).0,
-        })
+            executed_slot_subscriptions: tokio::sync::broadcast::channel(10).0,
+        };
+        while ledger_db.get_next_items_numbers().slot_number - 1 > last_finalized_slot {
+            ledger_db.revert_executed_slot()?;
+        }
+        Ok(ledger_db)
+    }
+
+    /// Fails if the ledger was written with a different layout than the current one, and
+    /// records the current layout in a new ledger.
+    fn check_schema_version(db: &DB) -> anyhow::Result<()> {
+        match db.get::<LedgerSchemaVersion>(&())? {
+            Some(version) => anyhow::ensure!(
+                version == LEDGER_SCHEMA_VERSION,
+                "The ledger uses the schema version {}, but this node only supports version {}. \
+                 Delete the ledger and resync the node",
+                version,
+                LEDGER_SCHEMA_VERSION
+            ),
+            None => {
+                // Ledgers written before the schema was versioned contain slots or have been
+                // pruned.
+                anyhow::ensure!(
+                    Self::last_version_written(db, SlotByNumber)?.is_none()
+                        && db.get::<LedgerPruningHorizon>(&())?.is_none(),
+                    "The ledger was written with an unversioned schema, which this node doesn't \
+                     support. Delete the ledger and resync the node"
+                );
+                db.put::<LedgerSchemaVersion>(&(), &LEDGER_SCHEMA_VERSION)?;
+            }
+        }
+        Ok(())
     }
 
     /// Get the next slot, block, transaction, and event numbers
@@ -118,6 +192,79 @@ impl LedgerDB {
         self.next_item_numbers.lock().unwrap().clone()
     }
 
+    /// Gets the numbers of the slot, batch, transaction and event right after the slot with the
+    /// given number, or [`None`] if the slot is unknown.
+    pub fn get_item_numbers_after_slot(
+        &self,
+        slot_number: u64,
+    ) -> anyhow::Result<Option<ItemNumbers>> {
+        let Some(slot) = self.db.get::<SlotByNumber>(&SlotNumber(slot_number))? else {
+            return Ok(None);
+        };
+        // Items are numbered contiguously, so the next items are either the first ones of
+        // the next slot, or the next ones to be written.
+        let next_item_numbers = self.get_next_items_numbers();
+        let batch_number = slot.batches.end.0;
+        let tx_number = match self.db.get::<BatchByNumber>(&BatchNumber(batch_number))? {
+            Some(batch) => batch.txs.start.0,
+            None => next_item_numbers.tx_number,
+        };
+        let event_number = match self.db.get::<TxByNumber>(&TxNumber(tx_number))? {
+            Some(tx) => tx.events.start.0,
+            None => next_item_numbers.event_number,
+        };
+        Ok(Some(ItemNumbers {
+            slot_number: slot_number + 1,
+            batch_number,
+            tx_number,
+            event_number,
+        }))
+    }
+
+    /// Makes the slot with the given number and hash the head of an empty ledger, so that a
+    /// node bootstrapped from a state snapshot resumes right after it. `next_item_numbers` are
+    /// the numbers of the items following the slot, and everything before them is reported as
+    /// pruned.
+    pub fn import_head(
+        &self,
+        slot_number: u64,
+        slot_hash: DbHash,
+        next_item_numbers: &ItemNumbers,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.get_head_slot()?.is_none(),
+            "Cannot import the head of a non-empty ledger"
+        );
+        anyhow::ensure!(
+            next_item_numbers.slot_number == slot_number + 1,
+            "The next slot number {} does not follow the head slot {}",
+            next_item_numbers.slot_number,
+            slot_number
+        );
+
+        // The batches of the head slot are not available, so it is stored without any.
+        let first_batch_number = BatchNumber(next_item_numbers.batch_number);
+        let slot = StoredSlot {
+            hash: slot_hash,
+            extra_data: vec![].into(),
+            batches: first_batch_number..first_batch_number,
+        };
+        let horizon = ItemNumbers {
+            slot_number,
+            ..next_item_numbers.clone()
+        };
+
+        let mut schema_batch = SchemaBatch::new();
+        self.put_slot(&slot, &SlotNumber(slot_number), &mut schema_batch)?;
+        schema_batch.put::<LedgerPruningHorizon>(&(), &horizon)?;
+        schema_batch.put::<LastFinalizedSlot>(&(), &SlotNumber(slot_number))?;
+        self.db.write_schemas(schema_batch)?;
+
+        *self.next_item_numbers.lock().unwrap() = next_item_numbers.clone();
+        *self.last_finalized_slot.lock().unwrap() = slot_number;
+        Ok(())
+    }
+
     /// Gets all slots with numbers `range.start` to `range.end`. If `range.end` is outside
     /// the range of the database, the result will smaller than the requested range.
     /// Note that this method blindly preallocates for the requested range, so it should not be exposed
@@ -151,6 +298,17 @@ impl LedgerDB {
         self.get_data_range::<TxByNumber, _, _>(range)
     }
 
+    /// Gets all events with numbers `range.start` to `range.end`. If `range.end` is outside
+    /// the range of the database, the result will smaller than the requested range.
+    /// Note that this method blindly preallocates for the requested range, so it should not be exposed
+    /// directly via rpc.
+    pub(crate) fn get_event_range(
+        &self,
+        range: &std::ops::Range<EventNumber>,
+    ) -> Result<Vec<Event>, anyhow::Error> {
+        self.get_data_range::<EventByNumber, _, _>(range)
+    }
+
     /// Gets all data with identifier in `range.start` to `range.end`. If `range.end` is outside
     /// the range of the database, the result will smaller than the requested range.
     /// Note that this method blindly preallocates for the requested range, so it should not be exposed
@@ -210,17 +368,126 @@ impl LedgerDB {
         schema_batch: &mut SchemaBatch,
     ) -> Result<(), anyhow::Error> {
         schema_batch.put::<EventByNumber>(event_number, event)?;
+        if let Some((module, _)) = event.key().module_and_kind() {
+            schema_batch.put::<EventByModule>(&(module.to_vec(), tx_number, *event_number), &())?;
+        }
         schema_batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
     }
 
-    /// Commits a slot to the database by inserting its events, transactions, and batches before
-    /// inserting the slot metadata.
+    /// Commits a slot which is already finalized on the DA layer.
+    /// Fails if some executed slots are not finalized yet.
     pub fn commit_slot<S: SlotData, B: Serialize, T: Serialize>(
         &self,
         data_to_commit: SlotCommit<S, B, T>,
     ) -> Result<(), anyhow::Error> {
+        let mut last_finalized_slot = self.last_finalized_slot.lock().unwrap();
+        anyhow::ensure!(
+            self.get_next_items_numbers().slot_number == *last_finalized_slot + 1,
+            "Cannot commit a finalized slot after slots which are not finalized yet"
+        );
+        let slot_number = self.write_slot(data_to_commit, |slot_number| slot_number)?;
+        *last_finalized_slot = slot_number;
+        drop(last_finalized_slot);
+
+        // Notify subscribers. These calls return an error IFF there are no subscribers, so we don't need to check the result
+        let _ = self.executed_slot_subscriptions.send(slot_number);
+        let _ = self.slot_subscriptions.send(slot_number);
+
+        Ok(())
+    }
+
+    /// Commits a slot which has been executed, but is not finalized on the DA layer yet.
+    /// It is finalized with [`LedgerDB::finalize_executed_slot`] or, if the DA layer
+    /// reorganizes, reverted with [`LedgerDB::revert_executed_slot`].
+    pub fn put_executed_slot<S: SlotData, B: Serialize, T: Serialize>(
+        &self,
+        data: SlotCommit<S, B, T>,
+    ) -> Result<(), anyhow::Error> {
+        let last_finalized_slot = *self.last_finalized_slot.lock().unwrap();
+        let slot_number = self.write_slot(data, |_| last_finalized_slot)?;
+
+        // Notify subscribers. This call returns an error IFF there are no subscribers, so we don't need to check the result
+        let _ = self.executed_slot_subscriptions.send(slot_number);
+
+        Ok(())
+    }
+
+    /// Finalizes the oldest executed slot which is not finalized yet, which must have the given hash.
+    pub fn finalize_executed_slot(&self, slot_hash: &DbHash) -> Result<(), anyhow::Error> {
+        let mut last_finalized_slot = self.last_finalized_slot.lock().unwrap();
+        let slot_number = SlotNumber(*last_finalized_slot + 1);
+        let slot = self
+            .db
+            .get::<SlotByNumber>(&slot_number)?
+            .ok_or_else(|| anyhow::anyhow!("Cannot finalize: no executed slot"))?;
+        anyhow::ensure!(
+            &slot.hash == slot_hash,
+            "Cannot finalize slot 0x{}: the next slot to finalize is 0x{}",
+            hex::encode(slot_hash),
+            hex::encode(slot.hash)
+        );
+        self.db.put::<LastFinalizedSlot>(&(), &slot_number)?;
+        *last_finalized_slot = slot_number.0;
+        drop(last_finalized_slot);
+
+        // Notify subscribers. This call returns an error IFF there are no subscribers, so we don't need to check the result
+        let _ = self.slot_subscriptions.send(slot_number.0);
+
+        Ok(())
+    }
+
+    /// Deletes the most recently executed slot, which has been reverted by the DA layer,
+    /// along with its batches, transactions, events and prover job. Its numbers are reused
+    /// by the next slot. Finalized slots can't be reverted.
+    pub fn revert_executed_slot(&self) -> Result<(), anyhow::Error> {
+        let last_finalized_slot = *self.last_finalized_slot.lock().unwrap();
+        let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
+        let slot_number = next_item_numbers.slot_number - 1;
+        anyhow::ensure!(
+            slot_number > last_finalized_slot,
+            "Cannot revert: no executed slot which is not finalized"
+        );
+        let slot = self
+            .db
+            .get::<SlotByNumber>(&SlotNumber(slot_number))?
+            .ok_or_else(|| anyhow::anyhow!("Missing executed slot {}", slot_number))?;
+
+        // The slot is the last one written, so the items which follow its first batch and
+        // transaction, if any, are its own.
+        let mut first = ItemNumbers {
+            slot_number,
+            batch_number: slot.batches.start.0,
+            ..next_item_numbers.clone()
+        };
+        if let Some(batch) = self.db.get::<BatchByNumber>(&slot.batches.start)? {
+            first.tx_number = batch.txs.start.0;
+            if let Some(tx) = self.db.get::<TxByNumber>(&batch.txs.start)? {
+                first.event_number = tx.events.start.0;
+            }
+        }
+
+        let mut schema_batch = SchemaBatch::new();
+        self.delete_slot(&slot, &mut first.clone(), &mut schema_batch)?;
+        schema_batch.delete::<SlotByNumber>(&SlotNumber(slot_number))?;
+        schema_batch.delete::<SlotByHash>(&slot.hash)?;
+        schema_batch.delete::<ProofBySlotHash>(&slot.hash)?;
+        schema_batch.delete::<ProverJobBySlotHash>(&slot.hash)?;
+        self.db.write_schemas(schema_batch)?;
+
+        *next_item_numbers = first;
+        Ok(())
+    }
+
+    /// Writes the slot to the database by inserting its events, transactions, and batches before
+    /// inserting the slot metadata, along with the number of the last finalized slot computed
+    /// from the number of the slot. Returns the number of the slot.
+    fn write_slot<S: SlotData, B: Serialize, T: Serialize>(
+        &self,
+        data_to_commit: SlotCommit<S, B, T>,
+        last_finalized_slot: impl FnOnce(u64) -> u64,
+    ) -> Result<u64, anyhow::Error> {
         // Create a scope to ensure that the lock is released before we commit to the db
-        let mut current_item_numbers = {
+        let current_item_numbers = {
             let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
             let item_numbers = next_item_numbers.clone();
             next_item_numbers.slot_number += 1;
@@ -230,73 +497,120 @@ impl LedgerDB {
             item_numbers
             // The lock is released here
         };
+        let slot = NumberedSlot::new(data_to_commit, current_item_numbers);
 
         let mut schema_batch = SchemaBatch::new();
 
-        let first_batch_number = current_item_numbers.batch_number;
-        let last_batch_number = first_batch_number + data_to_commit.batch_receipts.len() as u64;
         // Insert data from "bottom up" to ensure consistency if the application crashes during insertion
-        for batch_receipt in data_to_commit.batch_receipts.into_iter() {
-            let first_tx_number = current_item_numbers.tx_number;
-            let last_tx_number = first_tx_number + batch_receipt.tx_receipts.len() as u64;
-            // Insert transactions and events from each batch before inserting the batch
-            for tx in batch_receipt.tx_receipts.into_iter() {
-                let (tx_to_store, events) =
-                    split_tx_for_storage(tx, current_item_numbers.event_number);
-                for event in events.into_iter() {
-                    self.put_event(
-                        &event,
-                        &EventNumber(current_item_numbers.event_number),
-                        TxNumber(current_item_numbers.tx_number),
-                        &mut schema_batch,
-                    )?;
-                    current_item_numbers.event_number += 1;
-                }
-                self.put_transaction(
-                    &tx_to_store,
-                    &TxNumber(current_item_numbers.tx_number),
-                    &mut schema_batch,
-                )?;
-                current_item_numbers.tx_number += 1;
+        for (tx_number, tx) in slot.txs() {
+            for (event_number, event) in slot.tx_events(tx) {
+                self.put_event(event, &event_number, tx_number, &mut schema_batch)?;
             }
-
-            // Insert batch
-            let batch_to_store = StoredBatch {
-                hash: batch_receipt.batch_hash,
-                txs: TxNumber(first_tx_number)..TxNumber(last_tx_number),
-                custom_receipt: bincode::serialize(&batch_receipt.inner)
-                    .expect("serialization to vec is infallible")
-                    .into(),
-            };
-            self.put_batch(
-                &batch_to_store,
-                &BatchNumber(current_item_numbers.batch_number),
-                &mut schema_batch,
-            )?;
-            current_item_numbers.batch_number += 1;
+            self.put_transaction(tx, &tx_number, &mut schema_batch)?;
         }
+        for (batch_number, batch) in slot.batches() {
+            self.put_batch(batch, &batch_number, &mut schema_batch)?;
+        }
+        let slot_number = SlotNumber(slot.first.slot_number);
+        self.put_slot(&slot.slot, &slot_number, &mut schema_batch)?;
+        schema_batch
+            .put::<LastFinalizedSlot>(&(), &SlotNumber(last_finalized_slot(slot_number.0)))?;
 
-        // Once all batches are inserted, Insert slot
-        let slot_to_store = StoredSlot {
-            hash: data_to_commit.slot_data.hash(),
-            // TODO: Add a method to the slot data trait allowing additional data to be stored
-            extra_data: vec![].into(),
-            batches: BatchNumber(first_batch_number)..BatchNumber(last_batch_number),
+        self.db.write_schemas(schema_batch)?;
+        Ok(slot_number.0)
+    }
+
+    /// Gets the hashes of the transactions of the committed slot with the given number,
+    /// or [`None`] if the slot is unknown.
+    pub fn get_tx_hashes_by_slot_number(
+        &self,
+        slot_number: u64,
+    ) -> anyhow::Result<Option<Vec<DbHash>>> {
+        let Some(slot) = self.db.get::<SlotByNumber>(&SlotNumber(slot_number))? else {
+            return Ok(None);
         };
-        self.put_slot(
-            &slot_to_store,
-            &SlotNumber(current_item_numbers.slot_number),
-            &mut schema_batch,
-        )?;
+        let batches = self.get_batch_range(&slot.batches)?;
+        let (Some(first), Some(last)) = (batches.first(), batches.last()) else {
+            return Ok(Some(Vec::new()));
+        };
+        let txs = self.get_tx_range(&(first.txs.start..last.txs.end))?;
+        Ok(Some(txs.into_iter().map(|tx| tx.hash).collect()))
+    }
 
-        self.db.write_schemas(schema_batch)?;
+    /// Records a proof of the slot with the given hash, which has been published to the DA layer.
+    /// The prover job of the slot, if any, is marked as submitted.
+    pub fn put_proof(&self, slot_hash: &DbHash, proof: &StoredProof) -> Result<(), anyhow::Error> {
+        let mut schema_batch = SchemaBatch::new();
+        schema_batch.put::<ProofBySlotHash>(slot_hash, proof)?;
+        if let Some(mut job) = self.db.get::<ProverJobBySlotHash>(slot_hash)? {
+            job.status = StoredProofStatus::Submitted;
+            job.witness = vec![].into();
+            schema_batch.put::<ProverJobBySlotHash>(slot_hash, &job)?;
+        }
+        self.db.write_schemas(schema_batch)
+    }
 
-        // Notify subscribers. This call returns an error IFF there are no subscribers, so we don't need to check the result
-        let _ = self
-            .slot_subscriptions
-            .send(current_item_numbers.slot_number);
+    /// Gets the published proof of the slot with the given hash, if any.
+    pub fn get_proof_by_slot_hash(
+        &self,
+        slot_hash: &DbHash,
+    ) -> Result<Option<StoredProof>, anyhow::Error> {
+        self.db.get::<ProofBySlotHash>(slot_hash)
+    }
 
-        Ok(())
+    /// Records a slot which has been submitted for proving, so that the job survives restarts.
+    pub fn put_prover_job(
+        &self,
+        slot_hash: &DbHash,
+        job: &StoredProverJob,
+    ) -> Result<(), anyhow::Error> {
+        self.db.put::<ProverJobBySlotHash>(slot_hash, job)
+    }
+
+    /// Gets the prover job of the slot with the given hash, if any.
+    pub fn get_prover_job(
+        &self,
+        slot_hash: &DbHash,
+    ) -> Result<Option<StoredProverJob>, anyhow::Error> {
+        self.db.get::<ProverJobBySlotHash>(slot_hash)
+    }
+
+    /// Updates the status of the prover job of the slot with the given hash, and counts its failures.
+    /// The witness of the job is dropped once the job is done or abandoned.
+    pub fn set_proof_status(
+        &self,
+        slot_hash: &DbHash,
+        status: StoredProofStatus,
+    ) -> Result<(), anyhow::Error> {
+        let mut job = self.get_prover_job(slot_hash)?.ok_or_else(|| {
+            anyhow::anyhow!("Missing prover job for slot 0x{}", hex::encode(slot_hash))
+        })?;
+        if matches!(status, StoredProofStatus::Failed(_)) {
+            job.failed_attempts += 1;
+        }
+        job.status = status;
+        if job.status.is_done() || job.is_abandoned() {
+            job.witness = vec![].into();
+        }
+        self.put_prover_job(slot_hash, &job)
+    }
+
+    /// Gets all prover jobs which are neither done nor abandoned yet, ordered by DA height.
+    pub fn get_unfinished_prover_jobs(
+        &self,
+    ) -> Result<Vec<(DbHash, StoredProverJob)>, anyhow::Error> {
+        let mut iter = self.db.iter::<ProverJobBySlotHash>()?;
+        iter.seek_to_first();
+
+        let mut jobs = Vec::new();
+        for item in iter {
+            let (slot_hash, job) = item?;
+            if !job.status.is_done() && !job.is_abandoned() {
+                jobs.push((slot_hash, job));
+            }
+        }
+        jobs.sort_by_key(|(_, job)| job.da_height);
+        Ok(jobs)
     }
 
     fn last_version_written<T: Schema<Key = U>, U: Into<u64>>(
@@ -313,6 +627,103 @@ impl LedgerDB {
         }
     }
 
+    /// Gets the numbers of the first slot, batch, transaction and event which have not been pruned.
+    pub fn get_pruning_horizon(&self) -> anyhow::Result<ItemNumbers> {
+        Ok(self
+            .db
+            .get::<LedgerPruningHorizon>(&())?
+            .unwrap_or_default())
+    }
+
+    /// Deletes the slots which are outside the retention window of the `config`, along with their
+    /// batches, transactions, events, proofs and prover jobs, and returns the number of deleted
+    /// slots.
+    ///
+    /// Every slot is deleted atomically, along with the update of the pruning horizon, so that
+    /// queries for pruned items fail with a clear error instead of returning partial data.
+    pub fn prune(&self, config: &LedgerPruningConfig) -> anyhow::Result<usize> {
+        let next_slot_number = self.get_next_items_numbers().slot_number;
+        // Slots which are not finalized are kept, so that they can be reverted.
+        let first_retained_slot = next_slot_number
+            .saturating_sub(config.keep_last_slots.max(1))
+            .min(*self.last_finalized_slot.lock().unwrap() + 1);
+
+        let mut horizon = self.get_pruning_horizon()?;
+        let mut pruned = 0;
+        for slot_number in horizon.slot_number..first_retained_slot {
+            let mut schema_batch = SchemaBatch::new();
+            if let Some(slot) = self.db.get::<SlotByNumber>(&SlotNumber(slot_number))? {
+                self.delete_slot(&slot, &mut horizon, &mut schema_batch)?;
+                schema_batch.delete::<SlotByNumber>(&SlotNumber(slot_number))?;
+                schema_batch.delete::<SlotByHash>(&slot.hash)?;
+                schema_batch.delete::<ProofBySlotHash>(&slot.hash)?;
+                schema_batch.delete::<ProverJobBySlotHash>(&slot.hash)?;
+                pruned += 1;
+            }
+            horizon.slot_number = slot_number + 1;
+            schema_batch.put::<LedgerPruningHorizon>(&(), &horizon)?;
+            self.db.write_schemas(schema_batch)?;
+        }
+        Ok(pruned)
+    }
+
+    /// Deletes the batches, transactions and events of the `slot`, and moves the `horizon`
+    /// past them.
+    fn delete_slot(
+        &self,
+        slot: &StoredSlot,
+        horizon: &mut ItemNumbers,
+        schema_batch: &mut SchemaBatch,
+    ) -> anyhow::Result<()> {
+        for batch_number in slot.batches.start.0..slot.batches.end.0 {
+            let Some(batch) = self.db.get::<BatchByNumber>(&BatchNumber(batch_number))? else {
+                continue;
+            };
+            for tx_number in batch.txs.start.0..batch.txs.end.0 {
+                let Some(tx) = self.db.get::<TxByNumber>(&TxNumber(tx_number))? else {
+                    continue;
+                };
+                for event_number in tx.events.start.0..tx.events.end.0 {
+                    let event_number = EventNumber(event_number);
+                    if let Some(event) = self.db.get::<EventByNumber>(&event_number)? {
+                        if let Some((module, _)) = event.key().module_and_kind() {
+                            schema_batch.delete::<EventByModule>(&(
+                                module.to_vec(),
+                                TxNumber(tx_number),
+                                event_number,
+                            ))?;
+                        }
+                        schema_batch.delete::<EventByKey>(&(
+                            event.key().clone(),
+                            TxNumber(tx_number),
+                            event_number,
+                        ))?;
+                        schema_batch.delete::<EventByNumber>(&event_number)?;
+                    }
+                }
+                horizon.event_number = horizon.event_number.max(tx.events.end.0);
+                schema_batch.delete::<TxByNumber>(&TxNumber(tx_number))?;
+                schema_batch.delete::<TxByHash>(&tx.hash)?;
+            }
+            horizon.tx_number = horizon.tx_number.max(batch.txs.end.0);
+            schema_batch.delete::<BatchByNumber>(&BatchNumber(batch_number))?;
+            schema_batch.delete::<BatchByHash>(&batch.hash)?;
+        }
+        horizon.batch_number = horizon.batch_number.max(slot.batches.end.0);
+        Ok(())
+    }
+
+    /// Runs [`LedgerDB::prune`] on a dedicated thread every `interval`, until the returned handle
+    /// is dropped.
+    pub fn spawn_pruner(
+        &self,
+        config: LedgerPruningConfig,
+        interval: Duration,
+    ) -> std::io::Result<PrunerHandle> {
+        let ledger_db = self.clone();
+        spawn_pruner("ledger-pruner", interval, move || ledger_db.prune(&config))
+    }
+
     /// Get the most recent committed slot, if any
     pub fn get_head_slot(&self) -> anyhow::Result<Option<(SlotNumber, StoredSlot)>> {
         let mut iter = self.db.iter::<SlotByNumber>()?;
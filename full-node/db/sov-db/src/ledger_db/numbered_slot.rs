@@ -0,0 +1,87 @@
+use serde::Serialize;
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::Event;
+
+use super::{ItemNumbers, SlotCommit};
+use crate::schema::types::{
+    split_tx_for_storage, BatchNumber, EventNumber, StoredBatch, StoredSlot, StoredTransaction,
+    TxNumber,
+};
+
+/// The items of a slot in the form in which they are stored, numbered from the given
+/// [`ItemNumbers`].
+#[derive(Debug)]
+pub(super) struct NumberedSlot {
+    pub(super) first: ItemNumbers,
+    pub(super) slot: StoredSlot,
+    batches: Vec<StoredBatch>,
+    txs: Vec<StoredTransaction>,
+    events: Vec<Event>,
+}
+
+impl NumberedSlot {
+    /// Splits `data` into storable items, numbered from `first`.
+    pub(super) fn new<S: SlotData, B: Serialize, T: Serialize>(
+        data: SlotCommit<S, B, T>,
+        first: ItemNumbers,
+    ) -> Self {
+        let mut next = first.clone();
+        let mut batches = Vec::with_capacity(data.batch_receipts.len());
+        let mut txs = Vec::with_capacity(data.num_txs);
+        let mut events = Vec::with_capacity(data.num_events);
+        for batch_receipt in data.batch_receipts {
+            let first_tx_number = next.tx_number;
+            for tx in batch_receipt.tx_receipts {
+                let (tx_to_store, tx_events) = split_tx_for_storage(tx, next.event_number);
+                next.event_number += tx_events.len() as u64;
+                next.tx_number += 1;
+                txs.push(tx_to_store);
+                events.extend(tx_events);
+            }
+            batches.push(StoredBatch {
+                hash: batch_receipt.batch_hash,
+                txs: TxNumber(first_tx_number)..TxNumber(next.tx_number),
+                custom_receipt: bincode::serialize(&batch_receipt.inner)
+                    .expect("serialization to vec is infallible")
+                    .into(),
+            });
+            next.batch_number += 1;
+        }
+
+        let slot = StoredSlot {
+            hash: data.slot_data.hash(),
+            // TODO: Add a method to the slot data trait allowing additional data to be stored
+            extra_data: vec![].into(),
+            batches: BatchNumber(first.batch_number)..BatchNumber(next.batch_number),
+        };
+        Self {
+            first,
+            slot,
+            batches,
+            txs,
+            events,
+        }
+    }
+
+    pub(super) fn batches(&self) -> impl Iterator<Item = (BatchNumber, &StoredBatch)> {
+        (self.first.batch_number..)
+            .map(BatchNumber)
+            .zip(self.batches.iter())
+    }
+
+    pub(super) fn txs(&self) -> impl Iterator<Item = (TxNumber, &StoredTransaction)> {
+        (self.first.tx_number..).map(TxNumber).zip(self.txs.iter())
+    }
+
+    /// Returns the events emitted by `tx`, which must belong to this slot.
+    pub(super) fn tx_events<'a>(
+        &'a self,
+        tx: &StoredTransaction,
+    ) -> impl Iterator<Item = (EventNumber, &'a Event)> {
+        let start = (tx.events.start.0 - self.first.event_number) as usize;
+        let end = (tx.events.end.0 - self.first.event_number) as usize;
+        (tx.events.start.0..)
+            .map(EventNumber)
+            .zip(self.events[start..end].iter())
+    }
+}
@@ -1,17 +1,21 @@
 use serde::de::DeserializeOwned;
 use sov_rollup_interface::rpc::{
-    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
-    LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset,
-    TxIdentifier, TxResponse,
+    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventCursor, EventFilter, EventIdentifier,
+    EventResponse, EventsPage, ItemOrHash, LedgerRpcProvider, ProofStatus, QueryMode,
+    SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndKey, TxIdAndOffset, TxIdentifier,
+    TxResponse,
 };
-use sov_rollup_interface::stf::Event;
+use sov_rollup_interface::stf::{Event, EventKey};
+use sov_schema_db::{Schema, SeekKeyEncoder};
 use tokio::sync::broadcast::Receiver;
 
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, TxByHash, TxByNumber,
+    BatchByHash, BatchByNumber, EventByKey, EventByModule, EventByNumber, SlotByHash, SlotByNumber,
+    TxByHash, TxByNumber,
 };
 use crate::schema::types::{
-    BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
+    BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredProofStatus, StoredSlot,
+    StoredTransaction, TxNumber,
 };
 
 /// The maximum number of slots that can be requested in a single RPC range query
@@ -22,8 +26,23 @@ const MAX_BATCHES_PER_REQUEST: u64 = 20;
 const MAX_TRANSACTIONS_PER_REQUEST: u64 = 100;
 /// The maximum number of events that can be requested in a single RPC range query
 const MAX_EVENTS_PER_REQUEST: u64 = 500;
+/// The maximum number of events that a single filtered query reads from the database when
+/// the events are not selected through the key index. Queries which reach this limit return
+/// a partial page and a cursor from which to resume.
+const MAX_EVENTS_SCANNED_PER_REQUEST: u64 = 10_000;
 
-use super::LedgerDB;
+use super::{ItemNumbers, LedgerDB};
+
+/// Fails with a "pruned" error if `number` is below the first item which has not been pruned.
+fn ensure_not_pruned(item: &str, number: u64, first_retained: u64) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        number >= first_retained,
+        "{} {} has been pruned",
+        item,
+        number
+    );
+    Ok(())
+}
 
 impl LedgerRpcProvider for LedgerDB {
     fn get_slots<B: DeserializeOwned, T: DeserializeOwned>(
@@ -39,6 +58,7 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let horizon = self.get_pruning_horizon()?;
         let mut out = Vec::with_capacity(slot_ids.len());
         for slot_id in slot_ids {
             let slot_num = self.resolve_slot_identifier(slot_id)?;
@@ -47,6 +67,7 @@ impl LedgerRpcProvider for LedgerDB {
                     if let Some(stored_slot) = self.db.get::<SlotByNumber>(&num)? {
                         Some(self.populate_slot_response(num.into(), stored_slot, query_mode)?)
                     } else {
+                        ensure_not_pruned("Slot", num.0, horizon.slot_number)?;
                         None
                     }
                 }
@@ -69,6 +90,7 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let horizon = self.get_pruning_horizon()?;
         let mut out = Vec::with_capacity(batch_ids.len());
         for batch_id in batch_ids {
             let batch_num = self.resolve_batch_identifier(batch_id)?;
@@ -77,6 +99,7 @@ impl LedgerRpcProvider for LedgerDB {
                     if let Some(stored_batch) = self.db.get::<BatchByNumber>(&num)? {
                         Some(self.populate_batch_response(stored_batch, query_mode)?)
                     } else {
+                        ensure_not_pruned("Batch", num.0, horizon.batch_number)?;
                         None
                     }
                 }
@@ -89,7 +112,7 @@ impl LedgerRpcProvider for LedgerDB {
     fn get_transactions<T: DeserializeOwned>(
         &self,
         tx_ids: &[sov_rollup_interface::rpc::TxIdentifier],
-        _query_mode: QueryMode,
+        query_mode: QueryMode,
     ) -> Result<Vec<Option<TxResponse<T>>>, anyhow::Error> {
         anyhow::ensure!(
             tx_ids.len() <= MAX_TRANSACTIONS_PER_REQUEST as usize,
@@ -99,14 +122,16 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/191 Sort the input
         //      and use an iterator instead of querying for each slot individually
+        let horizon = self.get_pruning_horizon()?;
         let mut out: Vec<Option<TxResponse<T>>> = Vec::with_capacity(tx_ids.len());
         for id in tx_ids {
             let num = self.resolve_tx_identifier(id)?;
             out.push(match num {
                 Some(num) => {
                     if let Some(tx) = self.db.get::<TxByNumber>(&num)? {
-                        Some(tx.try_into()?)
+                        Some(self.populate_tx_response(tx, query_mode)?)
                     } else {
+                        ensure_not_pruned("Transaction", num.0, horizon.tx_number)?;
                         None
                     }
                 }
@@ -128,17 +153,70 @@ impl LedgerRpcProvider for LedgerDB {
         );
         // TODO: Sort the input and use an iterator instead of querying for each slot individually
         // https://github.com/Sovereign-Labs/sovereign-sdk/issues/191
+        let horizon = self.get_pruning_horizon()?;
         let mut out = Vec::with_capacity(event_ids.len());
         for id in event_ids {
             let num = self.resolve_event_identifier(id)?;
             out.push(match num {
-                Some(num) => self.db.get::<EventByNumber>(&num)?,
+                Some(num) => {
+                    let event = self.db.get::<EventByNumber>(&num)?;
+                    if event.is_none() {
+                        ensure_not_pruned("Event", num.0, horizon.event_number)?;
+                    }
+                    event
+                }
                 None => None,
             })
         }
         Ok(out)
     }
 
+    fn get_events_by_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: u64,
+    ) -> Result<EventsPage, anyhow::Error> {
+        anyhow::ensure!(
+            limit <= MAX_EVENTS_PER_REQUEST,
+            "requested too many events. Requested: {}. Max: {}",
+            limit,
+            MAX_EVENTS_PER_REQUEST
+        );
+        let horizon = self.get_pruning_horizon()?;
+        let next_ids = self.get_next_items_numbers();
+        let txs = self.resolve_filter_tx_range(filter, cursor, &horizon, &next_ids)?;
+        let first_event = cursor.map_or(0, |cursor| cursor.event_number);
+
+        let mut page = EventsPage {
+            events: Vec::new(),
+            next_cursor: None,
+        };
+        if limit == 0 || txs.is_empty() {
+            return Ok(page);
+        }
+        match (&filter.module, filter.kind) {
+            (Some(module), Some(kind)) => self.fill_page_from_index::<EventByKey, _>(
+                &EventKey::module_event(module, kind),
+                filter,
+                txs,
+                first_event,
+                limit,
+                &mut page,
+            )?,
+            (Some(module), None) => self.fill_page_from_index::<EventByModule, _>(
+                module,
+                filter,
+                txs,
+                first_event,
+                limit,
+                &mut page,
+            )?,
+            (None, _) => self.fill_page_from_txs(filter, txs, first_event, limit, &mut page)?,
+        }
+        Ok(page)
+    }
+
     fn get_head<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         query_mode: QueryMode,
@@ -273,6 +351,75 @@ impl LedgerRpcProvider for LedgerDB {
     fn subscribe_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
         Ok(self.slot_subscriptions.subscribe())
     }
+
+    fn get_executed_slot<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        number: u64,
+        query_mode: QueryMode,
+    ) -> Result<Option<SlotResponse<B, T>>, anyhow::Error> {
+        if number <= *self.last_finalized_slot.lock().unwrap() {
+            return Ok(None);
+        }
+        self.get_slot_by_number(number, query_mode)
+    }
+
+    fn get_executed_tx_by_hash<T: DeserializeOwned>(
+        &self,
+        hash: &[u8; 32],
+        query_mode: QueryMode,
+    ) -> Result<Option<TxResponse<T>>, anyhow::Error> {
+        let Some(tx_number) = self.db.get::<TxByHash>(hash)? else {
+            return Ok(None);
+        };
+        let first_executed_slot = *self.last_finalized_slot.lock().unwrap() + 1;
+        let first_executed_tx =
+            self.first_tx_of_slot(first_executed_slot, &self.get_next_items_numbers())?;
+        if tx_number.0 < first_executed_tx {
+            return Ok(None);
+        }
+        self.get_tx_by_number(tx_number.0, query_mode)
+    }
+
+    fn subscribe_executed_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
+        Ok(self.executed_slot_subscriptions.subscribe())
+    }
+
+    fn get_proof_status(
+        &self,
+        slot_id: &SlotIdentifier,
+    ) -> Result<Option<ProofStatus>, anyhow::Error> {
+        let slot_hash = match slot_id {
+            SlotIdentifier::Hash(hash) => *hash,
+            SlotIdentifier::Number(num) => match self.db.get::<SlotByNumber>(&SlotNumber(*num))? {
+                Some(slot) => slot.hash,
+                None => return Ok(None),
+            },
+        };
+
+        let Some(job) = self.get_prover_job(&slot_hash)? else {
+            return Ok(None);
+        };
+
+        let abandoned = job.is_abandoned();
+        let status = match job.status {
+            StoredProofStatus::Pending => ProofStatus::Pending,
+            StoredProofStatus::Proving => ProofStatus::Proving,
+            StoredProofStatus::Proved => ProofStatus::Proved,
+            StoredProofStatus::Failed(reason) => ProofStatus::Failed { reason, abandoned },
+            StoredProofStatus::Submitted => {
+                let proof = self.get_proof_by_slot_hash(&slot_hash)?.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Missing submitted proof for slot 0x{}",
+                        hex::encode(slot_hash)
+                    )
+                })?;
+                ProofStatus::Submitted {
+                    da_transaction_id: proof.da_transaction_id.as_ref().to_vec(),
+                }
+            }
+        };
+        Ok(Some(status))
+    }
 }
 
 impl LedgerDB {
@@ -342,10 +489,173 @@ impl LedgerDB {
                 }
             }
             EventIdentifier::Number(num) => Ok(Some(EventNumber(*num))),
-            EventIdentifier::TxIdAndKey(_) => todo!(),
+            EventIdentifier::TxIdAndKey(TxIdAndKey { tx_id, key }) => {
+                let Some(tx_num) = self.resolve_tx_identifier(tx_id)? else {
+                    return Ok(None);
+                };
+                // Index entries are ordered by transaction, so the first entry at or after
+                // `(key, tx_num)` is the first event with this key emitted by the transaction.
+                let mut iter = self.db.iter::<EventByKey>()?;
+                iter.seek(&(key.clone(), tx_num, EventNumber(0)))?;
+                match iter.next().transpose()? {
+                    Some(((event_key, event_tx, event_number), ()))
+                        if event_key == *key && event_tx == tx_num =>
+                    {
+                        Ok(Some(event_number))
+                    }
+                    _ => Ok(None),
+                }
+            }
         }
     }
 
+    /// Returns the range of transactions selected by the slot and transaction ranges of
+    /// `filter`, starting no earlier than `cursor`.
+    fn resolve_filter_tx_range(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        horizon: &ItemNumbers,
+        next_ids: &ItemNumbers,
+    ) -> Result<std::ops::Range<u64>, anyhow::Error> {
+        let mut start = None::<u64>;
+        let mut end = next_ids.tx_number;
+        if let Some(slots) = &filter.slot_range {
+            ensure_not_pruned("Slot", slots.start, horizon.slot_number)?;
+            start = Some(self.first_tx_of_slot(slots.start, next_ids)?);
+            end = end.min(self.first_tx_of_slot(slots.end, next_ids)?);
+        }
+        if let Some(txs) = &filter.tx_range {
+            start = Some(start.map_or(txs.start, |start| start.max(txs.start)));
+            end = end.min(txs.end);
+        }
+        if let Some(cursor) = cursor {
+            start = Some(start.map_or(cursor.tx_number, |start| start.max(cursor.tx_number)));
+        }
+        let start = match start {
+            Some(start) => {
+                // Only complain about pruned transactions if some of them were selected.
+                if start < end {
+                    ensure_not_pruned("Transaction", start, horizon.tx_number)?;
+                }
+                start
+            }
+            None => horizon.tx_number,
+        };
+        Ok(start..end)
+    }
+
+    /// Returns the number of the first transaction of the slot `slot_number`, or of the
+    /// next slot containing transactions if it has none.
+    fn first_tx_of_slot(&self, slot_number: u64, next_ids: &ItemNumbers) -> anyhow::Result<u64> {
+        let mut slots = self.db.iter::<SlotByNumber>()?;
+        slots.seek(&SlotNumber(slot_number))?;
+        let Some((_, slot)) = slots.next().transpose()? else {
+            return Ok(next_ids.tx_number);
+        };
+        let mut batches = self.db.iter::<BatchByNumber>()?;
+        batches.seek(&slot.batches.start)?;
+        Ok(match batches.next().transpose()? {
+            Some((_, batch)) => batch.txs.start.0,
+            None => next_ids.tx_number,
+        })
+    }
+
+    /// Adds the events indexed under `key` in the event index `I` and emitted by the
+    /// transactions `txs` to `page`, so that only the matching events are read.
+    fn fill_page_from_index<I, K>(
+        &self,
+        key: &K,
+        filter: &EventFilter,
+        txs: std::ops::Range<u64>,
+        first_event: u64,
+        limit: u64,
+        page: &mut EventsPage,
+    ) -> anyhow::Result<()>
+    where
+        I: Schema<Key = (K, TxNumber, EventNumber), Value = ()>,
+        K: Clone + PartialEq,
+        (K, TxNumber, EventNumber): SeekKeyEncoder<I>,
+    {
+        let mut iter = self.db.iter::<I>()?;
+        iter.seek(&(key.clone(), TxNumber(txs.start), EventNumber(first_event)))?;
+        for res in iter {
+            let ((event_key, tx_number, event_number), ()) = res?;
+            if event_key != *key || tx_number.0 >= txs.end {
+                break;
+            }
+            let Some(event) = self.db.get::<EventByNumber>(&event_number)? else {
+                continue;
+            };
+            if !filter.matches_key(event.key()) {
+                continue;
+            }
+            page.events.push(EventResponse {
+                number: event_number.0,
+                tx_number: tx_number.0,
+                event,
+            });
+            if page.events.len() as u64 == limit {
+                page.next_cursor = Some(EventCursor {
+                    tx_number: tx_number.0,
+                    event_number: event_number.0 + 1,
+                });
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the events matching `filter` emitted by the transactions `txs` to `page`, by
+    /// reading the events of each transaction in turn.
+    fn fill_page_from_txs(
+        &self,
+        filter: &EventFilter,
+        txs: std::ops::Range<u64>,
+        first_event: u64,
+        limit: u64,
+        page: &mut EventsPage,
+    ) -> anyhow::Result<()> {
+        let mut iter = self.db.iter::<TxByNumber>()?;
+        iter.seek(&TxNumber(txs.start))?;
+        let mut scanned = 0;
+        for res in iter {
+            let (tx_number, tx) = res?;
+            if tx_number.0 >= txs.end {
+                break;
+            }
+            for event_number in tx.events.start.0.max(first_event)..tx.events.end.0 {
+                if scanned == MAX_EVENTS_SCANNED_PER_REQUEST {
+                    page.next_cursor = Some(EventCursor {
+                        tx_number: tx_number.0,
+                        event_number,
+                    });
+                    return Ok(());
+                }
+                scanned += 1;
+                let Some(event) = self.db.get::<EventByNumber>(&EventNumber(event_number))? else {
+                    continue;
+                };
+                if !filter.matches_key(event.key()) {
+                    continue;
+                }
+                page.events.push(EventResponse {
+                    number: event_number,
+                    tx_number: tx_number.0,
+                    event,
+                });
+                if page.events.len() as u64 == limit {
+                    page.next_cursor = Some(EventCursor {
+                        tx_number: tx_number.0,
+                        event_number: event_number + 1,
+                    });
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn populate_slot_response<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         number: u64,
@@ -415,7 +725,7 @@ impl LedgerDB {
                 let num_txs = (batch.txs.end.0 - batch.txs.start.0) as usize;
                 let mut txs = Vec::with_capacity(num_txs);
                 for tx in self.get_tx_range(&batch.txs)? {
-                    txs.push(ItemOrHash::Full(tx.try_into()?));
+                    txs.push(ItemOrHash::Full(self.populate_tx_response(tx, mode)?));
                 }
 
                 let mut batch_response: BatchResponse<B, T> = batch.try_into()?;
@@ -424,14 +734,67 @@ impl LedgerDB {
             }
         })
     }
+
+    fn populate_tx_response<T: DeserializeOwned>(
+        &self,
+        tx: StoredTransaction,
+        mode: QueryMode,
+    ) -> Result<TxResponse<T>, anyhow::Error> {
+        let events = match mode {
+            QueryMode::Full => Some(self.get_event_range(&tx.events)?),
+            QueryMode::Compact | QueryMode::Standard => None,
+        };
+        let mut tx_response: TxResponse<T> = tx.try_into()?;
+        tx_response.events = events;
+        Ok(tx_response)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use sov_mock_da::{MockBlob, MockBlock};
-    use sov_rollup_interface::rpc::LedgerRpcProvider;
+    use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader};
+    use sov_rollup_interface::rpc::{
+        EventCursor, EventFilter, EventIdentifier, LedgerRpcProvider, ProofStatus, QueryMode,
+        SlotIdentifier, TxIdAndKey, TxIdentifier,
+    };
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+    use sov_schema_db::SchemaBatch;
+
+    use crate::ledger_db::{ItemNumbers, LedgerDB, SlotCommit, LEDGER_SCHEMA_VERSION};
+    use crate::pruning::LedgerPruningConfig;
+    use crate::schema::tables::LedgerSchemaVersion;
+    use crate::schema::types::{
+        StoredProof, StoredProofStatus, StoredProverJob, MAX_PROVER_JOB_ATTEMPTS,
+    };
+
+    /// Commits a slot with a single batch, whose transactions emit the given events.
+    fn commit_slot_with_events(db: &LedgerDB, txs: &[&[(&str, &str)]]) {
+        let tx_receipts = txs
+            .iter()
+            .map(|events| TransactionReceipt {
+                tx_hash: [0; 32],
+                body_to_save: None,
+                events: events.iter().map(|(k, v)| Event::new(k, v)).collect(),
+                receipt: (),
+            })
+            .collect();
+        let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+        commit.add_batch(BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts,
+            inner: (),
+        });
+        db.commit_slot(commit).unwrap();
+    }
 
-    use crate::ledger_db::{LedgerDB, SlotCommit};
+    fn event_numbers(db: &LedgerDB, filter: &EventFilter) -> Vec<u64> {
+        db.get_events_by_filter(filter, None, 10)
+            .unwrap()
+            .events
+            .into_iter()
+            .map(|event| event.number)
+            .collect()
+    }
     #[test]
     fn test_slot_subscription() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -444,4 +807,424 @@ mod tests {
 
         assert_eq!(rx.blocking_recv().unwrap(), 1);
     }
+
+    #[test]
+    fn test_proof_status() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let slot_hash = [1; 32];
+        let slot_id = SlotIdentifier::Hash(slot_hash);
+
+        assert_eq!(db.get_proof_status(&slot_id).unwrap(), None);
+
+        db.put_prover_job(
+            &slot_hash,
+            &StoredProverJob {
+                da_height: 1,
+                status: StoredProofStatus::Pending,
+                failed_attempts: 0,
+                witness: vec![1, 2, 3].into(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_proof_status(&slot_id).unwrap(),
+            Some(ProofStatus::Pending)
+        );
+
+        db.set_proof_status(&slot_hash, StoredProofStatus::Proving)
+            .unwrap();
+        assert_eq!(db.get_unfinished_prover_jobs().unwrap().len(), 1);
+
+        db.put_proof(
+            &slot_hash,
+            &StoredProof {
+                da_transaction_id: vec![4].into(),
+                proof: vec![5].into(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_proof_status(&slot_id).unwrap(),
+            Some(ProofStatus::Submitted {
+                da_transaction_id: vec![4]
+            })
+        );
+        assert!(db.get_unfinished_prover_jobs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_prover_job_is_abandoned() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let slot_hash = [1; 32];
+        let slot_id = SlotIdentifier::Hash(slot_hash);
+
+        db.put_prover_job(
+            &slot_hash,
+            &StoredProverJob {
+                da_height: 1,
+                status: StoredProofStatus::Pending,
+                failed_attempts: 0,
+                witness: vec![1, 2, 3].into(),
+            },
+        )
+        .unwrap();
+
+        // The job is resumed after each failure, until it fails for the last time.
+        for _ in 1..MAX_PROVER_JOB_ATTEMPTS {
+            db.set_proof_status(&slot_hash, StoredProofStatus::Proving)
+                .unwrap();
+            db.set_proof_status(&slot_hash, StoredProofStatus::Failed("error".to_string()))
+                .unwrap();
+            assert_eq!(db.get_unfinished_prover_jobs().unwrap().len(), 1);
+            assert_eq!(
+                db.get_proof_status(&slot_id).unwrap(),
+                Some(ProofStatus::Failed {
+                    reason: "error".to_string(),
+                    abandoned: false,
+                })
+            );
+        }
+
+        db.set_proof_status(&slot_hash, StoredProofStatus::Proving)
+            .unwrap();
+        db.set_proof_status(&slot_hash, StoredProofStatus::Failed("error".to_string()))
+            .unwrap();
+        assert!(db.get_unfinished_prover_jobs().unwrap().is_empty());
+        assert_eq!(
+            db.get_proof_status(&slot_id).unwrap(),
+            Some(ProofStatus::Failed {
+                reason: "error".to_string(),
+                abandoned: true,
+            })
+        );
+        let job = db.get_prover_job(&slot_hash).unwrap().unwrap();
+        assert!(job.witness.as_ref().is_empty());
+    }
+
+    #[test]
+    fn test_pruned_slots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for _ in 0..3 {
+            db.commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(MockBlock::default()))
+                .unwrap();
+        }
+
+        let config = LedgerPruningConfig { keep_last_slots: 1 };
+        assert_eq!(db.prune(&config).unwrap(), 2);
+        assert_eq!(db.prune(&config).unwrap(), 0);
+        assert_eq!(db.get_pruning_horizon().unwrap().slot_number, 3);
+
+        let err = db
+            .get_slot_by_number::<u32, u32>(1, QueryMode::Compact)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Slot 1 has been pruned");
+        assert!(db
+            .get_slot_by_number::<u32, u32>(3, QueryMode::Compact)
+            .unwrap()
+            .is_some());
+        // Slots which have not been committed yet are not reported as pruned.
+        assert!(db
+            .get_slot_by_number::<u32, u32>(4, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_prune_proofs_and_prover_jobs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for height in 1..=2u8 {
+            let block = MockBlock {
+                header: MockBlockHeader {
+                    prev_hash: [height - 1; 32].into(),
+                    hash: [height; 32].into(),
+                    height: height.into(),
+                },
+                ..Default::default()
+            };
+            db.commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(block))
+                .unwrap();
+            db.put_prover_job(
+                &[height; 32],
+                &StoredProverJob {
+                    da_height: height.into(),
+                    status: StoredProofStatus::Pending,
+                    failed_attempts: 0,
+                    witness: vec![height].into(),
+                },
+            )
+            .unwrap();
+            db.put_proof(
+                &[height; 32],
+                &StoredProof {
+                    da_transaction_id: vec![height].into(),
+                    proof: vec![height].into(),
+                },
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            db.prune(&LedgerPruningConfig { keep_last_slots: 1 })
+                .unwrap(),
+            1
+        );
+        assert!(db.get_prover_job(&[1; 32]).unwrap().is_none());
+        assert!(db.get_proof_by_slot_hash(&[1; 32]).unwrap().is_none());
+        assert!(db.get_prover_job(&[2; 32]).unwrap().is_some());
+        assert!(db.get_proof_by_slot_hash(&[2; 32]).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_events_by_filter() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        // Keys are made of a module and a kind, so "a0" is an event of kind `b'0'` of module "a".
+        // Slot 1 contains transactions 1 and 2 (events 1 to 3), slot 2 contains
+        // transaction 3 (events 4 and 5).
+        commit_slot_with_events(&db, &[&[("a0", "1"), ("b0", "2")], &[("a1", "3")]]);
+        commit_slot_with_events(&db, &[&[("ab0", "4"), ("a0", "5")]]);
+
+        let by_module = EventFilter {
+            module: Some(b"a".to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(event_numbers(&db, &by_module), vec![1, 3, 5]);
+        assert_eq!(
+            event_numbers(
+                &db,
+                &EventFilter {
+                    kind: Some(b'0'),
+                    ..by_module.clone()
+                }
+            ),
+            vec![1, 5]
+        );
+        assert_eq!(
+            event_numbers(
+                &db,
+                &EventFilter {
+                    kind: Some(b'0'),
+                    ..Default::default()
+                }
+            ),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            event_numbers(&db, &EventFilter::default()),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            event_numbers(
+                &db,
+                &EventFilter {
+                    key_prefix: Some(b"a".to_vec()),
+                    slot_range: Some(2..3),
+                    ..Default::default()
+                }
+            ),
+            vec![4, 5]
+        );
+        assert_eq!(
+            event_numbers(
+                &db,
+                &EventFilter {
+                    tx_range: Some(1..2),
+                    ..Default::default()
+                }
+            ),
+            vec![1, 2]
+        );
+
+        // Pages resume from the cursor of the previous page.
+        let page = db.get_events_by_filter(&by_module, None, 2).unwrap();
+        assert_eq!(page.events.len(), 2);
+        assert_eq!(
+            page.next_cursor,
+            Some(EventCursor {
+                tx_number: 2,
+                event_number: 4
+            })
+        );
+        let page = db
+            .get_events_by_filter(&by_module, page.next_cursor, 2)
+            .unwrap();
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].event, Event::new("a0", "5"));
+        assert_eq!(page.next_cursor, None);
+
+        let events = db
+            .get_events(&[EventIdentifier::TxIdAndKey(TxIdAndKey {
+                tx_id: TxIdentifier::Number(3),
+                key: Event::new("a0", "").key().clone(),
+            })])
+            .unwrap();
+        assert_eq!(events, vec![Some(Event::new("a0", "5"))]);
+    }
+
+    #[test]
+    fn test_schema_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        commit_slot_with_events(&db, &[&[("a0", "1")]]);
+        drop(db);
+        // A ledger written with the current schema can be reopened.
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        // A non-empty ledger written before the schema was versioned is rejected.
+        let mut schema_batch = SchemaBatch::new();
+        schema_batch.delete::<LedgerSchemaVersion>(&()).unwrap();
+        db.db.write_schemas(schema_batch).unwrap();
+        drop(db);
+        assert!(LedgerDB::with_path(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_other_schema_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        // A ledger written with another schema is rejected, even if it is empty.
+        db.db
+            .put::<LedgerSchemaVersion>(&(), &(LEDGER_SCHEMA_VERSION + 1))
+            .unwrap();
+        drop(db);
+        assert!(LedgerDB::with_path(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_executed_slots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+        commit.add_batch(BatchReceipt {
+            batch_hash: [1; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [2; 32],
+                body_to_save: None,
+                events: vec![Event::new("a", "1")],
+                receipt: (),
+            }],
+            inner: (),
+        });
+
+        let mut rx = db.subscribe_executed_slots().unwrap();
+        db.put_executed_slot(commit).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), 1);
+        let slot = db
+            .get_executed_slot::<(), ()>(1, QueryMode::Compact)
+            .unwrap()
+            .unwrap();
+        assert_eq!(slot.batch_range, 1..2);
+        let tx = db
+            .get_executed_tx_by_hash::<()>(&[2; 32], QueryMode::Full)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.event_range, 1..2);
+        assert_eq!(tx.events, Some(vec![Event::new("a", "1")]));
+        // Executed slots are committed right away.
+        assert_eq!(
+            db.get_tx_by_hash::<()>(&[2; 32], QueryMode::Full).unwrap(),
+            Some(tx.clone())
+        );
+
+        // Once finalized, the slot is not reported as executed anymore, and can't be reverted.
+        let mut finalized_rx = db.subscribe_slots().unwrap();
+        assert!(db.finalize_executed_slot(&[2; 32]).is_err());
+        db.finalize_executed_slot(&MockBlock::default().header.hash.into())
+            .unwrap();
+        assert_eq!(finalized_rx.try_recv().unwrap(), 1);
+        assert!(db
+            .get_executed_slot::<(), ()>(1, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get_executed_tx_by_hash::<()>(&[2; 32], QueryMode::Full)
+            .unwrap()
+            .is_none());
+        assert!(db.revert_executed_slot().is_err());
+
+        // A reverted slot is deleted, and its numbers are reused by the next slot.
+        let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+        commit.add_batch(BatchReceipt {
+            batch_hash: [3; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [4; 32],
+                body_to_save: None,
+                events: vec![Event::new("b", "2")],
+                receipt: (),
+            }],
+            inner: (),
+        });
+        db.put_executed_slot(commit.clone()).unwrap();
+        assert!(db
+            .get_executed_slot::<(), ()>(2, QueryMode::Compact)
+            .unwrap()
+            .is_some());
+        db.revert_executed_slot().unwrap();
+        assert!(db
+            .get_slot_by_number::<(), ()>(2, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get_tx_by_hash::<()>(&[4; 32], QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            db.get_next_items_numbers(),
+            ItemNumbers {
+                slot_number: 2,
+                batch_number: 2,
+                tx_number: 2,
+                event_number: 2,
+            }
+        );
+        db.put_executed_slot(commit).unwrap();
+        let tx = db
+            .get_executed_tx_by_hash::<()>(&[4; 32], QueryMode::Full)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.event_range, 2..3);
+
+        // Slots which are not finalized are reverted when the ledger is reopened.
+        drop(db);
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert!(db
+            .get_slot_by_number::<(), ()>(2, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        assert_eq!(db.get_next_items_numbers().slot_number, 2);
+    }
+
+    #[test]
+    fn test_tx_hashes_by_slot_number() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let tx_receipt = |tx_hash| TransactionReceipt {
+            tx_hash,
+            body_to_save: None,
+            events: vec![],
+            receipt: (),
+        };
+        let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+        for tx_hashes in [[[1; 32], [2; 32]], [[3; 32], [4; 32]]] {
+            commit.add_batch(BatchReceipt {
+                batch_hash: [0; 32],
+                tx_receipts: tx_hashes.into_iter().map(tx_receipt).collect(),
+                inner: (),
+            });
+        }
+        db.commit_slot(commit).unwrap();
+        db.commit_slot(SlotCommit::<_, (), ()>::new(MockBlock::default()))
+            .unwrap();
+
+        assert_eq!(
+            db.get_tx_hashes_by_slot_number(1).unwrap(),
+            Some(vec![[1; 32], [2; 32], [3; 32], [4; 32]])
+        );
+        assert_eq!(db.get_tx_hashes_by_slot_number(2).unwrap(), Some(vec![]));
+        assert_eq!(db.get_tx_hashes_by_slot_number(3).unwrap(), None);
+    }
 }
@@ -3,6 +3,9 @@
 //! - Types and traits for storing and retrieving ledger data can be found in the [`ledger_db`] module
 //! - DB "Table" definitions can be found in the [`schema`] module
 //! - Types and traits for storing state data can be found in the [`state_db`] module
+//! - The write-ahead log of the sequencer's mempool can be found in the [`sequencer_db`] module
+//! - Unfinalized snapshots on top of the state and native databases are handled by the [`snapshot_manager`] module
+//! - Background pruning of stale state and old ledger data is implemented in the [`pruning`] module
 //! - The default db configuration is generated in the [`rocks_db_config`] module
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
@@ -11,10 +14,18 @@
 /// This wrapper implements helper traits for writing blocks to the ledger, and for
 /// serving historical data via RPC
 pub mod ledger_db;
+/// Implements the pruning policies of the state and ledger databases.
+pub mod pruning;
 /// Implements helpers for configuring RocksDB.
 pub mod rocks_db_config;
 /// Defines the tables used by the Sovereign SDK.
 pub mod schema;
+/// Implements a wrapper around RocksDB meant for persisting the transactions and blobs
+/// of the sequencer until they are observed in a committed slot.
+pub mod sequencer_db;
+/// Implements a [`sov_schema_db::snapshot::QueryManager`] that traverses unfinalized
+/// snapshots down to the underlying RocksDB instance.
+pub mod snapshot_manager;
 /// Implements a wrapper around [RocksDB](https://rocksdb.org/) meant for storing rollup state.
 /// This is primarily used as the backing store for the [JMT(JellyfishMerkleTree)](https://docs.rs/jmt/latest/jmt/).
 pub mod state_db;
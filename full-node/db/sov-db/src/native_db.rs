@@ -1,58 +1,94 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use sov_schema_db::{SchemaBatch, DB};
+use sov_schema_db::snapshot::{DbSnapshot, FrozenDbSnapshot, QueryManager};
+use sov_schema_db::DB;
 
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{ModuleAccessoryState, NATIVE_TABLES};
 use crate::schema::types::StateKey;
+use crate::snapshot_manager::SnapshotManager;
+
+/// A typed wrapper around a [`DbSnapshot`] for storing native-only accessory state.
+/// Internally, this is roughly just an [`Arc<DbSnapshot>`].
+#[derive(Debug)]
+pub struct NativeDB<Q> {
+    /// The underlying [`DbSnapshot`], wrapped in an [`Arc`] for convenience.
+    db: Arc<DbSnapshot<Q>>,
+}
 
-/// A typed wrapper around RocksDB for storing native-only accessory state.
-/// Internally, this is roughly just an [`Arc<SchemaDB>`].
-#[derive(Clone, Debug)]
-pub struct NativeDB {
-    /// The underlying RocksDB instance, wrapped in an [`Arc`] for convenience
-    /// and [`DB`] for type safety.
-    db: Arc<DB>,
+impl<Q> Clone for NativeDB<Q> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+        }
+    }
 }
 
-impl NativeDB {
+impl NativeDB<SnapshotManager> {
     const DB_PATH_SUFFIX: &'static str = "native";
     const DB_NAME: &'static str = "native-db";
 
-    /// Opens a [`NativeDB`] (backed by RocksDB) at the specified path.
+    /// Opens the RocksDB instance which backs [`NativeDB`] at the specified path.
     /// The returned instance will be at the path `{path}/native-db`.
-    pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+    pub fn setup_schema_db(path: impl AsRef<Path>) -> Result<DB, anyhow::Error> {
         let path = path.as_ref().join(Self::DB_PATH_SUFFIX);
-        let inner = DB::open(
+        DB::open(
             path,
             Self::DB_NAME,
             NATIVE_TABLES.iter().copied(),
             &gen_rocksdb_options(&Default::default(), false),
-        )?;
+        )
+    }
+
+    /// Opens a [`NativeDB`] at the specified path, which reads directly from the database.
+    /// Writes are kept in memory and never persisted, so it is only suitable for tests.
+    #[cfg(any(test, feature = "arbitrary"))]
+    fn orphan(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let snapshot_manager = SnapshotManager::orphan(Self::setup_schema_db(path)?);
+        let db_snapshot = DbSnapshot::new(
+            0,
+            sov_schema_db::snapshot::ReadOnlyLock::new(Arc::new(std::sync::RwLock::new(
+                snapshot_manager,
+            ))),
+        );
+        Ok(Self::with_db_snapshot(db_snapshot))
+    }
+}
+
+impl<Q: QueryManager> NativeDB<Q> {
+    /// Creates a [`NativeDB`] on top of the given [`DbSnapshot`].
+    pub fn with_db_snapshot(db_snapshot: DbSnapshot<Q>) -> Self {
+        Self {
+            db: Arc::new(db_snapshot),
+        }
+    }
 
-        Ok(Self {
-            db: Arc::new(inner),
-        })
+    /// Consumes the [`NativeDB`] and returns all the writes made to it as a [`FrozenDbSnapshot`].
+    /// Fails if any clones of this [`NativeDB`] are still alive.
+    pub fn freeze(self) -> Result<FrozenDbSnapshot, anyhow::Error> {
+        let db_snapshot = Arc::try_unwrap(self.db).map_err(|_| {
+            anyhow::anyhow!("NativeDB cannot be frozen while there are other references to it")
+        })?;
+        Ok(db_snapshot.into())
     }
 
     /// Queries for a value in the [`NativeDB`], given a key.
     pub fn get_value_option(&self, key: &StateKey) -> anyhow::Result<Option<Vec<u8>>> {
         self.db
-            .get::<ModuleAccessoryState>(key)
+            .read::<ModuleAccessoryState>(key)
             .map(Option::flatten)
     }
 
-    /// Sets a sequence of key-value pairs in the [`NativeDB`]. The write is atomic.
+    /// Sets a sequence of key-value pairs in the [`NativeDB`].
     pub fn set_values(
         &self,
         key_value_pairs: impl IntoIterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
     ) -> anyhow::Result<()> {
-        let mut batch = SchemaBatch::default();
         for (key, value) in key_value_pairs {
-            batch.put::<ModuleAccessoryState>(&key, &value)?;
+            self.db.put::<ModuleAccessoryState>(&key, &value)?;
         }
-        self.db.write_schemas(batch)
+        Ok(())
     }
 }
 
@@ -74,7 +110,7 @@ pub mod arbitrary {
     #[derive(Debug)]
     pub struct ArbitraryNativeDB {
         /// The underlying RocksDB instance.
-        pub db: NativeDB,
+        pub db: NativeDB<SnapshotManager>,
         /// The temporary directory used to create the [`NativeDB`].
         pub path: TempDir,
     }
@@ -90,7 +126,7 @@ pub mod arbitrary {
     }
 
     impl Deref for ArbitraryNativeDB {
-        type Target = NativeDB;
+        type Target = NativeDB<SnapshotManager>;
 
         fn deref(&self) -> &Self::Target {
             &self.db
@@ -106,7 +142,7 @@ pub mod arbitrary {
     impl<'a> ::arbitrary::Arbitrary<'a> for ArbitraryNativeDB {
         fn arbitrary(_u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
             let path = TempDir::new().map_err(|_| ::arbitrary::Error::NotEnoughData)?;
-            let db = NativeDB::with_path(&path).map_err(|_| ::arbitrary::Error::IncorrectFormat)?;
+            let db = NativeDB::orphan(&path).map_err(|_| ::arbitrary::Error::IncorrectFormat)?;
             Ok(Self { db, path })
         }
     }
@@ -123,7 +159,7 @@ pub mod arbitrary {
                             anyhow::anyhow!(format!("failed to generate path for NativeDB: {e}"))
                         })
                         .and_then(|path| {
-                            let db = NativeDB::with_path(&path)?;
+                            let db = NativeDB::orphan(&path)?;
                             Ok(ArbitraryNativeDB { db, path })
                         }),
                 }
@@ -140,7 +176,7 @@ mod tests {
     #[test]
     fn get_after_set() {
         let tmpdir = tempfile::tempdir().unwrap();
-        let db = NativeDB::with_path(tmpdir.path()).unwrap();
+        let db = NativeDB::orphan(tmpdir.path()).unwrap();
 
         let key = b"foo".to_vec();
         let value = b"bar".to_vec();
@@ -152,7 +188,7 @@ mod tests {
     #[test]
     fn get_after_delete() {
         let tmpdir = tempfile::tempdir().unwrap();
-        let db = NativeDB::with_path(tmpdir.path()).unwrap();
+        let db = NativeDB::orphan(tmpdir.path()).unwrap();
 
         let key = b"deleted".to_vec();
         db.set_values(vec![(key.clone(), None)]).unwrap();
@@ -162,7 +198,7 @@ mod tests {
     #[test]
     fn get_nonexistent() {
         let tmpdir = tempfile::tempdir().unwrap();
-        let db = NativeDB::with_path(tmpdir.path()).unwrap();
+        let db = NativeDB::orphan(tmpdir.path()).unwrap();
 
         let key = b"spam".to_vec();
         assert_eq!(db.get_value_option(&key).unwrap(), None);
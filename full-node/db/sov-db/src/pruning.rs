@@ -0,0 +1,363 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use jmt::Version;
+use serde::Deserialize;
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::schema::tables::{
+    JmtNodes, JmtValues, StaleJmtNodes, StaleJmtValues, StatePruningHorizon,
+};
+use crate::schema::types::PrunedVersions;
+use crate::snapshot_manager::SnapshotManager;
+
+/// The maximum number of stale entries processed while holding the lock of the snapshot manager.
+const STATE_PRUNING_CHUNK_SIZE: usize = 10_000;
+
+/// Pruning policy of the state and ledger databases. Nothing is pruned by default.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PruningConfig {
+    /// Which versions of the state to keep. The state is never pruned if this is `None`.
+    #[serde(default)]
+    pub state: Option<StatePruningConfig>,
+    /// Which slots of the ledger to keep. The ledger is never pruned if this is `None`.
+    #[serde(default)]
+    pub ledger: Option<LedgerPruningConfig>,
+    /// The delay between two pruning runs, in seconds.
+    #[serde(default = "default_pruning_interval_secs")]
+    pub interval_secs: u64,
+}
+
+const fn default_pruning_interval_secs() -> u64 {
+    60
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self {
+            state: None,
+            ledger: None,
+            interval_secs: default_pruning_interval_secs(),
+        }
+    }
+}
+
+impl PruningConfig {
+    /// Returns the delay between two pruning runs.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+/// Pruning policy of the state database.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct StatePruningConfig {
+    /// The number of latest versions of the state to keep. At least the latest version is kept.
+    pub keep_last_versions: u64,
+    /// If set, every version which is a multiple of this interval is kept as a checkpoint.
+    /// Changing the interval only affects the versions pruned afterwards.
+    #[serde(default)]
+    pub checkpoint_interval: Option<u64>,
+}
+
+impl StatePruningConfig {
+    /// Returns `true` if a checkpoint falls in `first_version..end_version`.
+    fn has_checkpoint_in(&self, first_version: Version, end_version: Version) -> bool {
+        match self.checkpoint_interval {
+            Some(interval) if interval > 0 => {
+                let next_checkpoint = (first_version + interval - 1) / interval * interval;
+                next_checkpoint < end_version
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Pruning policy of the ledger database.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LedgerPruningConfig {
+    /// The number of latest slots to keep, along with their batches, transactions and events.
+    /// At least the latest slot is kept.
+    pub keep_last_slots: u64,
+}
+
+/// Deletes the JMT nodes and values which are only part of pruned versions of the state.
+///
+/// Every committed JMT update records the nodes and values it replaces in the stale indexes.
+/// A stale entry is only visible from the versions between the one which wrote it and the one
+/// which replaced it, so it can be deleted once all of these versions are pruned.
+pub struct StatePruner {
+    snapshot_manager: Arc<RwLock<SnapshotManager>>,
+    config: StatePruningConfig,
+}
+
+impl StatePruner {
+    /// Creates a new [`StatePruner`] for the database of the given [`SnapshotManager`].
+    pub fn new(snapshot_manager: Arc<RwLock<SnapshotManager>>, config: StatePruningConfig) -> Self {
+        Self {
+            snapshot_manager,
+            config,
+        }
+    }
+
+    /// Prunes every version of the state which is outside the retention window, and returns the
+    /// number of deleted nodes and values. The database is pruned in chunks, so that snapshots
+    /// can be committed in between.
+    pub fn prune(&self) -> anyhow::Result<usize> {
+        let mut deleted = 0;
+        loop {
+            let snapshot_manager = self
+                .snapshot_manager
+                .read()
+                .expect("Snapshot manager lock is poisoned");
+            let (processed, deleted_in_chunk) =
+                prune_state_chunk(snapshot_manager.db(), &self.config)?;
+            deleted += deleted_in_chunk;
+            if processed < STATE_PRUNING_CHUNK_SIZE {
+                return Ok(deleted);
+            }
+        }
+    }
+}
+
+/// Processes up to [`STATE_PRUNING_CHUNK_SIZE`] stale entries, and returns the number of
+/// processed entries along with the number of deleted nodes and values.
+fn prune_state_chunk(db: &DB, config: &StatePruningConfig) -> anyhow::Result<(usize, usize)> {
+    let mut nodes = db.iter::<JmtNodes>()?;
+    nodes.seek_to_last();
+    let latest_version = match nodes.next().transpose()? {
+        Some((node_key, _)) => node_key.version(),
+        None => return Ok((0, 0)),
+    };
+
+    // Versions `prune_below..=latest_version` are kept. The horizon never moves backwards,
+    // since the versions below it may be missing entirely, e.g. after a snapshot import.
+    let horizon = db.get::<StatePruningHorizon>(&())?;
+    let prune_below = (latest_version + 1)
+        .saturating_sub(config.keep_last_versions.max(1))
+        .max(horizon.map_or(0, |horizon| horizon.below));
+    let pruned_versions = PrunedVersions {
+        below: prune_below,
+        checkpoint_interval: config.checkpoint_interval,
+    };
+    // Publish the new horizon first, so that readers get a clear error instead of missing nodes.
+    if horizon.as_ref() != Some(&pruned_versions) {
+        db.put::<StatePruningHorizon>(&(), &pruned_versions)?;
+    }
+
+    let mut batch = SchemaBatch::new();
+    let mut processed = 0;
+    let mut deleted = 0;
+
+    let mut stale_nodes = db.iter::<StaleJmtNodes>()?;
+    stale_nodes.seek_to_first();
+    for item in stale_nodes.take(STATE_PRUNING_CHUNK_SIZE) {
+        let ((stale_since, node_key), ()) = item?;
+        if stale_since > prune_below {
+            break;
+        }
+        if !config.has_checkpoint_in(node_key.version(), stale_since) {
+            batch.delete::<JmtNodes>(&node_key)?;
+            deleted += 1;
+        }
+        batch.delete::<StaleJmtNodes>(&(stale_since, node_key))?;
+        processed += 1;
+    }
+
+    let mut stale_values = db.iter::<StaleJmtValues>()?;
+    stale_values.seek_to_first();
+    for item in stale_values.take(STATE_PRUNING_CHUNK_SIZE - processed) {
+        let ((stale_since, key, version), ()) = item?;
+        if stale_since > prune_below {
+            break;
+        }
+        if !config.has_checkpoint_in(version, stale_since) {
+            batch.delete::<JmtValues>(&(key.clone(), version))?;
+            deleted += 1;
+        }
+        batch.delete::<StaleJmtValues>(&(stale_since, key, version))?;
+        processed += 1;
+    }
+
+    db.write_schemas(batch)?;
+    Ok((processed, deleted))
+}
+
+/// A pruner running on a dedicated thread, see [`spawn_pruner`].
+///
+/// The pruner is stopped when the handle is dropped: the current run completes, and the thread
+/// is joined.
+pub struct PrunerHandle {
+    name: String,
+    /// Dropped to wake up the pruner and make it exit.
+    stop_sender: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PrunerHandle {
+    /// Stops the pruner after its current run, and waits for its thread to exit.
+    pub fn stop(mut self) {
+        self.stop_and_join();
+    }
+
+    fn stop_and_join(&mut self) {
+        self.stop_sender.take();
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                tracing::error!(pruner = self.name, "Pruner thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for PrunerHandle {
+    fn drop(&mut self) {
+        self.stop_and_join();
+    }
+}
+
+/// Runs `prune` on a dedicated thread every `interval`, until the returned handle is dropped.
+/// Failed runs are logged and retried on the next tick.
+pub fn spawn_pruner(
+    name: &str,
+    interval: Duration,
+    mut prune: impl FnMut() -> anyhow::Result<usize> + Send + 'static,
+) -> std::io::Result<PrunerHandle> {
+    let (stop_sender, stop_receiver) = mpsc::channel::<()>();
+    let thread_name = name.to_string();
+    let thread = std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || loop {
+            match prune() {
+                Ok(0) => {}
+                Ok(pruned) => tracing::debug!(pruner = thread_name, pruned, "Pruned stale data"),
+                Err(e) => tracing::error!(pruner = thread_name, error = ?e, "Pruning failed"),
+            }
+            match stop_receiver.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => {}
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+        })?;
+
+    Ok(PrunerHandle {
+        name: name.to_string(),
+        stop_sender: Some(stop_sender),
+        thread: Some(thread),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use jmt::storage::{Node, NodeBatch, NodeKey, StaleNodeIndex, TreeWriter};
+    use jmt::KeyHash;
+    use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock};
+
+    use super::*;
+    use crate::state_db::StateDB;
+
+    const KEY_HASH: KeyHash = KeyHash([1u8; 32]);
+
+    fn key() -> Vec<u8> {
+        vec![2u8; 32]
+    }
+
+    fn state_db(snapshot_manager: &Arc<RwLock<SnapshotManager>>) -> StateDB<SnapshotManager> {
+        StateDB::with_db_snapshot(DbSnapshot::new(
+            0,
+            ReadOnlyLock::new(snapshot_manager.clone()),
+        ))
+    }
+
+    /// Writes one version of the state per value, each replacing the root node and the value
+    /// of the previous version.
+    fn write_versions(snapshot_manager: &Arc<RwLock<SnapshotManager>>, values: &[u8]) {
+        let db = state_db(snapshot_manager);
+        db.put_preimages([(KEY_HASH, &key())]).unwrap();
+        for (version, value) in values.iter().enumerate() {
+            let version = version as Version;
+            let mut batch = NodeBatch::default();
+            batch.extend(
+                vec![(NodeKey::new_empty_path(version), Node::Null)],
+                vec![((version, KEY_HASH), Some(vec![*value]))],
+            );
+            db.write_node_batch(&batch).unwrap();
+            if version > 0 {
+                db.put_stale_node_indices(&[StaleNodeIndex {
+                    stale_since_version: version,
+                    node_key: NodeKey::new_empty_path(version - 1),
+                }])
+                .unwrap();
+            }
+        }
+        let mut snapshot_manager = snapshot_manager.write().unwrap();
+        snapshot_manager.add_snapshot(db.freeze().unwrap());
+        snapshot_manager.commit_snapshot(&0).unwrap();
+    }
+
+    #[test]
+    fn test_stop_pruner() {
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let pruner = {
+            let runs = runs.clone();
+            spawn_pruner("test-pruner", Duration::from_secs(3600), move || {
+                runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Ok(0)
+            })
+            .unwrap()
+        };
+        while runs.load(std::sync::atomic::Ordering::SeqCst) == 0 {
+            std::thread::yield_now();
+        }
+
+        // The pruner is woken up instead of waiting for the next tick.
+        let start = std::time::Instant::now();
+        pruner.stop();
+        assert!(start.elapsed() < Duration::from_secs(60));
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_prune_state_keeps_retained_versions_and_checkpoints() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let schema_db = StateDB::setup_schema_db(tmpdir.path()).unwrap();
+        let snapshot_manager = Arc::new(RwLock::new(SnapshotManager::orphan(schema_db)));
+        write_versions(&snapshot_manager, &[10, 11, 12, 13, 14, 15]);
+
+        let pruner = StatePruner::new(
+            snapshot_manager.clone(),
+            StatePruningConfig {
+                keep_last_versions: 2,
+                checkpoint_interval: Some(2),
+            },
+        );
+        // The root nodes and values of versions 1 and 3 are deleted.
+        assert_eq!(pruner.prune().unwrap(), 4);
+        // Nothing is left to prune.
+        assert_eq!(pruner.prune().unwrap(), 0);
+
+        let db = state_db(&snapshot_manager);
+        let pruned_versions = db.get_pruned_versions().unwrap().unwrap();
+        assert_eq!(pruned_versions.below, 4);
+        let schema_db = snapshot_manager.read().unwrap();
+        for version in [1, 3] {
+            assert!(pruned_versions.contains(version));
+            let node_key = NodeKey::new_empty_path(version);
+            assert!(schema_db.db().get::<JmtNodes>(&node_key).unwrap().is_none());
+            assert!(schema_db
+                .db()
+                .get::<JmtValues>(&(key(), version))
+                .unwrap()
+                .is_none());
+        }
+        drop(schema_db);
+        for (version, value) in [(0, 10), (2, 12), (4, 14), (5, 15)] {
+            assert!(!pruned_versions.contains(version));
+            assert_eq!(
+                db.get_value_option_by_key(version, &key()).unwrap(),
+                Some(vec![value])
+            );
+        }
+    }
+}
@@ -13,17 +13,39 @@
 //! - `TxNumber -> (TxHash,Tx)`
 //! - `TxHash -> TxNumber`
 //!
+//! Proof Tables:
+//! - `SlotHash -> StoredProof`
+//! - `SlotHash -> StoredProverJob`
+//!
 //! Event Tables:
-//! - `(EventKey, TxNumber) -> EventNumber`
+//! - `(EventKey, TxNumber, EventNumber) -> ()`
+//! - `(ModuleAddress, TxNumber, EventNumber) -> ()`
 //! - `EventNumber -> (EventKey, EventValue)`
 //!
+//! Ledger Pruning Tables:
+//! - `() -> ItemNumbers`
+//!
+//! Ledger Metadata Tables:
+//! - `() -> SchemaVersion`
+//!
 //! JMT Tables:
 //! - `KeyHash -> Key`
 //! - `(Key, Version) -> JmtValue`
 //! - `NodeKey -> Node`
 //!
+//! JMT Pruning Tables:
+//! - `(StaleSinceVersion, NodeKey) -> ()`
+//! - `(StaleSinceVersion, Key, Version) -> ()`
+//! - `() -> PrunedVersions`
+//!
 //! Module Accessory State Table:
 //! - `(ModuleAddress, Key) -> Value`
+//!
+//! Sequencer Tables:
+//! - `TxHash -> StoredMempoolTx`
+//! - `BlobNumber -> StoredSequencerBlob`
+//! - `SoftConfirmationPosition -> StoredSoftConfirmation`
+//! - `TxHash -> SoftConfirmationPosition`
 
 use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -34,9 +56,12 @@ use sov_schema_db::schema::{KeyDecoder, KeyEncoder, ValueCodec};
 use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
-    AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, EventNumber, JmtValue, SlotNumber,
-    StateKey, StoredBatch, StoredSlot, StoredTransaction, TxNumber,
+    AccessoryKey, AccessoryStateValue, BatchNumber, BlobNumber, DbHash, EventNumber, JmtValue,
+    PrunedVersions, SlotNumber, SoftConfirmationPosition, StateKey, StoredBatch, StoredMempoolTx,
+    StoredProof, StoredProverJob, StoredSequencerBlob, StoredSlot, StoredSoftConfirmation,
+    StoredTransaction, TxNumber,
 };
+use crate::ledger_db::ItemNumbers;
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
 /// account balances, nonces, etc.
@@ -44,6 +69,9 @@ pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
     JmtNodes::table_name(),
+    StaleJmtNodes::table_name(),
+    StaleJmtValues::table_name(),
+    StatePruningHorizon::table_name(),
 ];
 
 /// A list of all tables used by the LedgerDB. These tables store rollup "history" - meaning
@@ -56,7 +84,13 @@ pub const LEDGER_TABLES: &[&str] = &[
     TxByHash::table_name(),
     TxByNumber::table_name(),
     EventByKey::table_name(),
+    EventByModule::table_name(),
     EventByNumber::table_name(),
+    ProofBySlotHash::table_name(),
+    ProverJobBySlotHash::table_name(),
+    LedgerPruningHorizon::table_name(),
+    LedgerSchemaVersion::table_name(),
+    LastFinalizedSlot::table_name(),
 ];
 
 /// A list of all tables used by the NativeDB. These tables store
@@ -64,6 +98,16 @@ pub const LEDGER_TABLES: &[&str] = &[
 /// used for JSON-RPC and other tooling.
 pub const NATIVE_TABLES: &[&str] = &[ModuleAccessoryState::table_name()];
 
+/// A list of all tables used by the SequencerDB. These tables store the transactions
+/// and blobs of the sequencer until they are observed in a committed slot, and the soft
+/// confirmations of the sequencer until they are checked against a finalized slot.
+pub const SEQUENCER_TABLES: &[&str] = &[
+    MempoolTxByHash::table_name(),
+    SequencerBlobByNumber::table_name(),
+    SoftConfirmationByPosition::table_name(),
+    SoftConfirmationPositionByTxHash::table_name(),
+];
+
 /// Macro to define a table that implements [`sov_schema_db::Schema`].
 /// KeyCodec<Schema> and ValueCodec<Schema> must be implemented separately.
 ///
@@ -248,11 +292,68 @@ define_table_with_seek_key_codec!(
     (EventByNumber) EventNumber => Event
 );
 
-define_table_with_default_codec!(
-    /// A "secondary index" for event data by key
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for event data by key. Entries with the same key are
+    /// ordered by transaction and event number, so they can be iterated in the
+    /// order in which they were emitted.
     (EventByKey) (EventKey, TxNumber, EventNumber) => ()
 );
 
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for event data by the address of the module which emitted
+    /// the event, taken from the event key. Entries of the same module are ordered by
+    /// transaction and event number, like the ones of [`EventByKey`].
+    (EventByModule) (Vec<u8>, TxNumber, EventNumber) => ()
+);
+
+define_table_with_default_codec!(
+    /// Proofs published to the DA layer, indexed by the hash of the slot they prove
+    (ProofBySlotHash) DbHash => StoredProof
+);
+
+define_table_with_default_codec!(
+    /// Slots submitted for proving and the status of their proofs, indexed by slot hash
+    (ProverJobBySlotHash) DbHash => StoredProverJob
+);
+
+define_table_with_default_codec!(
+    /// The numbers of the first slot, batch, transaction and event which haven't been pruned.
+    /// Absent until the ledger is pruned for the first time.
+    (LedgerPruningHorizon) () => ItemNumbers
+);
+
+define_table_with_default_codec!(
+    /// The version of the layout of the ledger tables. Absent from ledgers written
+    /// before the layout was versioned.
+    (LedgerSchemaVersion) () => u64
+);
+
+define_table_with_default_codec!(
+    /// The number of the last slot finalized on the DA layer. The slots after it have been
+    /// executed, but can still be reverted. Absent if no slot has been committed yet.
+    (LastFinalizedSlot) () => SlotNumber
+);
+
+define_table_with_default_codec!(
+    /// Transactions accepted by the sequencer, which haven't been observed in a committed slot yet
+    (MempoolTxByHash) DbHash => StoredMempoolTx
+);
+
+define_table_with_seek_key_codec!(
+    /// Blobs published by the sequencer, which haven't been observed in a committed slot yet
+    (SequencerBlobByNumber) BlobNumber => StoredSequencerBlob
+);
+
+define_table_with_seek_key_codec!(
+    /// Soft confirmations of the sequencer, which haven't been checked against a finalized slot yet
+    (SoftConfirmationByPosition) SoftConfirmationPosition => StoredSoftConfirmation
+);
+
+define_table_with_default_codec!(
+    /// A "secondary index" for soft confirmation data by transaction hash
+    (SoftConfirmationPositionByTxHash) DbHash => SoftConfirmationPosition
+);
+
 define_table_without_codec!(
     /// The source of truth for JMT nodes
     (JmtNodes) NodeKey => Node
@@ -347,3 +448,73 @@ define_table_with_default_codec!(
     /// which requires the ability to fetch values by hash.
     (KeyHashToKey) [u8;32] => StateKey
 );
+
+/// Splits the big-endian version which prefixes the keys of the stale JMT indexes.
+fn decode_version_prefix(data: &[u8]) -> sov_schema_db::schema::Result<(Version, &[u8])> {
+    if data.len() < 8 {
+        return Err(CodecError::InvalidKeyLength {
+            expected: 8,
+            got: data.len(),
+        });
+    }
+    let (version, rest) = data.split_at(8);
+    let version = u64::from_be_bytes(version.try_into().expect("The prefix is 8 bytes long"));
+    Ok((version, rest))
+}
+
+define_table_without_codec!(
+    /// An index of the JMT nodes which are not part of the tree anymore, by the version which
+    /// replaced them. Used to prune the nodes once that version is old enough.
+    (StaleJmtNodes) (Version, NodeKey) => ()
+);
+
+impl KeyEncoder<StaleJmtNodes> for (Version, NodeKey) {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        // The version comes first, so that the index is sorted by the version which replaced the nodes
+        let mut output = self.0.to_be_bytes().to_vec();
+        output.extend(KeyEncoder::<JmtNodes>::encode_key(&self.1)?);
+        Ok(output)
+    }
+}
+
+impl KeyDecoder<StaleJmtNodes> for (Version, NodeKey) {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        let (stale_since, node_key) = decode_version_prefix(data)?;
+        Ok((
+            stale_since,
+            <NodeKey as KeyDecoder<JmtNodes>>::decode_key(node_key)?,
+        ))
+    }
+}
+
+impl_borsh_value_codec!(StaleJmtNodes, ());
+
+define_table_without_codec!(
+    /// An index of the JMT values which have been overwritten, by the version which overwrote
+    /// them. Used to prune the values once that version is old enough.
+    (StaleJmtValues) (Version, StateKey, Version) => ()
+);
+
+impl KeyEncoder<StaleJmtValues> for (Version, StateKey, Version) {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        let mut output = self.0.to_be_bytes().to_vec();
+        output.extend(KeyEncoder::<JmtValues>::encode_key(&(&self.1, self.2))?);
+        Ok(output)
+    }
+}
+
+impl KeyDecoder<StaleJmtValues> for (Version, StateKey, Version) {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        let (stale_since, value_key) = decode_version_prefix(data)?;
+        let (key, version) = <(StateKey, Version) as KeyDecoder<JmtValues>>::decode_key(value_key)?;
+        Ok((stale_since, key, version))
+    }
+}
+
+impl_borsh_value_codec!(StaleJmtValues, ());
+
+define_table_with_default_codec!(
+    /// The versions of the state which have been pruned.
+    /// Absent until the state is pruned for the first time.
+    (StatePruningHorizon) () => PrunedVersions
+);
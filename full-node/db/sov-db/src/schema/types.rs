@@ -56,7 +56,7 @@ pub(crate) type StateKey = Vec<u8>;
 /// The on-disk format of a slot. Specifies the batches contained in the slot
 /// and the hash of the da block. TODO(@preston-evans98): add any additional data
 /// required to reconstruct the da block proof.
-#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct StoredSlot {
     /// The slot's hash, as reported by the DA layer.
@@ -69,7 +69,7 @@ pub struct StoredSlot {
 
 /// The on-disk format for a batch. Stores the hash and identifies the range of transactions
 /// included in the batch.
-#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct StoredBatch {
     /// The hash of the batch, as reported by the DA layer.
@@ -92,6 +92,137 @@ impl<B: DeserializeOwned, T> TryFrom<StoredBatch> for BatchResponse<B, T> {
     }
 }
 
+/// The on-disk format of a proof which has been published to the DA layer.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct StoredProof {
+    /// The serialized id of the DA transaction which carries the proof.
+    /// For proofs published by other provers, this is the hash of the blob instead.
+    pub da_transaction_id: DbBytes,
+    /// The proof blob, exactly as it was posted to the DA layer.
+    pub proof: DbBytes,
+}
+
+/// The on-disk format of the status of a prover job.
+#[derive(Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub enum StoredProofStatus {
+    /// The slot is waiting to be proven.
+    Pending,
+    /// The proof of the slot is being generated.
+    Proving,
+    /// The slot has been proven, but there was no proof to publish.
+    Proved,
+    /// Proof generation failed with the given error. The job is retried with a backoff,
+    /// unless it has been abandoned. See [`StoredProverJob::is_abandoned`].
+    Failed(String),
+    /// The proof has been published to the DA layer. See [`StoredProof`].
+    Submitted,
+}
+
+impl StoredProofStatus {
+    /// Returns `true` if no more work is needed to prove the slot.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Proved | Self::Submitted)
+    }
+}
+
+/// How many times proving a slot can fail before its prover job is abandoned.
+pub const MAX_PROVER_JOB_ATTEMPTS: u32 = 3;
+
+/// The on-disk format of a slot which has been submitted for proving.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct StoredProverJob {
+    /// The height of the DA block, used to re-queue unfinished jobs in order.
+    pub da_height: u64,
+    /// The current status of the job.
+    pub status: StoredProofStatus,
+    /// How many times proof generation has failed for this slot.
+    pub failed_attempts: u32,
+    /// The serialized input of the prover. Cleared once the job is done or abandoned.
+    pub witness: DbBytes,
+}
+
+impl StoredProverJob {
+    /// Returns `true` if proof generation has failed too many times for the slot
+    /// to be proven again.
+    pub fn is_abandoned(&self) -> bool {
+        self.failed_attempts >= MAX_PROVER_JOB_ATTEMPTS
+    }
+}
+
+/// The on-disk format of a transaction accepted by the sequencer,
+/// which hasn't been observed in a committed slot yet.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
+pub struct StoredMempoolTx {
+    /// The order in which the sequencer accepted the transaction.
+    pub number: u64,
+    /// The serialized transaction.
+    pub body: Vec<u8>,
+}
+
+/// The on-disk format of a blob published by the sequencer,
+/// which hasn't been observed in a committed slot yet.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
+pub struct StoredSequencerBlob {
+    /// The hashes of the transactions in the blob, in order.
+    /// The transactions themselves are stored as [`StoredMempoolTx`].
+    pub tx_hashes: Vec<DbHash>,
+    /// How many times the blob has been submitted to the DA layer.
+    pub attempts: u32,
+}
+
+/// The on-disk format of the effect of a transaction, as pre-confirmed by the sequencer.
+#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize, Clone)]
+pub struct StoredSoftConfirmation {
+    /// The hash of the transaction.
+    pub tx_hash: DbHash,
+    /// The serialized receipt of the transaction.
+    pub custom_receipt: DbBytes,
+    /// The events emitted by the transaction.
+    pub events: Vec<Event>,
+}
+
+impl StoredSoftConfirmation {
+    /// Creates the soft confirmation of a transaction with the given receipt.
+    pub fn new<R: Serialize>(tx_hash: DbHash, receipt: &R, events: Vec<Event>) -> Self {
+        Self {
+            tx_hash,
+            custom_receipt: DbBytes::new(
+                bincode::serialize(receipt).expect("Serialization to vec is infallible"),
+            ),
+            events,
+        }
+    }
+
+    /// Returns true if the transaction had the pre-confirmed receipt and events
+    /// once it was executed.
+    pub fn matches<R: Serialize>(&self, tx: &TransactionReceipt<R>) -> bool {
+        let receipt = bincode::serialize(&tx.receipt).expect("Serialization to vec is infallible");
+        self.tx_hash == tx.tx_hash && *self.custom_receipt.0 == receipt && self.events == tx.events
+    }
+}
+
+/// The on-disk format of the versions of the state which have been pruned.
+#[derive(Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, Clone, Copy)]
+pub struct PrunedVersions {
+    /// Every version below this one has been pruned, apart from the checkpoints.
+    pub below: u64,
+    /// Every version which is a multiple of this interval is kept as a checkpoint.
+    pub checkpoint_interval: Option<u64>,
+}
+
+impl PrunedVersions {
+    /// Returns `true` if the given version of the state has been pruned.
+    pub fn contains(&self, version: u64) -> bool {
+        let is_checkpoint = self
+            .checkpoint_interval
+            .is_some_and(|interval| interval > 0 && version % interval == 0);
+        version < self.below && !is_checkpoint
+    }
+}
+
 /// The on-disk format of a transaction. Includes the txhash, the serialized tx data,
 /// and identifies the events emitted by this transaction
 #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize, Clone)]
@@ -113,6 +244,7 @@ impl<R: DeserializeOwned> TryFrom<StoredTransaction> for TxResponse<R> {
             hash: value.hash,
             event_range: value.events.start.into()..value.events.end.into(),
             body: value.body,
+            events: None,
             custom_receipt: bincode::deserialize(&value.custom_receipt.0)?,
         })
     }
@@ -197,6 +329,8 @@ u64_wrapper!(SlotNumber);
 u64_wrapper!(BatchNumber);
 u64_wrapper!(TxNumber);
 u64_wrapper!(EventNumber);
+u64_wrapper!(BlobNumber);
+u64_wrapper!(SoftConfirmationPosition);
 
 #[cfg(feature = "arbitrary")]
 pub mod arbitrary {
@@ -231,6 +365,15 @@ pub mod arbitrary {
         }
     }
 
+    impl<'a> ::arbitrary::Arbitrary<'a> for StoredProof {
+        fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+            Ok(StoredProof {
+                da_transaction_id: u.arbitrary()?,
+                proof: u.arbitrary()?,
+            })
+        }
+    }
+
     impl<'a> ::arbitrary::Arbitrary<'a> for StoredSlot {
         fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
             Ok(StoredSlot {
@@ -0,0 +1,277 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::rocks_db_config::gen_rocksdb_options;
+use crate::schema::tables::{
+    MempoolTxByHash, SequencerBlobByNumber, SoftConfirmationByPosition,
+    SoftConfirmationPositionByTxHash, SEQUENCER_TABLES,
+};
+use crate::schema::types::{
+    BlobNumber, DbHash, SoftConfirmationPosition, StoredMempoolTx, StoredSequencerBlob,
+    StoredSoftConfirmation,
+};
+
+const SEQUENCER_DB_PATH_SUFFIX: &str = "sequencer";
+
+/// A database which stores the transactions accepted by the sequencer and the blobs it has
+/// published, until they are observed in a committed slot. Serves as a write-ahead log, so that
+/// no transaction is lost if a blob fails to be published, or if the sequencer restarts.
+///
+/// Also stores the soft confirmations of the sequencer, until the runner checks them against
+/// the finalized slots.
+#[derive(Clone, Debug)]
+pub struct SequencerDB {
+    db: Arc<DB>,
+    next_tx_number: Arc<AtomicU64>,
+}
+
+impl SequencerDB {
+    /// Open a [`SequencerDB`] (backed by RocksDB) at the specified path.
+    /// The returned instance will be at the path `{path}/sequencer`.
+    pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref().join(SEQUENCER_DB_PATH_SUFFIX);
+        let inner = DB::open(
+            path,
+            "sequencer-db",
+            SEQUENCER_TABLES.iter().copied(),
+            &gen_rocksdb_options(&Default::default(), false),
+        )?;
+
+        let mut iter = inner.iter::<MempoolTxByHash>()?;
+        iter.seek_to_first();
+        let mut next_tx_number = 0;
+        for item in iter {
+            let (_, tx) = item?;
+            next_tx_number = next_tx_number.max(tx.number + 1);
+        }
+
+        Ok(Self {
+            db: Arc::new(inner),
+            next_tx_number: Arc::new(AtomicU64::new(next_tx_number)),
+        })
+    }
+
+    /// Records a transaction which has been accepted into the mempool.
+    pub fn put_tx(&self, hash: &DbHash, body: &[u8]) -> Result<(), anyhow::Error> {
+        let tx = StoredMempoolTx {
+            number: self.next_tx_number.fetch_add(1, Ordering::Relaxed),
+            body: body.to_vec(),
+        };
+        self.db.put::<MempoolTxByHash>(hash, &tx)
+    }
+
+    /// Gets all recorded transactions, in the order they were accepted.
+    pub fn get_txs(&self) -> Result<Vec<(DbHash, Vec<u8>)>, anyhow::Error> {
+        let mut iter = self.db.iter::<MempoolTxByHash>()?;
+        iter.seek_to_first();
+
+        let mut txs = Vec::new();
+        for item in iter {
+            txs.push(item?);
+        }
+        txs.sort_by_key(|(_, tx)| tx.number);
+        Ok(txs.into_iter().map(|(hash, tx)| (hash, tx.body)).collect())
+    }
+
+    /// Records a blob which is about to be submitted to the DA layer,
+    /// or updates the number of times it has been submitted.
+    pub fn put_blob(
+        &self,
+        number: BlobNumber,
+        blob: &StoredSequencerBlob,
+    ) -> Result<(), anyhow::Error> {
+        self.db.put::<SequencerBlobByNumber>(&number, blob)
+    }
+
+    /// Gets all recorded blobs, in the order they were published.
+    pub fn get_blobs(&self) -> Result<Vec<(BlobNumber, StoredSequencerBlob)>, anyhow::Error> {
+        let mut iter = self.db.iter::<SequencerBlobByNumber>()?;
+        iter.seek_to_first();
+
+        let mut blobs = Vec::new();
+        for item in iter {
+            blobs.push(item?);
+        }
+        Ok(blobs)
+    }
+
+    /// Atomically deletes the given transactions and blobs, once they don't need to be
+    /// published anymore.
+    pub fn delete<'a>(
+        &self,
+        tx_hashes: impl IntoIterator<Item = &'a DbHash>,
+        blobs: impl IntoIterator<Item = BlobNumber>,
+    ) -> Result<(), anyhow::Error> {
+        let mut schema_batch = SchemaBatch::new();
+        for hash in tx_hashes {
+            schema_batch.delete::<MempoolTxByHash>(hash)?;
+        }
+        for number in blobs {
+            schema_batch.delete::<SequencerBlobByNumber>(&number)?;
+        }
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Records the soft confirmation of a transaction at the given position.
+    pub fn put_soft_confirmation(
+        &self,
+        position: SoftConfirmationPosition,
+        confirmation: &StoredSoftConfirmation,
+    ) -> Result<(), anyhow::Error> {
+        let mut schema_batch = SchemaBatch::new();
+        schema_batch.put::<SoftConfirmationByPosition>(&position, confirmation)?;
+        schema_batch.put::<SoftConfirmationPositionByTxHash>(&confirmation.tx_hash, &position)?;
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Gets the soft confirmation of the transaction with the given hash, along with its position.
+    pub fn get_soft_confirmation(
+        &self,
+        tx_hash: &DbHash,
+    ) -> Result<Option<(SoftConfirmationPosition, StoredSoftConfirmation)>, anyhow::Error> {
+        let Some(position) = self.db.get::<SoftConfirmationPositionByTxHash>(tx_hash)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .db
+            .get::<SoftConfirmationByPosition>(&position)?
+            .map(|confirmation| (position, confirmation)))
+    }
+
+    /// Gets the position following the last recorded soft confirmation.
+    pub fn next_soft_confirmation_position(
+        &self,
+    ) -> Result<SoftConfirmationPosition, anyhow::Error> {
+        let mut iter = self.db.iter::<SoftConfirmationByPosition>()?;
+        iter.seek_to_last();
+        match iter.next() {
+            Some(item) => Ok(SoftConfirmationPosition(item?.0 .0 + 1)),
+            None => Ok(SoftConfirmationPosition(0)),
+        }
+    }
+
+    /// Atomically deletes all the soft confirmations up to the given position (inclusive),
+    /// and returns them in order.
+    pub fn take_soft_confirmations_up_to(
+        &self,
+        position: SoftConfirmationPosition,
+    ) -> Result<Vec<(SoftConfirmationPosition, StoredSoftConfirmation)>, anyhow::Error> {
+        let mut iter = self.db.iter::<SoftConfirmationByPosition>()?;
+        iter.seek_to_first();
+
+        let mut confirmations = Vec::new();
+        let mut schema_batch = SchemaBatch::new();
+        for item in iter {
+            let (current, confirmation) = item?;
+            if current > position {
+                break;
+            }
+            schema_batch.delete::<SoftConfirmationByPosition>(&current)?;
+            schema_batch.delete::<SoftConfirmationPositionByTxHash>(&confirmation.tx_hash)?;
+            confirmations.push((current, confirmation));
+        }
+        self.db.write_schemas(schema_batch)?;
+        Ok(confirmations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn txs_are_returned_in_order() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = SequencerDB::with_path(tmpdir.path()).unwrap();
+
+        db.put_tx(&[2; 32], b"first").unwrap();
+        db.put_tx(&[1; 32], b"second").unwrap();
+
+        // The order survives a restart.
+        drop(db);
+        let db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        db.put_tx(&[0; 32], b"third").unwrap();
+
+        assert_eq!(
+            db.get_txs().unwrap(),
+            vec![
+                ([2; 32], b"first".to_vec()),
+                ([1; 32], b"second".to_vec()),
+                ([0; 32], b"third".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn take_soft_confirmations() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        assert_eq!(
+            db.next_soft_confirmation_position().unwrap(),
+            SoftConfirmationPosition(0)
+        );
+
+        let confirmations: Vec<_> = (0..3)
+            .map(|i| StoredSoftConfirmation::new([i; 32], &u64::from(i), vec![]))
+            .collect();
+        for (position, confirmation) in confirmations.iter().enumerate() {
+            db.put_soft_confirmation(SoftConfirmationPosition(position as u64), confirmation)
+                .unwrap();
+        }
+        assert_eq!(
+            db.next_soft_confirmation_position().unwrap(),
+            SoftConfirmationPosition(3)
+        );
+        assert_eq!(
+            db.get_soft_confirmation(&[1; 32]).unwrap(),
+            Some((SoftConfirmationPosition(1), confirmations[1].clone()))
+        );
+
+        assert_eq!(
+            db.take_soft_confirmations_up_to(SoftConfirmationPosition(1))
+                .unwrap(),
+            vec![
+                (SoftConfirmationPosition(0), confirmations[0].clone()),
+                (SoftConfirmationPosition(1), confirmations[1].clone()),
+            ]
+        );
+        assert_eq!(db.get_soft_confirmation(&[1; 32]).unwrap(), None);
+        assert_eq!(
+            db.get_soft_confirmation(&[2; 32]).unwrap(),
+            Some((SoftConfirmationPosition(2), confirmations[2].clone()))
+        );
+    }
+
+    #[test]
+    fn delete_txs_and_blobs() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = SequencerDB::with_path(tmpdir.path()).unwrap();
+
+        db.put_tx(&[1; 32], b"first").unwrap();
+        db.put_tx(&[2; 32], b"second").unwrap();
+        for number in 0..2 {
+            let blob = StoredSequencerBlob {
+                tx_hashes: vec![[number as u8 + 1; 32]],
+                attempts: 1,
+            };
+            db.put_blob(BlobNumber(number), &blob).unwrap();
+        }
+
+        db.delete(&[[1; 32]], [BlobNumber(0)]).unwrap();
+
+        assert_eq!(db.get_txs().unwrap(), vec![([2; 32], b"second".to_vec())]);
+        assert_eq!(
+            db.get_blobs().unwrap(),
+            vec![(
+                BlobNumber(1),
+                StoredSequencerBlob {
+                    tx_hashes: vec![[2; 32]],
+                    attempts: 1,
+                }
+            )]
+        );
+    }
+}
@@ -12,8 +12,10 @@ use sov_schema_db::{
 use crate::snapshot_manager::DataLocation::Snapshot;
 
 /// Snapshot manager holds snapshots associated with particular DB and can traverse them backwards
-/// down to DB level
-/// Managed externally by [`NewProverStorageManager`]
+/// down to DB level.
+/// Snapshots and relations between them are managed externally, for example by the storage manager
+/// that keeps one snapshot per unfinalized DA block.
+#[derive(Debug)]
 pub struct SnapshotManager {
     db: sov_schema_db::DB,
     snapshots: HashMap<SnapshotId, FrozenDbSnapshot>,
@@ -22,7 +24,9 @@ pub struct SnapshotManager {
 }
 
 impl SnapshotManager {
-    pub(crate) fn new(
+    /// Create new [`SnapshotManager`] on top of the given database.
+    /// `to_parent` describes relations between snapshots and can be shared between several managers.
+    pub fn new(
         db: sov_schema_db::DB,
         to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
     ) -> Self {
@@ -33,18 +37,28 @@ impl SnapshotManager {
         }
     }
 
-    pub(crate) fn add_snapshot(&mut self, snapshot: FrozenDbSnapshot) {
+    /// Create new [`SnapshotManager`] without any relations between snapshots.
+    /// Any snapshot created on top of it reads directly from the database.
+    pub fn orphan(db: sov_schema_db::DB) -> Self {
+        Self::new(db, Arc::new(RwLock::new(HashMap::new())))
+    }
+
+    /// Adds frozen snapshot, so it can be queried by its children.
+    /// Panics if snapshot with the same id has been added already.
+    pub fn add_snapshot(&mut self, snapshot: FrozenDbSnapshot) {
         let snapshot_id = snapshot.get_id();
         if self.snapshots.insert(snapshot_id, snapshot).is_some() {
             panic!("Attempt to double save same snapshot");
         }
     }
 
-    pub(crate) fn discard_snapshot(&mut self, snapshot_id: &SnapshotId) {
+    /// Removes snapshot without writing it to the database.
+    pub fn discard_snapshot(&mut self, snapshot_id: &SnapshotId) {
         self.snapshots.remove(snapshot_id);
     }
 
-    pub(crate) fn commit_snapshot(&mut self, snapshot_id: &SnapshotId) -> anyhow::Result<()> {
+    /// Writes snapshot to the database and removes it from the manager.
+    pub fn commit_snapshot(&mut self, snapshot_id: &SnapshotId) -> anyhow::Result<()> {
         if !self.snapshots.contains_key(snapshot_id) {
             anyhow::bail!("Attempt to commit unknown snapshot");
         }
@@ -53,20 +67,53 @@ impl SnapshotManager {
         self.db.write_schemas(snapshot.into())
     }
 
-    #[cfg(test)]
-    pub(crate) fn is_empty(&self) -> bool {
+    /// Returns the underlying database, which only contains committed snapshots.
+    pub fn db(&self) -> &sov_schema_db::DB {
+        &self.db
+    }
+
+    /// Returns true if the manager does not hold any snapshots.
+    pub fn is_empty(&self) -> bool {
         self.snapshots.is_empty()
     }
 
-    pub(crate) fn contains_snapshot(&self, snapshot_id: &SnapshotId) -> bool {
+    /// Returns true if snapshot with the given id has been added to the manager.
+    pub fn contains_snapshot(&self, snapshot_id: &SnapshotId) -> bool {
         self.snapshots.contains_key(snapshot_id)
     }
 
     /// Returns iterator over keys in given [`Schema`] among all snapshots and DB in reverse lexicographical order
     pub fn iter<S: Schema>(
         &self,
-        mut snapshot_id: SnapshotId,
+        snapshot_id: SnapshotId,
+    ) -> anyhow::Result<SnapshotManagerIter<S>> {
+        let snapshot_iterators =
+            self.parent_snapshots(snapshot_id, |snapshot| snapshot.iter::<S>());
+        let db_iter = self.db.raw_iter::<S>()?;
+
+        Ok(SnapshotManagerIter::new(db_iter, snapshot_iterators))
+    }
+
+    /// Returns iterator over keys in given [`Schema`] among all snapshots and DB in reverse lexicographical order,
+    /// starting from the largest key that is smaller or equal to `seek_key`
+    pub fn iter_from<S: Schema>(
+        &self,
+        snapshot_id: SnapshotId,
+        seek_key: &SchemaKey,
     ) -> anyhow::Result<SnapshotManagerIter<S>> {
+        let snapshot_iterators =
+            self.parent_snapshots(snapshot_id, |snapshot| snapshot.iter_from::<S>(seek_key));
+        let db_iter = self.db.raw_iter_from::<S>(seek_key)?;
+
+        Ok(SnapshotManagerIter::new(db_iter, snapshot_iterators))
+    }
+
+    /// Creates an iterator for every parent of the given snapshot, the oldest snapshot goes first
+    fn parent_snapshots<'a, S: Schema>(
+        &'a self,
+        mut snapshot_id: SnapshotId,
+        snapshot_iter: impl Fn(&'a FrozenDbSnapshot) -> SchemaBatchIterator<'a, S>,
+    ) -> Vec<SchemaBatchIterator<'a, S>> {
         let mut snapshot_iterators = vec![];
         let to_parent = self.to_parent.read().unwrap();
         while let Some(parent_snapshot_id) = to_parent.get(&snapshot_id) {
@@ -75,15 +122,13 @@ impl SnapshotManager {
                 .get(parent_snapshot_id)
                 .expect("Inconsistency between `self.snapshots` and `self.to_parent`");
 
-            snapshot_iterators.push(parent_snapshot.iter::<S>());
+            snapshot_iterators.push(snapshot_iter(parent_snapshot));
 
             snapshot_id = *parent_snapshot_id;
         }
 
         snapshot_iterators.reverse();
-        let db_iter = self.db.raw_iter::<S>()?;
-
-        Ok(SnapshotManagerIter::new(db_iter, snapshot_iterators))
+        snapshot_iterators
     }
 }
 
@@ -223,6 +268,14 @@ impl QueryManager for SnapshotManager {
     fn iter<S: Schema>(&self, snapshot_id: SnapshotId) -> anyhow::Result<Self::Iter<'_, S>> {
         self.iter::<S>(snapshot_id)
     }
+
+    fn iter_from<S: Schema>(
+        &self,
+        snapshot_id: SnapshotId,
+        seek_key: &SchemaKey,
+    ) -> anyhow::Result<Self::Iter<'_, S>> {
+        self.iter_from::<S>(snapshot_id, seek_key)
+    }
 }
 
 #[cfg(test)]
@@ -230,16 +283,60 @@ mod tests {
     use std::collections::HashMap;
     use std::sync::{Arc, RwLock};
 
-    use sov_db::rocks_db_config::gen_rocksdb_options;
-    use sov_schema_db::schema::{KeyDecoder, ValueCodec};
+    use byteorder::{BigEndian, ReadBytesExt};
+    use sov_schema_db::schema::{KeyDecoder, KeyEncoder, Result as CodecResult, ValueCodec};
     use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, QueryManager};
-    use sov_schema_db::SchemaBatch;
+    use sov_schema_db::{define_schema, CodecError, SchemaBatch};
 
-    use crate::dummy_storage::{DummyField, DummyStateSchema, DUMMY_STATE_CF};
+    use crate::rocks_db_config::gen_rocksdb_options;
     use crate::snapshot_manager::SnapshotManager;
 
+    const DUMMY_STATE_CF: &str = "DummyStateCF";
+
+    define_schema!(DummyStateSchema, DummyField, DummyField, DUMMY_STATE_CF);
+
     type Schema = DummyStateSchema;
 
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    struct DummyField(u64);
+
+    impl DummyField {
+        fn as_bytes(&self) -> Vec<u8> {
+            self.0.to_be_bytes().to_vec()
+        }
+
+        fn from_bytes(data: &[u8]) -> CodecResult<Self> {
+            let mut reader = std::io::Cursor::new(data);
+            Ok(Self(
+                reader
+                    .read_u64::<BigEndian>()
+                    .map_err(|e| CodecError::Wrapped(e.into()))?,
+            ))
+        }
+    }
+
+    impl KeyEncoder<DummyStateSchema> for DummyField {
+        fn encode_key(&self) -> CodecResult<Vec<u8>> {
+            Ok(self.as_bytes())
+        }
+    }
+
+    impl KeyDecoder<DummyStateSchema> for DummyField {
+        fn decode_key(data: &[u8]) -> CodecResult<Self> {
+            Self::from_bytes(data)
+        }
+    }
+
+    impl ValueCodec<DummyStateSchema> for DummyField {
+        fn encode_value(&self) -> CodecResult<Vec<u8>> {
+            Ok(self.as_bytes())
+        }
+
+        fn decode_value(data: &[u8]) -> CodecResult<Self> {
+            Self::from_bytes(data)
+        }
+    }
+
     fn create_test_db(path: &std::path::Path) -> sov_schema_db::DB {
         let tables = vec![DUMMY_STATE_CF.to_string()];
         sov_schema_db::DB::open(
@@ -576,22 +673,34 @@ mod tests {
             (f1, f2),
         ];
 
+        let decode = |(k, v): (Vec<u8>, Vec<u8>)| {
+            let key = <<DummyStateSchema as sov_schema_db::Schema>::Key as KeyDecoder<
+                Schema,
+            >>::decode_key(&k)
+            .unwrap();
+            let value = <<DummyStateSchema as sov_schema_db::Schema>::Value as ValueCodec<
+                Schema,
+            >>::decode_value(&v)
+            .unwrap();
+            (key, value)
+        };
+
         let i = snapshot_manager.iter::<Schema>(4).unwrap();
-        let actual_fields: Vec<_> = i
-            .into_iter()
-            .map(|(k, v)| {
-                let key = <<DummyStateSchema as sov_schema_db::Schema>::Key as KeyDecoder<
-                    Schema,
-                >>::decode_key(&k)
-                .unwrap();
-                let value = <<DummyStateSchema as sov_schema_db::Schema>::Value as ValueCodec<
-                    Schema,
-                >>::decode_value(&v)
-                .unwrap();
-                (key, value)
-            })
-            .collect();
+        let actual_fields: Vec<_> = i.into_iter().map(decode).collect();
 
         assert_eq!(actual_fields, expected_fields);
+
+        // Seeking skips larger keys in every snapshot and in the DB
+        let seek_key = f7.as_bytes();
+        let i = snapshot_manager.iter_from::<Schema>(4, &seek_key).unwrap();
+        let actual_fields: Vec<_> = i.into_iter().map(decode).collect();
+
+        assert_eq!(actual_fields, expected_fields[3..]);
+
+        let seek_key = f8.as_bytes();
+        let i = snapshot_manager.iter_from::<Schema>(4, &seek_key).unwrap();
+        let actual_fields: Vec<_> = i.into_iter().map(decode).collect();
+
+        assert_eq!(actual_fields, expected_fields[2..]);
     }
 }
@@ -1,47 +1,77 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use jmt::storage::{TreeReader, TreeWriter};
+use jmt::storage::{StaleNodeIndex, TreeReader, TreeWriter};
 use jmt::{KeyHash, Version};
-use sov_schema_db::{SchemaBatch, DB};
+use sov_schema_db::snapshot::{DbSnapshot, FrozenDbSnapshot, QueryManager};
+use sov_schema_db::DB;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
-use crate::schema::types::StateKey;
-
-/// A typed wrapper around the db for storing rollup state. Internally,
-/// this is roughly just an `Arc<SchemaDB>`.
+use crate::schema::tables::{
+    JmtNodes, JmtValues, KeyHashToKey, StaleJmtNodes, StaleJmtValues, StatePruningHorizon,
+    STATE_TABLES,
+};
+use crate::schema::types::{PrunedVersions, StateKey};
+use crate::snapshot_manager::SnapshotManager;
+
+/// A typed wrapper around a [`DbSnapshot`] for storing rollup state. Internally,
+/// this is roughly just an `Arc<DbSnapshot>`: reads fall through to the parent snapshots
+/// and the database, while writes stay in the snapshot until it is committed by its manager.
 ///
 /// StateDB implements several convenience functions for state storage -
 /// notably the `TreeReader` and `TreeWriter` traits.
-#[derive(Clone, Debug)]
-pub struct StateDB {
-    /// The underlying database instance, wrapped in an [`Arc`] for convenience and [`SchemaDB`] for type safety
-    db: Arc<DB>,
-    /// The [`Version`] that will be used for the next batch of writes to the DB.
-    next_version: Arc<Mutex<Version>>,
+#[derive(Debug)]
+pub struct StateDB<Q> {
+    /// The underlying [`DbSnapshot`], wrapped in an [`Arc`] for convenience
+    db: Arc<DbSnapshot<Q>>,
+    /// The next version of the state, computed on the first request.
+    /// Parent snapshots are frozen, so it only changes when this snapshot writes new nodes.
+    next_version: Arc<Mutex<Option<Version>>>,
+}
+
+impl<Q> Clone for StateDB<Q> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            next_version: self.next_version.clone(),
+        }
+    }
 }
 
 const STATE_DB_PATH_SUFFIX: &str = "state";
 
-impl StateDB {
-    /// Open a [`StateDB`] (backed by RocksDB) at the specified path.
-    /// The returned instance will be at the path `{path}/state-db`.
-    pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
-        let path = path.as_ref().join(STATE_DB_PATH_SUFFIX);
-        let inner = DB::open(
-            path,
-            "state-db",
-            STATE_TABLES.iter().copied(),
-            &gen_rocksdb_options(&Default::default(), false),
-        )?;
-
-        let next_version = Self::last_version_written(&inner)?.unwrap_or_default() + 1;
-
-        Ok(Self {
-            db: Arc::new(inner),
-            next_version: Arc::new(Mutex::new(next_version)),
-        })
+impl StateDB<SnapshotManager> {
+    /// Opens a [`StateDB`] at the specified path, which reads directly from the database.
+    /// Writes are kept in memory and never persisted, so it is only suitable for tests.
+    #[cfg(any(test, feature = "arbitrary"))]
+    fn orphan(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let snapshot_manager = SnapshotManager::orphan(Self::setup_schema_db(path)?);
+        let db_snapshot = DbSnapshot::new(
+            0,
+            sov_schema_db::snapshot::ReadOnlyLock::new(Arc::new(std::sync::RwLock::new(
+                snapshot_manager,
+            ))),
+        );
+        Ok(Self::with_db_snapshot(db_snapshot))
+    }
+}
+
+impl<Q: QueryManager> StateDB<Q> {
+    /// Creates a [`StateDB`] on top of the given [`DbSnapshot`].
+    pub fn with_db_snapshot(db_snapshot: DbSnapshot<Q>) -> Self {
+        Self {
+            db: Arc::new(db_snapshot),
+            next_version: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Consumes the [`StateDB`] and returns all the writes made to it as a [`FrozenDbSnapshot`].
+    /// Fails if any clones of this [`StateDB`] are still alive.
+    pub fn freeze(self) -> Result<FrozenDbSnapshot, anyhow::Error> {
+        let db_snapshot = Arc::try_unwrap(self.db).map_err(|_| {
+            anyhow::anyhow!("StateDB cannot be frozen while there are other references to it")
+        })?;
+        Ok(db_snapshot.into())
     }
 
     /// Put the preimage of a hashed key into the database. Note that the preimage is not checked for correctness,
@@ -50,11 +80,33 @@ impl StateDB {
         &self,
         items: impl IntoIterator<Item = (KeyHash, &'a Vec<u8>)>,
     ) -> Result<(), anyhow::Error> {
-        let mut batch = SchemaBatch::new();
         for (key_hash, key) in items.into_iter() {
-            batch.put::<KeyHashToKey>(&key_hash.0, key)?;
+            self.db.put::<KeyHashToKey>(&key_hash.0, key)?;
         }
-        self.db.write_schemas(batch)
+        Ok(())
+    }
+
+    /// Records the JMT nodes which are no longer part of the latest version of the tree,
+    /// so that they can be deleted once every version they belong to has been pruned.
+    pub fn put_stale_node_indices<'a>(
+        &self,
+        indices: impl IntoIterator<Item = &'a StaleNodeIndex>,
+    ) -> Result<(), anyhow::Error> {
+        for index in indices.into_iter() {
+            self.db
+                .put::<StaleJmtNodes>(&(index.stale_since_version, index.node_key.clone()), &())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the versions of the state which have been pruned, if any.
+    pub fn get_pruned_versions(&self) -> anyhow::Result<Option<PrunedVersions>> {
+        self.db.read::<StatePruningHorizon>(&())
+    }
+
+    /// Marks the given versions of the state as pruned.
+    pub fn put_pruned_versions(&self, pruned_versions: &PrunedVersions) -> anyhow::Result<()> {
+        self.db.put::<StatePruningHorizon>(&(), pruned_versions)
     }
 
     /// Get an optional value from the database, given a version and a key hash.
@@ -63,13 +115,10 @@ impl StateDB {
         version: Version,
         key: &StateKey,
     ) -> anyhow::Result<Option<jmt::OwnedValue>> {
-        let mut iter = self.db.iter::<JmtValues>()?;
         // find the latest instance of the key whose version <= target
-        iter.seek_for_prev(&(&key, version))?;
-        let found = iter.next();
+        let found = self.db.get_prev::<JmtValues>(&(&key, version))?;
         match found {
-            Some(result) => {
-                let ((found_key, found_version), value) = result?;
+            Some(((found_key, found_version), value)) => {
                 if &found_key == key {
                     anyhow::ensure!(found_version <= version, "Bug! iterator isn't returning expected values. expected a version <= {version:} but found {found_version:}");
                     Ok(value)
@@ -81,36 +130,33 @@ impl StateDB {
         }
     }
 
-    /// Increment the `next_version` counter by 1.
-    pub fn inc_next_version(&self) {
-        let mut version = self.next_version.lock().unwrap();
-        *version += 1;
-    }
-
-    /// Get the current value of the `next_version` counter
+    /// Get the [`Version`] that will be used for the next batch of writes,
+    /// which is one more than the latest version visible from the underlying snapshot.
     pub fn get_next_version(&self) -> Version {
-        let version = self.next_version.lock().unwrap();
-        *version
+        let mut next_version = self
+            .next_version
+            .lock()
+            .expect("Next version lock must not be poisoned");
+        *next_version.get_or_insert_with(|| {
+            let last_version = self
+                .last_version_written()
+                .expect("Unable to read latest version from the db");
+            last_version.unwrap_or_default() + 1
+        })
     }
 
-    fn last_version_written(db: &DB) -> anyhow::Result<Option<Version>> {
-        let mut iter = db.iter::<JmtNodes>()?;
-        iter.seek_to_last();
-
-        let version = match iter.next() {
-            Some(Ok((key, _))) => Some(key.version()),
-            _ => None,
-        };
-        Ok(version)
+    fn last_version_written(&self) -> anyhow::Result<Option<Version>> {
+        let largest_node = self.db.get_largest::<JmtNodes>()?;
+        Ok(largest_node.map(|(node_key, _)| node_key.version()))
     }
 }
 
-impl TreeReader for StateDB {
+impl<Q: QueryManager> TreeReader for StateDB<Q> {
     fn get_node_option(
         &self,
         node_key: &jmt::storage::NodeKey,
     ) -> anyhow::Result<Option<jmt::storage::Node>> {
-        self.db.get::<JmtNodes>(node_key)
+        self.db.read::<JmtNodes>(node_key)
     }
 
     fn get_value_option(
@@ -118,7 +164,7 @@ impl TreeReader for StateDB {
         version: Version,
         key_hash: KeyHash,
     ) -> anyhow::Result<Option<jmt::OwnedValue>> {
-        if let Some(key) = self.db.get::<KeyHashToKey>(&key_hash.0)? {
+        if let Some(key) = self.db.read::<KeyHashToKey>(&key_hash.0)? {
             self.get_value_option_by_key(version, &key)
         } else {
             Ok(None)
@@ -132,23 +178,44 @@ impl TreeReader for StateDB {
     }
 }
 
-impl TreeWriter for StateDB {
+impl<Q: QueryManager> TreeWriter for StateDB<Q> {
     fn write_node_batch(&self, node_batch: &jmt::storage::NodeBatch) -> anyhow::Result<()> {
-        let mut batch = SchemaBatch::new();
+        let mut max_version = None;
         for (node_key, node) in node_batch.nodes() {
-            batch.put::<JmtNodes>(node_key, node)?;
+            self.db.put::<JmtNodes>(node_key, node)?;
+            max_version = max_version.max(Some(node_key.version()));
+        }
+        if let Some(max_version) = max_version {
+            let mut next_version = self
+                .next_version
+                .lock()
+                .expect("Next version lock must not be poisoned");
+            // When the version has not been computed yet, it is read from the snapshot on demand.
+            if let Some(next_version) = next_version.as_mut() {
+                *next_version = (*next_version).max(max_version + 1);
+            }
         }
 
         for ((version, key_hash), value) in node_batch.values() {
             let key_preimage =
                 self.db
-                    .get::<KeyHashToKey>(&key_hash.0)?
+                    .read::<KeyHashToKey>(&key_hash.0)?
                     .ok_or(anyhow::format_err!(
                         "Could not find preimage for key hash {key_hash:?}. Has `StateDB::put_preimage` been called for this key?"
                     ))?;
-            batch.put::<JmtValues>(&(key_preimage, *version), value)?;
+            // The previous value of the key becomes stale as of this version.
+            if let Some(((prev_key, prev_version), _)) =
+                self.db.get_prev::<JmtValues>(&(&key_preimage, *version))?
+            {
+                if prev_key == key_preimage && prev_version < *version {
+                    self.db.put::<StaleJmtValues>(
+                        &(*version, key_preimage.clone(), prev_version),
+                        &(),
+                    )?;
+                }
+            }
+            self.db.put::<JmtValues>(&(key_preimage, *version), value)?;
         }
-        self.db.write_schemas(batch)?;
         Ok(())
     }
 }
@@ -171,7 +238,7 @@ pub mod arbitrary {
     #[derive(Debug)]
     pub struct ArbitraryDB {
         /// The underlying RocksDB instance.
-        pub db: StateDB,
+        pub db: StateDB<SnapshotManager>,
         /// The temporary directory used to create the [`StateDB`].
         pub path: TempDir,
     }
@@ -187,7 +254,7 @@ pub mod arbitrary {
     }
 
     impl Deref for ArbitraryDB {
-        type Target = StateDB;
+        type Target = StateDB<SnapshotManager>;
 
         fn deref(&self) -> &Self::Target {
             &self.db
@@ -203,7 +270,7 @@ pub mod arbitrary {
     impl<'a> ::arbitrary::Arbitrary<'a> for ArbitraryDB {
         fn arbitrary(_u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
             let path = TempDir::new().map_err(|_| ::arbitrary::Error::NotEnoughData)?;
-            let db = StateDB::with_path(&path).map_err(|_| ::arbitrary::Error::IncorrectFormat)?;
+            let db = StateDB::orphan(&path).map_err(|_| ::arbitrary::Error::IncorrectFormat)?;
             Ok(Self { db, path })
         }
     }
@@ -218,7 +285,7 @@ pub mod arbitrary {
                             anyhow::anyhow!(format!("failed to generate path for StateDB: {e}"))
                         })
                         .and_then(|path| {
-                            let db = StateDB::with_path(&path)?;
+                            let db = StateDB::orphan(&path)?;
                             Ok(ArbitraryDB { db, path })
                         }),
                 }
@@ -240,7 +307,7 @@ mod state_db_tests {
     #[test]
     fn test_simple() {
         let tmpdir = tempfile::tempdir().unwrap();
-        let db = StateDB::with_path(tmpdir.path()).unwrap();
+        let db = StateDB::orphan(tmpdir.path()).unwrap();
         let key_hash = KeyHash([1u8; 32]);
         let key = vec![2u8; 100];
         let value = [8u8; 150];
@@ -146,6 +146,11 @@ impl<'a> RawDbReverseIterator<'a> {
         db_iter.seek_to_last();
         RawDbReverseIterator { db_iter }
     }
+
+    pub(crate) fn new_from(mut db_iter: rocksdb::DBRawIterator<'a>, seek_key: &[u8]) -> Self {
+        db_iter.seek_for_prev(seek_key);
+        RawDbReverseIterator { db_iter }
+    }
 }
 
 impl<'a> Iterator for RawDbReverseIterator<'a> {
@@ -177,6 +177,20 @@ impl DB {
         ))
     }
 
+    /// Returns a [`RawDbReverseIterator`] which iterates over raw values backwards,
+    /// starting from the largest key that is smaller or equal to `seek_key`
+    pub fn raw_iter_from<S: Schema>(
+        &self,
+        seek_key: &SchemaKey,
+    ) -> anyhow::Result<RawDbReverseIterator> {
+        let cf_handle = self.get_cf_handle(S::COLUMN_FAMILY_NAME)?;
+        Ok(RawDbReverseIterator::new_from(
+            self.inner
+                .raw_iterator_cf_opt(cf_handle, Default::default()),
+            seek_key,
+        ))
+    }
+
     /// Returns a forward [`SchemaIterator`] on a certain schema with the provided read options.
     pub fn iter_with_opts<S: Schema>(
         &self,
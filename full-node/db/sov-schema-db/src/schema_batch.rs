@@ -1,5 +1,6 @@
 use std::collections::{btree_map, BTreeMap, HashMap};
 use std::iter::Rev;
+use std::ops::Bound;
 
 use crate::metrics::SCHEMADB_BATCH_PUT_LATENCY_SECONDS;
 use crate::schema::{ColumnFamilyName, KeyCodec, ValueCodec};
@@ -66,7 +67,20 @@ impl SchemaBatch {
     pub fn iter<S: Schema>(&self) -> SchemaBatchIterator<'_, S> {
         let some_rows = self.last_writes.get(&S::COLUMN_FAMILY_NAME);
         SchemaBatchIterator {
-            inner: some_rows.map(|rows| rows.iter().rev()),
+            inner: some_rows.map(|rows| rows.range::<SchemaKey, _>(..).rev()),
+            _phantom_schema: std::marker::PhantomData,
+        }
+    }
+
+    /// Iterate over the writes in the batch for a given column family in reversed lexicographic order,
+    /// starting from the largest key that is smaller or equal to `seek_key`
+    pub fn iter_from<S: Schema>(&self, seek_key: &SchemaKey) -> SchemaBatchIterator<'_, S> {
+        let some_rows = self.last_writes.get(&S::COLUMN_FAMILY_NAME);
+        SchemaBatchIterator {
+            inner: some_rows.map(|rows| {
+                rows.range::<SchemaKey, _>((Bound::Unbounded, Bound::Included(seek_key)))
+                    .rev()
+            }),
             _phantom_schema: std::marker::PhantomData,
         }
     }
@@ -74,7 +88,7 @@ impl SchemaBatch {
 
 /// Iterator over [`SchemaBatch`] for a given column family in reversed lexicographic order
 pub struct SchemaBatchIterator<'a, S: Schema> {
-    inner: Option<Rev<btree_map::Iter<'a, SchemaKey, Operation>>>,
+    inner: Option<Rev<btree_map::Range<'a, SchemaKey, Operation>>>,
     _phantom_schema: std::marker::PhantomData<S>,
 }
 
@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, LockResult, Mutex, RwLock, RwLockReadGuard};
 
-use crate::schema::{KeyCodec, ValueCodec};
+use crate::schema::{KeyCodec, KeyDecoder, ValueCodec};
 use crate::schema_batch::SchemaBatchIterator;
 use crate::{Operation, Schema, SchemaBatch, SchemaKey, SchemaValue, SeekKeyEncoder};
 
@@ -26,6 +26,15 @@ pub trait QueryManager {
     /// Returns an iterator over all key-value pairs in given [`Schema`] in reverse lexicographic order
     /// Starting from given [`SnapshotId`]
     fn iter<S: Schema>(&self, snapshot_id: SnapshotId) -> anyhow::Result<Self::Iter<'_, S>>;
+
+    /// Returns an iterator over key-value pairs in given [`Schema`] in reverse lexicographic order,
+    /// starting from the largest key that is smaller or equal to `seek_key`
+    /// Starting from given [`SnapshotId`]
+    fn iter_from<S: Schema>(
+        &self,
+        snapshot_id: SnapshotId,
+        seek_key: &SchemaKey,
+    ) -> anyhow::Result<Self::Iter<'_, S>>;
 }
 
 /// Simple wrapper around `RwLock` that only allows read access.
@@ -116,8 +125,8 @@ impl<Q: QueryManager> DbSnapshot<Q> {
             .delete(key)
     }
 
-    /// Get value of largest key written value for given [`Schema`]
-    pub fn get_largest<S: Schema>(&self) -> anyhow::Result<Option<S::Value>> {
+    /// Get largest key and its value for given [`Schema`]
+    pub fn get_largest<S: Schema>(&self) -> anyhow::Result<Option<(S::Key, S::Value)>> {
         let local_cache = self
             .cache
             .lock()
@@ -136,19 +145,20 @@ impl<Q: QueryManager> DbSnapshot<Q> {
             parent_iter: parent_iter.peekable(),
         };
 
-        if let Some((_, value)) = combined_iter.next() {
+        if let Some((key, value)) = combined_iter.next() {
+            let key = S::Key::decode_key(&key)?;
             let value = S::Value::decode_value(&value)?;
-            return Ok(Some(value));
+            return Ok(Some((key, value)));
         }
 
         Ok(None)
     }
 
-    /// Get value in [`Schema`] that is smaller or equal than give `seek_key`
+    /// Get the largest key in [`Schema`] that is smaller or equal than given `seek_key`, and its value
     pub fn get_prev<S: Schema>(
         &self,
         seek_key: &impl SeekKeyEncoder<S>,
-    ) -> anyhow::Result<Option<S::Value>> {
+    ) -> anyhow::Result<Option<(S::Key, S::Value)>> {
         let seek_key = seek_key.encode_seek_key()?;
 
         let local_cache = self
@@ -156,30 +166,24 @@ impl<Q: QueryManager> DbSnapshot<Q> {
             .lock()
             .expect("Local cache lock must not be poisoned");
 
-        let local_cache_iter = local_cache.iter::<S>();
+        let local_cache_iter = local_cache.iter_from::<S>(&seek_key);
 
         let parent = self
             .parents_manager
             .read()
             .expect("Parent snapshots lock must not be poisoned");
 
-        let parent_iter = parent.iter::<S>(self.id)?;
+        let parent_iter = parent.iter_from::<S>(self.id, &seek_key)?;
 
-        let combined_iter: SnapshotIter<'_, Q, S> = SnapshotIter {
+        let mut combined_iter: SnapshotIter<'_, Q, S> = SnapshotIter {
             local_cache_iter: local_cache_iter.peekable(),
             parent_iter: parent_iter.peekable(),
         };
 
-        if let Some((_, value)) = combined_iter
-            .filter_map(|(key, value)| {
-                if key <= seek_key {
-                    return Some((key, value));
-                }
-                None
-            })
-            .next()
-        {
-            return Ok(Some(S::Value::decode_value(&value)?));
+        if let Some((key, value)) = combined_iter.next() {
+            let key = S::Key::decode_key(&key)?;
+            let value = S::Value::decode_value(&value)?;
+            return Ok(Some((key, value)));
         }
 
         Ok(None)
@@ -241,6 +245,7 @@ impl<'a, Q: QueryManager + 'a, S: Schema> Iterator for SnapshotIter<'a, Q, S> {
 }
 
 /// Read only version of [`DbSnapshot`], for usage inside [`QueryManager`]
+#[derive(Debug)]
 pub struct FrozenDbSnapshot {
     id: SnapshotId,
     cache: SchemaBatch,
@@ -261,6 +266,12 @@ impl FrozenDbSnapshot {
     pub fn iter<S: Schema>(&self) -> SchemaBatchIterator<'_, S> {
         self.cache.iter::<S>()
     }
+
+    /// Iterate over operations in snapshot in reversed lexicographic order,
+    /// starting from the largest key that is smaller or equal to `seek_key`
+    pub fn iter_from<S: Schema>(&self, seek_key: &SchemaKey) -> SchemaBatchIterator<'_, S> {
+        self.cache.iter_from::<S>(seek_key)
+    }
 }
 
 impl<Q> From<DbSnapshot<Q>> for FrozenDbSnapshot {
@@ -315,6 +326,14 @@ impl QueryManager for NoopQueryManager {
     fn iter<S: Schema>(&self, _snapshot_id: SnapshotId) -> anyhow::Result<Self::Iter<'_, S>> {
         Ok(std::iter::empty())
     }
+
+    fn iter_from<S: Schema>(
+        &self,
+        _snapshot_id: SnapshotId,
+        _seek_key: &SchemaKey,
+    ) -> anyhow::Result<Self::Iter<'_, S>> {
+        Ok(std::iter::empty())
+    }
 }
 
 #[cfg(test)]
@@ -346,19 +365,26 @@ mod tests {
         }
 
         fn iter<S: Schema>(&self, _snapshot_id: SnapshotId) -> anyhow::Result<Self::Iter<'_, S>> {
-            let collected: Vec<(SchemaKey, SchemaValue)> = self
-                .cache
-                .iter::<S>()
-                .filter_map(|(k, op)| match op {
-                    Operation::Put { value } => Some((k.to_vec(), value.to_vec())),
-                    Operation::Delete => None,
-                })
-                .collect();
-
-            Ok(collected.into_iter())
+            Ok(collect_puts(self.cache.iter::<S>()).into_iter())
+        }
+
+        fn iter_from<S: Schema>(
+            &self,
+            _snapshot_id: SnapshotId,
+            seek_key: &SchemaKey,
+        ) -> anyhow::Result<Self::Iter<'_, S>> {
+            Ok(collect_puts(self.cache.iter_from::<S>(seek_key)).into_iter())
         }
     }
 
+    fn collect_puts<S: Schema>(iter: SchemaBatchIterator<'_, S>) -> Vec<(SchemaKey, SchemaValue)> {
+        iter.filter_map(|(k, op)| match op {
+            Operation::Put { value } => Some((k.to_vec(), value.to_vec())),
+            Operation::Delete => None,
+        })
+        .collect()
+    }
+
     fn encode_key(key: &TestCompositeField) -> Vec<u8> {
         <TestCompositeField as KeyEncoder<TestSchema>>::encode_key(key).unwrap()
     }
@@ -428,4 +454,45 @@ mod tests {
 
         assert_eq!(expected_values, actual_values);
     }
+
+    #[test]
+    fn test_db_snapshot_iterator_from_seek_key() {
+        let k1 = TestCompositeField(0, 1, 0);
+        let k2 = TestCompositeField(0, 1, 2);
+        let k3 = TestCompositeField(3, 1, 0);
+        let k4 = TestCompositeField(3, 2, 0);
+
+        let mut parent_values = SchemaBatch::new();
+        parent_values.put::<TestSchema>(&k1, &TestField(1)).unwrap();
+        parent_values.put::<TestSchema>(&k3, &TestField(3)).unwrap();
+        parent_values.put::<TestSchema>(&k4, &TestField(4)).unwrap();
+
+        let mut local_cache = SchemaBatch::new();
+        local_cache.delete::<TestSchema>(&k3).unwrap();
+        local_cache.put::<TestSchema>(&k2, &TestField(20)).unwrap();
+
+        let manager = SingleSnapshotQueryManager {
+            cache: parent_values,
+        };
+
+        let seek_key = encode_key(&TestCompositeField(3, 1, 5));
+        let local_cache_iter = local_cache.iter_from::<TestSchema>(&seek_key).peekable();
+        let manager_iter = manager
+            .iter_from::<TestSchema>(0, &seek_key)
+            .unwrap()
+            .peekable();
+
+        let snapshot_iter = SnapshotIter::<'_, SingleSnapshotQueryManager, TestSchema> {
+            local_cache_iter,
+            parent_iter: manager_iter,
+        };
+
+        let actual_values: Vec<(SchemaKey, SchemaValue)> = snapshot_iter.collect();
+        let expected_values = vec![
+            (encode_key(&k2), encode_value(&TestField(20))),
+            (encode_key(&k1), encode_value(&TestField(1))),
+        ];
+
+        assert_eq!(expected_values, actual_values);
+    }
 }
@@ -293,14 +293,18 @@ fn test_db_snapshot_get_last_value() {
     let snapshot_1 =
         DbSnapshot::<LinearSnapshotManager>::new(0, ReadOnlyLock::new(manager.clone()));
 
-    assert!(snapshot_1.get_largest::<S>().unwrap().is_none());
+    assert!(snapshot_1
+        .get_largest::<S>()
+        .unwrap()
+        .map(|(_, v)| v)
+        .is_none());
 
     snapshot_1
         .put::<S>(&TestCompositeField(8, 2, 3), &TestField(6))
         .unwrap();
 
     {
-        let latest = snapshot_1.get_largest::<S>().unwrap();
+        let latest = snapshot_1.get_largest::<S>().unwrap().map(|(_, v)| v);
         assert_eq!(Some(TestField(6)), latest);
     }
 
@@ -313,7 +317,7 @@ fn test_db_snapshot_get_last_value() {
         DbSnapshot::<LinearSnapshotManager>::new(1, ReadOnlyLock::new(manager.clone()));
 
     {
-        let latest = snapshot_2.get_largest::<S>().unwrap();
+        let latest = snapshot_2.get_largest::<S>().unwrap().map(|(_, v)| v);
         assert_eq!(Some(TestField(6)), latest);
     }
 
@@ -321,7 +325,7 @@ fn test_db_snapshot_get_last_value() {
         .put::<S>(&TestCompositeField(8, 1, 3), &TestField(7))
         .unwrap();
     {
-        let latest = snapshot_2.get_largest::<S>().unwrap();
+        let latest = snapshot_2.get_largest::<S>().unwrap().map(|(_, v)| v);
         assert_eq!(Some(TestField(6)), latest);
     }
     // Largest value from local is picked up
@@ -329,7 +333,7 @@ fn test_db_snapshot_get_last_value() {
         .put::<S>(&TestCompositeField(8, 3, 1), &TestField(8))
         .unwrap();
     {
-        let latest = snapshot_2.get_largest::<S>().unwrap();
+        let latest = snapshot_2.get_largest::<S>().unwrap().map(|(_, v)| v);
         assert_eq!(Some(TestField(8)), latest);
     }
 
@@ -338,7 +342,7 @@ fn test_db_snapshot_get_last_value() {
         .delete::<S>(&TestCompositeField(8, 3, 1))
         .unwrap();
     {
-        let latest = snapshot_2.get_largest::<S>().unwrap();
+        let latest = snapshot_2.get_largest::<S>().unwrap().map(|(_, v)| v);
         assert_eq!(Some(TestField(6)), latest);
     }
 }
@@ -354,6 +358,7 @@ fn test_db_snapshot_get_prev_value() {
     assert!(snapshot_1
         .get_prev::<S>(&TestCompositeField(8, 2, 3))
         .unwrap()
+        .map(|(_, v)| v)
         .is_none());
 
     snapshot_1
@@ -381,6 +386,7 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_1
             .get_prev::<S>(&TestCompositeField(8, 2, 3))
             .unwrap()
+            .map(|(_, v)| v)
     );
     // Previous: value from 8.2.0
     assert_eq!(
@@ -388,6 +394,7 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_1
             .get_prev::<S>(&TestCompositeField(8, 2, 1))
             .unwrap()
+            .map(|(_, v)| v)
     );
 
     {
@@ -403,6 +410,7 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_2
             .get_prev::<S>(&TestCompositeField(8, 2, 3))
             .unwrap()
+            .map(|(_, v)| v)
     );
     // Previous: value from 8.2.0
     assert_eq!(
@@ -410,6 +418,7 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_2
             .get_prev::<S>(&TestCompositeField(8, 2, 1))
             .unwrap()
+            .map(|(_, v)| v)
     );
     snapshot_2
         .put::<S>(&TestCompositeField(8, 2, 0), &TestField(20))
@@ -423,12 +432,14 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_2
             .get_prev::<S>(&TestCompositeField(8, 2, 3))
             .unwrap()
+            .map(|(_, v)| v)
     );
     assert_eq!(
         Some(TestField(20)),
         snapshot_2
             .get_prev::<S>(&TestCompositeField(8, 2, 1))
             .unwrap()
+            .map(|(_, v)| v)
     );
     snapshot_2
         .delete::<S>(&TestCompositeField(8, 2, 3))
@@ -438,6 +449,7 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_2
             .get_prev::<S>(&TestCompositeField(8, 2, 3))
             .unwrap()
+            .map(|(_, v)| v)
     );
     {
         let mut manager = manager.write().unwrap();
@@ -450,17 +462,20 @@ fn test_db_snapshot_get_prev_value() {
         snapshot_3
             .get_prev::<S>(&TestCompositeField(8, 2, 1))
             .unwrap()
+            .map(|(_, v)| v)
     );
     assert_eq!(
         Some(TestField(20)),
         snapshot_3
             .get_prev::<S>(&TestCompositeField(8, 2, 3))
             .unwrap()
+            .map(|(_, v)| v)
     );
     assert_eq!(
         Some(TestField(14)),
         snapshot_3
             .get_prev::<S>(&TestCompositeField(8, 3, 4))
             .unwrap()
+            .map(|(_, v)| v)
     );
 }
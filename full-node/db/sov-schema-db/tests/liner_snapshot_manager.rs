@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 use sov_schema_db::schema::{KeyCodec, ValueCodec};
 use sov_schema_db::snapshot::{FrozenDbSnapshot, QueryManager, SnapshotId};
-use sov_schema_db::{Operation, Schema, SchemaKey, SchemaValue};
+use sov_schema_db::{Operation, Schema, SchemaBatchIterator, SchemaKey, SchemaValue};
 
 #[derive(Default)]
 pub struct LinearSnapshotManager {
@@ -35,12 +35,30 @@ impl QueryManager for LinearSnapshotManager {
         Ok(None)
     }
 
-    // For simplicity it just stores all values in the vector that is returned
     fn iter<S: Schema>(&self, snapshot_id: SnapshotId) -> anyhow::Result<Self::Iter<'_, S>> {
+        self.collect_values(snapshot_id, |snapshot| snapshot.iter::<S>())
+    }
+
+    fn iter_from<S: Schema>(
+        &self,
+        snapshot_id: SnapshotId,
+        seek_key: &SchemaKey,
+    ) -> anyhow::Result<Self::Iter<'_, S>> {
+        self.collect_values(snapshot_id, |snapshot| snapshot.iter_from::<S>(seek_key))
+    }
+}
+
+impl LinearSnapshotManager {
+    // For simplicity it just stores all values in the vector that is returned
+    fn collect_values<'a, S: Schema>(
+        &'a self,
+        snapshot_id: SnapshotId,
+        snapshot_iter: impl Fn(&'a FrozenDbSnapshot) -> SchemaBatchIterator<'a, S>,
+    ) -> anyhow::Result<std::vec::IntoIter<(SchemaKey, SchemaValue)>> {
         let mut iterators = vec![];
         // The most recent snapshot is on the right(end of the vector)
         for snapshot in self.snapshots[..snapshot_id as usize].iter() {
-            let iter = snapshot.iter::<S>();
+            let iter = snapshot_iter(snapshot);
             iterators.push(iter.peekable());
         }
 
@@ -2,11 +2,12 @@ use std::borrow::BorrowMut;
 use std::collections::VecDeque;
 
 use borsh::BorshSerialize;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, TxFee};
 
 pub struct EthBatchBuilder<C: sov_modules_api::Context> {
     mempool: VecDeque<Vec<u8>>,
     sov_tx_signer_private_key: C::PrivateKey,
+    chain_id: u64,
     nonce: u64,
     min_blob_size: Option<usize>,
 }
@@ -15,12 +16,14 @@ impl<C: sov_modules_api::Context> EthBatchBuilder<C> {
     /// Creates a new `EthBatchBuilder`.
     pub fn new(
         sov_tx_signer_private_key: C::PrivateKey,
+        chain_id: u64,
         nonce: u64,
         min_blob_size: Option<usize>,
     ) -> Self {
         EthBatchBuilder {
             mempool: VecDeque::new(),
             sov_tx_signer_private_key,
+            chain_id,
             nonce,
             min_blob_size,
         }
@@ -34,9 +37,13 @@ impl<C: sov_modules_api::Context> EthBatchBuilder<C> {
         let nonce = self.nonce.borrow_mut();
 
         while let Some(raw_message) = self.mempool.pop_front() {
+            // The EVM meters and charges the gas of its own transactions.
             let raw_tx = Transaction::<C>::new_signed_tx(
                 &self.sov_tx_signer_private_key,
                 raw_message,
+                self.chain_id,
+                None,
+                TxFee::zero(),
                 *nonce,
             )
             .try_to_vec()
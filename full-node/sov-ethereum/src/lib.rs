@@ -70,10 +70,16 @@ pub mod experimental {
             sov_accounts::Response::AccountEmpty { .. } => 0,
         };
 
+        // Transactions must be signed for the chain id set at genesis
+        let chain_id = Runtime::<C, Da::Spec>::default()
+            .chain_state
+            .get_chain_id(&mut WorkingSet::<C>::new(storage.clone()));
+
         let mut rpc = RpcModule::new(Ethereum::new(
             da_service,
             Arc::new(Mutex::new(EthBatchBuilder::new(
                 sov_tx_signer_priv_key,
+                chain_id,
                 sov_tx_signer_nonce,
                 min_blob_size,
             ))),
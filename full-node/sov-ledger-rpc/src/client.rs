@@ -5,7 +5,8 @@
 
 use jsonrpsee::proc_macros::rpc;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventCursor, EventFilter, EventIdentifier, EventResponse, EventsPage,
+    ProofStatus, QueryMode, SlotIdentifier, TxIdentifier,
 };
 use sov_rollup_interface::stf::Event;
 
@@ -57,6 +58,16 @@ where
     #[method(name = "getEvents")]
     async fn get_events(&self, event_ids: Vec<EventIdentifier>) -> RpcResult<Vec<Option<Event>>>;
 
+    /// Gets the events matching a filter, in the order in which they were
+    /// emitted. Pass the `next_cursor` of a page to get the following page.
+    #[method(name = "getEventsByFilter")]
+    async fn get_events_by_filter(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventCursor>,
+        limit: Option<u64>,
+    ) -> RpcResult<EventsPage>;
+
     /// Gets a single slot by hash.
     #[method(name = "getSlotByHash")]
     async fn get_slot_by_hash(
@@ -138,8 +149,35 @@ where
         query_mode: QueryMode,
     ) -> RpcResult<Vec<Option<Tx>>>;
 
+    /// Gets the status of the proof of a single slot.
+    #[method(name = "getProofStatus")]
+    async fn get_proof_status(&self, slot_id: SlotIdentifier) -> RpcResult<Option<ProofStatus>>;
+
     /// Subscription method to receive a notification each time a slot is
     /// processed.
     #[subscription(name = "subscribeSlots", item = u64)]
     async fn subscribe_slots(&self) -> SubscriptionResult;
+
+    /// Subscription method to receive the batches of each slot, with their
+    /// transactions and events, as soon as the slot is executed. If
+    /// `wait_for_finalization` is set, the batches are sent once the slot is
+    /// finalized on the DA layer instead.
+    #[subscription(name = "subscribeBatches", item = Batch)]
+    async fn subscribe_batches(&self, wait_for_finalization: Option<bool>) -> SubscriptionResult;
+
+    /// Subscription method to receive a transaction, with its receipt and
+    /// events, once it is executed or, if `wait_for_finalization` is set,
+    /// finalized on the DA layer. The subscription ends after the transaction
+    /// is sent.
+    #[subscription(name = "subscribeTransaction", item = Tx)]
+    async fn subscribe_transaction(
+        &self,
+        tx_hash: HexHash,
+        wait_for_finalization: Option<bool>,
+    ) -> SubscriptionResult;
+
+    /// Subscription method to receive the events matching a filter as the
+    /// slots which emit them are processed.
+    #[subscription(name = "subscribeEvents", item = EventResponse)]
+    async fn subscribe_events(&self, filter: EventFilter) -> SubscriptionResult;
 }
@@ -1,17 +1,26 @@
 //! A JSON-RPC server implementation for any [`LedgerRpcProvider`].
 
 use futures::future::Either;
+use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::types::ErrorObjectOwned;
-use jsonrpsee::{RpcModule, SubscriptionMessage};
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
 use serde::de::DeserializeOwned;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{RuntimeEventJsonSchema, RuntimeEventProcessor};
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventCursor, EventFilter, EventIdentifier, ItemOrHash, LedgerRpcProvider,
+    QueryMode, SlotIdentifier, TxIdentifier,
 };
+use sov_rollup_interface::stf::Event;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
 
 use crate::HexHash;
 
 const LEDGER_RPC_ERROR: &str = "LEDGER_RPC_ERROR";
+/// The number of events returned by `ledger_getEventsByFilter` when the request
+/// doesn't specify a limit.
+const DEFAULT_EVENTS_PER_PAGE: u64 = 100;
 
 /// Creates a new [`jsonrpsee::RpcModule`] that exposes all JSON-RPC methods
 /// necessary to interface with the [`LedgerRpcProvider`].
@@ -76,6 +85,13 @@ where
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
 
+    rpc.register_method("ledger_getEventsByFilter", move |params, ledger| {
+        let args: FilterArgs = params.parse()?;
+        ledger
+            .get_events_by_filter(&args.0, args.1, args.2.unwrap_or(DEFAULT_EVENTS_PER_PAGE))
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+
     // By-hash getters.
     rpc.register_method("ledger_getSlotByHash", move |params, ledger| {
         let args: QueryArgs<HexHash> = extract_query_args(params)?;
@@ -142,6 +158,14 @@ where
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
 
+    // Proof status.
+    rpc.register_method("ledger_getProofStatus", move |params, ledger| {
+        let slot_id: SlotIdentifier = params.one()?;
+        ledger
+            .get_proof_status(&slot_id)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+
     rpc.register_subscription(
         "ledger_subscribeSlots",
         "ledger_slotProcessed",
@@ -178,12 +202,286 @@ where
         },
     )?;
 
+    rpc.register_subscription(
+        "ledger_subscribeEvents",
+        "ledger_eventEmitted",
+        "ledger_unsubscribeEvents",
+        |params, pending_subscription, db| async move {
+            let filter: EventFilter = params.sequence().optional_next()?.unwrap_or_default();
+            let rx = db
+                .subscribe_slots()
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+
+            notify_slots(pending_subscription, rx, |slot_number| {
+                Ok(SlotNotifications {
+                    messages: slot_event_messages(&*db, &filter, slot_number)?,
+                    last: false,
+                })
+            })
+            .await
+        },
+    )?;
+    rpc.register_subscription(
+        "ledger_subscribeBatches",
+        "ledger_batchProcessed",
+        "ledger_unsubscribeBatches",
+        |params, pending_subscription, db| async move {
+            let wait_for_finalization: bool =
+                params.sequence().optional_next()?.unwrap_or_default();
+            let rx = slot_receiver(&*db, wait_for_finalization)
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+
+            notify_slots(pending_subscription, rx, |slot_number| {
+                let slot = db.get_slot_by_number::<B, Tx>(slot_number, QueryMode::Full)?;
+                let mut messages = Vec::new();
+                for batch in slot.and_then(|slot| slot.batches).unwrap_or_default() {
+                    if let ItemOrHash::Full(batch) = batch {
+                        messages.push(SubscriptionMessage::from_json(&batch)?);
+                    }
+                }
+                Ok(SlotNotifications {
+                    messages,
+                    last: false,
+                })
+            })
+            .await
+        },
+    )?;
+    rpc.register_subscription(
+        "ledger_subscribeTransaction",
+        "ledger_transactionProcessed",
+        "ledger_unsubscribeTransaction",
+        |params, pending_subscription, db| async move {
+            let mut params = params.sequence();
+            let tx_hash: HexHash = params.next()?;
+            let wait_for_finalization: bool = params.optional_next()?.unwrap_or_default();
+            // Subscribe before looking the transaction up, so that it can't be missed.
+            let rx = slot_receiver(&*db, wait_for_finalization)
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+
+            let find_tx = || -> anyhow::Result<SlotNotifications> {
+                // Executed slots are committed right away, so their transactions
+                // are only skipped when waiting for finalization.
+                let tx = if wait_for_finalization
+                    && db
+                        .get_executed_tx_by_hash::<Tx>(&tx_hash.0, QueryMode::Compact)?
+                        .is_some()
+                {
+                    None
+                } else {
+                    db.get_tx_by_hash::<Tx>(&tx_hash.0, QueryMode::Full)?
+                };
+                Ok(match tx {
+                    Some(tx) => SlotNotifications {
+                        messages: vec![SubscriptionMessage::from_json(&tx)?],
+                        last: true,
+                    },
+                    None => SlotNotifications {
+                        messages: vec![],
+                        last: false,
+                    },
+                })
+            };
+            let current = find_tx().map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+            if current.last {
+                let subscription = pending_subscription.accept().await?;
+                for msg in current.messages {
+                    // Sending only fails if the subscriber has canceled
+                    let _ = subscription.send(msg).await;
+                }
+                return Ok(());
+            }
+            notify_slots(pending_subscription, rx, |_| find_tx()).await
+        },
+    )?;
+
     Ok(rpc)
 }
 
+/// The notifications to send to a subscriber after a slot is processed.
+struct SlotNotifications {
+    messages: Vec<SubscriptionMessage>,
+    /// Whether the subscription ends once the messages are sent.
+    last: bool,
+}
+
+/// Accepts the subscription and, each time a slot number is received from `rx`, sends the
+/// notifications returned by `notifications_for_slot`. Runs until the subscriber goes away
+/// or the last notification is sent.
+///
+/// If the subscriber falls behind and `rx` skips some slots, the notifications of the
+/// skipped slots are sent along with the ones of the next slot received.
+async fn notify_slots<F>(
+    pending_subscription: PendingSubscriptionSink,
+    mut rx: Receiver<u64>,
+    mut notifications_for_slot: F,
+) -> SubscriptionResult
+where
+    F: FnMut(u64) -> anyhow::Result<SlotNotifications>,
+{
+    // Accept the subscription. This message is sent immediately
+    let subscription = pending_subscription.accept().await?;
+    let closed = subscription.closed();
+    futures::pin_mut!(closed);
+    // The number of slots skipped by `rx` since the last slot received.
+    let mut skipped_slots = 0;
+
+    // This loop continues running until the subscription ends.
+    loop {
+        let next_msg = rx.recv();
+        futures::pin_mut!(next_msg);
+        match futures::future::select(closed, next_msg).await {
+            // If the subscription closed, we're done
+            Either::Left(_) => break Ok(()),
+            // Otherwise, we need to send the notifications of the slot
+            Either::Right((outcome, channel_closing_future)) => {
+                closed = channel_closing_future;
+                let slot_number = match outcome {
+                    Ok(slot_number) => slot_number,
+                    // Slots are numbered consecutively, so the skipped slots are the ones
+                    // right before the next slot received.
+                    Err(RecvError::Lagged(skipped)) => {
+                        skipped_slots += skipped;
+                        continue;
+                    }
+                    Err(e @ RecvError::Closed) => return Err(e.into()),
+                };
+                let first_slot = slot_number.saturating_sub(skipped_slots);
+                skipped_slots = 0;
+                for slot_number in first_slot..=slot_number {
+                    let notifications = notifications_for_slot(slot_number)
+                        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+                    for msg in notifications.messages {
+                        // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                        if subscription.send(msg).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    if notifications.last {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Subscribes to the slots as soon as they are executed, or once they are finalized on the
+/// DA layer and committed to the ledger if `wait_for_finalization` is set.
+fn slot_receiver<T: LedgerRpcProvider>(
+    ledger: &T,
+    wait_for_finalization: bool,
+) -> anyhow::Result<Receiver<u64>> {
+    if wait_for_finalization {
+        ledger.subscribe_slots()
+    } else {
+        ledger.subscribe_executed_slots()
+    }
+}
+
+/// Returns the notifications for the events of slot `slot_number` which match `filter`,
+/// one per event.
+fn slot_event_messages<T: LedgerRpcProvider>(
+    ledger: &T,
+    filter: &EventFilter,
+    slot_number: u64,
+) -> anyhow::Result<Vec<SubscriptionMessage>> {
+    let mut messages = Vec::new();
+    if let Some(slots) = &filter.slot_range {
+        if !slots.contains(&slot_number) {
+            return Ok(messages);
+        }
+    }
+    let slot_filter = EventFilter {
+        slot_range: Some(slot_number..slot_number + 1),
+        ..filter.clone()
+    };
+    let mut cursor = None;
+    loop {
+        let page = ledger.get_events_by_filter(&slot_filter, cursor, DEFAULT_EVENTS_PER_PAGE)?;
+        for event in page.events {
+            messages.push(SubscriptionMessage::from_json(&event)?);
+        }
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(messages),
+        }
+    }
+}
+
+/// Creates a new [`jsonrpsee::RpcModule`] that exposes the events of the
+/// [`LedgerRpcProvider`] decoded into the runtime event enum of `runtime`, and
+/// the JSON schema of that enum.
+///
+/// Events that were not emitted by a module of the runtime are returned as
+/// `null`, like events that don't exist.
+pub fn decoded_events_rpc_module<T, RT>(
+    ledger: T,
+    runtime: RT,
+) -> anyhow::Result<RpcModule<(T, RT)>>
+where
+    T: LedgerRpcProvider + Send + Sync + 'static,
+    RT: RuntimeEventJsonSchema + Send + Sync + 'static,
+    RT::RuntimeEvent: serde::Serialize,
+{
+    let mut rpc = RpcModule::new((ledger, runtime));
+
+    rpc.register_method("ledger_getDecodedEvents", move |params, ctx| {
+        let (ledger, runtime) = ctx;
+        let ids: Vec<EventIdentifier> = params.parse().or_else(|_| params.one())?;
+        let events = ledger
+            .get_events(&ids)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+        events
+            .iter()
+            .map(|event| decode_event(runtime, event.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    rpc.register_method("ledger_getDecodedEventByNumber", move |params, ctx| {
+        let (ledger, runtime) = ctx;
+        let args: u64 = params.one()?;
+        let event = ledger
+            .get_event_by_number(args)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+        decode_event(runtime, event.as_ref())
+    })?;
+    rpc.register_method("ledger_getEventJsonSchema", move |_, _| {
+        serde_json::from_str::<serde_json::Value>(&RT::event_json_schema())
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+
+    Ok(rpc)
+}
+
+/// Decodes an event with the `runtime` and converts it to JSON, since the
+/// runtime event enum is not required to be [`Clone`].
+fn decode_event<RT>(
+    runtime: &RT,
+    event: Option<&Event>,
+) -> Result<Option<serde_json::Value>, ErrorObjectOwned>
+where
+    RT: RuntimeEventProcessor,
+    RT::RuntimeEvent: serde::Serialize,
+{
+    event
+        .and_then(|event| runtime.decode_event(event))
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+}
+
 #[derive(serde::Deserialize)]
 struct RangeArgs(u64, u64, #[serde(default)] QueryMode);
 
+/// The arguments of `ledger_getEventsByFilter`: the filter, the cursor from which
+/// to resume, and the maximum number of events to return.
+#[derive(serde::Deserialize)]
+struct FilterArgs(
+    #[serde(default)] EventFilter,
+    #[serde(default)] Option<EventCursor>,
+    #[serde(default)] Option<u64>,
+);
+
 /// A structure containing serialized query arguments for RPC queries.
 #[derive(serde::Deserialize)]
 struct QueryArgs<T>(T, #[serde(default)] QueryMode);
@@ -3,20 +3,26 @@ use std::sync::Arc;
 
 use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
 use jsonrpsee::core::params::ArrayParams;
-use sov_db::ledger_db::LedgerDB;
+use sov_db::ledger_db::{LedgerDB, SlotCommit};
 use sov_ledger_rpc::client::RpcClient;
 use sov_ledger_rpc::server::rpc_module;
 use sov_ledger_rpc::HexHash;
+use sov_mock_da::MockBlock;
 use sov_modules_api::Event;
 use sov_rollup_interface::rpc::{
-    BatchResponse, EventIdentifier, QueryMode, SlotResponse, TxIdAndOffset, TxIdentifier,
-    TxResponse,
+    BatchResponse, EventFilter, EventIdentifier, QueryMode, SlotIdentifier, SlotResponse,
+    TxIdAndOffset, TxIdentifier, TxResponse,
 };
+use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use tempfile::tempdir;
 
 async fn rpc_server() -> (jsonrpsee::server::ServerHandle, SocketAddr) {
     let dir = tempdir().unwrap();
     let db = LedgerDB::with_path(dir).unwrap();
+    ledger_rpc_server(db).await
+}
+
+async fn ledger_rpc_server(db: LedgerDB) -> (jsonrpsee::server::ServerHandle, SocketAddr) {
     let rpc_module = rpc_module::<LedgerDB, u32, u32>(db).unwrap();
 
     let server = jsonrpsee::server::ServerBuilder::default()
@@ -63,6 +69,10 @@ async fn getters_succeed() {
         .await
         .unwrap();
     rpc_client.get_events(vec![]).await.unwrap();
+    rpc_client
+        .get_events_by_filter(EventFilter::default(), None, None)
+        .await
+        .unwrap();
 
     let hash = HexHash([0; 32]);
     rpc_client
@@ -103,6 +113,14 @@ async fn getters_succeed() {
         .get_txs_range(0, 1, QueryMode::Compact)
         .await
         .unwrap();
+
+    assert_eq!(
+        rpc_client
+            .get_proof_status(SlotIdentifier::Number(0))
+            .await
+            .unwrap(),
+        None
+    );
 }
 
 #[tokio::test]
@@ -113,6 +131,135 @@ async fn subscribe_slots_succeeds() {
     rpc_client.subscribe_slots().await.unwrap();
 }
 
+#[tokio::test]
+async fn subscribe_batches_succeeds() {
+    let (_server_handle, addr) = rpc_server().await;
+    let rpc_client = rpc_client(addr).await;
+
+    rpc_client.subscribe_batches(None).await.unwrap();
+    rpc_client.subscribe_batches(Some(true)).await.unwrap();
+}
+
+/// Returns a slot with a single batch, which contains a single transaction. The batch and the
+/// transaction have the same hash.
+fn slot_with_tx(hash: [u8; 32]) -> SlotCommit<MockBlock, u32, u32> {
+    let mut commit = SlotCommit::new(MockBlock::default());
+    commit.add_batch(BatchReceipt {
+        batch_hash: hash,
+        tx_receipts: vec![TransactionReceipt {
+            tx_hash: hash,
+            body_to_save: None,
+            events: vec![],
+            receipt: 0,
+        }],
+        inner: 0,
+    });
+    commit
+}
+
+#[tokio::test]
+async fn subscribe_batches_notifies_executed_slots() {
+    let dir = tempdir().unwrap();
+    let db = LedgerDB::with_path(dir.path()).unwrap();
+    let (_server_handle, addr) = ledger_rpc_server(db.clone()).await;
+    let rpc_client = rpc_client(addr).await;
+
+    let mut subscription = rpc_client.subscribe_batches(None).await.unwrap();
+    db.put_executed_slot(slot_with_tx([1; 32])).unwrap();
+    let batch = subscription.next().await.unwrap().unwrap();
+    assert_eq!(batch.hash, [1; 32]);
+    assert_eq!(batch.tx_range, 1..2);
+}
+
+#[tokio::test]
+async fn subscribe_batches_notifies_finalized_slots() {
+    let dir = tempdir().unwrap();
+    let db = LedgerDB::with_path(dir.path()).unwrap();
+    let (_server_handle, addr) = ledger_rpc_server(db.clone()).await;
+    let rpc_client = rpc_client(addr).await;
+
+    let mut subscription = rpc_client.subscribe_batches(Some(true)).await.unwrap();
+    // A slot which is reverted before being finalized is never notified.
+    db.put_executed_slot(slot_with_tx([1; 32])).unwrap();
+    db.revert_executed_slot().unwrap();
+    db.put_executed_slot(slot_with_tx([2; 32])).unwrap();
+    db.finalize_executed_slot(&MockBlock::default().header.hash.into())
+        .unwrap();
+    let batch = subscription.next().await.unwrap().unwrap();
+    assert_eq!(batch.hash, [2; 32]);
+}
+
+#[tokio::test]
+async fn subscribe_batches_catches_up_after_lagging() {
+    let dir = tempdir().unwrap();
+    let db = LedgerDB::with_path(dir.path()).unwrap();
+    let (_server_handle, addr) = ledger_rpc_server(db.clone()).await;
+    let rpc_client = rpc_client(addr).await;
+
+    let mut subscription = rpc_client.subscribe_batches(None).await.unwrap();
+    // Executes more slots than the slot notification channel can hold, so the subscription
+    // may lag behind.
+    for i in 1..=32 {
+        db.put_executed_slot(slot_with_tx([i; 32])).unwrap();
+    }
+    for i in 1..=32 {
+        let batch = subscription.next().await.unwrap().unwrap();
+        assert_eq!(batch.hash, [i; 32]);
+    }
+}
+
+#[tokio::test]
+async fn subscribe_transaction_notifies_executed_and_finalized_txs() {
+    let dir = tempdir().unwrap();
+    let db = LedgerDB::with_path(dir.path()).unwrap();
+    let (_server_handle, addr) = ledger_rpc_server(db.clone()).await;
+    let rpc_client = rpc_client(addr).await;
+
+    let mut executed = rpc_client
+        .subscribe_transaction(HexHash([1; 32]), None)
+        .await
+        .unwrap();
+    let mut finalized = rpc_client
+        .subscribe_transaction(HexHash([1; 32]), Some(true))
+        .await
+        .unwrap();
+    db.put_executed_slot(slot_with_tx([1; 32])).unwrap();
+    let tx = executed.next().await.unwrap().unwrap();
+    assert_eq!(tx.hash, [1; 32]);
+    assert_eq!(tx.event_range, 1..1);
+
+    db.finalize_executed_slot(&MockBlock::default().header.hash.into())
+        .unwrap();
+    let tx = finalized.next().await.unwrap().unwrap();
+    assert_eq!(tx.hash, [1; 32]);
+}
+
+#[tokio::test]
+async fn subscribe_transaction_succeeds() {
+    let (_server_handle, addr) = rpc_server().await;
+    let rpc_client = rpc_client(addr).await;
+
+    rpc_client
+        .subscribe_transaction(HexHash([0; 32]), None)
+        .await
+        .unwrap();
+    rpc_client
+        .subscribe_transaction(HexHash([0; 32]), Some(true))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn subscribe_events_succeeds() {
+    let (_server_handle, addr) = rpc_server().await;
+    let rpc_client = rpc_client(addr).await;
+
+    rpc_client
+        .subscribe_events(EventFilter::default())
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn get_head_with_optional_query_mode() {
     let (_server_handle, addr) = rpc_server().await;
@@ -1,20 +1,33 @@
-mod dummy_storage;
-mod snapshot_manager;
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+pub mod snapshot;
 
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
+use sov_db::native_db::NativeDB;
+use sov_db::pruning::{spawn_pruner, PrunerHandle, StatePruner, StatePruningConfig};
+use sov_db::snapshot_manager::SnapshotManager;
+use sov_db::state_db::StateDB;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
-use sov_rollup_interface::storage::HierarchicalStorageManager;
-use sov_schema_db::snapshot::{DbSnapshot, FrozenDbSnapshot, ReadOnlyLock, SnapshotId};
-use sov_state::MerkleProofSpec;
-
-use crate::dummy_storage::NewProverStorage;
-use crate::snapshot_manager::SnapshotManager;
-
-struct NewProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
+use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
+use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock, SnapshotId};
+use sov_state::config::Config;
+use sov_state::{MerkleProofSpec, ProverStorage};
+
+/// Snapshot ids of the blocks start from 1, so this id never has a parent,
+/// and storage created on top of it reads directly from the database.
+const FINALIZED_SNAPSHOT_ID: SnapshotId = 0;
+
+/// Implementation of [`HierarchicalStorageManager`] for [`ProverStorage`].
+/// Each unfinalized DA block gets its own [`ProverStorage`] on top of the snapshot of its parent block,
+/// so changes on different forks are isolated from each other.
+/// Changes are written to the database only when the block is finalized.
+pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     // L1 forks representation
     // Chain: prev_block -> child_blocks
     chain_forks: HashMap<Da::SlotHash, Vec<Da::SlotHash>>,
@@ -29,15 +42,27 @@ struct NewProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     state_snapshot_manager: Arc<RwLock<SnapshotManager>>,
     accessory_snapshot_manager: Arc<RwLock<SnapshotManager>>,
 
+    // Stopped along with the manager
+    state_pruner: Option<PrunerHandle>,
+
     phantom_mp_spec: PhantomData<S>,
 }
 
-impl<Da: DaSpec, S: MerkleProofSpec> NewProverStorageManager<Da, S>
+impl<Da: DaSpec, S: MerkleProofSpec> ProverStorageManager<Da, S>
 where
     Da::SlotHash: Hash,
 {
-    #[allow(dead_code)]
-    pub fn new(state_db: sov_schema_db::DB, native_db: sov_schema_db::DB) -> Self {
+    /// Create new [`ProverStorageManager`] from state config
+    pub fn new(config: Config) -> anyhow::Result<Self> {
+        let path = config.path;
+        let state_db = StateDB::<SnapshotManager>::setup_schema_db(&path)?;
+        let native_db = NativeDB::<SnapshotManager>::setup_schema_db(&path)?;
+
+        Ok(Self::with_db_handles(state_db, native_db))
+    }
+
+    /// Create new [`ProverStorageManager`] on top of already opened state and native databases.
+    pub fn with_db_handles(state_db: sov_schema_db::DB, native_db: sov_schema_db::DB) -> Self {
         let snapshot_id_to_parent = Arc::new(RwLock::new(HashMap::new()));
 
         let state_snapshot_manager = SnapshotManager::new(state_db, snapshot_id_to_parent.clone());
@@ -47,15 +72,48 @@ where
         Self {
             chain_forks: Default::default(),
             blocks_to_parent: Default::default(),
-            latest_snapshot_id: 0,
+            latest_snapshot_id: FINALIZED_SNAPSHOT_ID,
             block_hash_to_snapshot_id: Default::default(),
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
+            state_pruner: None,
             phantom_mp_spec: Default::default(),
         }
     }
 
+    /// Spawns a thread which prunes the state database every `interval`, according to `config`.
+    /// Only finalized versions of the state are pruned, since the others are not written yet.
+    /// The thread is stopped when the manager is dropped, and replaces any previous pruner.
+    pub fn spawn_state_pruner(
+        &mut self,
+        config: StatePruningConfig,
+        interval: Duration,
+    ) -> std::io::Result<()> {
+        let pruner = StatePruner::new(self.state_snapshot_manager.clone(), config);
+        self.state_pruner = Some(spawn_pruner("state-pruner", interval, move || {
+            pruner.prune()
+        })?);
+        Ok(())
+    }
+
+    fn create_storage_on_snapshot(&self, snapshot_id: SnapshotId) -> ProverStorage<S> {
+        let state_db_snapshot = DbSnapshot::new(
+            snapshot_id,
+            ReadOnlyLock::new(self.state_snapshot_manager.clone()),
+        );
+
+        let native_db_snapshot = DbSnapshot::new(
+            snapshot_id,
+            ReadOnlyLock::new(self.accessory_snapshot_manager.clone()),
+        );
+
+        ProverStorage::with_db_handles(
+            StateDB::with_db_snapshot(state_db_snapshot),
+            NativeDB::with_db_snapshot(native_db_snapshot),
+        )
+    }
+
     #[cfg(test)]
     fn is_empty(&self) -> bool {
         self.chain_forks.is_empty()
@@ -67,13 +125,27 @@ where
     }
 }
 
-impl<Da: DaSpec, S: MerkleProofSpec> HierarchicalStorageManager<Da>
-    for NewProverStorageManager<Da, S>
+impl<Da: DaSpec, S: MerkleProofSpec> StorageManager for ProverStorageManager<Da, S>
+where
+    Da::SlotHash: Hash,
+{
+    type NativeStorage = ProverStorage<S>;
+    type NativeChangeSet = ProverStorage<S>;
+
+    /// Returns storage on top of the finalized state.
+    /// Changes written to this storage are never persisted, and it doesn't see the changes of
+    /// blocks which are not finalized yet.
+    fn get_native_storage(&self) -> Self::NativeStorage {
+        self.create_storage_on_snapshot(FINALIZED_SNAPSHOT_ID)
+    }
+}
+
+impl<Da: DaSpec, S: MerkleProofSpec> HierarchicalStorageManager<Da> for ProverStorageManager<Da, S>
 where
     Da::SlotHash: Hash,
 {
-    type NativeStorage = NewProverStorage<S, SnapshotManager>;
-    type NativeChangeSet = NewProverStorage<S, SnapshotManager>;
+    type NativeStorage = ProverStorage<S>;
+    type NativeChangeSet = ProverStorage<S>;
 
     fn get_native_storage_on(
         &mut self,
@@ -122,20 +194,7 @@ where
             }
         };
 
-        let state_db_snapshot = DbSnapshot::new(
-            new_snapshot_id,
-            ReadOnlyLock::new(self.state_snapshot_manager.clone()),
-        );
-
-        let native_db_snapshot = DbSnapshot::new(
-            new_snapshot_id,
-            ReadOnlyLock::new(self.accessory_snapshot_manager.clone()),
-        );
-
-        Ok(NewProverStorage::with_db_handlers(
-            state_db_snapshot,
-            native_db_snapshot,
-        ))
+        Ok(self.create_storage_on_snapshot(new_snapshot_id))
     }
 
     fn save_change_set(
@@ -149,9 +208,7 @@ where
                 block_header
             );
         }
-        let (state_db, native_db) = change_set.freeze();
-        let state_snapshot: FrozenDbSnapshot = state_db.into();
-        let native_snapshot: FrozenDbSnapshot = native_db.into();
+        let (state_snapshot, native_snapshot) = change_set.freeze()?;
         let snapshot_id = state_snapshot.get_id();
         if snapshot_id != native_snapshot.get_id() {
             anyhow::bail!(
@@ -248,19 +305,61 @@ where
 mod tests {
     use std::path;
 
-    use sov_db::rocks_db_config::gen_rocksdb_options;
     use sov_mock_da::{MockBlockHeader, MockHash};
-    use sov_schema_db::snapshot::FrozenDbSnapshot;
+    use sov_state::storage::{NativeStorage, StorageKey, StorageValue};
+    use sov_state::{ArrayWitness, OrderedReadsAndWrites, Storage, StorageInternalCache};
 
     use super::*;
-    use crate::dummy_storage::{
-        DummyField, DummyNativeSchema, DummyStateSchema, DUMMY_NATIVE_CF, DUMMY_STATE_CF,
-    };
 
     type Da = sov_mock_da::MockDaSpec;
     type S = sov_state::DefaultStorageSpec;
 
-    fn validate_internal_consistency(storage_manager: &NewProverStorageManager<Da, S>) {
+    fn storage_key(key: u64) -> StorageKey {
+        StorageKey::from(key.to_string().as_str())
+    }
+
+    fn into_ordered_writes(changes: &[(u64, Option<u64>)]) -> OrderedReadsAndWrites {
+        let mut cache = StorageInternalCache::default();
+        for (key, value) in changes {
+            let key = storage_key(*key);
+            match value {
+                Some(value) => cache.set(&key, StorageValue::from(value.to_be_bytes().to_vec())),
+                None => cache.delete(&key),
+            }
+        }
+        cache.into()
+    }
+
+    fn from_storage_value(value: StorageValue) -> u64 {
+        u64::from_be_bytes(value.value().try_into().unwrap())
+    }
+
+    /// Writes changes as a single state update. `None` value means deletion.
+    fn write_changes(
+        storage: &ProverStorage<S>,
+        state_changes: &[(u64, Option<u64>)],
+        native_changes: &[(u64, Option<u64>)],
+    ) {
+        let witness = ArrayWitness::default();
+        let (_, state_update) = storage
+            .compute_state_update(into_ordered_writes(state_changes), &witness)
+            .unwrap();
+        storage.commit(&state_update, &into_ordered_writes(native_changes));
+    }
+
+    fn read_state(storage: &ProverStorage<S>, key: u64) -> Option<u64> {
+        storage
+            .get(&storage_key(key), &ArrayWitness::default())
+            .map(from_storage_value)
+    }
+
+    fn read_native(storage: &ProverStorage<S>, key: u64) -> Option<u64> {
+        storage
+            .get_accessory(&storage_key(key))
+            .map(from_storage_value)
+    }
+
+    fn validate_internal_consistency(storage_manager: &ProverStorageManager<Da, S>) {
         let snapshot_id_to_parent = storage_manager.snapshot_id_to_parent.read().unwrap();
         let state_snapshot_manager = storage_manager.state_snapshot_manager.read().unwrap();
         let native_snapshot_manager = storage_manager.state_snapshot_manager.read().unwrap();
@@ -322,22 +421,8 @@ mod tests {
         state_path: &path::Path,
         native_path: &path::Path,
     ) -> (sov_schema_db::DB, sov_schema_db::DB) {
-        let state_tables = vec![DUMMY_STATE_CF.to_string()];
-        let state_db = sov_schema_db::DB::open(
-            state_path,
-            "state_db",
-            state_tables,
-            &gen_rocksdb_options(&Default::default(), false),
-        )
-        .unwrap();
-        let native_tables = vec![DUMMY_NATIVE_CF.to_string()];
-        let native_db = sov_schema_db::DB::open(
-            native_path,
-            "native_db",
-            native_tables,
-            &gen_rocksdb_options(&Default::default(), false),
-        )
-        .unwrap();
+        let state_db = StateDB::<SnapshotManager>::setup_schema_db(state_path).unwrap();
+        let native_db = NativeDB::<SnapshotManager>::setup_schema_db(native_path).unwrap();
 
         (state_db, native_db)
     }
@@ -349,7 +434,7 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let storage_manager = ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
         validate_internal_consistency(&storage_manager);
     }
@@ -361,7 +446,8 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_header = MockBlockHeader {
@@ -401,7 +487,8 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_header = MockBlockHeader {
@@ -420,12 +507,8 @@ mod tests {
 
         // We just check, that both storage have same underlying id.
         // This is more tight with implementation.
-        let (state_db_1, native_db_1) = storage_1.freeze();
-        let state_snapshot_1 = FrozenDbSnapshot::from(state_db_1);
-        let native_snapshot_1 = FrozenDbSnapshot::from(native_db_1);
-        let (state_db_2, native_db_2) = storage_2.freeze();
-        let state_snapshot_2 = FrozenDbSnapshot::from(state_db_2);
-        let native_snapshot_2 = FrozenDbSnapshot::from(native_db_2);
+        let (state_snapshot_1, native_snapshot_1) = storage_1.freeze().unwrap();
+        let (state_snapshot_2, native_snapshot_2) = storage_2.freeze().unwrap();
 
         assert_eq!(state_snapshot_1.get_id(), state_snapshot_2.get_id());
         assert_eq!(native_snapshot_1.get_id(), native_snapshot_2.get_id());
@@ -446,7 +529,8 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_header = MockBlockHeader {
@@ -471,7 +555,8 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_a = MockBlockHeader {
@@ -503,7 +588,8 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_header = MockBlockHeader {
@@ -543,14 +629,15 @@ mod tests {
         let snapshot_1 = {
             let (state_db, native_db) = build_dbs(state_tmpdir_1.path(), native_tmpdir_1.path());
             let mut storage_manager_temp =
-                NewProverStorageManager::<Da, S>::new(state_db, native_db);
+                ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
             storage_manager_temp
                 .get_native_storage_on(&block_a)
                 .unwrap()
         };
 
         let (state_db, native_db) = build_dbs(state_tmpdir_2.path(), native_tmpdir_2.path());
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
 
         let result = storage_manager.save_change_set(&block_a, snapshot_1);
         assert!(result.is_err());
@@ -588,7 +675,7 @@ mod tests {
         let (snapshot_alien_1, snapshot_alien_2) = {
             let (state_db, native_db) = build_dbs(state_tmpdir_1.path(), native_tmpdir_1.path());
             let mut storage_manager_temp =
-                NewProverStorageManager::<Da, S>::new(state_db, native_db);
+                ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
             // ID = 1
             let snapshot_a = storage_manager_temp
                 .get_native_storage_on(&block_a)
@@ -601,7 +688,8 @@ mod tests {
         };
 
         let (state_db, native_db) = build_dbs(state_tmpdir_2.path(), native_tmpdir_2.path());
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
 
         let snapshot_own_a = storage_manager.get_native_storage_on(&block_a).unwrap();
         let _snapshot_own_b = storage_manager.get_native_storage_on(&block_b).unwrap();
@@ -629,7 +717,8 @@ mod tests {
         let native_tmpdir = tempfile::tempdir().unwrap();
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         let block_from_i = |i: u8| MockBlockHeader {
@@ -658,7 +747,8 @@ mod tests {
         let native_tmpdir = tempfile::tempdir().unwrap();
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
         assert!(storage_manager.is_empty());
 
         // 1    2    3
@@ -713,27 +803,34 @@ mod tests {
 
         let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
 
-        // State DB has following values initially:
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        assert!(storage_manager.is_empty());
+
+        // Genesis block is finalized right away, so DB has following values initially:
+        // State:
         // 1 = 1
         // 2 = 2
-        let one = DummyField(1);
-        let two = DummyField(2);
-
-        state_db.put::<DummyStateSchema>(&one, &one).unwrap();
-        state_db.put::<DummyStateSchema>(&two, &two).unwrap();
-
-        // Native DB has following values initially
+        // Native:
         // 1 = 100
         // 2 = 200
-
-        native_db
-            .put::<DummyNativeSchema>(&one, &DummyField(100))
+        let block_genesis = MockBlockHeader {
+            prev_hash: MockHash::from([100; 32]),
+            hash: MockHash::from([0; 32]),
+            height: 0,
+        };
+        let storage_genesis = storage_manager
+            .get_native_storage_on(&block_genesis)
             .unwrap();
-        native_db
-            .put::<DummyNativeSchema>(&two, &DummyField(200))
+        write_changes(
+            &storage_genesis,
+            &[(1, Some(1)), (2, Some(2))],
+            &[(1, Some(100)), (2, Some(200))],
+        );
+        storage_manager
+            .save_change_set(&block_genesis, storage_genesis)
             .unwrap();
-
-        let mut storage_manager = NewProverStorageManager::<Da, S>::new(state_db, native_db);
+        storage_manager.finalize(&block_genesis).unwrap();
         assert!(storage_manager.is_empty());
 
         // Chains:
@@ -821,52 +918,47 @@ mod tests {
 
         // A
         let storage_a = storage_manager.get_native_storage_on(&block_a).unwrap();
-        storage_a.write_state(1, 3).unwrap();
-        storage_a.write_state(3, 4).unwrap();
-        storage_a.write_native(3, 400).unwrap();
+        write_changes(&storage_a, &[(1, Some(3)), (3, Some(4))], &[(3, Some(400))]);
         storage_manager
             .save_change_set(&block_a, storage_a)
             .unwrap();
         // B
         let storage_b = storage_manager.get_native_storage_on(&block_b).unwrap();
-        storage_b.write_state(3, 4).unwrap();
-        storage_b.write_native(3, 500).unwrap();
+        write_changes(&storage_b, &[(3, Some(4))], &[(3, Some(500))]);
         storage_manager
             .save_change_set(&block_b, storage_b)
             .unwrap();
         // C
         let storage_c = storage_manager.get_native_storage_on(&block_c).unwrap();
-        storage_c.delete_state(1).unwrap();
-        storage_c.write_state(4, 5).unwrap();
-        storage_c.write_native(1, 600).unwrap();
+        write_changes(&storage_c, &[(1, None), (4, Some(5))], &[(1, Some(600))]);
         storage_manager
             .save_change_set(&block_c, storage_c)
             .unwrap();
         // D
         let storage_d = storage_manager.get_native_storage_on(&block_d).unwrap();
-        storage_d.write_state(3, 6).unwrap();
+        write_changes(&storage_d, &[(3, Some(6))], &[]);
         storage_manager
             .save_change_set(&block_d, storage_d)
             .unwrap();
         // F
         let storage_f = storage_manager.get_native_storage_on(&block_f).unwrap();
-        storage_f.write_state(1, 7).unwrap();
-        storage_f.write_native(3, 700).unwrap();
-        storage_f.delete_state(3).unwrap();
-        storage_f.delete_native(1).unwrap();
+        write_changes(
+            &storage_f,
+            &[(1, Some(7)), (3, None)],
+            &[(3, Some(700)), (1, None)],
+        );
         storage_manager
             .save_change_set(&block_f, storage_f)
             .unwrap();
         // G
         let storage_g = storage_manager.get_native_storage_on(&block_g).unwrap();
-        storage_g.write_state(1, 8).unwrap();
-        storage_g.write_native(2, 9).unwrap();
+        write_changes(&storage_g, &[(1, Some(8))], &[(2, Some(9))]);
         storage_manager
             .save_change_set(&block_g, storage_g)
             .unwrap();
         // L
         let storage_l = storage_manager.get_native_storage_on(&block_l).unwrap();
-        storage_l.write_state(1, 10).unwrap();
+        write_changes(&storage_l, &[(1, Some(10))], &[]);
         storage_manager
             .save_change_set(&block_l, storage_l)
             .unwrap();
@@ -912,42 +1004,42 @@ mod tests {
         let storage_k = storage_manager.get_native_storage_on(&block_k).unwrap();
 
         let assert_main_fork = || {
-            assert_eq!(None, storage_e.read_state(1).unwrap());
-            assert_eq!(Some(2), storage_e.read_state(2).unwrap());
-            assert_eq!(Some(6), storage_e.read_state(3).unwrap());
-            assert_eq!(Some(5), storage_e.read_state(4).unwrap());
-            assert_eq!(Some(600), storage_e.read_native(1).unwrap());
-            assert_eq!(Some(200), storage_e.read_native(2).unwrap());
-            assert_eq!(Some(500), storage_e.read_native(3).unwrap());
-            assert_eq!(None, storage_e.read_native(4).unwrap());
-
-            assert_eq!(Some(10), storage_m.read_state(1).unwrap());
-            assert_eq!(Some(2), storage_m.read_state(2).unwrap());
-            assert_eq!(Some(4), storage_m.read_state(3).unwrap());
-            assert_eq!(None, storage_m.read_state(4).unwrap());
-            assert_eq!(Some(100), storage_m.read_native(1).unwrap());
-            assert_eq!(Some(200), storage_m.read_native(2).unwrap());
-            assert_eq!(Some(500), storage_m.read_native(3).unwrap());
-            assert_eq!(None, storage_m.read_native(4).unwrap());
-
-            assert_eq!(Some(8), storage_h.read_state(1).unwrap());
-            assert_eq!(Some(2), storage_h.read_state(2).unwrap());
-            assert_eq!(Some(4), storage_h.read_state(3).unwrap());
-            assert_eq!(None, storage_h.read_state(4).unwrap());
-            assert_eq!(Some(100), storage_h.read_native(1).unwrap());
-            assert_eq!(Some(9), storage_h.read_native(2).unwrap());
-            assert_eq!(Some(500), storage_h.read_native(3).unwrap());
-            assert_eq!(None, storage_h.read_native(4).unwrap());
+            assert_eq!(None, read_state(&storage_e, 1));
+            assert_eq!(Some(2), read_state(&storage_e, 2));
+            assert_eq!(Some(6), read_state(&storage_e, 3));
+            assert_eq!(Some(5), read_state(&storage_e, 4));
+            assert_eq!(Some(600), read_native(&storage_e, 1));
+            assert_eq!(Some(200), read_native(&storage_e, 2));
+            assert_eq!(Some(500), read_native(&storage_e, 3));
+            assert_eq!(None, read_native(&storage_e, 4));
+
+            assert_eq!(Some(10), read_state(&storage_m, 1));
+            assert_eq!(Some(2), read_state(&storage_m, 2));
+            assert_eq!(Some(4), read_state(&storage_m, 3));
+            assert_eq!(None, read_state(&storage_m, 4));
+            assert_eq!(Some(100), read_native(&storage_m, 1));
+            assert_eq!(Some(200), read_native(&storage_m, 2));
+            assert_eq!(Some(500), read_native(&storage_m, 3));
+            assert_eq!(None, read_native(&storage_m, 4));
+
+            assert_eq!(Some(8), read_state(&storage_h, 1));
+            assert_eq!(Some(2), read_state(&storage_h, 2));
+            assert_eq!(Some(4), read_state(&storage_h, 3));
+            assert_eq!(None, read_state(&storage_h, 4));
+            assert_eq!(Some(100), read_native(&storage_h, 1));
+            assert_eq!(Some(9), read_native(&storage_h, 2));
+            assert_eq!(Some(500), read_native(&storage_h, 3));
+            assert_eq!(None, read_native(&storage_h, 4));
         };
         assert_main_fork();
-        assert_eq!(Some(7), storage_k.read_state(1).unwrap());
-        assert_eq!(Some(2), storage_k.read_state(2).unwrap());
-        assert_eq!(None, storage_k.read_state(3).unwrap());
-        assert_eq!(None, storage_k.read_state(4).unwrap());
-        assert_eq!(None, storage_k.read_native(1).unwrap());
-        assert_eq!(Some(200), storage_k.read_native(2).unwrap());
-        assert_eq!(Some(700), storage_k.read_native(3).unwrap());
-        assert_eq!(None, storage_k.read_native(4).unwrap());
+        assert_eq!(Some(7), read_state(&storage_k, 1));
+        assert_eq!(Some(2), read_state(&storage_k, 2));
+        assert_eq!(None, read_state(&storage_k, 3));
+        assert_eq!(None, read_state(&storage_k, 4));
+        assert_eq!(None, read_native(&storage_k, 1));
+        assert_eq!(Some(200), read_native(&storage_k, 2));
+        assert_eq!(Some(700), read_native(&storage_k, 3));
+        assert_eq!(None, read_native(&storage_k, 4));
 
         validate_internal_consistency(&storage_manager);
         // After finalization of A
@@ -961,6 +1053,163 @@ mod tests {
         validate_internal_consistency(&storage_manager);
         storage_manager.finalize(&block_d).unwrap();
         validate_internal_consistency(&storage_manager);
-        // TODO: Check that values are in the database
+
+        // Finalized changes are in the database
+        let finalized_storage = storage_manager.get_native_storage();
+        assert_eq!(None, read_state(&finalized_storage, 1));
+        assert_eq!(Some(2), read_state(&finalized_storage, 2));
+        assert_eq!(Some(6), read_state(&finalized_storage, 3));
+        assert_eq!(Some(5), read_state(&finalized_storage, 4));
+        assert_eq!(Some(600), read_native(&finalized_storage, 1));
+        assert_eq!(Some(200), read_native(&finalized_storage, 2));
+        assert_eq!(Some(500), read_native(&finalized_storage, 3));
+        assert_eq!(None, read_native(&finalized_storage, 4));
+    }
+
+    #[test]
+    fn read_through_snapshots() {
+        let state_tmpdir = tempfile::tempdir().unwrap();
+        let native_tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        let block_a = MockBlockHeader {
+            prev_hash: MockHash::from([0; 32]),
+            hash: MockHash::from([1; 32]),
+            height: 1,
+        };
+        let block_b = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([2; 32]),
+            height: 2,
+        };
+
+        let storage_a = storage_manager.get_native_storage_on(&block_a).unwrap();
+        write_changes(&storage_a, &[(1, Some(1)), (2, Some(2))], &[(1, Some(100))]);
+        storage_manager
+            .save_change_set(&block_a, storage_a)
+            .unwrap();
+
+        // Each block gets its own snapshot, with its parent snapshot as parent.
+        let storage_b = storage_manager.get_native_storage_on(&block_b).unwrap();
+        let snapshot_a = storage_manager.block_hash_to_snapshot_id[&block_a.hash];
+        let snapshot_b = storage_manager.block_hash_to_snapshot_id[&block_b.hash];
+        assert_ne!(snapshot_a, snapshot_b);
+        assert_eq!(
+            Some(&snapshot_a),
+            storage_manager
+                .snapshot_id_to_parent
+                .read()
+                .unwrap()
+                .get(&snapshot_b)
+        );
+
+        // The changes of the parent block are visible through its snapshot.
+        assert_eq!(Some(1), read_state(&storage_b, 1));
+        assert_eq!(Some(2), read_state(&storage_b, 2));
+        assert_eq!(Some(100), read_native(&storage_b, 1));
+        write_changes(&storage_b, &[(2, None), (3, Some(3))], &[(1, Some(200))]);
+        assert_eq!(None, read_state(&storage_b, 2));
+        assert_eq!(Some(3), read_state(&storage_b, 3));
+        assert_eq!(Some(200), read_native(&storage_b, 1));
+        storage_manager
+            .save_change_set(&block_b, storage_b)
+            .unwrap();
+
+        // The changes of a block are not visible to its siblings.
+        let block_c = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([12; 32]),
+            height: 2,
+        };
+        let storage_c = storage_manager.get_native_storage_on(&block_c).unwrap();
+        assert_eq!(Some(1), read_state(&storage_c, 1));
+        assert_eq!(Some(2), read_state(&storage_c, 2));
+        assert_eq!(None, read_state(&storage_c, 3));
+        assert_eq!(Some(100), read_native(&storage_c, 1));
+        validate_internal_consistency(&storage_manager);
+    }
+
+    #[test]
+    fn discard_abandoned_fork() {
+        let state_tmpdir = tempfile::tempdir().unwrap();
+        let native_tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(state_tmpdir.path(), native_tmpdir.path());
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        // 1    2
+        // A -> B
+        // \ -> C
+        let block_a = MockBlockHeader {
+            prev_hash: MockHash::from([0; 32]),
+            hash: MockHash::from([1; 32]),
+            height: 1,
+        };
+        let block_b = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([2; 32]),
+            height: 2,
+        };
+        let block_c = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([12; 32]),
+            height: 2,
+        };
+
+        let storage_a = storage_manager.get_native_storage_on(&block_a).unwrap();
+        write_changes(&storage_a, &[(1, Some(1))], &[]);
+        storage_manager
+            .save_change_set(&block_a, storage_a)
+            .unwrap();
+
+        let storage_b = storage_manager.get_native_storage_on(&block_b).unwrap();
+        write_changes(&storage_b, &[(1, Some(2)), (2, Some(2))], &[(1, Some(200))]);
+        let root_b = storage_b.get_root_hash(storage_b.latest_version()).unwrap();
+        storage_manager
+            .save_change_set(&block_b, storage_b)
+            .unwrap();
+
+        let storage_c = storage_manager.get_native_storage_on(&block_c).unwrap();
+        write_changes(&storage_c, &[(1, Some(3)), (3, Some(3))], &[(1, Some(300))]);
+        storage_manager
+            .save_change_set(&block_c, storage_c)
+            .unwrap();
+        let snapshot_c = storage_manager.block_hash_to_snapshot_id[&block_c.hash];
+
+        // The storage used to serve RPC requests doesn't see unfinalized changes.
+        let finalized_storage = storage_manager.get_native_storage();
+        assert_eq!(None, read_state(&finalized_storage, 1));
+
+        storage_manager.finalize(&block_a).unwrap();
+        storage_manager.finalize(&block_b).unwrap();
+        validate_internal_consistency(&storage_manager);
+        assert!(storage_manager.is_empty());
+
+        // The snapshot of the abandoned fork is discarded, and none of its changes are persisted.
+        assert!(!storage_manager
+            .state_snapshot_manager
+            .read()
+            .unwrap()
+            .contains_snapshot(&snapshot_c));
+        assert!(!storage_manager
+            .accessory_snapshot_manager
+            .read()
+            .unwrap()
+            .contains_snapshot(&snapshot_c));
+        let finalized_storage = storage_manager.get_native_storage();
+        assert_eq!(Some(2), read_state(&finalized_storage, 1));
+        assert_eq!(Some(2), read_state(&finalized_storage, 2));
+        assert_eq!(None, read_state(&finalized_storage, 3));
+        assert_eq!(Some(200), read_native(&finalized_storage, 1));
+        assert_eq!(
+            root_b,
+            finalized_storage
+                .get_root_hash(finalized_storage.latest_version())
+                .unwrap()
+        );
     }
 }
@@ -0,0 +1,495 @@
+//! Export and import of state snapshots, so that new nodes can bootstrap from the state of a
+//! finalized slot instead of replaying every DA block since genesis.
+//!
+//! A snapshot is a directory holding a [`SnapshotManifest`] in [`MANIFEST_FILE_NAME`], along
+//! with chunk files of borsh-encoded `(key, value)` pairs: the JMT leaves of the exported
+//! version of the state, keyed by their preimages, and the accessory state of the `NativeDB`.
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::storage::{Node, NodeBatch, NodeKey, TreeWriter};
+use jmt::{JellyfishMerkleTree, KeyHash, Version};
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::{ItemNumbers, LedgerDB};
+use sov_db::native_db::NativeDB;
+use sov_db::schema::tables::{JmtValues, ModuleAccessoryState};
+use sov_db::schema::types::PrunedVersions;
+use sov_db::snapshot_manager::SnapshotManager;
+use sov_db::state_db::StateDB;
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::rpc::utils::rpc_hex;
+use sov_rollup_interface::rpc::{LedgerRpcProvider, QueryMode};
+use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock};
+use sov_state::storage::NativeStorage;
+use sov_state::{MerkleProofSpec, ProverStorage, Storage};
+
+use crate::FINALIZED_SNAPSHOT_ID;
+
+/// The name of the manifest file in a snapshot directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// The maximum number of entries in a single chunk file.
+const ENTRIES_PER_CHUNK: usize = 10_000;
+
+/// Describes the content of a snapshot directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// The number of the slot right before the exported version of the state.
+    pub slot_number: u64,
+    /// The hash of the DA block of the slot.
+    #[serde(with = "rpc_hex")]
+    pub slot_hash: [u8; 32],
+    /// The exported version of the state.
+    pub version: Version,
+    /// The root of the exported version of the state.
+    #[serde(with = "rpc_hex")]
+    pub state_root: [u8; 32],
+    /// The numbers of the ledger items following the slot.
+    pub next_item_numbers: ItemNumbers,
+    /// The chunks holding the JMT leaves.
+    pub state_chunks: Vec<SnapshotChunk>,
+    /// The chunks holding the accessory state.
+    pub accessory_chunks: Vec<SnapshotChunk>,
+}
+
+/// A chunk file of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotChunk {
+    /// The name of the file, relative to the snapshot directory.
+    pub file_name: String,
+    /// The number of entries in the chunk.
+    pub num_entries: u64,
+    /// The hash of the content of the file.
+    #[serde(with = "rpc_hex")]
+    pub hash: [u8; 32],
+}
+
+type Entries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Writes the entries of one kind into chunk files of at most [`ENTRIES_PER_CHUNK`] entries.
+struct ChunkWriter<'a, S: MerkleProofSpec> {
+    snapshot_dir: &'a Path,
+    prefix: &'static str,
+    entries: Entries,
+    chunks: Vec<SnapshotChunk>,
+    _phantom_spec: std::marker::PhantomData<S>,
+}
+
+impl<'a, S: MerkleProofSpec> ChunkWriter<'a, S> {
+    fn new(snapshot_dir: &'a Path, prefix: &'static str) -> Self {
+        Self {
+            snapshot_dir,
+            prefix,
+            entries: Vec::with_capacity(ENTRIES_PER_CHUNK),
+            chunks: Vec::new(),
+            _phantom_spec: Default::default(),
+        }
+    }
+
+    fn push(&mut self, key: Vec<u8>, value: Vec<u8>) -> anyhow::Result<()> {
+        self.entries.push((key, value));
+        if self.entries.len() >= ENTRIES_PER_CHUNK {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        let data = self.entries.try_to_vec()?;
+        let file_name = format!("{}-{:06}.chunk", self.prefix, self.chunks.len());
+        fs::write(self.snapshot_dir.join(&file_name), &data)?;
+        self.chunks.push(SnapshotChunk {
+            file_name,
+            num_entries: self.entries.len() as u64,
+            hash: S::Hasher::digest(&data).into(),
+        });
+        self.entries.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<Vec<SnapshotChunk>> {
+        self.flush()?;
+        Ok(self.chunks)
+    }
+}
+
+/// Reads a chunk file and checks it against its hash in the manifest.
+fn read_chunk<S: MerkleProofSpec>(
+    snapshot_dir: &Path,
+    chunk: &SnapshotChunk,
+) -> anyhow::Result<Entries> {
+    let data = fs::read(snapshot_dir.join(&chunk.file_name))?;
+    let hash: [u8; 32] = S::Hasher::digest(&data).into();
+    anyhow::ensure!(
+        hash == chunk.hash,
+        "Chunk {} is corrupted: expected hash 0x{}, got 0x{}",
+        chunk.file_name,
+        hex::encode(chunk.hash),
+        hex::encode(hash)
+    );
+    let entries = Entries::try_from_slice(&data)?;
+    anyhow::ensure!(
+        entries.len() as u64 == chunk.num_entries,
+        "Chunk {} holds {} entries instead of {}",
+        chunk.file_name,
+        entries.len(),
+        chunk.num_entries
+    );
+    Ok(entries)
+}
+
+/// Wraps the database into an orphan [`SnapshotManager`], and returns it along with a snapshot
+/// reading directly from the database.
+fn open_db(
+    schema_db: sov_schema_db::DB,
+) -> (Arc<RwLock<SnapshotManager>>, DbSnapshot<SnapshotManager>) {
+    let snapshot_manager = Arc::new(RwLock::new(SnapshotManager::orphan(schema_db)));
+    let db_snapshot = DbSnapshot::new(
+        FINALIZED_SNAPSHOT_ID,
+        ReadOnlyLock::new(snapshot_manager.clone()),
+    );
+    (snapshot_manager, db_snapshot)
+}
+
+/// Exports the given `version` of the state stored at `storage_path`, which must directly
+/// follow the slot with the given number in the `ledger_db`, into `snapshot_dir`.
+///
+/// The accessory state is not versioned, so its latest finalized content is exported: the
+/// rollup must not be running while the snapshot is taken.
+pub fn export_snapshot<S: MerkleProofSpec>(
+    storage_path: impl AsRef<Path>,
+    ledger_db: &LedgerDB,
+    slot_number: u64,
+    version: Version,
+    snapshot_dir: impl AsRef<Path>,
+) -> anyhow::Result<SnapshotManifest> {
+    let snapshot_dir = snapshot_dir.as_ref();
+    let slot_hash = ledger_db
+        .get_slot_by_number::<(), ()>(slot_number, QueryMode::Compact)?
+        .ok_or_else(|| anyhow::anyhow!("Slot {} is not in the ledger", slot_number))?
+        .hash;
+    let next_item_numbers = ledger_db
+        .get_item_numbers_after_slot(slot_number)?
+        .ok_or_else(|| anyhow::anyhow!("Slot {} is not in the ledger", slot_number))?;
+
+    let (state_manager, state_snapshot) =
+        open_db(StateDB::<SnapshotManager>::setup_schema_db(&storage_path)?);
+    let (native_manager, native_snapshot) =
+        open_db(NativeDB::<SnapshotManager>::setup_schema_db(&storage_path)?);
+    let storage = ProverStorage::<S>::with_db_handles(
+        StateDB::with_db_snapshot(state_snapshot),
+        NativeDB::with_db_snapshot(native_snapshot),
+    )
+    .at_version(version)?;
+    let state_root = storage.get_root_hash(version)?.0;
+
+    fs::create_dir_all(snapshot_dir)?;
+
+    // Values are sorted by key, then by version: the value of a key at `version` is the last
+    // one written at or before it.
+    let mut state_chunks = ChunkWriter::<S>::new(snapshot_dir, "state");
+    {
+        let state_manager = state_manager
+            .read()
+            .expect("Snapshot manager lock is poisoned");
+        let mut values = state_manager.db().iter::<JmtValues>()?;
+        values.seek_to_first();
+        let mut current: Option<(Vec<u8>, Option<Vec<u8>>)> = None;
+        for item in values {
+            let ((key, value_version), value) = item?;
+            if value_version > version {
+                continue;
+            }
+            match &mut current {
+                Some((current_key, current_value)) if *current_key == key => *current_value = value,
+                _ => {
+                    if let Some((key, Some(value))) = current.replace((key, value)) {
+                        state_chunks.push(key, value)?;
+                    }
+                }
+            }
+        }
+        if let Some((key, Some(value))) = current {
+            state_chunks.push(key, value)?;
+        }
+    }
+
+    let mut accessory_chunks = ChunkWriter::<S>::new(snapshot_dir, "accessory");
+    {
+        let native_manager = native_manager
+            .read()
+            .expect("Snapshot manager lock is poisoned");
+        let mut values = native_manager.db().iter::<ModuleAccessoryState>()?;
+        values.seek_to_first();
+        for item in values {
+            if let (key, Some(value)) = item? {
+                accessory_chunks.push(key, value)?;
+            }
+        }
+    }
+
+    let manifest = SnapshotManifest {
+        slot_number,
+        slot_hash,
+        version,
+        state_root,
+        next_item_numbers,
+        state_chunks: state_chunks.finish()?,
+        accessory_chunks: accessory_chunks.finish()?,
+    };
+    fs::write(
+        snapshot_dir.join(MANIFEST_FILE_NAME),
+        serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    Ok(manifest)
+}
+
+/// Imports the snapshot in `snapshot_dir` into the empty databases at `storage_path`, and
+/// makes its slot the head of the empty `ledger_db`, so that the rollup resumes right after it.
+///
+/// The state root is recomputed from the imported leaves and checked against the manifest
+/// before anything is written. Versions of the state before the snapshot are reported as pruned.
+/// All the leaves are held in memory while the tree is rebuilt.
+pub fn import_snapshot<S: MerkleProofSpec>(
+    snapshot_dir: impl AsRef<Path>,
+    storage_path: impl AsRef<Path>,
+    ledger_db: &LedgerDB,
+) -> anyhow::Result<SnapshotManifest> {
+    let snapshot_dir = snapshot_dir.as_ref();
+    let manifest: SnapshotManifest =
+        serde_json::from_slice(&fs::read(snapshot_dir.join(MANIFEST_FILE_NAME))?)?;
+    anyhow::ensure!(
+        manifest.version > 0,
+        "Version 0 of the state cannot be imported"
+    );
+    anyhow::ensure!(
+        ledger_db.get_head_slot()?.is_none(),
+        "Cannot import a snapshot into a non-empty ledger"
+    );
+
+    let (state_manager, state_snapshot) =
+        open_db(StateDB::<SnapshotManager>::setup_schema_db(&storage_path)?);
+    let (native_manager, native_snapshot) =
+        open_db(NativeDB::<SnapshotManager>::setup_schema_db(&storage_path)?);
+    let state_db = StateDB::with_db_snapshot(state_snapshot);
+    let native_db = NativeDB::with_db_snapshot(native_snapshot);
+    anyhow::ensure!(
+        ProverStorage::<S>::with_db_handles(state_db.clone(), native_db.clone()).is_empty(),
+        "Cannot import a snapshot into a non-empty state"
+    );
+
+    let mut leaves = Vec::new();
+    for chunk in &manifest.state_chunks {
+        leaves.extend(read_chunk::<S>(snapshot_dir, chunk)?);
+    }
+    let mut accessory_values = Vec::new();
+    for chunk in &manifest.accessory_chunks {
+        accessory_values.extend(read_chunk::<S>(snapshot_dir, chunk)?);
+    }
+
+    state_db.put_preimages(
+        leaves
+            .iter()
+            .map(|(key, _)| (KeyHash::with::<S::Hasher>(key), key)),
+    )?;
+    // The history before the snapshot is unknown, so the tree is built on top of an empty
+    // tree at the previous version.
+    let mut empty_root = NodeBatch::default();
+    empty_root.extend(
+        vec![(NodeKey::new_empty_path(manifest.version - 1), Node::Null)],
+        vec![],
+    );
+    state_db.write_node_batch(&empty_root)?;
+    let (state_root, tree_update) = JellyfishMerkleTree::<_, S::Hasher>::new(&state_db)
+        .put_value_set(
+            leaves
+                .into_iter()
+                .map(|(key, value)| (KeyHash::with::<S::Hasher>(&key), Some(value))),
+            manifest.version,
+        )?;
+    anyhow::ensure!(
+        state_root.0 == manifest.state_root,
+        "The imported state root 0x{} does not match the root 0x{} of the manifest",
+        hex::encode(state_root.0),
+        hex::encode(manifest.state_root)
+    );
+    state_db.write_node_batch(&tree_update.node_batch)?;
+    state_db.put_pruned_versions(&PrunedVersions {
+        below: manifest.version,
+        checkpoint_interval: None,
+    })?;
+
+    native_db.set_values(
+        accessory_values
+            .into_iter()
+            .map(|(key, value)| (key, Some(value))),
+    )?;
+
+    for (snapshot_manager, frozen_snapshot) in [
+        (state_manager, state_db.freeze()?),
+        (native_manager, native_db.freeze()?),
+    ] {
+        let mut snapshot_manager = snapshot_manager
+            .write()
+            .expect("Snapshot manager lock is poisoned");
+        snapshot_manager.add_snapshot(frozen_snapshot);
+        snapshot_manager.commit_snapshot(&FINALIZED_SNAPSHOT_ID)?;
+    }
+
+    ledger_db.import_head(
+        manifest.slot_number,
+        manifest.slot_hash,
+        &manifest.next_item_numbers,
+    )?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_db::ledger_db::SlotCommit;
+    use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader, MockDaSpec, MockHash};
+    use sov_rollup_interface::storage::HierarchicalStorageManager;
+    use sov_state::config::Config;
+    use sov_state::storage::{StorageKey, StorageValue};
+    use sov_state::{
+        ArrayWitness, DefaultStorageSpec, OrderedReadsAndWrites, StorageInternalCache,
+    };
+
+    use super::*;
+    use crate::ProverStorageManager;
+
+    type S = DefaultStorageSpec;
+
+    fn into_ordered_writes(changes: &[(&str, Option<&str>)]) -> OrderedReadsAndWrites {
+        let mut cache = StorageInternalCache::default();
+        for (key, value) in changes {
+            let key = StorageKey::from(*key);
+            match value {
+                Some(value) => cache.set(&key, StorageValue::from(value.as_bytes().to_vec())),
+                None => cache.delete(&key),
+            }
+        }
+        cache.into()
+    }
+
+    /// Finalizes one block per entry of `changes`, each writing `(state, accessory)` changes,
+    /// and commits the matching slots to the ledger.
+    fn write_blocks(
+        path: &Path,
+        changes: &[(&[(&str, Option<&str>)], &[(&str, Option<&str>)])],
+    ) -> LedgerDB {
+        let mut storage_manager = ProverStorageManager::<MockDaSpec, S>::new(Config {
+            path: path.to_path_buf(),
+        })
+        .unwrap();
+        let ledger_db = LedgerDB::with_path(path).unwrap();
+        for (height, (state_changes, accessory_changes)) in changes.iter().enumerate() {
+            let header = MockBlockHeader {
+                prev_hash: MockHash::from([height as u8; 32]),
+                hash: MockHash::from([height as u8 + 1; 32]),
+                height: height as u64 + 1,
+            };
+            let storage = storage_manager.get_native_storage_on(&header).unwrap();
+            let (_, state_update) = storage
+                .compute_state_update(into_ordered_writes(state_changes), &ArrayWitness::default())
+                .unwrap();
+            storage.commit(&state_update, &into_ordered_writes(accessory_changes));
+            storage_manager.save_change_set(&header, storage).unwrap();
+            storage_manager.finalize(&header).unwrap();
+
+            let block = MockBlock {
+                header,
+                ..Default::default()
+            };
+            ledger_db
+                .commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(block))
+                .unwrap();
+        }
+        ledger_db
+    }
+
+    fn read(storage: &ProverStorage<S>, key: &str) -> Option<Vec<u8>> {
+        storage
+            .get(&StorageKey::from(key), &ArrayWitness::default())
+            .map(|value| value.value().to_vec())
+    }
+
+    #[test]
+    fn test_export_and_import_snapshot() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+
+        let source_ledger = write_blocks(
+            source_dir.path(),
+            &[
+                (&[("a", Some("1")), ("b", Some("2"))], &[("x", Some("10"))]),
+                (&[("a", Some("3")), ("b", None), ("c", Some("4"))], &[]),
+            ],
+        );
+        let exported =
+            export_snapshot::<S>(source_dir.path(), &source_ledger, 2, 2, snapshot_dir.path())
+                .unwrap();
+        assert_eq!(exported.state_chunks[0].num_entries, 2);
+        assert_eq!(exported.accessory_chunks[0].num_entries, 1);
+
+        let target_ledger = LedgerDB::with_path(target_dir.path()).unwrap();
+        let imported =
+            import_snapshot::<S>(snapshot_dir.path(), target_dir.path(), &target_ledger).unwrap();
+        assert_eq!(imported, exported);
+        assert_eq!(target_ledger.get_head_slot().unwrap().unwrap().0 .0, 2);
+        assert_eq!(
+            target_ledger.get_next_items_numbers(),
+            source_ledger.get_next_items_numbers()
+        );
+
+        let storage_manager = ProverStorageManager::<MockDaSpec, S>::new(Config {
+            path: target_dir.path().to_path_buf(),
+        })
+        .unwrap();
+        let storage = storage_manager.get_native_storage();
+        assert_eq!(storage.get_root_hash(2).unwrap().0, exported.state_root);
+        assert_eq!(read(&storage, "a"), Some(b"3".to_vec()));
+        assert_eq!(read(&storage, "b"), None);
+        assert_eq!(read(&storage, "c"), Some(b"4".to_vec()));
+        assert_eq!(
+            storage
+                .get_accessory(&StorageKey::from("x"))
+                .map(|value| value.value().to_vec()),
+            Some(b"10".to_vec())
+        );
+        let err = storage.at_version(1).err().unwrap();
+        assert_eq!(err.to_string(), "Version 1 of the state has been pruned");
+    }
+
+    #[test]
+    fn test_import_snapshot_with_wrong_root() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+
+        let source_ledger = write_blocks(source_dir.path(), &[(&[("a", Some("1"))], &[])]);
+        let mut manifest =
+            export_snapshot::<S>(source_dir.path(), &source_ledger, 1, 1, snapshot_dir.path())
+                .unwrap();
+        manifest.state_root = [0; 32];
+        fs::write(
+            snapshot_dir.path().join(MANIFEST_FILE_NAME),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let target_ledger = LedgerDB::with_path(target_dir.path()).unwrap();
+        let err = import_snapshot::<S>(snapshot_dir.path(), target_dir.path(), &target_ledger)
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match the root"));
+        // Nothing has been written.
+        assert!(target_ledger.get_head_slot().unwrap().is_none());
+    }
+}
@@ -1,14 +1,21 @@
 use std::collections::VecDeque;
 use std::io::Cursor;
+use std::marker::PhantomData;
 
 use anyhow::{bail, Context as ErrorContext};
 use borsh::BorshDeserialize;
 use sov_modules_api::digest::Digest;
-use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, DispatchCall, PublicKey, Spec, WorkingSet};
-use sov_rollup_interface::services::batch_builder::BatchBuilder;
+use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::transaction::{Transaction, TxFee};
+use sov_modules_api::{Context, DaSpec, DispatchCall, PublicKey, Spec, WorkingSet};
+use sov_rollup_interface::services::batch_builder::{
+    BatchBuilder, DroppedTx, MempoolSize, PendingTx, TxWithHash,
+};
 use tracing::{info, warn};
 
+mod priority;
+pub use priority::PriorityBatchBuilder;
+
 /// Transaction stored in the mempool.
 pub struct PooledTransaction<C: Context, R: DispatchCall<Context = C>> {
     /// Raw transaction bytes.
@@ -37,25 +44,114 @@ where
     C: Context,
     R: DispatchCall<Context = C>,
 {
+    /// Deserializes and verifies a transaction submitted to the mempool, and decodes its runtime message.
+    /// The transaction must have been signed for the rollup with the given `chain_id` and be valid at `visible_height`.
+    fn new(raw: Vec<u8>, chain_id: u64, visible_height: u64) -> anyhow::Result<Self> {
+        // Deserialize
+        let mut data = Cursor::new(&raw);
+        let tx = Transaction::<C>::deserialize_reader(&mut data)
+            .context("Failed to deserialize transaction")?;
+
+        // Verify
+        tx.verify().context("Failed to verify transaction")?;
+        tx.check_validity(chain_id, visible_height)
+            .context("Transaction can't be executed on this rollup")?;
+
+        // Decode
+        let msg = R::decode_call(tx.runtime_msg())
+            .map_err(anyhow::Error::new)
+            .context("Failed to decode message in transaction")?;
+
+        Ok(Self {
+            raw,
+            tx,
+            msg: Some(msg),
+        })
+    }
+
     fn calculate_hash(&self) -> [u8; 32] {
         <C as Spec>::Hasher::digest(&self.raw[..]).into()
     }
+
+    /// Describes the transaction while it waits in the mempool.
+    fn pending_tx(&self) -> PendingTx {
+        PendingTx {
+            hash: self.calculate_hash(),
+            sender: self.tx.pub_key().to_address::<C::Address>().to_string(),
+            nonce: self.tx.nonce(),
+            size: self.raw.len(),
+        }
+    }
+
+    /// Reports the transaction as dropped from the mempool for the given reason.
+    fn dropped(&self, reason: impl Into<String>) -> DroppedTx {
+        DroppedTx {
+            hash: self.calculate_hash(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Executes the runtime message of the transaction on top of `working_set`.
+    fn dispatch(
+        &mut self,
+        runtime: &R,
+        visible_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        // Take the decoded runtime message cached upon accepting transaction
+        // into the pool or attempt to decode the message again if
+        // the transaction was previously executed,
+        // but discarded from the batch due to the batch size.
+        let msg = self.msg.take().unwrap_or_else(||
+                // SAFETY: The transaction was accepted into the pool,
+                // so we know that the runtime message is valid. 
+                R::decode_call(self.tx.runtime_msg()).expect("noop; qed"));
+
+        // TODO: Bug(!), because potential discrepancy. Should be resolved by https://github.com/Sovereign-Labs/sovereign-sdk/issues/434
+        let sender_address: C::Address = self.tx.pub_key().to_address();
+        // The DA address of the sequencer is only known once the batch has been posted.
+        let ctx = C::new(
+            sender_address,
+            Vec::new(),
+            self.calculate_hash(),
+            visible_height,
+        );
+
+        runtime.dispatch_call(msg, working_set, &ctx)?;
+        Ok(())
+    }
 }
 
 /// BatchBuilder that creates batches of transactions in the order they were submitted
 /// Only transactions that were successfully dispatched are included.
-pub struct FiFoStrictBatchBuilder<C: Context, R: DispatchCall<Context = C>> {
+/// Transactions are dispatched at the slot height reported by the kernel, with an empty
+/// [`Context::sequencer`], since the batch hasn't been posted to the DA layer yet.
+pub struct FiFoStrictBatchBuilder<C, Da, R, K>
+where
+    C: Context,
+    Da: DaSpec,
+    R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
+{
     mempool: VecDeque<PooledTransaction<C, R>>,
+    /// The total size of the raw transactions in the mempool.
+    mempool_bytes: usize,
+    /// The transactions dropped since they were last taken.
+    dropped: Vec<DroppedTx>,
     mempool_max_txs_count: usize,
     runtime: R,
+    kernel: K,
     max_batch_size_bytes: usize,
     current_storage: C::Storage,
+    phantom_da: PhantomData<Da>,
 }
 
-impl<C, R> FiFoStrictBatchBuilder<C, R>
+impl<C, Da, R, K> FiFoStrictBatchBuilder<C, Da, R, K>
 where
     C: Context,
+    Da: DaSpec,
     R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
 {
     /// BatchBuilder constructor.
     pub fn new(
@@ -66,25 +162,32 @@ where
     ) -> Self {
         Self {
             mempool: VecDeque::new(),
+            mempool_bytes: 0,
+            dropped: Vec::new(),
             mempool_max_txs_count,
             max_batch_size_bytes,
             runtime,
+            kernel: K::default(),
             current_storage,
+            phantom_da: PhantomData,
         }
     }
 }
 
-impl<C, R> BatchBuilder for FiFoStrictBatchBuilder<C, R>
+impl<C, Da, R, K> BatchBuilder for FiFoStrictBatchBuilder<C, Da, R, K>
 where
     C: Context,
+    Da: DaSpec,
     R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
 {
     /// Attempt to add transaction to the mempool.
     ///
     /// The transaction is discarded if:
     /// - mempool is full
     /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
-    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<()> {
+    /// - transaction was signed for another rollup or has expired
+    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<[u8; 32]> {
         if self.mempool.len() >= self.mempool_max_txs_count {
             bail!("Mempool is full")
         }
@@ -96,60 +199,48 @@ where
             )
         }
 
-        // Deserialize
-        let mut data = Cursor::new(&raw);
-        let tx = Transaction::<C>::deserialize_reader(&mut data)
-            .context("Failed to deserialize transaction")?;
-
-        // Verify
-        tx.verify().context("Failed to verify transaction")?;
-
-        // Decode
-        let msg = R::decode_call(tx.runtime_msg())
-            .map_err(anyhow::Error::new)
-            .context("Failed to decode message in transaction")?;
-
-        self.mempool.push_back(PooledTransaction {
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let pooled = PooledTransaction::new(
             raw,
-            tx,
-            msg: Some(msg),
-        });
-        Ok(())
+            self.kernel.chain_id(&mut working_set),
+            self.kernel.visible_height(&mut working_set),
+        )?;
+
+        let tx_hash = pooled.calculate_hash();
+        self.mempool_bytes += pooled.raw.len();
+        self.mempool.push_back(pooled);
+        Ok(tx_hash)
     }
 
     /// Builds a new batch of valid transactions in order they were added to mempool
     /// Only transactions, which are dispatched successfully are included in the batch
-    fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+    fn get_next_blob(&mut self) -> anyhow::Result<Vec<TxWithHash>> {
         let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let visible_height = self.kernel.visible_height(&mut working_set);
+        let chain_id = self.kernel.chain_id(&mut working_set);
         let mut txs = Vec::new();
         let mut current_batch_size = 0;
 
         while let Some(mut pooled) = self.mempool.pop_front() {
-            // Take the decoded runtime message cached upon accepting transaction
-            // into the pool or attempt to decode the message again if
-            // the transaction was previously executed,
-            // but discarded from the batch due to the batch size.
-            let msg = pooled.msg.take().unwrap_or_else(||
-                    // SAFETY: The transaction was accepted into the pool,
-                    // so we know that the runtime message is valid. 
-                    R::decode_call(pooled.tx.runtime_msg()).expect("noop; qed"));
-
-            // Execute
-            {
-                // TODO: Bug(!), because potential discrepancy. Should be resolved by https://github.com/Sovereign-Labs/sovereign-sdk/issues/434
-                let sender_address: C::Address = pooled.tx.pub_key().to_address();
-                // FIXME! This should use the correct height
-                let ctx = C::new(sender_address, 0);
-
-                if let Err(error) = self.runtime.dispatch_call(msg, &mut working_set, &ctx) {
-                    warn!(%error, tx = hex::encode(&pooled.raw), "Error during transaction dispatch");
-                    continue;
-                }
+            self.mempool_bytes -= pooled.raw.len();
+            // The transaction may have expired while waiting in the mempool.
+            if let Err(error) = pooled.tx.check_validity(chain_id, visible_height) {
+                warn!(%error, tx = hex::encode(&pooled.raw), "Dropping transaction from the mempool");
+                self.dropped.push(pooled.dropped(error.to_string()));
+                continue;
+            }
+
+            if let Err(error) = pooled.dispatch(&self.runtime, visible_height, &mut working_set) {
+                warn!(%error, tx = hex::encode(&pooled.raw), "Error during transaction dispatch");
+                self.dropped
+                    .push(pooled.dropped(format!("Transaction dispatch failed: {error}")));
+                continue;
             }
 
             // In order to fill batch as big as possible, we only check if valid tx can fit in the batch.
             let tx_len = pooled.raw.len();
             if current_batch_size + tx_len > self.max_batch_size_bytes {
+                self.mempool_bytes += tx_len;
                 self.mempool.push_front(pooled);
                 break;
             }
@@ -162,7 +253,10 @@ where
                 hash = hex::encode(tx_hash),
                 "Transaction has been included in the batch",
             );
-            txs.push(pooled.raw);
+            txs.push(TxWithHash {
+                raw_tx: pooled.raw,
+                hash: tx_hash,
+            });
         }
 
         if txs.is_empty() {
@@ -171,19 +265,42 @@ where
 
         Ok(txs)
     }
+
+    fn mempool_size(&self) -> MempoolSize {
+        MempoolSize {
+            txs: self.mempool.len(),
+            bytes: self.mempool_bytes,
+        }
+    }
+
+    fn pending_txs(&self) -> Vec<PendingTx> {
+        self.mempool
+            .iter()
+            .map(PooledTransaction::pending_tx)
+            .collect()
+    }
+
+    fn take_dropped_txs(&mut self) -> Vec<DroppedTx> {
+        std::mem::take(&mut self.dropped)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use borsh::BorshSerialize;
     use rand::Rng;
+    use sov_mock_da::{MockBlockHeader, MockDaSpec};
     use sov_modules_api::default_context::DefaultContext;
     use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
     use sov_modules_api::default_signature::DefaultPublicKey;
     use sov_modules_api::macros::DefaultRuntime;
-    use sov_modules_api::transaction::Transaction;
+    use sov_modules_api::transaction::{Transaction, TxFee};
     use sov_modules_api::{Context, DispatchCall, EncodeCall, Genesis, MessageCodec, PrivateKey};
+    use sov_modules_core::capabilities::mocks::MockKernel;
+    use sov_prover_storage_manager::ProverStorageManager;
     use sov_rollup_interface::services::batch_builder::BatchBuilder;
+    use sov_rollup_interface::storage::HierarchicalStorageManager;
+    use sov_state::config::Config;
     use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
     use sov_value_setter::{CallMessage, ValueSetter, ValueSetterConfig};
     use tempfile::TempDir;
@@ -210,11 +327,28 @@ mod tests {
         let msg = CallMessage::SetValue(value);
         let msg = <TestRuntime<C> as EncodeCall<ValueSetter<DefaultContext>>>::encode_call(msg);
 
-        Transaction::<DefaultContext>::new_signed_tx(private_key, msg, 1)
+        Transaction::<DefaultContext>::new_signed_tx(private_key, msg, 0, None, TxFee::zero(), 1)
             .try_to_vec()
             .unwrap()
     }
 
+    fn generate_tx_with_validity(chain_id: u64, valid_until: Option<u64>) -> Vec<u8> {
+        let private_key = DefaultPrivateKey::generate();
+        let msg = CallMessage::SetValue(1);
+        let msg = <TestRuntime<C> as EncodeCall<ValueSetter<DefaultContext>>>::encode_call(msg);
+
+        Transaction::<DefaultContext>::new_signed_tx(
+            &private_key,
+            msg,
+            chain_id,
+            valid_until,
+            TxFee::zero(),
+            1,
+        )
+        .try_to_vec()
+        .unwrap()
+    }
+
     fn generate_random_bytes() -> Vec<u8> {
         let mut rng = rand::thread_rng();
 
@@ -225,7 +359,7 @@ mod tests {
 
     fn generate_signed_tx_with_invalid_payload(private_key: &DefaultPrivateKey) -> Vec<u8> {
         let msg = generate_random_bytes();
-        Transaction::<DefaultContext>::new_signed_tx(private_key, msg, 1)
+        Transaction::<DefaultContext>::new_signed_tx(private_key, msg, 0, None, TxFee::zero(), 1)
             .try_to_vec()
             .unwrap()
     }
@@ -234,10 +368,17 @@ mod tests {
         batch_size_bytes: usize,
         tmpdir: &TempDir,
     ) -> (
-        FiFoStrictBatchBuilder<C, TestRuntime<C>>,
+        FiFoStrictBatchBuilder<C, MockDaSpec, TestRuntime<C>, MockKernel<C, MockDaSpec>>,
         ProverStorage<DefaultStorageSpec>,
     ) {
-        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut storage_manager =
+            ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(Config {
+                path: tmpdir.path().to_path_buf(),
+            })
+            .unwrap();
+        let storage = storage_manager
+            .get_native_storage_on(&MockBlockHeader::default())
+            .unwrap();
 
         let batch_builder = FiFoStrictBatchBuilder::new(
             batch_size_bytes,
@@ -342,6 +483,38 @@ mod tests {
                 .starts_with("Failed to decode message"))
         }
 
+        #[test]
+        fn reject_tx_for_other_chain() {
+            let tx = generate_tx_with_validity(1, None);
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
+
+            let accept_result = batch_builder.accept_tx(tx);
+            assert!(accept_result.is_err());
+            assert!(accept_result
+                .unwrap_err()
+                .to_string()
+                .starts_with("Transaction can't be executed on this rollup"))
+        }
+
+        #[test]
+        fn reject_expired_tx() {
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, _) = create_batch_builder(usize::MAX, &tmpdir);
+            batch_builder.kernel = MockKernel::new(5, 5);
+
+            batch_builder
+                .accept_tx(generate_tx_with_validity(0, Some(5)))
+                .unwrap();
+            let accept_result = batch_builder.accept_tx(generate_tx_with_validity(0, Some(4)));
+            assert!(accept_result.is_err());
+            assert!(accept_result
+                .unwrap_err()
+                .to_string()
+                .starts_with("Transaction can't be executed on this rollup"))
+        }
+
         #[test]
         fn zero_sized_mempool_cant_accept_tx() {
             let tx = generate_random_valid_tx();
@@ -420,20 +593,68 @@ mod tests {
             let (mut batch_builder, storage) = create_batch_builder(batch_size, &tmpdir);
             setup_runtime(storage, Some(value_setter_admin.pub_key()));
 
-            for tx in &txs {
-                batch_builder.accept_tx(tx.clone()).unwrap();
-            }
+            let tx_hashes: Vec<[u8; 32]> = txs
+                .iter()
+                .map(|tx| batch_builder.accept_tx(tx.clone()).unwrap())
+                .collect();
 
-            assert_eq!(txs.len(), batch_builder.mempool.len());
+            assert_eq!(
+                MempoolSize {
+                    txs: txs.len(),
+                    bytes: txs.iter().map(Vec::len).sum(),
+                },
+                batch_builder.mempool_size()
+            );
 
             let build_result = batch_builder.get_next_blob();
             assert!(build_result.is_ok());
             let blob = build_result.unwrap();
             assert_eq!(2, blob.len());
-            assert!(blob.contains(&txs[0]));
-            assert!(blob.contains(&txs[2]));
-            assert!(!blob.contains(&txs[3]));
-            assert_eq!(1, batch_builder.mempool.len());
+            assert!(blob.contains(&TxWithHash {
+                raw_tx: txs[0].clone(),
+                hash: tx_hashes[0],
+            }));
+            assert!(blob.contains(&TxWithHash {
+                raw_tx: txs[2].clone(),
+                hash: tx_hashes[2],
+            }));
+            assert!(!blob.iter().any(|tx| tx.raw_tx == txs[3]));
+            assert_eq!(
+                MempoolSize {
+                    txs: 1,
+                    bytes: txs[3].len(),
+                },
+                batch_builder.mempool_size()
+            );
+            assert_eq!(
+                batch_builder
+                    .pending_txs()
+                    .into_iter()
+                    .map(|tx| tx.hash)
+                    .collect::<Vec<_>>(),
+                vec![tx_hashes[3]]
+            );
+            let dropped = batch_builder.take_dropped_txs();
+            assert_eq!(1, dropped.len());
+            assert_eq!(tx_hashes[1], dropped[0].hash);
+            assert!(dropped[0].reason.starts_with("Transaction dispatch failed"));
+        }
+
+        #[test]
+        fn build_batch_drops_expired_txs() {
+            let tx = generate_tx_with_validity(0, Some(1));
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            setup_runtime(storage, None);
+            batch_builder.accept_tx(tx).unwrap();
+
+            // The slot height advances past the expiry of the transaction.
+            batch_builder.kernel = MockKernel::new(2, 2);
+
+            let build_result = batch_builder.get_next_blob();
+            assert!(build_result.is_err());
+            assert!(batch_builder.mempool.is_empty());
         }
     }
 }
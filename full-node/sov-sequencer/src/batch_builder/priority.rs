@@ -0,0 +1,738 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context as ErrorContext};
+use borsh::BorshDeserialize;
+use sov_accounts::Accounts;
+use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, DaSpec, DispatchCall, GasUnit, WorkingSet};
+use sov_rollup_interface::services::batch_builder::{
+    BatchBuilder, DroppedTx, MempoolSize, PendingTx, TxWithHash,
+};
+use tracing::{info, warn};
+
+use super::PooledTransaction;
+
+/// A transaction waiting in the mempool of a [`PriorityBatchBuilder`].
+struct PendingTransaction<C: Context, R: DispatchCall<Context = C>> {
+    pooled: PooledTransaction<C, R>,
+    accepted_at: Instant,
+}
+
+impl<C, R> PendingTransaction<C, R>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+{
+    fn fee(&self) -> FeePriority {
+        FeePriority::of(&self.pooled.tx)
+    }
+}
+
+/// The fee transactions are prioritized by: the priority fee summed over all gas dimensions,
+/// then the max fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct FeePriority {
+    priority_fee: u64,
+    max_fee: u64,
+}
+
+impl FeePriority {
+    fn of<C: Context>(tx: &Transaction<C>) -> Self {
+        let fee = tx.fee();
+        Self {
+            priority_fee: fee
+                .priority_fee
+                .dimensions()
+                .iter()
+                .fold(0u64, |sum, dimension| sum.saturating_add(*dimension)),
+            max_fee: fee.max_fee,
+        }
+    }
+}
+
+/// BatchBuilder that groups transactions by sender and includes them in nonce order.
+///
+/// A sender's transactions are only released once they are contiguous with the nonce of its
+/// account in `sov-accounts`, or with its last batched transaction until that one has been executed.
+/// Senders paying higher fees are included first.
+/// A pending transaction is replaced if its nonce is resubmitted with a higher fee.
+/// Transactions are evicted once they are older than the max age, or to make room for
+/// a transaction paying a higher fee once the mempool is full.
+pub struct PriorityBatchBuilder<C, Da, R, K>
+where
+    C: Context,
+    Da: DaSpec,
+    R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
+{
+    /// The pending transactions of each sender, by nonce.
+    senders: HashMap<C::PublicKey, BTreeMap<u64, PendingTransaction<C, R>>>,
+    /// The nonce following the last batched transaction of each sender,
+    /// until the nonce of its account has caught up.
+    batched_nonces: HashMap<C::PublicKey, u64>,
+    mempool_size: MempoolSize,
+    /// The transactions dropped since they were last taken.
+    dropped: Vec<DroppedTx>,
+    mempool_max_txs_count: usize,
+    max_batch_size_bytes: usize,
+    max_tx_age: Duration,
+    runtime: R,
+    kernel: K,
+    accounts: Accounts<C>,
+    current_storage: C::Storage,
+    phantom_da: PhantomData<Da>,
+}
+
+impl<C, Da, R, K> PriorityBatchBuilder<C, Da, R, K>
+where
+    C: Context,
+    Da: DaSpec,
+    R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
+{
+    /// BatchBuilder constructor.
+    pub fn new(
+        max_batch_size_bytes: usize,
+        mempool_max_txs_count: usize,
+        max_tx_age: Duration,
+        runtime: R,
+        current_storage: C::Storage,
+    ) -> Self {
+        Self {
+            senders: HashMap::new(),
+            batched_nonces: HashMap::new(),
+            mempool_size: MempoolSize::default(),
+            dropped: Vec::new(),
+            mempool_max_txs_count,
+            max_batch_size_bytes,
+            max_tx_age,
+            runtime,
+            kernel: K::default(),
+            accounts: Accounts::default(),
+            current_storage,
+            phantom_da: PhantomData,
+        }
+    }
+
+    /// Inserts a transaction, returning the pending one it replaces, if any.
+    fn insert(&mut self, pending: PendingTransaction<C, R>) -> Option<PendingTransaction<C, R>> {
+        self.mempool_size.txs += 1;
+        self.mempool_size.bytes += pending.pooled.raw.len();
+        let replaced = self
+            .senders
+            .entry(pending.pooled.tx.pub_key().clone())
+            .or_default()
+            .insert(pending.pooled.tx.nonce(), pending);
+        if let Some(replaced) = &replaced {
+            self.mempool_size.txs -= 1;
+            self.mempool_size.bytes -= replaced.pooled.raw.len();
+        }
+        replaced
+    }
+
+    fn remove(&mut self, sender: &C::PublicKey, nonce: u64) -> Option<PendingTransaction<C, R>> {
+        let txs = self.senders.get_mut(sender)?;
+        let removed = txs.remove(&nonce)?;
+        if txs.is_empty() {
+            self.senders.remove(sender);
+        }
+        self.mempool_size.txs -= 1;
+        self.mempool_size.bytes -= removed.pooled.raw.len();
+        Some(removed)
+    }
+
+    /// Drops the transactions which have been waiting in the mempool for longer than the max age.
+    fn evict_expired(&mut self) {
+        let now = Instant::now();
+        let max_tx_age = self.max_tx_age;
+        let mempool_size = &mut self.mempool_size;
+        let dropped = &mut self.dropped;
+        self.senders.retain(|_, txs| {
+            txs.retain(|_, pending| {
+                let expired = now.duration_since(pending.accepted_at) > max_tx_age;
+                if expired {
+                    warn!(
+                        tx = hex::encode(&pending.pooled.raw),
+                        "Dropping transaction from the mempool, it has been pending for too long"
+                    );
+                    mempool_size.txs -= 1;
+                    mempool_size.bytes -= pending.pooled.raw.len();
+                    dropped.push(
+                        pending
+                            .pooled
+                            .dropped("Transaction has been pending for too long"),
+                    );
+                }
+                !expired
+            });
+            !txs.is_empty()
+        });
+    }
+
+    /// Makes room for a transaction paying `fee` by evicting the last pending transaction
+    /// of the sender paying the lowest fee, if it is lower than `fee`.
+    fn evict_lower_fee(&mut self, fee: FeePriority) -> anyhow::Result<()> {
+        let lowest = self
+            .senders
+            .iter()
+            .filter_map(|(sender, txs)| {
+                let (nonce, pending) = txs.iter().next_back()?;
+                Some((pending.fee(), sender.clone(), *nonce))
+            })
+            .min_by_key(|(lowest_fee, ..)| *lowest_fee);
+
+        match lowest {
+            Some((lowest_fee, sender, nonce)) if lowest_fee < fee => {
+                if let Some(evicted) = self.remove(&sender, nonce) {
+                    warn!(
+                        tx = hex::encode(&evicted.pooled.raw),
+                        "Evicting transaction from the full mempool for a transaction paying a higher fee"
+                    );
+                    self.dropped.push(evicted.pooled.dropped(
+                        "Evicted from the full mempool by a transaction paying a higher fee",
+                    ));
+                }
+                Ok(())
+            }
+            _ => bail!("Mempool is full"),
+        }
+    }
+
+    /// Returns the next nonce of the sender, which is the nonce of its account,
+    /// unless transactions with later nonces have already been batched.
+    fn next_nonce(
+        &mut self,
+        pub_key: &C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<u64> {
+        let account = self
+            .accounts
+            .get_account(pub_key.clone(), working_set)
+            .context("Failed to query the account of the sender")?;
+        let account_nonce = match account {
+            sov_accounts::Response::AccountExists { nonce, .. } => nonce,
+            sov_accounts::Response::AccountEmpty => 0,
+        };
+
+        match self.batched_nonces.get(pub_key) {
+            Some(batched_nonce) if *batched_nonce > account_nonce => Ok(*batched_nonce),
+            Some(_) => {
+                self.batched_nonces.remove(pub_key);
+                Ok(account_nonce)
+            }
+            None => Ok(account_nonce),
+        }
+    }
+}
+
+impl<C, Da, R, K> BatchBuilder for PriorityBatchBuilder<C, Da, R, K>
+where
+    C: Context,
+    Da: DaSpec,
+    R: DispatchCall<Context = C>,
+    K: Kernel<C, Da>,
+{
+    /// Attempt to add transaction to the mempool.
+    ///
+    /// The transaction is discarded if:
+    /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
+    /// - transaction was signed for another rollup or has expired
+    /// - its nonce has already been used by the sender
+    /// - a transaction with the same nonce and a higher or equal fee is pending
+    /// - mempool is full of transactions paying higher or equal fees
+    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<[u8; 32]> {
+        if raw.len() > self.max_batch_size_bytes {
+            bail!(
+                "Transaction too big. Max allowed size: {}",
+                self.max_batch_size_bytes
+            )
+        }
+
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let pooled = PooledTransaction::<C, R>::new(
+            raw,
+            self.kernel.chain_id(&mut working_set),
+            self.kernel.visible_height(&mut working_set),
+        )?;
+
+        let sender = pooled.tx.pub_key();
+        let nonce = pooled.tx.nonce();
+        let next_nonce = self.next_nonce(sender, &mut working_set)?;
+        if nonce < next_nonce {
+            bail!("Transaction nonce {nonce} has already been used, the next nonce of the sender is {next_nonce}");
+        }
+
+        self.evict_expired();
+        let pending = PendingTransaction {
+            pooled,
+            accepted_at: Instant::now(),
+        };
+        let fee = pending.fee();
+        let tx_hash = pending.pooled.calculate_hash();
+        let replaced_fee = self
+            .senders
+            .get(pending.pooled.tx.pub_key())
+            .and_then(|txs| txs.get(&nonce))
+            .map(PendingTransaction::fee);
+
+        match replaced_fee {
+            Some(replaced_fee) if fee <= replaced_fee => {
+                bail!(
+                    "A transaction with nonce {nonce} and a higher or equal fee is already pending"
+                )
+            }
+            Some(_) => {
+                if let Some(replaced) = self.insert(pending) {
+                    self.dropped.push(replaced.pooled.dropped(
+                        "Replaced by a transaction with the same nonce paying a higher fee",
+                    ));
+                }
+                info!(
+                    hash = hex::encode(tx_hash),
+                    nonce, "Transaction has replaced a pending one with a lower fee",
+                );
+            }
+            None => {
+                if self.mempool_size.txs >= self.mempool_max_txs_count {
+                    self.evict_lower_fee(fee)?;
+                }
+                self.insert(pending);
+            }
+        }
+        Ok(tx_hash)
+    }
+
+    /// Builds a new batch of valid transactions, releasing the transactions of each sender
+    /// in nonce order, starting from its next nonce.
+    /// Senders whose next transaction pays a higher fee are included first.
+    /// Only transactions, which are dispatched successfully are included in the batch.
+    /// Once a transaction is dropped, the following transactions of its sender stay in the mempool,
+    /// until the missing nonce is resubmitted.
+    fn get_next_blob(&mut self) -> anyhow::Result<Vec<TxWithHash>> {
+        self.evict_expired();
+
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let visible_height = self.kernel.visible_height(&mut working_set);
+        let chain_id = self.kernel.chain_id(&mut working_set);
+
+        // The senders whose transactions can be released, with the next nonce to release.
+        let mut ready: Vec<(C::PublicKey, u64)> = Vec::new();
+        // The next transaction of each ready sender, by fee, then by age.
+        let mut queue = BinaryHeap::new();
+        let senders: Vec<C::PublicKey> = self.senders.keys().cloned().collect();
+        for sender in senders {
+            let next_nonce = self.next_nonce(&sender, &mut working_set)?;
+            let used_nonces: Vec<u64> = self.senders[&sender]
+                .range(..next_nonce)
+                .map(|(nonce, _)| *nonce)
+                .collect();
+            for nonce in used_nonces {
+                if let Some(removed) = self.remove(&sender, nonce) {
+                    self.dropped.push(
+                        removed
+                            .pooled
+                            .dropped("Transaction nonce has already been used"),
+                    );
+                }
+            }
+
+            if let Some(next) = self
+                .senders
+                .get(&sender)
+                .and_then(|txs| txs.get(&next_nonce))
+            {
+                queue.push((next.fee(), Reverse(next.accepted_at), ready.len()));
+                ready.push((sender, next_nonce));
+            }
+        }
+
+        let mut txs = Vec::new();
+        let mut current_batch_size = 0;
+
+        while let Some((_, _, index)) = queue.pop() {
+            let (sender, nonce) = ready[index].clone();
+            let mut pending = self
+                .remove(&sender, nonce)
+                .expect("Only pending transactions are queued");
+
+            // The transaction may have expired while waiting in the mempool.
+            if let Err(error) = pending.pooled.tx.check_validity(chain_id, visible_height) {
+                warn!(%error, tx = hex::encode(&pending.pooled.raw), "Dropping transaction from the mempool");
+                self.dropped.push(pending.pooled.dropped(error.to_string()));
+                continue;
+            }
+
+            // The remaining transactions of the sender wait for the next batch,
+            // if this one doesn't fit.
+            let tx_len = pending.pooled.raw.len();
+            if current_batch_size + tx_len > self.max_batch_size_bytes {
+                self.insert(pending);
+                continue;
+            }
+
+            if let Err(error) =
+                pending
+                    .pooled
+                    .dispatch(&self.runtime, visible_height, &mut working_set)
+            {
+                warn!(%error, tx = hex::encode(&pending.pooled.raw), "Error during transaction dispatch");
+                self.dropped.push(
+                    pending
+                        .pooled
+                        .dropped(format!("Transaction dispatch failed: {error}")),
+                );
+                continue;
+            }
+
+            current_batch_size += tx_len;
+
+            let tx_hash: [u8; 32] = pending.pooled.calculate_hash();
+            info!(
+                hash = hex::encode(tx_hash),
+                nonce, "Transaction has been included in the batch",
+            );
+            txs.push(TxWithHash {
+                raw_tx: pending.pooled.raw,
+                hash: tx_hash,
+            });
+            self.batched_nonces.insert(sender.clone(), nonce + 1);
+
+            // Release the next nonce of the sender.
+            if let Some(next) = self
+                .senders
+                .get(&sender)
+                .and_then(|txs| txs.get(&(nonce + 1)))
+            {
+                queue.push((next.fee(), Reverse(next.accepted_at), index));
+                ready[index].1 = nonce + 1;
+            }
+        }
+
+        if txs.is_empty() {
+            bail!("No valid transactions are available");
+        }
+
+        Ok(txs)
+    }
+
+    fn mempool_size(&self) -> MempoolSize {
+        self.mempool_size
+    }
+
+    /// Returns the pending transactions of each sender in nonce order.
+    fn pending_txs(&self) -> Vec<PendingTx> {
+        self.senders
+            .values()
+            .flat_map(|txs| txs.values().map(|pending| pending.pooled.pending_tx()))
+            .collect()
+    }
+
+    fn take_dropped_txs(&mut self) -> Vec<DroppedTx> {
+        std::mem::take(&mut self.dropped)
+    }
+
+    /// Releases the nonces of the reverted transactions, so that their senders can resubmit them.
+    fn revert_batched_txs(&mut self, txs: &[TxWithHash]) {
+        for tx in txs {
+            let Ok(tx) = Transaction::<C>::try_from_slice(&tx.raw_tx) else {
+                continue;
+            };
+            if let Some(batched_nonce) = self.batched_nonces.get_mut(tx.pub_key()) {
+                *batched_nonce = (*batched_nonce).min(tx.nonce());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use sov_accessory_state::{AccessorySetter, CallMessage};
+    use sov_accounts::AccountConfig;
+    use sov_mock_da::{MockBlockHeader, MockDaSpec};
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::hooks::TxHooks;
+    use sov_modules_api::macros::DefaultRuntime;
+    use sov_modules_api::transaction::TxFee;
+    use sov_modules_api::{
+        EncodeCall, GasMeter, Genesis, MessageCodec, PrivateKey, PublicKey, Spec,
+    };
+    use sov_modules_core::capabilities::mocks::MockKernel;
+    use sov_prover_storage_manager::ProverStorageManager;
+    use sov_rollup_interface::storage::HierarchicalStorageManager;
+    use sov_state::config::Config;
+    use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    type C = DefaultContext;
+    type TestBatchBuilder =
+        PriorityBatchBuilder<C, MockDaSpec, TestRuntime<C>, MockKernel<C, MockDaSpec>>;
+
+    #[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+    #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+    struct TestRuntime<T: Context> {
+        accounts: sov_accounts::Accounts<T>,
+        accessory_setter: sov_accessory_state::AccessorySetter<T>,
+    }
+
+    fn generate_tx(private_key: &DefaultPrivateKey, nonce: u64, priority_fee: u64) -> Vec<u8> {
+        let msg = CallMessage::SetValue(format!("{nonce}"));
+        let msg = <TestRuntime<C> as EncodeCall<AccessorySetter<C>>>::encode_call(msg);
+
+        Transaction::<C>::new_signed_tx(
+            private_key,
+            msg,
+            0,
+            None,
+            TxFee::new(0, [priority_fee, 0]),
+            nonce,
+        )
+        .try_to_vec()
+        .unwrap()
+    }
+
+    fn create_batch_builder(
+        mempool_max_txs_count: usize,
+        max_tx_age: Duration,
+        tmpdir: &TempDir,
+    ) -> (TestBatchBuilder, ProverStorage<DefaultStorageSpec>) {
+        let mut storage_manager =
+            ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(Config {
+                path: tmpdir.path().to_path_buf(),
+            })
+            .unwrap();
+        let storage = storage_manager
+            .get_native_storage_on(&MockBlockHeader::default())
+            .unwrap();
+
+        let runtime = TestRuntime::<C>::default();
+        let mut working_set = WorkingSet::new(storage.clone());
+        let config = GenesisConfig::<C>::new(AccountConfig { pub_keys: vec![] }, ());
+        runtime.genesis(&config, &mut working_set).unwrap();
+        let (log, witness) = working_set.checkpoint().freeze();
+        storage.validate_and_commit(log, &witness).unwrap();
+
+        let batch_builder = PriorityBatchBuilder::new(
+            usize::MAX,
+            mempool_max_txs_count,
+            max_tx_age,
+            TestRuntime::<C>::default(),
+            storage.clone(),
+        );
+        (batch_builder, storage)
+    }
+
+    /// Bumps the nonce of the sender's account, as executing the transaction would.
+    fn execute_tx(raw: &[u8], storage: &ProverStorage<DefaultStorageSpec>) {
+        let tx = Transaction::<C>::try_from_slice(raw).unwrap();
+        let accounts = sov_accounts::Accounts::<C>::default();
+        let mut working_set = WorkingSet::new(storage.clone());
+        let sender = accounts
+            .pre_dispatch_tx_hook(&tx, &[0, 0], &mut working_set)
+            .unwrap();
+        let ctx = C::new(sender, Vec::new(), [0; 32], 0);
+        accounts
+            .post_dispatch_tx_hook(&tx, &ctx, &GasMeter::default(), &mut working_set)
+            .unwrap();
+        let (log, witness) = working_set.checkpoint().freeze();
+        storage.validate_and_commit(log, &witness).unwrap();
+    }
+
+    const MAX_TX_AGE: Duration = Duration::from_secs(60 * 60);
+
+    fn next_blob(batch_builder: &mut TestBatchBuilder) -> Vec<Vec<u8>> {
+        let blob = batch_builder.get_next_blob().unwrap();
+        blob.into_iter().map(|tx| tx.raw_tx).collect()
+    }
+
+    #[test]
+    fn releases_contiguous_nonces() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, storage) = create_batch_builder(10, MAX_TX_AGE, &tmpdir);
+        let private_key = DefaultPrivateKey::generate();
+
+        let txs: Vec<_> = (0..4)
+            .map(|nonce| generate_tx(&private_key, nonce, 1))
+            .collect();
+        batch_builder.accept_tx(txs[3].clone()).unwrap();
+        batch_builder.accept_tx(txs[1].clone()).unwrap();
+        batch_builder.accept_tx(txs[0].clone()).unwrap();
+
+        let blob = next_blob(&mut batch_builder);
+        assert_eq!(blob, vec![txs[0].clone(), txs[1].clone()]);
+        assert_eq!(batch_builder.mempool_size().txs, 1);
+
+        // The nonce after a gap is held back, until the missing one is resubmitted.
+        assert!(batch_builder.get_next_blob().is_err());
+        batch_builder.accept_tx(txs[2].clone()).unwrap();
+        let blob = next_blob(&mut batch_builder);
+        assert_eq!(blob, vec![txs[2].clone(), txs[3].clone()]);
+
+        for tx in &txs {
+            execute_tx(tx, &storage);
+        }
+        let accept_result = batch_builder.accept_tx(txs[3].clone());
+        assert_eq!(
+            "Transaction nonce 3 has already been used, the next nonce of the sender is 4",
+            accept_result.unwrap_err().to_string()
+        );
+        batch_builder
+            .accept_tx(generate_tx(&private_key, 4, 1))
+            .unwrap();
+        assert_eq!(next_blob(&mut batch_builder).len(), 1);
+    }
+
+    #[test]
+    fn orders_senders_by_fee() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, _) = create_batch_builder(10, MAX_TX_AGE, &tmpdir);
+        let low = DefaultPrivateKey::generate();
+        let high = DefaultPrivateKey::generate();
+
+        let low_txs = [generate_tx(&low, 0, 1), generate_tx(&low, 1, 1)];
+        let high_txs = [generate_tx(&high, 0, 5), generate_tx(&high, 1, 2)];
+        for tx in low_txs.iter().chain(&high_txs) {
+            batch_builder.accept_tx(tx.clone()).unwrap();
+        }
+
+        let blob = next_blob(&mut batch_builder);
+        assert_eq!(
+            blob,
+            vec![
+                high_txs[0].clone(),
+                high_txs[1].clone(),
+                low_txs[0].clone(),
+                low_txs[1].clone()
+            ]
+        );
+        assert_eq!(batch_builder.mempool_size(), MempoolSize::default());
+    }
+
+    #[test]
+    fn replaces_tx_with_higher_fee() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, _) = create_batch_builder(10, MAX_TX_AGE, &tmpdir);
+        let private_key = DefaultPrivateKey::generate();
+
+        let replaced_hash = batch_builder
+            .accept_tx(generate_tx(&private_key, 0, 2))
+            .unwrap();
+        let accept_result = batch_builder.accept_tx(generate_tx(&private_key, 0, 2));
+        assert_eq!(
+            "A transaction with nonce 0 and a higher or equal fee is already pending",
+            accept_result.unwrap_err().to_string()
+        );
+
+        let replacement = generate_tx(&private_key, 0, 3);
+        let replacement_hash = batch_builder.accept_tx(replacement.clone()).unwrap();
+        assert_eq!(batch_builder.mempool_size().txs, 1);
+        assert_eq!(
+            batch_builder.pending_txs(),
+            vec![PendingTx {
+                hash: replacement_hash,
+                sender: private_key
+                    .pub_key()
+                    .to_address::<<C as Spec>::Address>()
+                    .to_string(),
+                nonce: 0,
+                size: replacement.len(),
+            }]
+        );
+        assert_eq!(
+            batch_builder.take_dropped_txs(),
+            vec![DroppedTx {
+                hash: replaced_hash,
+                reason: "Replaced by a transaction with the same nonce paying a higher fee"
+                    .to_string(),
+            }]
+        );
+        assert!(batch_builder.take_dropped_txs().is_empty());
+        assert_eq!(next_blob(&mut batch_builder), vec![replacement]);
+    }
+
+    #[test]
+    fn evicts_lower_fee_tx_on_full_mempool() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, _) = create_batch_builder(2, MAX_TX_AGE, &tmpdir);
+        let low = DefaultPrivateKey::generate();
+        let high = DefaultPrivateKey::generate();
+
+        batch_builder.accept_tx(generate_tx(&low, 0, 1)).unwrap();
+        batch_builder.accept_tx(generate_tx(&low, 1, 1)).unwrap();
+
+        let accept_result = batch_builder.accept_tx(generate_tx(&high, 0, 1));
+        assert_eq!("Mempool is full", accept_result.unwrap_err().to_string());
+
+        let high_tx = generate_tx(&high, 0, 2);
+        batch_builder.accept_tx(high_tx.clone()).unwrap();
+        assert_eq!(batch_builder.mempool_size().txs, 2);
+        assert_eq!(
+            next_blob(&mut batch_builder),
+            vec![high_tx, generate_tx(&low, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn evicts_expired_txs() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, _) = create_batch_builder(10, Duration::ZERO, &tmpdir);
+        let private_key = DefaultPrivateKey::generate();
+
+        let hash = batch_builder
+            .accept_tx(generate_tx(&private_key, 0, 1))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert!(batch_builder.get_next_blob().is_err());
+        assert_eq!(batch_builder.mempool_size(), MempoolSize::default());
+        assert!(batch_builder.pending_txs().is_empty());
+        assert_eq!(
+            batch_builder.take_dropped_txs(),
+            vec![DroppedTx {
+                hash,
+                reason: "Transaction has been pending for too long".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reverted_txs_release_their_nonces() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (mut batch_builder, _) = create_batch_builder(10, MAX_TX_AGE, &tmpdir);
+        let private_key = DefaultPrivateKey::generate();
+
+        let txs: Vec<_> = (0..3)
+            .map(|nonce| generate_tx(&private_key, nonce, 1))
+            .collect();
+        batch_builder.accept_tx(txs[0].clone()).unwrap();
+        batch_builder.accept_tx(txs[1].clone()).unwrap();
+        let blob = batch_builder.get_next_blob().unwrap();
+        assert_eq!(blob.len(), 2);
+
+        // Only the nonces from the first reverted transaction on are released.
+        batch_builder.revert_batched_txs(&blob[1..]);
+        let accept_result = batch_builder.accept_tx(txs[0].clone());
+        assert_eq!(
+            "Transaction nonce 0 has already been used, the next nonce of the sender is 1",
+            accept_result.unwrap_err().to_string()
+        );
+        batch_builder.accept_tx(txs[1].clone()).unwrap();
+        batch_builder.accept_tx(txs[2].clone()).unwrap();
+        assert_eq!(
+            next_blob(&mut batch_builder),
+            vec![txs[1].clone(), txs[2].clone()]
+        );
+
+        // Reverting the whole blob releases all of its nonces.
+        batch_builder.revert_batched_txs(&blob);
+        batch_builder.accept_tx(txs[0].clone()).unwrap();
+        assert_eq!(next_blob(&mut batch_builder), vec![txs[0].clone()]);
+    }
+}
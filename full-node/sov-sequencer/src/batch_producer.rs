@@ -0,0 +1,267 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::{Stream, StreamExt};
+use sov_rollup_interface::services::batch_builder::BatchBuilder;
+use sov_rollup_interface::services::da::DaService;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+use crate::mempool_wal::{serialize_blob, DueBlobs};
+use crate::Sequencer;
+
+/// A handle to the background task publishing the batches of a [`Sequencer`].
+/// Dropping the handle stops the task as well.
+pub struct BatchProducerHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl BatchProducerHandle {
+    /// Stops the batch producer, once it has published the transactions left in the mempool.
+    pub async fn shutdown(self) -> anyhow::Result<()> {
+        // The task stops when the sender is dropped, so a failed send can be ignored.
+        let _ = self.shutdown.send(());
+        self.task.await?;
+        Ok(())
+    }
+}
+
+impl<B, T> Sequencer<B, T>
+where
+    B: BatchBuilder + Send + Sync + 'static,
+    T: DaService,
+{
+    /// Spawns a task publishing a batch whenever the mempool reaches one of the thresholds
+    /// of the sequencer's config, when the max batch interval elapses,
+    /// or, if enabled, on every new finalized DA block.
+    /// If the mempool is persisted, the task also resubmits the blobs which haven't been
+    /// observed in a committed slot in time.
+    /// Must be called from within a Tokio runtime.
+    pub fn spawn_batch_producer(self: &Arc<Self>) -> BatchProducerHandle {
+        let (shutdown, shutdown_receiver) = oneshot::channel();
+        let task = tokio::spawn(self.clone().produce_batches(shutdown_receiver));
+        BatchProducerHandle { shutdown, task }
+    }
+
+    async fn produce_batches(self: Arc<Self>, mut shutdown: oneshot::Receiver<()>) {
+        let mut da_headers = if self.config.publish_on_da_head {
+            match self.da_service.subscribe_finalized_header().await {
+                Ok(headers) => Some(Box::pin(headers)),
+                Err(e) => {
+                    warn!(error = %e, "Failed to subscribe to finalized DA headers, batches won't be published on new DA blocks");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut committed_slots = match self
+            .wal()
+            .and_then(|wal| wal.map(|wal| wal.subscribe_committed_slots()).transpose())
+        {
+            Ok(committed_slots) => committed_slots,
+            Err(e) => {
+                warn!(
+                    error = ?e,
+                    "Failed to subscribe to committed slots, published blobs won't be confirmed"
+                );
+                None
+            }
+        };
+        let mut last_committed_slot = None;
+
+        let mut interval = tokio::time::interval(self.config.max_batch_interval());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let next_resubmission = match self.wal() {
+                Ok(wal) => wal.and_then(|wal| wal.next_resubmission()),
+                Err(_) => None,
+            };
+            let trigger = tokio::select! {
+                _ = &mut shutdown => break,
+                _ = self.batch_ready.notified() => "mempool threshold",
+                _ = interval.tick() => "batch interval",
+                header = next_header(&mut da_headers) => match header {
+                    Some(Ok(_)) => "new DA head",
+                    Some(Err(e)) => {
+                        warn!(error = %e, "Failed to receive finalized DA header");
+                        continue;
+                    }
+                    None => {
+                        warn!("Finalized DA header subscription has ended");
+                        da_headers = None;
+                        continue;
+                    }
+                },
+                slot_number = next_committed_slot(&mut committed_slots) => {
+                    match slot_number {
+                        Ok(slot_number) => {
+                            // Slots missed by a lagging receiver are observed
+                            // along with the next one.
+                            let first = last_committed_slot
+                                .map_or(slot_number, |last: u64| last + 1);
+                            for slot_number in first..=slot_number {
+                                self.observe_committed_slot(slot_number);
+                            }
+                            last_committed_slot = Some(slot_number);
+                            if let Err(e) = self.rebase_soft_confirmations() {
+                                warn!(error = ?e, "Failed to rebase soft confirmations");
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            debug!(skipped, "Committed slot subscription has lagged behind");
+                        }
+                        Err(RecvError::Closed) => {
+                            warn!(
+                                "Committed slot subscription has ended, published blobs won't be confirmed"
+                            );
+                            committed_slots = None;
+                        }
+                    }
+                    continue;
+                }
+                _ = sleep_until(next_resubmission) => {
+                    self.resubmit_blobs().await;
+                    continue;
+                }
+            };
+            self.publish_pending_txs(trigger).await;
+            interval.reset();
+        }
+
+        self.publish_pending_txs("shutdown").await;
+        debug!("Batch producer has stopped");
+    }
+
+    /// Publishes a batch, unless the mempool is empty.
+    async fn publish_pending_txs(&self, trigger: &str) {
+        let _publishing = self.publishing.lock().await;
+        match self.mempool_size() {
+            Ok(mempool_size) if mempool_size.txs == 0 => return,
+            Ok(_) => {}
+            Err(e) => {
+                warn!(error = ?e, trigger, "Failed to check the size of the mempool");
+                return;
+            }
+        }
+
+        match self.publish_next_blob().await {
+            Ok(num_txs) => info!(num_txs, trigger, "Batch has been published"),
+            Err(e) => warn!(error = ?e, trigger, "Failed to publish batch"),
+        }
+
+        // A batch may not fit all the transactions, so the remaining ones can still be over a threshold.
+        if matches!(self.mempool_size(), Ok(mempool_size) if self.config.is_batch_ready(mempool_size))
+        {
+            self.batch_ready.notify_one();
+        }
+    }
+
+    /// Confirms the published blobs which have been included in the committed slot.
+    fn observe_committed_slot(&self, slot_number: u64) {
+        let result = self
+            .wal()
+            .and_then(|wal| match wal {
+                Some(mut wal) => wal.observe_committed_slot(slot_number),
+                None => Ok(Vec::new()),
+            })
+            .and_then(|committed_txs| {
+                // The status of the committed transactions is available from the ledger.
+                self.tx_statuses()?.forget(&committed_txs);
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!(error = ?e, slot_number, "Failed to confirm the blobs of committed slot");
+        }
+    }
+
+    /// Resubmits the published blobs which haven't been observed in a committed slot in time.
+    async fn resubmit_blobs(&self) {
+        let _publishing = self.publishing.lock().await;
+        let due_blobs = match self.wal().and_then(|wal| match wal {
+            Some(mut wal) => wal.take_due_blobs(&self.config),
+            None => Ok(DueBlobs {
+                resubmitted: Vec::new(),
+                dropped: Vec::new(),
+            }),
+        }) {
+            Ok(due_blobs) => due_blobs,
+            Err(e) => {
+                warn!(error = ?e, "Failed to get the blobs to resubmit");
+                return;
+            }
+        };
+
+        for (txs, reason) in due_blobs.dropped {
+            match self.tx_statuses() {
+                Ok(mut tx_statuses) => {
+                    for tx in &txs {
+                        tx_statuses.set_dropped(tx.hash, reason.clone());
+                    }
+                }
+                Err(e) => warn!(error = ?e, "Failed to record the transactions of dropped blobs"),
+            }
+            // The senders can resubmit the transactions of the dropped blob with the same nonces.
+            if let Err(e) = self.revert_batched_txs(&txs) {
+                warn!(error = ?e, "Failed to revert the transactions of dropped blobs");
+            }
+        }
+
+        for (blob_id, txs) in due_blobs.resubmitted {
+            let num_txs = txs.len();
+            let result = match serialize_blob(&txs) {
+                Ok(blob) => self
+                    .da_service
+                    .send_transaction(&blob)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{:?}", e)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(da_transaction_id) => {
+                    info!(num_txs, blob_id, "Blob has been resubmitted");
+                    let tx_hashes: Vec<[u8; 32]> = txs.iter().map(|tx| tx.hash).collect();
+                    match self.tx_statuses() {
+                        Ok(mut tx_statuses) => {
+                            tx_statuses.set_submitted(&tx_hashes, blob_id, &da_transaction_id)
+                        }
+                        Err(e) => warn!(error = ?e, "Failed to record the resubmitted blob"),
+                    }
+                }
+                Err(e) => warn!(error = ?e, num_txs, blob_id, "Failed to resubmit blob"),
+            }
+        }
+    }
+}
+
+/// Waits for the next committed slot, or forever if there's no subscription.
+async fn next_committed_slot(
+    slots: &mut Option<broadcast::Receiver<u64>>,
+) -> Result<u64, RecvError> {
+    match slots {
+        Some(slots) => slots.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits until the given instant, or forever if there's none.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits for the next header of the subscription, or forever if there's none.
+async fn next_header<S: Stream + Unpin>(headers: &mut Option<S>) -> Option<S::Item> {
+    match headers {
+        Some(headers) => headers.next().await,
+        None => std::future::pending().await,
+    }
+}
@@ -1,18 +1,33 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Concrete implementations of `[BatchBuilder]`
 pub mod batch_builder;
+mod batch_producer;
+mod mempool_wal;
+pub mod soft_confirmation;
+mod tx_status;
 /// Utilities for the sequencer rpc
 pub mod utils;
 
 use anyhow::anyhow;
+pub use batch_producer::BatchProducerHandle;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
+use mempool_wal::{serialize_blob, MempoolWal};
+use soft_confirmation::{SignedSoftConfirmation, SoftConfirmations, SoftConfirmer};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::sequencer_db::SequencerDB;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
-use sov_rollup_interface::services::batch_builder::BatchBuilder;
+use sov_rollup_interface::rpc::utils::rpc_hex;
+use sov_rollup_interface::services::batch_builder::{
+    BatchBuilder, BatchProductionConfig, MempoolSize, PendingTx, TxWithHash,
+};
 use sov_rollup_interface::services::da::DaService;
+use tokio::sync::Notify;
+use tx_status::TxStatuses;
+pub use tx_status::{PoolStats, TxStatus};
 
 const SEQUENCER_RPC_ERROR: &str = "SEQUENCER_RPC_ERROR";
 
@@ -20,98 +35,425 @@ const SEQUENCER_RPC_ERROR: &str = "SEQUENCER_RPC_ERROR";
 pub struct Sequencer<B: BatchBuilder, T: DaService> {
     batch_builder: Mutex<B>,
     da_service: T,
+    /// Held while a batch is built and sent, so blobs are published in the order they were built.
+    publishing: tokio::sync::Mutex<()>,
+    /// Notified when the mempool reaches one of the thresholds of the [`BatchProductionConfig`].
+    batch_ready: Notify,
+    config: BatchProductionConfig,
+    /// Persists the mempool until its transactions are observed in a committed slot, if enabled.
+    wal: Option<Mutex<MempoolWal>>,
+    /// Confirms the effects of the accepted transactions, if enabled.
+    soft_confirmations: Option<Mutex<SoftConfirmations>>,
+    /// The status of the accepted transactions, until they are observed in a committed slot.
+    tx_statuses: Mutex<TxStatuses<T::TransactionId>>,
 }
 
 impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T> {
     /// Creates new Sequencer from BatchBuilder and DaService
     pub fn new(batch_builder: B, da_service: T) -> Self {
+        Self::with_config(batch_builder, da_service, BatchProductionConfig::default())
+    }
+
+    /// Creates new Sequencer, which publishes batches according to `config`
+    /// once its batch producer is spawned.
+    pub fn with_config(batch_builder: B, da_service: T, config: BatchProductionConfig) -> Self {
         Self {
             batch_builder: Mutex::new(batch_builder),
             da_service,
+            publishing: tokio::sync::Mutex::new(()),
+            batch_ready: Notify::new(),
+            config,
+            wal: None,
+            soft_confirmations: None,
+            tx_statuses: Mutex::new(TxStatuses::new()),
+        }
+    }
+
+    /// Creates new Sequencer, which persists its mempool in `db` until the transactions are
+    /// observed in a slot committed to `ledger_db`. Blobs which are not observed in time are
+    /// resubmitted by the batch producer, according to `config`.
+    /// The transactions persisted by a previous run are added back to the mempool.
+    pub fn with_wal(
+        mut batch_builder: B,
+        da_service: T,
+        config: BatchProductionConfig,
+        db: SequencerDB,
+        ledger_db: LedgerDB,
+    ) -> anyhow::Result<Self> {
+        let (wal, unpublished) = MempoolWal::open(db, ledger_db, &config)?;
+        let mut tx_statuses = TxStatuses::new();
+        for (hash, blob_id) in wal.in_flight_txs() {
+            tx_statuses.set_batched(&[*hash], blob_id);
         }
+        for tx in unpublished {
+            match batch_builder.accept_tx(tx.raw_tx) {
+                Ok(hash) => tx_statuses.set_pending(hash),
+                Err(e) => {
+                    tracing::warn!(
+                        hash = hex::encode(tx.hash),
+                        error = ?e,
+                        "Dropping persisted transaction, it is not accepted into the mempool anymore"
+                    );
+                    wal.delete_tx(&tx.hash)?;
+                    tx_statuses.set_dropped(tx.hash, e.to_string());
+                }
+            }
+        }
+        // Restoring a transaction may evict another one from the mempool.
+        for dropped in batch_builder.take_dropped_txs() {
+            wal.delete_tx(&dropped.hash)?;
+            tx_statuses.set_dropped(dropped.hash, dropped.reason);
+        }
+
+        Ok(Self {
+            wal: Some(Mutex::new(wal)),
+            tx_statuses: Mutex::new(tx_statuses),
+            ..Self::with_config(batch_builder, da_service, config)
+        })
     }
 
-    async fn submit_batch(&self) -> anyhow::Result<usize> {
-        // Need to release lock before await, so the Future is `Send`.
-        // But potentially it can create blobs that are sent out of order.
-        // It can be improved with atomics,
-        // so a new batch is only created after previous was submitted.
+    /// Makes the sequencer execute every accepted transaction speculatively with `confirmer`,
+    /// and respond with a signed confirmation of its effect. Requires the mempool to be
+    /// persisted, as the confirmations are recorded along with it for the runner to check.
+    pub fn with_soft_confirmations(
+        mut self,
+        confirmer: Box<dyn SoftConfirmer + Send>,
+    ) -> anyhow::Result<Self> {
+        let (next_position, pending_txs) = {
+            let wal = self
+                .wal()?
+                .ok_or_else(|| anyhow!("Soft confirmations require a persisted mempool"))?;
+            (wal.next_soft_confirmation_position()?, wal.pending_txs()?)
+        };
+        let mut soft_confirmations = SoftConfirmations::new(confirmer, next_position);
+        // The transactions accepted by a previous run are part of the speculative state.
+        soft_confirmations.rebase(&pending_txs);
+
+        self.soft_confirmations = Some(Mutex::new(soft_confirmations));
+        Ok(self)
+    }
+
+    /// Builds the next blob and sends it to the DA layer.
+    /// Must be called while holding the `publishing` lock.
+    async fn publish_next_blob(&self) -> anyhow::Result<usize> {
         tracing::info!("Submit batch request has been received!");
-        let blob = {
+        let (blob, blob_id, tx_hashes, unsaved_txs) = {
             let mut batch_builder = self
                 .batch_builder
                 .lock()
                 .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
-            batch_builder.get_next_blob()?
+            let txs = batch_builder.get_next_blob();
+            // Building the blob may drop transactions, even if none is left to include.
+            self.record_dropped_txs(&mut batch_builder)?;
+            let txs = txs?;
+
+            let blob: Vec<u8> = serialize_blob(&txs)?;
+            let tx_hashes: Vec<[u8; 32]> = txs.iter().map(|tx| tx.hash).collect();
+            // The blob is recorded before it is sent, so it is resubmitted if sending fails.
+            // Otherwise, its transactions are kept to be reverted if sending fails.
+            let (blob_id, unsaved_txs) = match self.wal()? {
+                Some(mut wal) => (wal.put_blob(txs, &self.config)?, Vec::new()),
+                None => (self.tx_statuses()?.next_blob_id(), txs),
+            };
+            self.tx_statuses()?.set_batched(&tx_hashes, blob_id);
+            (blob, blob_id, tx_hashes, unsaved_txs)
         };
-        let num_txs = blob.len();
-        let blob: Vec<u8> = borsh::to_vec(&blob)?;
 
         match self.da_service.send_transaction(&blob).await {
-            Ok(_) => Ok(num_txs),
-            Err(e) => Err(anyhow!("failed to submit batch: {:?}", e)),
+            Ok(da_transaction_id) => {
+                self.tx_statuses()?
+                    .set_submitted(&tx_hashes, blob_id, &da_transaction_id);
+                Ok(tx_hashes.len())
+            }
+            Err(e) => {
+                // Without a persisted mempool, the blob won't be resubmitted.
+                if self.wal.is_none() {
+                    self.revert_batched_txs(&unsaved_txs)?;
+                    let mut tx_statuses = self.tx_statuses()?;
+                    for hash in &tx_hashes {
+                        tx_statuses.set_dropped(*hash, format!("Failed to submit blob: {:?}", e));
+                    }
+                }
+                Err(anyhow!("failed to submit batch: {:?}", e))
+            }
         }
     }
 
-    fn accept_tx(&self, tx: Vec<u8>) -> anyhow::Result<()> {
+    /// Accepts a transaction into the mempool, and returns its hash along with the confirmation
+    /// of its effect if soft confirmations are enabled.
+    fn accept_tx(&self, tx: Vec<u8>) -> anyhow::Result<([u8; 32], Option<SignedSoftConfirmation>)> {
         tracing::info!("Accepting tx: 0x{}", hex::encode(&tx));
-        let mut batch_builder = self
+        let (tx_hash, mempool_size, confirmation) = {
+            let mut batch_builder = self
+                .batch_builder
+                .lock()
+                .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+            let accepted = batch_builder.accept_tx(tx.clone());
+            // Accepting a transaction may evict other ones, even if it is rejected.
+            self.record_dropped_txs(&mut batch_builder)?;
+            let tx_hash = accepted?;
+            // Recorded while the mempool is locked, so the transaction can't be published before.
+            let wal = self.wal()?;
+            if let Some(wal) = &wal {
+                wal.put_tx(&tx_hash, &tx)?;
+            }
+            self.tx_statuses()?.set_pending(tx_hash);
+            // Confirmed while the mempool is locked, so the positions follow the order of the mempool.
+            let confirmation = match self.soft_confirmations()? {
+                Some(mut soft_confirmations) => match soft_confirmations.confirm(&tx) {
+                    Ok(confirmation) => {
+                        if let Some(wal) = &wal {
+                            wal.put_soft_confirmation(&confirmation.confirmation)?;
+                        }
+                        Some(confirmation)
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            hash = hex::encode(tx_hash),
+                            error = ?e,
+                            "Failed to confirm transaction"
+                        );
+                        None
+                    }
+                },
+                None => None,
+            };
+            (tx_hash, batch_builder.mempool_size(), confirmation)
+        };
+        if self.config.is_batch_ready(mempool_size) {
+            self.batch_ready.notify_one();
+        }
+        Ok((tx_hash, confirmation))
+    }
+
+    /// Records the transactions dropped by the batch builder, which won't be published.
+    /// Must be called while holding the mempool lock.
+    fn record_dropped_txs(&self, batch_builder: &mut B) -> anyhow::Result<()> {
+        let dropped = batch_builder.take_dropped_txs();
+        if dropped.is_empty() {
+            return Ok(());
+        }
+        let wal = self.wal()?;
+        let mut tx_statuses = self.tx_statuses()?;
+        for tx in dropped {
+            if let Some(wal) = &wal {
+                wal.delete_tx(&tx.hash)?;
+            }
+            tx_statuses.set_dropped(tx.hash, tx.reason);
+        }
+        Ok(())
+    }
+
+    /// Gives the transactions of a blob which won't be published back to the batch builder.
+    fn revert_batched_txs(&self, txs: &[TxWithHash]) -> anyhow::Result<()> {
+        self.batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?
+            .revert_batched_txs(txs);
+        Ok(())
+    }
+
+    /// Returns the status of a transaction, or `None` if it is unknown to the sequencer
+    /// or has been observed in a committed slot.
+    fn tx_status(&self, hash: &[u8; 32]) -> anyhow::Result<Option<TxStatus<T::TransactionId>>> {
+        Ok(self.tx_statuses()?.get(hash))
+    }
+
+    /// Returns the transactions of the given sender waiting in the mempool.
+    fn pending_txs(&self, sender: &str) -> anyhow::Result<Vec<PendingTx>> {
+        let batch_builder = self
             .batch_builder
             .lock()
             .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
-        batch_builder.accept_tx(tx)?;
+        Ok(batch_builder
+            .pending_txs()
+            .into_iter()
+            .filter(|tx| tx.sender == sender)
+            .collect())
+    }
+
+    fn pool_stats(&self) -> anyhow::Result<PoolStats> {
+        let mempool_size = self.mempool_size()?;
+        Ok(PoolStats {
+            pending_txs: mempool_size.txs,
+            pending_bytes: mempool_size.bytes,
+            ..self.tx_statuses()?.stats()
+        })
+    }
+
+    /// Executes the transactions which haven't been observed in a committed slot yet again,
+    /// on top of the latest committed state, if soft confirmations are enabled.
+    fn rebase_soft_confirmations(&self) -> anyhow::Result<()> {
+        if self.soft_confirmations.is_none() {
+            return Ok(());
+        }
+        // No transaction can be accepted while the speculative state is rebuilt.
+        let _batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        let pending_txs = match self.wal()? {
+            Some(wal) => wal.pending_txs()?,
+            None => Vec::new(),
+        };
+        if let Some(mut soft_confirmations) = self.soft_confirmations()? {
+            soft_confirmations.rebase(&pending_txs);
+        }
         Ok(())
     }
+
+    fn wal(&self) -> anyhow::Result<Option<MutexGuard<'_, MempoolWal>>> {
+        self.wal
+            .as_ref()
+            .map(|wal| {
+                wal.lock()
+                    .map_err(|e| anyhow!("failed to lock mempool WAL: {}", e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn soft_confirmations(&self) -> anyhow::Result<Option<MutexGuard<'_, SoftConfirmations>>> {
+        self.soft_confirmations
+            .as_ref()
+            .map(|soft_confirmations| {
+                soft_confirmations
+                    .lock()
+                    .map_err(|e| anyhow!("failed to lock soft confirmations: {}", e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn tx_statuses(&self) -> anyhow::Result<MutexGuard<'_, TxStatuses<T::TransactionId>>> {
+        self.tx_statuses
+            .lock()
+            .map_err(|e| anyhow!("failed to lock transaction statuses: {}", e.to_string()))
+    }
+
+    fn mempool_size(&self) -> anyhow::Result<MempoolSize> {
+        let batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        Ok(batch_builder.mempool_size())
+    }
 }
 
 fn register_txs_rpc_methods<B, D>(
-    rpc: &mut RpcModule<Sequencer<B, D>>,
+    rpc: &mut RpcModule<Arc<Sequencer<B, D>>>,
 ) -> Result<(), jsonrpsee::core::Error>
 where
     B: BatchBuilder + Send + Sync + 'static,
     D: DaService,
 {
-    rpc.register_async_method(
-        "sequencer_publishBatch",
-        |params, batch_builder| async move {
-            let mut params_iter = params.sequence();
-            while let Some(tx) = params_iter.optional_next::<Vec<u8>>()? {
-                batch_builder
-                    .accept_tx(tx)
-                    .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))?;
-            }
-            let num_txs = batch_builder
-                .submit_batch()
-                .await
+    rpc.register_async_method("sequencer_publishBatch", |params, sequencer| async move {
+        // The transactions are accepted while holding the publishing lock,
+        // so the batch producer can't publish them before this request does.
+        let _publishing = sequencer.publishing.lock().await;
+        let mut params_iter = params.sequence();
+        while let Some(tx) = params_iter.optional_next::<Vec<u8>>()? {
+            sequencer
+                .accept_tx(tx)
                 .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))?;
+        }
+        let num_txs = sequencer
+            .publish_next_blob()
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))?;
 
-            Ok::<String, ErrorObjectOwned>(format!("Submitted {} transactions", num_txs))
-        },
-    )?;
+        Ok::<String, ErrorObjectOwned>(format!("Submitted {} transactions", num_txs))
+    })?;
     rpc.register_method("sequencer_acceptTx", move |params, sequencer| {
         let tx: SubmitTransaction = params.one()?;
         let response = match sequencer.accept_tx(tx.body) {
-            Ok(()) => SubmitTransactionResponse::Registered,
+            Ok((hash, None)) => SubmitTransactionResponse::Registered(hash),
+            Ok((_, Some(confirmation))) => SubmitTransactionResponse::SoftConfirmed(confirmation),
             Err(e) => SubmitTransactionResponse::Failed(e.to_string()),
         };
         Ok::<_, ErrorObjectOwned>(response)
     })?;
+    rpc.register_method("sequencer_txStatus", |params, sequencer| {
+        let hash: HexHash = params.one()?;
+        sequencer
+            .tx_status(&hash.0)
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("sequencer_pendingTxs", |params, sequencer| {
+        let sender: String = params.one()?;
+        sequencer
+            .pending_txs(&sender)
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("sequencer_poolStats", |_, sequencer| {
+        sequencer
+            .pool_stats()
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
 
     Ok(())
 }
 
-/// Creates an RPC module with the sequencer's methods
-pub fn get_sequencer_rpc<B, D>(batch_builder: B, da_service: D) -> RpcModule<Sequencer<B, D>>
+fn sequencer_rpc<B, D>(sequencer: Arc<Sequencer<B, D>>) -> RpcModule<Arc<Sequencer<B, D>>>
 where
     B: BatchBuilder + Send + Sync + 'static,
     D: DaService,
 {
-    let sequencer = Sequencer::new(batch_builder, da_service);
     let mut rpc = RpcModule::new(sequencer);
     register_txs_rpc_methods::<B, D>(&mut rpc).expect("Failed to register sequencer RPC methods");
     rpc
 }
 
+/// Creates an RPC module with the sequencer's methods.
+/// Batches are only published through the `sequencer_publishBatch` method.
+pub fn get_sequencer_rpc<B, D>(batch_builder: B, da_service: D) -> RpcModule<Arc<Sequencer<B, D>>>
+where
+    B: BatchBuilder + Send + Sync + 'static,
+    D: DaService,
+{
+    sequencer_rpc(Arc::new(Sequencer::new(batch_builder, da_service)))
+}
+
+/// Creates an RPC module with the sequencer's methods and spawns its batch producer,
+/// which publishes batches in the background according to `config`.
+/// Must be called from within a Tokio runtime.
+pub fn start_sequencer<B, D>(
+    batch_builder: B,
+    da_service: D,
+    config: BatchProductionConfig,
+) -> (RpcModule<Arc<Sequencer<B, D>>>, BatchProducerHandle)
+where
+    B: BatchBuilder + Send + Sync + 'static,
+    D: DaService,
+{
+    let sequencer = Arc::new(Sequencer::with_config(batch_builder, da_service, config));
+    let batch_producer = sequencer.spawn_batch_producer();
+    (sequencer_rpc(sequencer), batch_producer)
+}
+
+/// Same as [`start_sequencer`], but the mempool is persisted in `db` until its transactions
+/// are observed in a slot committed to `ledger_db`, as described in [`Sequencer::with_wal`].
+/// Accepted transactions are confirmed by `soft_confirmer`, if any, as described in
+/// [`Sequencer::with_soft_confirmations`].
+pub fn start_sequencer_with_wal<B, D>(
+    batch_builder: B,
+    da_service: D,
+    config: BatchProductionConfig,
+    db: SequencerDB,
+    ledger_db: LedgerDB,
+    soft_confirmer: Option<Box<dyn SoftConfirmer + Send>>,
+) -> anyhow::Result<(RpcModule<Arc<Sequencer<B, D>>>, BatchProducerHandle)>
+where
+    B: BatchBuilder + Send + Sync + 'static,
+    D: DaService,
+{
+    let mut sequencer = Sequencer::with_wal(batch_builder, da_service, config, db, ledger_db)?;
+    if let Some(soft_confirmer) = soft_confirmer {
+        sequencer = sequencer.with_soft_confirmations(soft_confirmer)?;
+    }
+    let sequencer = Arc::new(sequencer);
+    let batch_producer = sequencer.spawn_batch_producer();
+    Ok((sequencer_rpc(sequencer), batch_producer))
+}
+
 /// A transaction to be submitted to the rollup
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct SubmitTransaction {
@@ -125,11 +467,17 @@ impl SubmitTransaction {
     }
 }
 
+/// A `0x`-prefixed hex encoded transaction hash, as taken by the sequencer's RPC methods.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HexHash(#[serde(with = "rpc_hex")] [u8; 32]);
+
 /// The result of submitting a transaction to the rollup
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum SubmitTransactionResponse {
-    /// Submission succeeded
-    Registered,
+    /// Submission succeeded, with the hash of the transaction
+    Registered(#[serde(with = "rpc_hex")] [u8; 32]),
+    /// Submission succeeded, and the sequencer has confirmed the effect of the transaction
+    SoftConfirmed(SignedSoftConfirmation),
     /// Submission failed with given reason
     Failed(String),
 }
@@ -137,26 +485,41 @@ pub enum SubmitTransactionResponse {
 #[cfg(test)]
 mod tests {
 
-    use sov_mock_da::{MockAddress, MockDaService};
-    use sov_rollup_interface::da::BlobReaderTrait;
+    use std::time::Duration;
+
+    use borsh::BorshDeserialize;
+    use sov_db::ledger_db::SlotCommit;
+    use sov_mock_da::{MockAddress, MockBlock, MockDaService};
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::PrivateKey;
+    use sov_modules_stf_blueprint::TxEffect;
+    use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+    use sov_rollup_interface::services::batch_builder::DroppedTx;
+    use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 
+    use super::soft_confirmation::SoftConfirmation;
     use super::*;
 
     /// BatchBuilder used in tests.
+    #[derive(Default)]
     pub struct MockBatchBuilder {
         /// Mempool with transactions.
         pub mempool: Vec<Vec<u8>>,
+        /// The batched transactions which have been reverted.
+        pub reverted: Vec<TxWithHash>,
     }
 
     // It only takes the first byte of the tx, when submits it.
     // This allows to show effect of batch builder
     impl BatchBuilder for MockBatchBuilder {
-        fn accept_tx(&mut self, tx: Vec<u8>) -> anyhow::Result<()> {
+        fn accept_tx(&mut self, tx: Vec<u8>) -> anyhow::Result<[u8; 32]> {
+            let hash = mock_tx_hash(&tx);
             self.mempool.push(tx);
-            Ok(())
+            Ok(hash)
         }
 
-        fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        fn get_next_blob(&mut self) -> anyhow::Result<Vec<TxWithHash>> {
             if self.mempool.is_empty() {
                 anyhow::bail!("Mock mempool is empty");
             }
@@ -164,7 +527,10 @@ mod tests {
                 .into_iter()
                 .filter_map(|tx| {
                     if !tx.is_empty() {
-                        Some(vec![tx[0]])
+                        Some(TxWithHash {
+                            raw_tx: vec![tx[0]],
+                            hash: mock_tx_hash(&tx),
+                        })
                     } else {
                         None
                     }
@@ -172,11 +538,232 @@ mod tests {
                 .collect();
             Ok(txs)
         }
+
+        fn mempool_size(&self) -> MempoolSize {
+            MempoolSize {
+                txs: self.mempool.len(),
+                bytes: self.mempool.iter().map(Vec::len).sum(),
+            }
+        }
+
+        fn pending_txs(&self) -> Vec<PendingTx> {
+            self.mempool
+                .iter()
+                .map(|tx| PendingTx {
+                    hash: mock_tx_hash(tx),
+                    sender: mock_sender(tx),
+                    nonce: 0,
+                    size: tx.len(),
+                })
+                .collect()
+        }
+
+        fn take_dropped_txs(&mut self) -> Vec<DroppedTx> {
+            Vec::new()
+        }
+
+        fn revert_batched_txs(&mut self, txs: &[TxWithHash]) {
+            self.reverted.extend_from_slice(txs);
+        }
+    }
+
+    /// A DA service which fails to send any blob.
+    struct FailingDaService(MockDaService);
+
+    #[async_trait::async_trait]
+    impl DaService for FailingDaService {
+        type Spec = <MockDaService as DaService>::Spec;
+        type Verifier = <MockDaService as DaService>::Verifier;
+        type FilteredBlock = <MockDaService as DaService>::FilteredBlock;
+        type HeaderStream = <MockDaService as DaService>::HeaderStream;
+        type TransactionId = <MockDaService as DaService>::TransactionId;
+        type Error = <MockDaService as DaService>::Error;
+
+        async fn get_block_at(&self, height: u64) -> Result<Self::FilteredBlock, Self::Error> {
+            self.0.get_block_at(height).await
+        }
+
+        async fn get_last_finalized_block_header(
+            &self,
+        ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+            self.0.get_last_finalized_block_header().await
+        }
+
+        async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
+            self.0.subscribe_finalized_header().await
+        }
+
+        async fn get_head_block_header(
+            &self,
+        ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+            self.0.get_head_block_header().await
+        }
+
+        fn extract_relevant_blobs(
+            &self,
+            block: &Self::FilteredBlock,
+        ) -> Vec<<Self::Spec as DaSpec>::BlobTransaction> {
+            self.0.extract_relevant_blobs(block)
+        }
+
+        async fn get_extraction_proof(
+            &self,
+            block: &Self::FilteredBlock,
+            blobs: &[<Self::Spec as DaSpec>::BlobTransaction],
+        ) -> (
+            <Self::Spec as DaSpec>::InclusionMultiProof,
+            <Self::Spec as DaSpec>::CompletenessProof,
+        ) {
+            self.0.get_extraction_proof(block, blobs).await
+        }
+
+        async fn send_transaction(&self, _blob: &[u8]) -> Result<Self::TransactionId, Self::Error> {
+            anyhow::bail!("The DA layer is unavailable")
+        }
+    }
+
+    /// Confirms every transaction as successful, using as much gas as its length.
+    struct MockSoftConfirmer {
+        private_key: DefaultPrivateKey,
+        /// The transactions executed since the last reset.
+        executed: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl SoftConfirmer for MockSoftConfirmer {
+        fn execute(&mut self, raw_tx: &[u8]) -> anyhow::Result<TransactionReceipt<TxEffect>> {
+            self.executed.lock().unwrap().push(raw_tx.to_vec());
+            Ok(TransactionReceipt {
+                tx_hash: mock_tx_hash(raw_tx),
+                body_to_save: None,
+                events: vec![],
+                receipt: TxEffect::Successful {
+                    fee: raw_tx.len() as u64,
+                },
+            })
+        }
+
+        fn sign(&self, confirmation: SoftConfirmation) -> SignedSoftConfirmation {
+            SignedSoftConfirmation::sign::<DefaultContext>(confirmation, &self.private_key)
+        }
+
+        fn reset(&mut self) {
+            self.executed.lock().unwrap().clear();
+        }
+    }
+
+    /// The hash of a transaction, as reported by the [`MockBatchBuilder`].
+    fn mock_tx_hash(tx: &[u8]) -> [u8; 32] {
+        [tx.first().copied().unwrap_or_default(); 32]
+    }
+
+    /// The sender of a transaction, as reported by the [`MockBatchBuilder`].
+    fn mock_sender(tx: &[u8]) -> String {
+        format!("sender-{}", tx.last().copied().unwrap_or_default())
+    }
+
+    fn batch_production_config() -> BatchProductionConfig {
+        BatchProductionConfig {
+            max_pending_txs: usize::MAX,
+            max_pending_bytes: usize::MAX,
+            max_batch_interval_ms: 60 * 60 * 1000,
+            publish_on_da_head: false,
+            blob_resubmission_backoff_ms: 60 * 60 * 1000,
+            max_blob_resubmissions: u32::MAX,
+        }
+    }
+
+    fn sequencer_with_wal(
+        da_service: &MockDaService,
+        config: BatchProductionConfig,
+        path: &std::path::Path,
+    ) -> Sequencer<MockBatchBuilder, MockDaService> {
+        Sequencer::with_wal(
+            MockBatchBuilder::default(),
+            da_service.clone(),
+            config,
+            SequencerDB::with_path(path).unwrap(),
+            LedgerDB::with_path(path).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Commits a slot including the transactions with the given hashes.
+    fn commit_slot(ledger_db: &LedgerDB, tx_hashes: &[[u8; 32]]) {
+        let tx_receipts = tx_hashes
+            .iter()
+            .map(|tx_hash| TransactionReceipt {
+                tx_hash: *tx_hash,
+                body_to_save: None,
+                events: vec![],
+                receipt: (),
+            })
+            .collect();
+        let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+        commit.add_batch(BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts,
+            inner: (),
+        });
+        ledger_db.commit_slot(commit).unwrap();
+    }
+
+    async fn wait_for_empty_wal(db: &SequencerDB) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !db.get_blobs().unwrap().is_empty() || !db.get_txs().unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The persisted mempool has not been emptied");
+    }
+
+    async fn accept_tx<B, D>(rpc: &RpcModule<Arc<Sequencer<B, D>>>, tx: Vec<u8>)
+    where
+        B: BatchBuilder + Send + Sync + 'static,
+        D: DaService,
+    {
+        let hash = mock_tx_hash(&tx);
+        let request = SubmitTransaction { body: tx };
+        let result: SubmitTransactionResponse =
+            rpc.call("sequencer_acceptTx", [request]).await.unwrap();
+        assert_eq!(SubmitTransactionResponse::Registered(hash), result);
+    }
+
+    async fn tx_status(
+        rpc: &RpcModule<Arc<Sequencer<MockBatchBuilder, MockDaService>>>,
+        hash: [u8; 32],
+    ) -> Option<TxStatus<()>> {
+        rpc.call("sequencer_txStatus", [format!("0x{}", hex::encode(hash))])
+            .await
+            .unwrap()
+    }
+
+    async fn wait_for_tx_status(
+        rpc: &RpcModule<Arc<Sequencer<MockBatchBuilder, MockDaService>>>,
+        hash: [u8; 32],
+        expected: Option<TxStatus<()>>,
+    ) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while tx_status(rpc, hash).await != expected {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The transaction has not reached the expected status");
+    }
+
+    async fn published_blob(da_service: &MockDaService, height: u64) -> Vec<Vec<u8>> {
+        let mut block =
+            tokio::time::timeout(Duration::from_secs(5), da_service.get_block_at(height))
+                .await
+                .expect("No batch has been published")
+                .unwrap();
+        Vec::<Vec<u8>>::try_from_slice(block.blobs[0].full_data()).unwrap()
     }
 
     #[tokio::test]
     async fn test_submit_on_empty_mempool() {
-        let batch_builder = MockBatchBuilder { mempool: vec![] };
+        let batch_builder = MockBatchBuilder::default();
         let da_service = MockDaService::new(MockAddress::default());
         let rpc = get_sequencer_rpc(batch_builder, da_service.clone());
 
@@ -198,6 +785,7 @@ mod tests {
         let tx2 = vec![3, 4, 5];
         let batch_builder = MockBatchBuilder {
             mempool: vec![tx1.clone(), tx2.clone()],
+            ..Default::default()
         };
         let da_service = MockDaService::new(MockAddress::default());
         let rpc = get_sequencer_rpc(batch_builder, da_service.clone());
@@ -216,7 +804,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_accept_tx() {
-        let batch_builder = MockBatchBuilder { mempool: vec![] };
+        let batch_builder = MockBatchBuilder::default();
         let da_service = MockDaService::new(MockAddress::default());
 
         let rpc = get_sequencer_rpc(batch_builder, da_service.clone());
@@ -225,7 +813,10 @@ mod tests {
         let request = SubmitTransaction { body: tx.clone() };
         let result: SubmitTransactionResponse =
             rpc.call("sequencer_acceptTx", [request]).await.unwrap();
-        assert_eq!(SubmitTransactionResponse::Registered, result);
+        assert_eq!(
+            SubmitTransactionResponse::Registered(mock_tx_hash(&tx)),
+            result
+        );
 
         let arg: &[u8] = &[];
         let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
@@ -239,6 +830,394 @@ mod tests {
         assert_eq!(expected, block_data);
     }
 
+    #[tokio::test]
+    async fn test_tx_status_and_pending_txs() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = get_sequencer_rpc(batch_builder, da_service.clone());
+
+        let tx = vec![1, 2, 3];
+        let hash = mock_tx_hash(&tx);
+        assert_eq!(None, tx_status(&rpc, hash).await);
+
+        accept_tx(&rpc, tx.clone()).await;
+        assert_eq!(Some(TxStatus::Pending), tx_status(&rpc, hash).await);
+        let pending: Vec<PendingTx> = rpc
+            .call("sequencer_pendingTxs", [mock_sender(&tx)])
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![PendingTx {
+                hash,
+                sender: mock_sender(&tx),
+                nonce: 0,
+                size: tx.len(),
+            }],
+            pending
+        );
+        let pending: Vec<PendingTx> = rpc
+            .call("sequencer_pendingTxs", [mock_sender(&[4])])
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+
+        let arg: &[u8] = &[];
+        let stats: PoolStats = rpc.call("sequencer_poolStats", arg).await.unwrap();
+        assert_eq!(
+            PoolStats {
+                pending_txs: 1,
+                pending_bytes: tx.len(),
+                ..Default::default()
+            },
+            stats
+        );
+
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            Some(TxStatus::SubmittedToDa {
+                blob_id: 0,
+                da_transaction_id: (),
+            }),
+            tx_status(&rpc, hash).await
+        );
+        let stats: PoolStats = rpc.call("sequencer_poolStats", arg).await.unwrap();
+        assert_eq!(
+            PoolStats {
+                submitted_txs: 1,
+                ..Default::default()
+            },
+            stats
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_producer_publishes_on_tx_threshold() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let config = BatchProductionConfig {
+            max_pending_txs: 2,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer(batch_builder, da_service.clone(), config);
+
+        accept_tx(&rpc, vec![1, 2, 3]).await;
+        accept_tx(&rpc, vec![4, 5, 6]).await;
+
+        assert_eq!(vec![vec![1], vec![4]], published_blob(&da_service, 0).await);
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_producer_publishes_on_byte_threshold() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let config = BatchProductionConfig {
+            max_pending_bytes: 5,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer(batch_builder, da_service.clone(), config);
+
+        accept_tx(&rpc, vec![1, 2, 3, 4, 5]).await;
+
+        assert_eq!(vec![vec![1]], published_blob(&da_service, 0).await);
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_producer_publishes_on_interval() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let config = BatchProductionConfig {
+            max_batch_interval_ms: 10,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer(batch_builder, da_service.clone(), config);
+
+        accept_tx(&rpc, vec![7, 8, 9]).await;
+
+        assert_eq!(vec![vec![7]], published_blob(&da_service, 0).await);
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_producer_publishes_pending_txs_on_shutdown() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let (rpc, batch_producer) =
+            start_sequencer(batch_builder, da_service.clone(), batch_production_config());
+
+        accept_tx(&rpc, vec![1, 2, 3]).await;
+        batch_producer.shutdown().await.unwrap();
+
+        assert_eq!(vec![vec![1]], published_blob(&da_service, 0).await);
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_with_batch_producer() {
+        let batch_builder = MockBatchBuilder::default();
+        let da_service = MockDaService::new(MockAddress::default());
+        let config = BatchProductionConfig {
+            max_pending_txs: 1,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer(batch_builder, da_service.clone(), config);
+
+        // The transactions are published by the request, even though they reach the threshold.
+        let _: String = rpc
+            .call("sequencer_publishBatch", [vec![1u8, 2], vec![3, 4]])
+            .await
+            .unwrap();
+
+        assert_eq!(vec![vec![1], vec![3]], published_blob(&da_service, 0).await);
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_persisted_txs_are_restored_after_restart() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        {
+            let sequencer =
+                sequencer_with_wal(&da_service, batch_production_config(), tmpdir.path());
+            sequencer.accept_tx(vec![1, 2, 3]).unwrap();
+            sequencer.accept_tx(vec![4, 5, 6]).unwrap();
+        }
+
+        let sequencer = sequencer_with_wal(&da_service, batch_production_config(), tmpdir.path());
+        assert_eq!(sequencer.mempool_size().unwrap().txs, 2);
+        sequencer.publish_next_blob().await.unwrap();
+        assert_eq!(vec![vec![1], vec![4]], published_blob(&da_service, 0).await);
+    }
+
+    #[tokio::test]
+    async fn test_blob_is_resubmitted_until_committed() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let sequencer_db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+        let config = BatchProductionConfig {
+            blob_resubmission_backoff_ms: 10,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer_with_wal(
+            MockBatchBuilder::default(),
+            da_service.clone(),
+            config,
+            sequencer_db.clone(),
+            ledger_db.clone(),
+            None,
+        )
+        .unwrap();
+
+        let _: String = rpc
+            .call("sequencer_publishBatch", [vec![1u8, 2], vec![3, 4]])
+            .await
+            .unwrap();
+        assert_eq!(vec![vec![1], vec![3]], published_blob(&da_service, 0).await);
+
+        // The blob is resubmitted, as long as it hasn't been observed in a committed slot.
+        assert_eq!(vec![vec![1], vec![3]], published_blob(&da_service, 1).await);
+        assert_eq!(
+            Some(TxStatus::SubmittedToDa {
+                blob_id: 0,
+                da_transaction_id: (),
+            }),
+            tx_status(&rpc, mock_tx_hash(&[1])).await
+        );
+        commit_slot(&ledger_db, &[mock_tx_hash(&[1]), mock_tx_hash(&[3])]);
+
+        wait_for_empty_wal(&sequencer_db).await;
+        // The committed transactions are only available from the ledger.
+        wait_for_tx_status(&rpc, mock_tx_hash(&[1]), None).await;
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_accepted_txs_are_soft_confirmed() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let sequencer_db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        let private_key = DefaultPrivateKey::generate();
+        let confirmer = MockSoftConfirmer {
+            private_key: private_key.clone(),
+            executed: Default::default(),
+        };
+        let sequencer = Sequencer::with_wal(
+            MockBatchBuilder::default(),
+            da_service,
+            batch_production_config(),
+            sequencer_db.clone(),
+            LedgerDB::with_path(tmpdir.path()).unwrap(),
+        )
+        .unwrap()
+        .with_soft_confirmations(Box::new(confirmer))
+        .unwrap();
+        let rpc = sequencer_rpc(Arc::new(sequencer));
+
+        for (position, tx) in [vec![1u8, 2], vec![3, 4, 5]].into_iter().enumerate() {
+            let request = SubmitTransaction { body: tx.clone() };
+            let result: SubmitTransactionResponse =
+                rpc.call("sequencer_acceptTx", [request]).await.unwrap();
+            let signed = match result {
+                SubmitTransactionResponse::SoftConfirmed(signed) => signed,
+                result => panic!("Transaction has not been confirmed: {:?}", result),
+            };
+
+            assert_eq!(
+                signed.confirmation,
+                SoftConfirmation {
+                    tx_hash: mock_tx_hash(&tx),
+                    position: position as u64,
+                    effect: TxEffect::Successful {
+                        fee: tx.len() as u64
+                    },
+                    events: vec![],
+                }
+            );
+            let pub_key = signed.verify::<DefaultContext>().unwrap();
+            assert_eq!(pub_key, private_key.pub_key());
+        }
+
+        // The confirmations are recorded for the runner.
+        let (position, _) = sequencer_db
+            .get_soft_confirmation(&mock_tx_hash(&[3]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_soft_confirmations_are_rebased_on_committed_slots() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+        let executed: Arc<Mutex<Vec<Vec<u8>>>> = Default::default();
+        let confirmer = MockSoftConfirmer {
+            private_key: DefaultPrivateKey::generate(),
+            executed: executed.clone(),
+        };
+        let (rpc, batch_producer) = start_sequencer_with_wal(
+            MockBatchBuilder::default(),
+            da_service.clone(),
+            batch_production_config(),
+            SequencerDB::with_path(tmpdir.path()).unwrap(),
+            ledger_db.clone(),
+            Some(Box::new(confirmer)),
+        )
+        .unwrap();
+
+        let _: String = rpc
+            .call("sequencer_publishBatch", [vec![1u8, 2]])
+            .await
+            .unwrap();
+        assert_eq!(vec![vec![1]], published_blob(&da_service, 0).await);
+        let request = SubmitTransaction { body: vec![3, 4] };
+        let _: SubmitTransactionResponse = rpc.call("sequencer_acceptTx", [request]).await.unwrap();
+        assert_eq!(*executed.lock().unwrap(), vec![vec![1, 2], vec![3, 4]]);
+
+        // Only the transaction which hasn't been committed is executed again.
+        commit_slot(&ledger_db, &[mock_tx_hash(&[1])]);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while *executed.lock().unwrap() != vec![vec![3, 4]] {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The soft confirmations have not been rebased");
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_blob_is_dropped_after_max_resubmissions() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let sequencer_db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        let config = BatchProductionConfig {
+            blob_resubmission_backoff_ms: 10,
+            max_blob_resubmissions: 1,
+            ..batch_production_config()
+        };
+        let (rpc, batch_producer) = start_sequencer_with_wal(
+            MockBatchBuilder::default(),
+            da_service.clone(),
+            config,
+            sequencer_db.clone(),
+            LedgerDB::with_path(tmpdir.path()).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        accept_tx(&rpc, vec![1, 2, 3]).await;
+        let arg: &[u8] = &[];
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+
+        assert_eq!(vec![vec![1]], published_blob(&da_service, 1).await);
+        wait_for_empty_wal(&sequencer_db).await;
+        wait_for_tx_status(
+            &rpc,
+            mock_tx_hash(&[1]),
+            Some(TxStatus::Dropped {
+                reason: "Blob 0 has not been observed in a committed slot after 2 submissions"
+                    .to_string(),
+            }),
+        )
+        .await;
+        batch_producer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unsent_blob_is_reverted_without_wal() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1, 2, 3]],
+            ..Default::default()
+        };
+        let da_service = FailingDaService(MockDaService::new(MockAddress::default()));
+        let sequencer = Sequencer::new(batch_builder, da_service);
+
+        assert!(sequencer.publish_next_blob().await.is_err());
+        assert_eq!(
+            sequencer.batch_builder.lock().unwrap().reverted,
+            vec![TxWithHash {
+                raw_tx: vec![1],
+                hash: mock_tx_hash(&[1]),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_blob_is_reverted() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let config = BatchProductionConfig {
+            blob_resubmission_backoff_ms: 10,
+            max_blob_resubmissions: 1,
+            ..batch_production_config()
+        };
+        let sequencer = Arc::new(sequencer_with_wal(&da_service, config, tmpdir.path()));
+
+        sequencer.accept_tx(vec![1, 2, 3]).unwrap();
+        sequencer.publish_next_blob().await.unwrap();
+        // A blob which is sent successfully isn't reverted until it is dropped.
+        assert!(sequencer.batch_builder.lock().unwrap().reverted.is_empty());
+
+        let batch_producer = sequencer.spawn_batch_producer();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while sequencer.batch_builder.lock().unwrap().reverted.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The dropped blob has not been reverted");
+        assert_eq!(
+            sequencer.batch_builder.lock().unwrap().reverted,
+            vec![TxWithHash {
+                raw_tx: vec![1],
+                hash: mock_tx_hash(&[1]),
+            }]
+        );
+        batch_producer.shutdown().await.unwrap();
+    }
+
     #[tokio::test]
     #[ignore = "TBD"]
     async fn test_full_flow() {}
@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::{
+    BlobNumber, SoftConfirmationPosition, StoredSequencerBlob, StoredSoftConfirmation,
+};
+use sov_db::sequencer_db::SequencerDB;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
+use sov_rollup_interface::services::batch_builder::{BatchProductionConfig, TxWithHash};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+use crate::soft_confirmation::SoftConfirmation;
+
+/// A blob which has been published, but hasn't been observed in a committed slot yet.
+struct InFlightBlob {
+    txs: Vec<TxWithHash>,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+/// The blobs which are due to be resubmitted, and the transactions of the blobs which have been
+/// resubmitted too many times.
+pub(crate) struct DueBlobs {
+    /// The number and the transactions of each blob to resubmit.
+    pub(crate) resubmitted: Vec<(u64, Vec<TxWithHash>)>,
+    /// The transactions of each blob which won't be resubmitted anymore, with the reason.
+    pub(crate) dropped: Vec<(Vec<TxWithHash>, String)>,
+}
+
+/// Write-ahead log of the mempool of a [`Sequencer`](crate::Sequencer).
+///
+/// Accepted transactions and published blobs are recorded in the [`SequencerDB`], and are only
+/// deleted once the runner commits a slot which includes them. Blobs which aren't observed in
+/// time are resubmitted, and the recorded transactions are added back to the mempool on restart.
+pub(crate) struct MempoolWal {
+    db: SequencerDB,
+    ledger_db: LedgerDB,
+    /// The published blobs which haven't been observed in a committed slot yet.
+    blobs: BTreeMap<u64, InFlightBlob>,
+    /// The blob which each published transaction belongs to.
+    blob_by_tx: HashMap<[u8; 32], u64>,
+    next_blob_number: u64,
+}
+
+impl MempoolWal {
+    /// Opens the write-ahead log, and returns the recorded transactions which haven't been
+    /// published yet, in the order they were accepted.
+    pub(crate) fn open(
+        db: SequencerDB,
+        ledger_db: LedgerDB,
+        config: &BatchProductionConfig,
+    ) -> anyhow::Result<(Self, Vec<TxWithHash>)> {
+        let mut txs: HashMap<[u8; 32], Vec<u8>> = HashMap::new();
+        let mut order = Vec::new();
+        for (hash, raw_tx) in db.get_txs()? {
+            order.push(hash);
+            txs.insert(hash, raw_tx);
+        }
+
+        let mut wal = Self {
+            db,
+            ledger_db,
+            blobs: BTreeMap::new(),
+            blob_by_tx: HashMap::new(),
+            next_blob_number: 0,
+        };
+
+        // The blobs may have landed while the sequencer was down, so the runner is given time
+        // to observe them before they are resubmitted.
+        let now = Instant::now();
+        for (number, blob) in wal.db.get_blobs()? {
+            let blob_txs: Vec<TxWithHash> = blob
+                .tx_hashes
+                .iter()
+                .filter_map(|hash| {
+                    let raw_tx = txs.remove(hash)?;
+                    Some(TxWithHash {
+                        raw_tx,
+                        hash: *hash,
+                    })
+                })
+                .collect();
+            if blob_txs.is_empty() {
+                wal.db.delete(std::iter::empty(), [number])?;
+                continue;
+            }
+            wal.insert_blob(
+                number.0,
+                InFlightBlob {
+                    txs: blob_txs,
+                    attempts: blob.attempts,
+                    next_attempt: now + config.blob_resubmission_backoff(blob.attempts),
+                },
+            );
+        }
+
+        let unpublished = order
+            .into_iter()
+            .filter_map(|hash| {
+                let raw_tx = txs.remove(&hash)?;
+                Some(TxWithHash { raw_tx, hash })
+            })
+            .collect();
+        Ok((wal, unpublished))
+    }
+
+    /// Returns a receiver notified with the number of every slot committed by the runner.
+    pub(crate) fn subscribe_committed_slots(&self) -> anyhow::Result<broadcast::Receiver<u64>> {
+        self.ledger_db.subscribe_slots()
+    }
+
+    /// Records a transaction which has been accepted into the mempool.
+    pub(crate) fn put_tx(&self, hash: &[u8; 32], raw_tx: &[u8]) -> anyhow::Result<()> {
+        self.db.put_tx(hash, raw_tx)
+    }
+
+    /// Deletes a recorded transaction, which won't be published.
+    pub(crate) fn delete_tx(&self, hash: &[u8; 32]) -> anyhow::Result<()> {
+        self.db.delete([hash], std::iter::empty())
+    }
+
+    /// Returns the recorded transactions which haven't been observed in a committed slot yet,
+    /// in the order they were accepted.
+    pub(crate) fn pending_txs(&self) -> anyhow::Result<Vec<Vec<u8>>> {
+        Ok(self
+            .db
+            .get_txs()?
+            .into_iter()
+            .map(|(_, raw_tx)| raw_tx)
+            .collect())
+    }
+
+    /// Returns the position of the next soft confirmation, following the recorded ones.
+    pub(crate) fn next_soft_confirmation_position(&self) -> anyhow::Result<u64> {
+        Ok(self.db.next_soft_confirmation_position()?.0)
+    }
+
+    /// Records a soft confirmation, so that the runner can check it against the finalized slots.
+    pub(crate) fn put_soft_confirmation(
+        &self,
+        confirmation: &SoftConfirmation,
+    ) -> anyhow::Result<()> {
+        let stored = StoredSoftConfirmation::new(
+            confirmation.tx_hash,
+            &confirmation.effect,
+            confirmation.events.clone(),
+        );
+        self.db
+            .put_soft_confirmation(SoftConfirmationPosition(confirmation.position), &stored)
+    }
+
+    /// Returns the transactions of the published blobs which haven't been observed
+    /// in a committed slot yet, along with the number of their blob.
+    pub(crate) fn in_flight_txs(&self) -> impl Iterator<Item = (&[u8; 32], u64)> {
+        self.blob_by_tx.iter().map(|(hash, number)| (hash, *number))
+    }
+
+    /// Records a blob, before it is submitted to the DA layer for the first time,
+    /// and returns its number.
+    pub(crate) fn put_blob(
+        &mut self,
+        txs: Vec<TxWithHash>,
+        config: &BatchProductionConfig,
+    ) -> anyhow::Result<u64> {
+        let number = self.next_blob_number;
+        let stored = StoredSequencerBlob {
+            tx_hashes: txs.iter().map(|tx| tx.hash).collect(),
+            attempts: 1,
+        };
+        self.db.put_blob(BlobNumber(number), &stored)?;
+        self.insert_blob(
+            number,
+            InFlightBlob {
+                txs,
+                attempts: stored.attempts,
+                next_attempt: Instant::now() + config.blob_resubmission_backoff(stored.attempts),
+            },
+        );
+        Ok(number)
+    }
+
+    fn insert_blob(&mut self, number: u64, blob: InFlightBlob) {
+        for tx in &blob.txs {
+            self.blob_by_tx.insert(tx.hash, number);
+        }
+        self.blobs.insert(number, blob);
+        self.next_blob_number = self.next_blob_number.max(number + 1);
+    }
+
+    fn remove_blob(&mut self, number: u64) -> anyhow::Result<Option<InFlightBlob>> {
+        let Some(blob) = self.blobs.remove(&number) else {
+            return Ok(None);
+        };
+        for tx in &blob.txs {
+            self.blob_by_tx.remove(&tx.hash);
+        }
+        self.db
+            .delete(blob.txs.iter().map(|tx| &tx.hash), [BlobNumber(number)])?;
+        Ok(Some(blob))
+    }
+
+    /// Returns when the next blob is due to be resubmitted, if any.
+    pub(crate) fn next_resubmission(&self) -> Option<Instant> {
+        self.blobs.values().map(|blob| blob.next_attempt).min()
+    }
+
+    /// Returns the transactions of the blobs which are due to be resubmitted, and records
+    /// the new attempt. Blobs which have been resubmitted too many times are dropped.
+    pub(crate) fn take_due_blobs(
+        &mut self,
+        config: &BatchProductionConfig,
+    ) -> anyhow::Result<DueBlobs> {
+        let now = Instant::now();
+        let due: Vec<u64> = self
+            .blobs
+            .iter()
+            .filter(|(_, blob)| blob.next_attempt <= now)
+            .map(|(number, _)| *number)
+            .collect();
+
+        let mut due_blobs = DueBlobs {
+            resubmitted: Vec::new(),
+            dropped: Vec::new(),
+        };
+        for number in due {
+            let blob = self.blobs.get_mut(&number).expect("The blob is in flight");
+            if blob.attempts > config.max_blob_resubmissions {
+                let attempts = blob.attempts;
+                if let Some(dropped) = self.remove_blob(number)? {
+                    warn!(
+                        blob = number,
+                        attempts,
+                        num_txs = dropped.txs.len(),
+                        "Blob has not been observed in a committed slot, dropping its transactions"
+                    );
+                    let reason = format!(
+                        "Blob {number} has not been observed in a committed slot after {attempts} submissions"
+                    );
+                    due_blobs.dropped.push((dropped.txs, reason));
+                }
+                continue;
+            }
+
+            blob.attempts += 1;
+            blob.next_attempt = now + config.blob_resubmission_backoff(blob.attempts);
+            let stored = StoredSequencerBlob {
+                tx_hashes: blob.txs.iter().map(|tx| tx.hash).collect(),
+                attempts: blob.attempts,
+            };
+            due_blobs.resubmitted.push((number, blob.txs.clone()));
+            self.db.put_blob(BlobNumber(number), &stored)?;
+        }
+        Ok(due_blobs)
+    }
+
+    /// Deletes the blobs whose transactions have been included in the committed slot
+    /// with the given number, along with their transactions, and returns their hashes.
+    pub(crate) fn observe_committed_slot(
+        &mut self,
+        slot_number: u64,
+    ) -> anyhow::Result<Vec<[u8; 32]>> {
+        let Some(tx_hashes) = self.ledger_db.get_tx_hashes_by_slot_number(slot_number)? else {
+            warn!(slot_number, "Committed slot is not available in the ledger");
+            return Ok(Vec::new());
+        };
+
+        let mut committed_blobs: Vec<u64> = tx_hashes
+            .iter()
+            .filter_map(|hash| self.blob_by_tx.get(hash).copied())
+            .collect();
+        committed_blobs.sort_unstable();
+        committed_blobs.dedup();
+
+        let mut committed_txs = Vec::new();
+        for number in committed_blobs {
+            if let Some(blob) = self.remove_blob(number)? {
+                debug!(
+                    blob = number,
+                    slot_number,
+                    num_txs = blob.txs.len(),
+                    "Blob has been observed in a committed slot"
+                );
+                committed_txs.extend(blob.txs.iter().map(|tx| tx.hash));
+            }
+        }
+        Ok(committed_txs)
+    }
+}
+
+/// Serializes the transactions of a blob, as they are sent to the DA layer.
+pub(crate) fn serialize_blob(txs: &[TxWithHash]) -> anyhow::Result<Vec<u8>> {
+    let raw_txs: Vec<Vec<u8>> = txs.iter().map(|tx| tx.raw_tx.clone()).collect();
+    Ok(borsh::to_vec(&raw_txs)?)
+}
@@ -0,0 +1,242 @@
+//! Soft confirmations: the effects of the accepted transactions, as executed speculatively
+//! by the sequencer, signed before the transactions are published.
+use std::io::Cursor;
+use std::marker::PhantomData;
+
+use anyhow::Context as ErrorContext;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_modules_api::digest::Digest;
+use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, DaSpec, DispatchCall, PrivateKey, Signature, Spec, WorkingSet};
+use sov_modules_stf_blueprint::{apply_tx, Runtime, TxEffect, TxEnvironment};
+use sov_rollup_interface::stf::{Event, TransactionReceipt};
+use tracing::warn;
+
+/// The effect of a transaction, as executed speculatively by the sequencer
+/// on top of the transactions it has confirmed before.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct SoftConfirmation {
+    /// The hash of the transaction.
+    pub tx_hash: [u8; 32],
+    /// The sequencer publishes the transactions it has confirmed in the order of their positions.
+    pub position: u64,
+    /// The effect of the transaction.
+    pub effect: TxEffect,
+    /// The events emitted by the transaction.
+    pub events: Vec<Event>,
+}
+
+/// A [`SoftConfirmation`] signed by the sequencer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignedSoftConfirmation {
+    /// The confirmation.
+    pub confirmation: SoftConfirmation,
+    /// The borsh-serialized public key of the sequencer.
+    pub pub_key: Vec<u8>,
+    /// The borsh-serialized signature of the borsh-serialized confirmation.
+    pub signature: Vec<u8>,
+}
+
+impl SignedSoftConfirmation {
+    /// Signs the confirmation with the given key.
+    pub fn sign<C: Context>(confirmation: SoftConfirmation, private_key: &C::PrivateKey) -> Self {
+        let msg = borsh::to_vec(&confirmation).expect("Serialization to vec is infallible");
+        Self {
+            confirmation,
+            pub_key: borsh::to_vec(&private_key.pub_key())
+                .expect("Serialization to vec is infallible"),
+            signature: borsh::to_vec(&private_key.sign(&msg))
+                .expect("Serialization to vec is infallible"),
+        }
+    }
+
+    /// Verifies the signature of the confirmation, and returns the public key which signed it.
+    pub fn verify<C: Context>(&self) -> anyhow::Result<C::PublicKey> {
+        let pub_key = C::PublicKey::try_from_slice(&self.pub_key)
+            .context("Failed to deserialize public key")?;
+        let signature = C::Signature::try_from_slice(&self.signature)
+            .context("Failed to deserialize signature")?;
+        let msg = borsh::to_vec(&self.confirmation)?;
+        signature.verify(&pub_key, &msg)?;
+        Ok(pub_key)
+    }
+}
+
+/// Executes the transactions accepted by a [`Sequencer`](crate::Sequencer) speculatively,
+/// and signs the soft confirmations of their effects.
+pub trait SoftConfirmer {
+    /// Executes a transaction on top of the transactions executed since the last reset.
+    fn execute(&mut self, raw_tx: &[u8]) -> anyhow::Result<TransactionReceipt<TxEffect>>;
+
+    /// Signs the confirmation with the key of the sequencer.
+    fn sign(&self, confirmation: SoftConfirmation) -> SignedSoftConfirmation;
+
+    /// Discards the speculative state, so the next transaction is executed on top of the
+    /// latest committed state.
+    fn reset(&mut self);
+}
+
+/// A [`SoftConfirmer`] which executes the transactions exactly like the
+/// [`StfBlueprint`](sov_modules_stf_blueprint::StfBlueprint) executes the transactions
+/// of a batch. The only difference is the DA address of the sequencer, which is empty in
+/// the [`Context`] of the transactions since the blob including them hasn't been posted yet.
+pub struct SpeculativeExecutor<C, Da, RT, K>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+    K: Kernel<C, Da>,
+{
+    runtime: RT,
+    kernel: K,
+    storage: C::Storage,
+    private_key: C::PrivateKey,
+    /// The state after the transactions executed since the last reset.
+    working_set: Option<WorkingSet<C>>,
+    phantom_da: PhantomData<Da>,
+}
+
+impl<C, Da, RT, K> SpeculativeExecutor<C, Da, RT, K>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+    K: Kernel<C, Da>,
+{
+    /// Creates an executor running on top of `storage`,
+    /// which signs the confirmations with `private_key`.
+    pub fn new(runtime: RT, storage: C::Storage, private_key: C::PrivateKey) -> Self {
+        Self {
+            runtime,
+            kernel: K::default(),
+            storage,
+            private_key,
+            working_set: None,
+            phantom_da: PhantomData,
+        }
+    }
+}
+
+impl<C, Da, RT, K> SoftConfirmer for SpeculativeExecutor<C, Da, RT, K>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+    K: Kernel<C, Da>,
+{
+    fn execute(&mut self, raw_tx: &[u8]) -> anyhow::Result<TransactionReceipt<TxEffect>> {
+        let tx = Transaction::<C>::deserialize_reader(&mut Cursor::new(raw_tx))
+            .context("Failed to deserialize transaction")?;
+        tx.verify().context("Failed to verify transaction")?;
+        let msg = RT::decode_call(tx.runtime_msg())
+            .map_err(anyhow::Error::new)
+            .context("Failed to decode message in transaction")?;
+        let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(raw_tx).into();
+
+        let mut working_set = self
+            .working_set
+            .take()
+            .unwrap_or_else(|| WorkingSet::new(self.storage.clone()));
+        let env = TxEnvironment {
+            // The blob which will include the transaction hasn't been posted yet,
+            // so its DA sender is unknown.
+            sequencer: Vec::new(),
+            visible_height: self.kernel.visible_height(&mut working_set),
+            chain_id: self.kernel.chain_id(&mut working_set),
+        };
+        let (receipt, working_set) = apply_tx(
+            &self.runtime,
+            &self.kernel,
+            &env,
+            &tx,
+            tx_hash,
+            msg,
+            working_set,
+        );
+        self.working_set = Some(working_set);
+        Ok(receipt)
+    }
+
+    fn sign(&self, confirmation: SoftConfirmation) -> SignedSoftConfirmation {
+        SignedSoftConfirmation::sign::<C>(confirmation, &self.private_key)
+    }
+
+    fn reset(&mut self) {
+        self.working_set = None;
+    }
+}
+
+/// The [`SoftConfirmer`] of a sequencer, along with the position of its next confirmation.
+pub(crate) struct SoftConfirmations {
+    confirmer: Box<dyn SoftConfirmer + Send>,
+    next_position: u64,
+}
+
+impl SoftConfirmations {
+    pub(crate) fn new(confirmer: Box<dyn SoftConfirmer + Send>, next_position: u64) -> Self {
+        Self {
+            confirmer,
+            next_position,
+        }
+    }
+
+    /// Executes an accepted transaction, and signs the confirmation of its effect.
+    pub(crate) fn confirm(&mut self, raw_tx: &[u8]) -> anyhow::Result<SignedSoftConfirmation> {
+        let receipt = self.confirmer.execute(raw_tx)?;
+        let confirmation = SoftConfirmation {
+            tx_hash: receipt.tx_hash,
+            position: self.next_position,
+            effect: receipt.receipt,
+            events: receipt.events,
+        };
+        self.next_position += 1;
+        Ok(self.confirmer.sign(confirmation))
+    }
+
+    /// Executes the transactions which haven't been committed yet again,
+    /// on top of the latest committed state.
+    pub(crate) fn rebase(&mut self, pending_txs: &[Vec<u8>]) {
+        self.confirmer.reset();
+        for raw_tx in pending_txs {
+            if let Err(e) = self.confirmer.execute(raw_tx) {
+                warn!(error = ?e, "Failed to execute pending transaction speculatively");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+
+    use super::*;
+
+    #[test]
+    fn signed_confirmation_is_verified() {
+        let private_key = DefaultPrivateKey::generate();
+        let confirmation = SoftConfirmation {
+            tx_hash: [1; 32],
+            position: 3,
+            effect: TxEffect::Successful { fee: 10 },
+            events: vec![Event::new("key", "value")],
+        };
+        let mut signed = SignedSoftConfirmation::sign::<DefaultContext>(confirmation, &private_key);
+
+        let pub_key = signed.verify::<DefaultContext>().unwrap();
+        assert_eq!(pub_key, private_key.pub_key());
+
+        signed.confirmation.position = 2;
+        assert!(signed.verify::<DefaultContext>().is_err());
+    }
+}
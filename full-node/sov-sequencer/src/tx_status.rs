@@ -0,0 +1,160 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// How many transactions which have been submitted to the DA layer or dropped keep their status.
+/// Without a persisted mempool, submitted transactions are never observed in a committed slot,
+/// so their statuses are only forgotten once this many transactions have followed them.
+const MAX_FINISHED_TXS: usize = 10_000;
+
+/// The status of a transaction submitted to a [`Sequencer`](crate::Sequencer).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TxStatus<TxId> {
+    /// The transaction is waiting in the mempool.
+    Pending,
+    /// The transaction has been included in a blob, which hasn't been submitted to the DA layer.
+    Batched {
+        /// The blob including the transaction.
+        blob_id: u64,
+    },
+    /// The blob including the transaction has been submitted to the DA layer,
+    /// and hasn't been observed in a committed slot yet.
+    SubmittedToDa {
+        /// The blob including the transaction.
+        blob_id: u64,
+        /// The DA layer transaction carrying the blob, as of its latest submission.
+        da_transaction_id: TxId,
+    },
+    /// The transaction has been dropped, and won't be published.
+    Dropped {
+        /// Why the transaction has been dropped.
+        reason: String,
+    },
+}
+
+impl<TxId> TxStatus<TxId> {
+    /// Returns true if the transaction has left the sequencer.
+    fn is_finished(&self) -> bool {
+        matches!(self, Self::SubmittedToDa { .. } | Self::Dropped { .. })
+    }
+}
+
+/// The transactions of a [`Sequencer`](crate::Sequencer), by status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolStats {
+    /// The number of transactions waiting in the mempool.
+    pub pending_txs: usize,
+    /// The total size of the transactions waiting in the mempool, in bytes.
+    pub pending_bytes: usize,
+    /// The number of transactions in blobs which haven't been submitted to the DA layer.
+    pub batched_txs: usize,
+    /// The number of transactions in blobs which have been submitted to the DA layer,
+    /// and haven't been observed in a committed slot yet.
+    pub submitted_txs: usize,
+    /// The number of recently dropped transactions.
+    pub dropped_txs: usize,
+}
+
+/// Tracks the status of the transactions accepted by a [`Sequencer`](crate::Sequencer),
+/// until they are observed in a committed slot.
+pub(crate) struct TxStatuses<TxId> {
+    statuses: HashMap<[u8; 32], TxStatus<TxId>>,
+    /// The transactions which have been submitted to the DA layer or dropped, oldest first.
+    finished: VecDeque<[u8; 32]>,
+    /// The id of the next blob, if the blobs aren't numbered by the write-ahead log.
+    next_blob_id: u64,
+}
+
+impl<TxId: Clone> TxStatuses<TxId> {
+    pub(crate) fn new() -> Self {
+        Self {
+            statuses: HashMap::new(),
+            finished: VecDeque::new(),
+            next_blob_id: 0,
+        }
+    }
+
+    pub(crate) fn get(&self, hash: &[u8; 32]) -> Option<TxStatus<TxId>> {
+        self.statuses.get(hash).cloned()
+    }
+
+    /// Returns the id of the next blob, for sequencers which don't persist their mempool.
+    pub(crate) fn next_blob_id(&mut self) -> u64 {
+        let blob_id = self.next_blob_id;
+        self.next_blob_id += 1;
+        blob_id
+    }
+
+    pub(crate) fn set_pending(&mut self, hash: [u8; 32]) {
+        self.set(hash, TxStatus::Pending);
+    }
+
+    pub(crate) fn set_batched(&mut self, hashes: &[[u8; 32]], blob_id: u64) {
+        for hash in hashes {
+            self.set(*hash, TxStatus::Batched { blob_id });
+        }
+    }
+
+    pub(crate) fn set_submitted(
+        &mut self,
+        hashes: &[[u8; 32]],
+        blob_id: u64,
+        da_transaction_id: &TxId,
+    ) {
+        for hash in hashes {
+            self.set(
+                *hash,
+                TxStatus::SubmittedToDa {
+                    blob_id,
+                    da_transaction_id: da_transaction_id.clone(),
+                },
+            );
+        }
+    }
+
+    pub(crate) fn set_dropped(&mut self, hash: [u8; 32], reason: String) {
+        self.set(hash, TxStatus::Dropped { reason });
+    }
+
+    /// Forgets the transactions which have been observed in a committed slot.
+    pub(crate) fn forget(&mut self, hashes: &[[u8; 32]]) {
+        for hash in hashes {
+            self.statuses.remove(hash);
+        }
+    }
+
+    /// Counts the tracked transactions by status. The pending transactions are counted
+    /// by the batch builder instead.
+    pub(crate) fn stats(&self) -> PoolStats {
+        let mut stats = PoolStats::default();
+        for status in self.statuses.values() {
+            match status {
+                TxStatus::Pending => {}
+                TxStatus::Batched { .. } => stats.batched_txs += 1,
+                TxStatus::SubmittedToDa { .. } => stats.submitted_txs += 1,
+                TxStatus::Dropped { .. } => stats.dropped_txs += 1,
+            }
+        }
+        stats
+    }
+
+    fn set(&mut self, hash: [u8; 32], status: TxStatus<TxId>) {
+        let was_finished = matches!(self.statuses.get(&hash), Some(status) if status.is_finished());
+        if status.is_finished() && !was_finished {
+            self.finished.push_back(hash);
+        }
+        self.statuses.insert(hash, status);
+
+        while self.finished.len() > MAX_FINISHED_TXS {
+            let oldest = self
+                .finished
+                .pop_front()
+                .expect("The queue is longer than the limit");
+            // The transaction may have been accepted again since.
+            if matches!(self.statuses.get(&oldest), Some(status) if status.is_finished()) {
+                self.statuses.remove(&oldest);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+use anyhow::ensure;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::zk::{StateTransition, ValidityCondition, ZkvmGuest};
+
+use crate::SlotProofOutput;
+
+#[derive(Serialize, Deserialize)]
+/// Data required to aggregate the proofs of consecutive slots.
+pub struct AggregationData<CodeCommitment, Address> {
+    /// The code commitment of the program which proved the individual slots.
+    pub slot_code_commitment: CodeCommitment,
+    /// The proofs of consecutive slots, ordered by height.
+    pub slot_proofs: Vec<Vec<u8>>,
+    /// The address rewarded for the aggregated proof.
+    pub rewarded_address: Address,
+}
+
+/// Verifies the proofs of consecutive slots and commits to a single [`StateTransition`] covering all of them.
+pub struct StateTransitionAggregator<Zk, Da, Address, Root, H> {
+    phantom: PhantomData<(Zk, Da, Address, Root, H)>,
+}
+
+impl<Zk, Da, Address, Root, H> Default for StateTransitionAggregator<Zk, Da, Address, Root, H> {
+    fn default() -> Self {
+        Self {
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Zk, Da, Address, Root, H> StateTransitionAggregator<Zk, Da, Address, Root, H>
+where
+    Zk: ZkvmGuest,
+    Da: DaSpec,
+    Address: Serialize + DeserializeOwned,
+    Root: Serialize + DeserializeOwned + PartialEq + AsRef<[u8]>,
+    H: Digest,
+{
+    /// Create a [`StateTransitionAggregator`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aggregate the slot proofs provided by the host
+    pub fn run_aggregation(&self, zkvm: &Zk) -> anyhow::Result<()> {
+        let data: AggregationData<Zk::CodeCommitment, Address> = zkvm.read_from_host();
+
+        let outputs = data
+            .slot_proofs
+            .iter()
+            .map(|proof| {
+                Zk::verify_and_deserialize_output::<SlotProofOutput<Root, Da>>(
+                    proof,
+                    &data.slot_code_commitment,
+                )
+                .map_err(|e| anyhow::anyhow!("Slot proof failed to verify: {:?}", e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let transition = aggregate_outputs::<Da, Address, Root, H>(outputs, data.rewarded_address)?;
+
+        zkvm.commit(&transition);
+        Ok(())
+    }
+}
+
+/// Combines the outputs of the proofs of consecutive slots into a single [`StateTransition`].
+///
+/// Fails if there are no outputs, if a slot does not directly follow the previous one on the
+/// DA layer or does not start from the state root it left, or if their validity conditions
+/// cannot be combined.
+pub fn aggregate_outputs<Da, Address, Root, H>(
+    outputs: impl IntoIterator<Item = SlotProofOutput<Root, Da>>,
+    rewarded_address: Address,
+) -> anyhow::Result<StateTransition<Da, Address, Root>>
+where
+    Da: DaSpec,
+    Root: PartialEq + AsRef<[u8]>,
+    H: Digest,
+{
+    let mut outputs = outputs.into_iter();
+    let first = outputs
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No slot proofs to aggregate"))?;
+
+    let mut transition = StateTransition {
+        initial_state_root: first.pre_state_root,
+        final_state_root: first.post_state_root,
+        initial_slot_hash: first.da_block_hash.clone(),
+        slot_hash: first.da_block_hash,
+        rewarded_address,
+        validity_condition: first.validity_condition,
+    };
+
+    for output in outputs {
+        ensure!(
+            output.prev_da_block_hash == transition.slot_hash,
+            "Slot {:?} does not follow the previous slot {:?} on the DA layer",
+            output.da_block_hash,
+            transition.slot_hash,
+        );
+        ensure!(
+            output.pre_state_root == transition.final_state_root,
+            "Slot {:?} starts from state root 0x{}, but the previous slot {:?} ended with 0x{}",
+            output.da_block_hash,
+            hex::encode(output.pre_state_root.as_ref()),
+            transition.slot_hash,
+            hex::encode(transition.final_state_root.as_ref()),
+        );
+
+        transition.validity_condition = transition
+            .validity_condition
+            .combine::<H>(output.validity_condition)
+            .map_err(Into::<anyhow::Error>::into)?;
+        transition.final_state_root = output.post_state_root;
+        transition.slot_hash = output.da_block_hash;
+    }
+
+    Ok(transition)
+}
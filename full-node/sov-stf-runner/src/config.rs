@@ -1,9 +1,12 @@
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use sov_db::pruning::PruningConfig;
+use sov_rollup_interface::services::batch_builder::BatchProductionConfig;
 
 /// Configuration for StateTransitionRunner.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -12,6 +15,14 @@ pub struct RunnerConfig {
     pub start_height: u64,
     /// RPC configuration.
     pub rpc_config: RpcConfig,
+    /// The maximum number of finalized slots waiting for their proofs.
+    /// Once the backlog is full, block execution pauses until the prover catches up.
+    #[serde(default = "default_max_proof_backlog")]
+    pub max_proof_backlog: usize,
+}
+
+const fn default_max_proof_backlog() -> usize {
+    16
 }
 
 /// RPC configuration.
@@ -28,6 +39,74 @@ pub struct RpcConfig {
 pub struct StorageConfig {
     /// Path that can be utilized by concrete implementation
     pub path: PathBuf,
+    /// Which versions of the state and which ledger items to keep. Nothing is pruned by default.
+    #[serde(default)]
+    pub pruning: PruningConfig,
+}
+
+/// Sequencer configuration.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct SequencerConfig {
+    /// When batches are published.
+    #[serde(default)]
+    pub batch_production: BatchProductionConfig,
+    /// How the transactions waiting in the mempool are ordered into batches.
+    #[serde(default)]
+    pub mempool: MempoolConfig,
+    /// Soft confirmations of the accepted transactions. Disabled if omitted.
+    #[serde(default)]
+    pub soft_confirmations: Option<SoftConfirmationConfig>,
+}
+
+/// Soft confirmation configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SoftConfirmationConfig {
+    /// The JSON file holding the private key the sequencer signs its confirmations with.
+    pub private_key_path: PathBuf,
+}
+
+/// The batch builders the sequencer can order its mempool with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchBuilderKind {
+    /// Transactions are included in the order they were accepted.
+    #[default]
+    Fifo,
+    /// Transactions are included in nonce order per sender, and senders paying higher fees first.
+    Priority,
+}
+
+/// Mempool configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MempoolConfig {
+    /// The batch builder ordering the mempool.
+    pub kind: BatchBuilderKind,
+    /// The maximum number of transactions waiting in the mempool.
+    pub max_txs: usize,
+    /// The maximum size of a batch in bytes.
+    pub max_batch_size_bytes: usize,
+    /// How long a transaction can wait in the mempool before being dropped.
+    /// Only used by the [`BatchBuilderKind::Priority`] batch builder.
+    pub max_tx_age_secs: u64,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            kind: BatchBuilderKind::default(),
+            max_txs: u32::MAX as usize,
+            max_batch_size_bytes: 100 * 1024,
+            max_tx_age_secs: 600,
+        }
+    }
+}
+
+impl MempoolConfig {
+    /// How long a transaction can wait in the mempool before being dropped.
+    pub fn max_tx_age(&self) -> Duration {
+        Duration::from_secs(self.max_tx_age_secs)
+    }
 }
 
 /// Rollup Configuration
@@ -39,6 +118,9 @@ pub struct RollupConfig<DaServiceConfig> {
     pub runner: RunnerConfig,
     /// Data Availability service configuration.
     pub da: DaServiceConfig,
+    /// Sequencer configuration. Uses the defaults if omitted.
+    #[serde(default)]
+    pub sequencer: SequencerConfig,
 }
 
 /// Reads toml file as a specific type.
@@ -61,6 +143,7 @@ mod tests {
     use std::io::Write;
     use std::path::PathBuf;
 
+    use sov_db::pruning::{LedgerPruningConfig, StatePruningConfig};
     use tempfile::NamedTempFile;
 
     use super::*;
@@ -98,6 +181,7 @@ mod tests {
                     bind_host: "127.0.0.1".to_string(),
                     bind_port: 12345,
                 },
+                max_proof_backlog: 16,
             },
 
             da: sov_celestia_adapter::CelestiaConfig {
@@ -108,6 +192,77 @@ mod tests {
             },
             storage: StorageConfig {
                 path: PathBuf::from("/tmp"),
+                pruning: PruningConfig::default(),
+            },
+            sequencer: SequencerConfig::default(),
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_sequencer_config() {
+        let config = r#"
+            [batch_production]
+            max_pending_txs = 10
+            max_batch_interval_ms = 500
+            publish_on_da_head = false
+            [mempool]
+            kind = "priority"
+            max_tx_age_secs = 60
+            [soft_confirmations]
+            private_key_path = "sequencer_private_key.json"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: SequencerConfig = from_toml_path(config_file.path()).unwrap();
+        let expected = SequencerConfig {
+            batch_production: BatchProductionConfig {
+                max_pending_txs: 10,
+                max_pending_bytes: BatchProductionConfig::default().max_pending_bytes,
+                max_batch_interval_ms: 500,
+                publish_on_da_head: false,
+                ..Default::default()
+            },
+            mempool: MempoolConfig {
+                kind: BatchBuilderKind::Priority,
+                max_tx_age_secs: 60,
+                ..Default::default()
+            },
+            soft_confirmations: Some(SoftConfirmationConfig {
+                private_key_path: PathBuf::from("sequencer_private_key.json"),
+            }),
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_pruning_config() {
+        let config = r#"
+            path = "/tmp"
+            [pruning]
+            interval_secs = 10
+            [pruning.state]
+            keep_last_versions = 100
+            checkpoint_interval = 1000
+            [pruning.ledger]
+            keep_last_slots = 5000
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: StorageConfig = from_toml_path(config_file.path()).unwrap();
+        let expected = StorageConfig {
+            path: PathBuf::from("/tmp"),
+            pruning: PruningConfig {
+                state: Some(StatePruningConfig {
+                    keep_last_versions: 100,
+                    checkpoint_interval: Some(1000),
+                }),
+                ledger: Some(LedgerPruningConfig {
+                    keep_last_slots: 5000,
+                }),
+                interval_secs: 10,
             },
         };
         assert_eq!(config, expected);
@@ -8,7 +8,11 @@ mod config;
 #[cfg(feature = "mock")]
 pub mod mock;
 #[cfg(feature = "native")]
+mod proof_pipeline;
+#[cfg(feature = "native")]
 mod prover_service;
+#[cfg(feature = "native")]
+mod soft_confirmations;
 
 #[cfg(feature = "native")]
 use std::path::Path;
@@ -23,16 +27,36 @@ pub use prover_service::*;
 #[cfg(feature = "native")]
 mod runner;
 #[cfg(feature = "native")]
-pub use config::{from_toml_path, RollupConfig, RunnerConfig, StorageConfig};
+pub use config::{
+    from_toml_path, BatchBuilderKind, MempoolConfig, RollupConfig, RunnerConfig, SequencerConfig,
+    SoftConfirmationConfig, StorageConfig,
+};
 #[cfg(feature = "native")]
 pub use runner::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "native")]
+pub use soft_confirmations::{check_soft_confirmations, SoftConfirmationDivergence};
 use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::zk::ProofEnvelope;
 
 /// Implements the `StateTransitionVerifier` type for checking the validity of a state transition
 pub mod verifier;
 
+/// Implements the `StateTransitionAggregator` type for combining proofs of consecutive slots
+pub mod aggregator;
+
+/// The public outputs of the proof of a single slot.
+pub type SlotProofOutput<StateRoot, Da> = verifier::StateTransitionOutput<
+    StateRoot,
+    <Da as DaSpec>::SlotHash,
+    <Da as DaSpec>::ValidityCondition,
+>;
+
+/// The envelope in which state transition proofs are posted to the DA layer.
+pub type StateTransitionProofEnvelope<StateRoot, Da, CodeCommitment> =
+    ProofEnvelope<SlotProofOutput<StateRoot, Da>, CodeCommitment>;
+
 #[derive(Serialize, BorshDeserialize, BorshSerialize, Deserialize)]
 // Prevent serde from generating spurious trait bounds. The correct serde bounds are already enforced by the
 // StateTransitionFunction, DA, and Zkvm traits.
@@ -0,0 +1,501 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::{DbHash, StoredProof, StoredProofStatus};
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::services::da::DaService;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::{
+    ProofProcessingStatus, ProofSubmissionStatus, ProverService, SlotProofOutput,
+    StateTransitionData,
+};
+
+/// How often the prover service is polled while proofs are being generated.
+const POLLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the pipeline waits before proving a slot again after its first failure.
+/// The delay doubles with every further failure.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+type DaSpecOf<Ps> = <<Ps as ProverService>::DaService as DaService>::Spec;
+
+/// Proves the slots sent by the runner and publishes their proofs, independently of block execution.
+///
+/// The runner only sends the hash of a slot: the witness is read back from the prover job
+/// persisted in the [`LedgerDB`], and the progress of the job is recorded there.
+pub(crate) struct ProofPipeline<Ps: ProverService> {
+    prover_service: Ps,
+    ledger_db: LedgerDB,
+    receiver: mpsc::Receiver<DbHash>,
+    /// A slot rejected by the busy prover. It is retried before any new slot is received.
+    next_slot: Option<DbHash>,
+    /// Slots whose proofs are being generated.
+    in_progress: Vec<<DaSpecOf<Ps> as DaSpec>::SlotHash>,
+    /// Slots whose proving failed, with the time from which they can be proven again.
+    retries: Vec<(Instant, DbHash)>,
+    retry_backoff: Duration,
+}
+
+impl<Ps> ProofPipeline<Ps>
+where
+    Ps: ProverService,
+    Ps::StateRoot: DeserializeOwned,
+    Ps::Witness: DeserializeOwned,
+{
+    pub(crate) fn new(
+        prover_service: Ps,
+        ledger_db: LedgerDB,
+        receiver: mpsc::Receiver<DbHash>,
+    ) -> Self {
+        Self {
+            prover_service,
+            ledger_db,
+            receiver,
+            next_slot: None,
+            in_progress: Vec::new(),
+            retries: Vec::new(),
+            retry_backoff: RETRY_BACKOFF,
+        }
+    }
+
+    /// Runs until the sender is dropped and all the received slots are proven or abandoned.
+    /// Failures are recorded on the prover job of the slot, and never stop the pipeline.
+    pub(crate) async fn run(mut self) -> anyhow::Result<()> {
+        loop {
+            self.publish_finished_proofs().await;
+
+            if self.next_slot.is_none() {
+                self.next_slot = self.take_due_retry();
+            }
+            if self.next_slot.is_none() {
+                self.next_slot = if self.in_progress.is_empty() && self.retries.is_empty() {
+                    match self.receiver.recv().await {
+                        Some(slot_hash) => Some(slot_hash),
+                        None => return Ok(()),
+                    }
+                } else {
+                    self.receiver.try_recv().ok()
+                };
+            }
+
+            if let Some(slot_hash) = self.next_slot.take() {
+                match self.start_proving(&slot_hash).await {
+                    // The prover might be able to accept more slots right away.
+                    Ok(true) => continue,
+                    Ok(false) => self.next_slot = Some(slot_hash),
+                    Err(e) => {
+                        self.record_failure(&slot_hash, e);
+                        continue;
+                    }
+                }
+            }
+
+            tokio::time::sleep(POLLING_INTERVAL).await;
+        }
+    }
+
+    /// Submits the slot to the prover. Returns `false` if the prover is busy.
+    async fn start_proving(&mut self, slot_hash: &DbHash) -> anyhow::Result<bool> {
+        let job = self.ledger_db.get_prover_job(slot_hash)?.ok_or_else(|| {
+            anyhow::anyhow!("Missing prover job for slot 0x{}", hex::encode(slot_hash))
+        })?;
+        // The slot may have been proven by another prover in the meantime.
+        if job.status.is_done() {
+            debug!("Slot 0x{} is already proven", hex::encode(slot_hash));
+            return Ok(true);
+        }
+        let (transition_data, public_outputs): (
+            StateTransitionData<Ps::StateRoot, Ps::Witness, DaSpecOf<Ps>>,
+            SlotProofOutput<Ps::StateRoot, DaSpecOf<Ps>>,
+        ) = bincode::deserialize(job.witness.as_ref())
+            .context("Failed to deserialize prover job")?;
+        let header_hash = transition_data.da_block_header.hash();
+
+        self.prover_service
+            .submit_witness(transition_data, public_outputs)
+            .await;
+
+        match self.prover_service.prove(header_hash.clone()).await {
+            Ok(ProofProcessingStatus::ProvingInProgress) => {
+                debug!("Started proving slot 0x{}", hex::encode(slot_hash));
+                self.in_progress.push(header_hash);
+                // The proof is published even if its progress can't be recorded.
+                if let Err(e) = self
+                    .ledger_db
+                    .set_proof_status(slot_hash, StoredProofStatus::Proving)
+                {
+                    warn!(
+                        "Failed to record the proving of slot 0x{}: {:?}",
+                        hex::encode(slot_hash),
+                        e
+                    );
+                }
+                Ok(true)
+            }
+            // The prover drops the witnesses it rejects, so it is submitted again on the next attempt.
+            Ok(ProofProcessingStatus::Busy) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Publishes the proofs which are ready and records them in the ledger.
+    async fn publish_finished_proofs(&mut self) {
+        let mut still_in_progress = Vec::with_capacity(self.in_progress.len());
+
+        for header_hash in std::mem::take(&mut self.in_progress) {
+            let slot_hash: DbHash = header_hash.clone().into();
+            let status = self
+                .prover_service
+                .send_proof_to_da(header_hash.clone())
+                .await;
+
+            let result = match status {
+                Ok(ProofSubmissionStatus::Success(Some(published))) => {
+                    info!("Published proof of slot 0x{}", hex::encode(slot_hash));
+                    self.ledger_db.put_proof(
+                        &slot_hash,
+                        &StoredProof {
+                            da_transaction_id: published.da_transaction_id.into(),
+                            proof: published.blob.into(),
+                        },
+                    )
+                }
+                Ok(ProofSubmissionStatus::Success(None)) => self
+                    .ledger_db
+                    .set_proof_status(&slot_hash, StoredProofStatus::Proved),
+                // TODO(#1185): Add timeout handling.
+                Ok(ProofSubmissionStatus::ProofGenerationInProgress) => {
+                    still_in_progress.push(header_hash);
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                self.record_failure(&slot_hash, e);
+            }
+        }
+
+        self.in_progress = still_in_progress;
+    }
+
+    /// Records a failed attempt to prove the slot, and schedules it to be proven again
+    /// unless it has failed too many times.
+    fn record_failure(&mut self, slot_hash: &DbHash, error: anyhow::Error) {
+        warn!(
+            "Failed to prove slot 0x{}: {:?}",
+            hex::encode(slot_hash),
+            error
+        );
+        let job = self
+            .ledger_db
+            .set_proof_status(slot_hash, StoredProofStatus::Failed(error.to_string()))
+            .and_then(|()| self.ledger_db.get_prover_job(slot_hash));
+        let job = match job {
+            Ok(Some(job)) => job,
+            // The slot is not retried, but its job is resumed on restart if it still exists.
+            result => {
+                warn!(
+                    "Failed to record the proof failure of slot 0x{}: {:?}",
+                    hex::encode(slot_hash),
+                    result.err()
+                );
+                return;
+            }
+        };
+        if job.is_abandoned() {
+            warn!(
+                "Abandoning the prover job of slot 0x{} after {} failed attempts",
+                hex::encode(slot_hash),
+                job.failed_attempts
+            );
+            return;
+        }
+
+        let backoff = self.retry_backoff * 2u32.pow(job.failed_attempts.saturating_sub(1));
+        self.retries.push((Instant::now() + backoff, *slot_hash));
+    }
+
+    /// Takes the slot which has been waiting the longest to be proven again, if its backoff is over.
+    fn take_due_retry(&mut self) -> Option<DbHash> {
+        let now = Instant::now();
+        let (index, _) = self
+            .retries
+            .iter()
+            .enumerate()
+            .filter(|(_, (retry_at, _))| *retry_at <= now)
+            .min_by_key(|(_, (retry_at, _))| *retry_at)?;
+        Some(self.retries.remove(index).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use sov_db::schema::types::{StoredProverJob, MAX_PROVER_JOB_ATTEMPTS};
+    use sov_mock_da::{MockBlockHeader, MockDaService, MockDaSpec, MockHash, MockValidityCond};
+
+    use super::*;
+    use crate::{ProverServiceError, WitnessSubmissionStatus};
+
+    #[derive(Default)]
+    struct ProverState {
+        /// How many slots can be proven at the same time.
+        capacity: usize,
+        /// The slots being proven.
+        proving: Vec<MockHash>,
+        /// Every slot the prover started to prove, in order.
+        started: Vec<MockHash>,
+        /// How many times the prover rejected a slot because it was busy.
+        busy_responses: usize,
+        /// Whether the proofs being generated are ready.
+        release_proofs: bool,
+        /// How many more times proving a slot fails.
+        failures: HashMap<MockHash, u32>,
+    }
+
+    /// A prover service with a limited capacity, whose proofs are released by the test.
+    #[derive(Clone)]
+    struct QueueProverService {
+        state: Arc<Mutex<ProverState>>,
+    }
+
+    #[async_trait]
+    impl ProverService for QueueProverService {
+        type StateRoot = [u8; 0];
+        type Witness = ();
+        type DaService = MockDaService;
+
+        async fn submit_witness(
+            &self,
+            _state_transition_data: StateTransitionData<Self::StateRoot, Self::Witness, MockDaSpec>,
+            _public_outputs: SlotProofOutput<Self::StateRoot, MockDaSpec>,
+        ) -> WitnessSubmissionStatus {
+            WitnessSubmissionStatus::SubmittedForProving
+        }
+
+        async fn prove(
+            &self,
+            block_header_hash: MockHash,
+        ) -> Result<ProofProcessingStatus, ProverServiceError> {
+            let mut state = self.state.lock().unwrap();
+            if let Some(failures) = state.failures.get_mut(&block_header_hash) {
+                if *failures > 0 {
+                    *failures -= 1;
+                    return Err(anyhow::anyhow!("Proving failed").into());
+                }
+            }
+            if state.proving.len() >= state.capacity {
+                state.busy_responses += 1;
+                return Ok(ProofProcessingStatus::Busy);
+            }
+            state.proving.push(block_header_hash);
+            state.started.push(block_header_hash);
+            Ok(ProofProcessingStatus::ProvingInProgress)
+        }
+
+        async fn send_proof_to_da(
+            &self,
+            block_header_hash: MockHash,
+        ) -> Result<ProofSubmissionStatus, anyhow::Error> {
+            let mut state = self.state.lock().unwrap();
+            if !state.release_proofs {
+                return Ok(ProofSubmissionStatus::ProofGenerationInProgress);
+            }
+            state.proving.retain(|hash| *hash != block_header_hash);
+            Ok(ProofSubmissionStatus::Success(None))
+        }
+    }
+
+    fn put_prover_job(ledger_db: &LedgerDB, height: u64) -> MockHash {
+        let header_hash = MockHash::from([height as u8; 32]);
+        let transition_data = StateTransitionData::<[u8; 0], (), MockDaSpec> {
+            pre_state_root: [],
+            da_block_header: MockBlockHeader {
+                prev_hash: [0; 32].into(),
+                hash: header_hash,
+                height,
+            },
+            inclusion_proof: [0; 32],
+            completeness_proof: (),
+            blobs: vec![],
+            state_transition_witness: (),
+        };
+        let public_outputs = SlotProofOutput::<[u8; 0], MockDaSpec> {
+            pre_state_root: [],
+            post_state_root: [],
+            da_block_hash: header_hash,
+            prev_da_block_hash: [0; 32].into(),
+            validity_condition: MockValidityCond::default(),
+        };
+        ledger_db
+            .put_prover_job(
+                &header_hash.into(),
+                &StoredProverJob {
+                    da_height: height,
+                    status: StoredProofStatus::Pending,
+                    failed_attempts: 0,
+                    witness: bincode::serialize(&(&transition_data, &public_outputs))
+                        .unwrap()
+                        .into(),
+                },
+            )
+            .unwrap();
+        header_hash
+    }
+
+    fn prover_job(ledger_db: &LedgerDB, header_hash: MockHash) -> StoredProverJob {
+        ledger_db
+            .get_prover_job(&header_hash.into())
+            .unwrap()
+            .unwrap()
+    }
+
+    fn proof_status(ledger_db: &LedgerDB, header_hash: MockHash) -> StoredProofStatus {
+        prover_job(ledger_db, header_hash).status
+    }
+
+    #[tokio::test]
+    async fn queued_slots_are_proven_in_order_once_the_prover_is_available() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+        let state = Arc::new(Mutex::new(ProverState {
+            capacity: 2,
+            ..Default::default()
+        }));
+        let prover_service = QueueProverService {
+            state: state.clone(),
+        };
+
+        let header_hashes: Vec<MockHash> = (1..=5)
+            .map(|height| put_prover_job(&ledger_db, height))
+            .collect();
+        let (sender, receiver) = mpsc::channel(header_hashes.len());
+        for header_hash in &header_hashes {
+            sender.send((*header_hash).into()).await.unwrap();
+        }
+        let pipeline = ProofPipeline::new(prover_service, ledger_db.clone(), receiver);
+        let pipeline = tokio::spawn(pipeline.run());
+
+        // The prover is filled up, and rejects the next slot until a proof is ready.
+        for _ in 0..100 {
+            if state.lock().unwrap().busy_responses > 1 {
+                break;
+            }
+            tokio::time::sleep(POLLING_INTERVAL).await;
+        }
+        assert!(state.lock().unwrap().busy_responses > 1);
+        assert_eq!(state.lock().unwrap().started, header_hashes[..2]);
+        for header_hash in &header_hashes[..2] {
+            assert_eq!(
+                proof_status(&ledger_db, *header_hash),
+                StoredProofStatus::Proving
+            );
+        }
+        for header_hash in &header_hashes[2..] {
+            assert_eq!(
+                proof_status(&ledger_db, *header_hash),
+                StoredProofStatus::Pending
+            );
+        }
+
+        // The queue drains once proofs are ready, and the pipeline stops with the runner.
+        state.lock().unwrap().release_proofs = true;
+        drop(sender);
+        pipeline.await.unwrap().unwrap();
+
+        assert_eq!(state.lock().unwrap().started, header_hashes);
+        for header_hash in &header_hashes {
+            assert_eq!(
+                proof_status(&ledger_db, *header_hash),
+                StoredProofStatus::Proved
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_slots_are_retried_until_abandoned() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+        let header_hashes: Vec<MockHash> = (1..=3)
+            .map(|height| put_prover_job(&ledger_db, height))
+            .collect();
+        let state = Arc::new(Mutex::new(ProverState {
+            capacity: 1,
+            release_proofs: true,
+            failures: HashMap::from([
+                (header_hashes[0], 1),
+                (header_hashes[1], MAX_PROVER_JOB_ATTEMPTS),
+            ]),
+            ..Default::default()
+        }));
+        let prover_service = QueueProverService {
+            state: state.clone(),
+        };
+
+        let (sender, receiver) = mpsc::channel(header_hashes.len());
+        for header_hash in &header_hashes {
+            sender.send((*header_hash).into()).await.unwrap();
+        }
+        drop(sender);
+        let mut pipeline = ProofPipeline::new(prover_service, ledger_db.clone(), receiver);
+        pipeline.retry_backoff = Duration::from_millis(10);
+
+        // Failures don't stop the pipeline, which keeps proving the other slots.
+        pipeline.run().await.unwrap();
+
+        let first_job = prover_job(&ledger_db, header_hashes[0]);
+        assert_eq!(first_job.status, StoredProofStatus::Proved);
+        assert_eq!(first_job.failed_attempts, 1);
+
+        let abandoned_job = prover_job(&ledger_db, header_hashes[1]);
+        assert!(matches!(abandoned_job.status, StoredProofStatus::Failed(_)));
+        assert_eq!(abandoned_job.failed_attempts, MAX_PROVER_JOB_ATTEMPTS);
+        assert!(abandoned_job.is_abandoned());
+
+        assert_eq!(
+            proof_status(&ledger_db, header_hashes[2]),
+            StoredProofStatus::Proved
+        );
+        let started = state.lock().unwrap().started.clone();
+        assert_eq!(started.len(), 2);
+        assert!(started.contains(&header_hashes[0]));
+        assert!(started.contains(&header_hashes[2]));
+    }
+
+    #[tokio::test]
+    async fn slots_without_prover_job_are_skipped() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+        let header_hash = put_prover_job(&ledger_db, 1);
+        let state = Arc::new(Mutex::new(ProverState {
+            capacity: 1,
+            release_proofs: true,
+            ..Default::default()
+        }));
+        let prover_service = QueueProverService {
+            state: state.clone(),
+        };
+
+        let (sender, receiver) = mpsc::channel(2);
+        sender.send([9; 32]).await.unwrap();
+        sender.send(header_hash.into()).await.unwrap();
+        drop(sender);
+        let pipeline = ProofPipeline::new(prover_service, ledger_db.clone(), receiver);
+
+        pipeline.run().await.unwrap();
+
+        assert_eq!(state.lock().unwrap().started, vec![header_hash]);
+        assert_eq!(
+            proof_status(&ledger_db, header_hash),
+            StoredProofStatus::Proved
+        );
+    }
+}
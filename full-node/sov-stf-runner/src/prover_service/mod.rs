@@ -6,7 +6,7 @@ use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
 use thiserror::Error;
 
-use crate::StateTransitionData;
+use crate::{SlotProofOutput, StateTransitionData};
 
 /// The possible configurations of the prover.
 pub enum RollupProverConfig {
@@ -29,11 +29,21 @@ pub enum WitnessSubmissionStatus {
     WitnessExist,
 }
 
+/// A proof which has been posted to the DA layer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PublishedProof {
+    /// The serialized id of the DA transaction carrying the proof.
+    pub da_transaction_id: Vec<u8>,
+    /// The encoded [`StateTransitionProofEnvelope`](crate::StateTransitionProofEnvelope), exactly as it was posted to the DA.
+    pub blob: Vec<u8>,
+}
+
 /// Represents the status of a DA proof submission.
 #[derive(Debug, Eq, PartialEq)]
 pub enum ProofSubmissionStatus {
     /// Indicates successful submission of the proof to the DA.
-    Success,
+    /// Empty proofs (e.g. when proving is skipped or simulated) are not posted, in which case this is `None`.
+    Success(Option<PublishedProof>),
     /// Indicates that proof generation is currently in progress.
     ProofGenerationInProgress,
 }
@@ -74,7 +84,7 @@ pub trait ProverService {
     /// Data Availability service.
     type DaService: DaService;
 
-    /// Submit a witness for proving.
+    /// Submit a witness for proving, along with the public outputs that executing it produced.
     async fn submit_witness(
         &self,
         state_transition_data: StateTransitionData<
@@ -82,6 +92,7 @@ pub trait ProverService {
             Self::Witness,
             <Self::DaService as DaService>::Spec,
         >,
+        public_outputs: SlotProofOutput<Self::StateRoot, <Self::DaService as DaService>::Spec>,
     ) -> WitnessSubmissionStatus;
 
     /// Creates ZKP prove for a block corresponding to `block_header_hash`.
@@ -90,8 +101,8 @@ pub trait ProverService {
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofProcessingStatus, ProverServiceError>;
 
-    /// Sends the ZK proof to the DA.
-    /// This method is noy yet fully implemented: see #1185
+    /// Sends the ZK proof to the DA, wrapped in a [`StateTransitionProofEnvelope`](crate::StateTransitionProofEnvelope).
+    /// The proof is kept by the service until it has been successfully posted.
     async fn send_proof_to_da(
         &self,
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
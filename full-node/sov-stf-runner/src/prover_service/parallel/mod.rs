@@ -1,6 +1,7 @@
 mod prover;
 use std::sync::Arc;
 
+use anyhow::Context;
 use async_trait::async_trait;
 use prover::Prover;
 use serde::de::DeserializeOwned;
@@ -8,13 +9,13 @@ use serde::Serialize;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::ZkvmHost;
+use sov_rollup_interface::zk::{Proof, ProofEnvelope, ZkvmHost};
 
-use super::{ProverService, ProverServiceError};
+use super::{ProverService, ProverServiceError, PublishedProof};
 use crate::verifier::StateTransitionVerifier;
 use crate::{
     ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, RollupProverConfig,
-    StateTransitionData, WitnessSubmissionStatus,
+    SlotProofOutput, StateTransitionData, StateTransitionProofEnvelope, WitnessSubmissionStatus,
 };
 
 /// Prover service that generates proofs in parallel.
@@ -30,6 +31,8 @@ where
     prover_config: Arc<ProofGenConfig<V, Da, Vm>>,
     zk_storage: V::PreState,
     prover_state: Prover<StateRoot, Witness, Da>,
+    da_service: Arc<Da>,
+    code_commitment: Vm::CodeCommitment,
 }
 
 impl<StateRoot, Witness, Da, Vm, V> ParallelProverService<StateRoot, Witness, Da, Vm, V>
@@ -41,11 +44,15 @@ where
     V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync,
     V::PreState: Clone + Send + Sync,
 {
-    /// Creates a new prover.
+    /// Creates a new prover. Generated proofs are posted to `da_service`, tagged with
+    /// the `code_commitment` of the guest program which produced them.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         vm: Vm,
         zk_stf: V,
+        da_service: Da,
         da_verifier: Da::Verifier,
+        code_commitment: Vm::CodeCommitment,
         config: RollupProverConfig,
         zk_storage: V::PreState,
         num_threads: usize,
@@ -67,6 +74,8 @@ where
             prover_config,
             prover_state: Prover::new(num_threads),
             zk_storage,
+            da_service: Arc::new(da_service),
+            code_commitment,
         }
     }
 
@@ -74,14 +83,25 @@ where
     pub fn new_with_default_workers(
         vm: Vm,
         zk_stf: V,
+        da_service: Da,
         da_verifier: Da::Verifier,
+        code_commitment: Vm::CodeCommitment,
         config: RollupProverConfig,
         zk_storage: V::PreState,
     ) -> Self {
         let num_cpus = num_cpus::get();
         assert!(num_cpus > 1, "Unable to create parallel prover service");
 
-        Self::new(vm, zk_stf, da_verifier, config, zk_storage, num_cpus - 1)
+        Self::new(
+            vm,
+            zk_stf,
+            da_service,
+            da_verifier,
+            code_commitment,
+            config,
+            zk_storage,
+            num_cpus - 1,
+        )
     }
 }
 
@@ -93,6 +113,7 @@ where
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
     Da: DaService,
     Vm: ZkvmHost + 'static,
+    Vm::CodeCommitment: Send + Sync,
     V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
     V::PreState: Clone + Send + Sync,
 {
@@ -109,8 +130,10 @@ where
             Self::Witness,
             <Self::DaService as DaService>::Spec,
         >,
+        public_outputs: SlotProofOutput<Self::StateRoot, Da::Spec>,
     ) -> WitnessSubmissionStatus {
-        self.prover_state.submit_witness(state_transition_data)
+        self.prover_state
+            .submit_witness(state_transition_data, public_outputs)
     }
 
     async fn prove(
@@ -132,7 +155,39 @@ where
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error> {
-        self.prover_state
-            .get_proof_submission_status_and_remove_on_success(block_header_hash)
+        let Some((proof, public_outputs)) =
+            self.prover_state.get_proof(block_header_hash.clone())?
+        else {
+            return Ok(ProofSubmissionStatus::ProofGenerationInProgress);
+        };
+
+        // There is nothing worth posting for an empty proof.
+        if proof == Proof::Empty {
+            self.prover_state.remove(&block_header_hash);
+            return Ok(ProofSubmissionStatus::Success(None));
+        }
+
+        let envelope: StateTransitionProofEnvelope<_, _, _> = ProofEnvelope::V1 {
+            public_outputs,
+            code_commitment: self.code_commitment.clone(),
+            proof,
+        };
+        let blob = envelope.encode()?;
+
+        let tx_id = self
+            .da_service
+            .send_transaction(&blob)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to send proof to the DA: {:?}", e))?;
+        let da_transaction_id =
+            bincode::serialize(&tx_id).context("Failed to serialize DA transaction id")?;
+
+        // The proof is only dropped once it has been posted, so a failed submission can be retried.
+        self.prover_state.remove(&block_header_hash);
+
+        Ok(ProofSubmissionStatus::Success(Some(PublishedProof {
+            da_transaction_id,
+            blob,
+        })))
     }
 }
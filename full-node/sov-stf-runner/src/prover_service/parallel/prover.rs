@@ -12,14 +12,17 @@ use sov_rollup_interface::zk::{Proof, ZkvmHost};
 
 use super::ProverServiceError;
 use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, StateTransitionData,
+    ProofGenConfig, ProofProcessingStatus, SlotProofOutput, StateTransitionData,
     WitnessSubmissionStatus,
 };
 
 enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
-    WitnessSubmitted(StateTransitionData<StateRoot, Witness, Da>),
+    WitnessSubmitted(
+        StateTransitionData<StateRoot, Witness, Da>,
+        SlotProofOutput<StateRoot, Da>,
+    ),
     ProvingInProgress,
-    Proved(Proof),
+    Proved(Proof, SlotProofOutput<StateRoot, Da>),
     Err(anyhow::Error),
 }
 
@@ -44,10 +47,12 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
     fn set_to_proved(
         &mut self,
         hash: Da::SlotHash,
-        proof: Result<Proof, anyhow::Error>,
+        proof: Result<(Proof, SlotProofOutput<StateRoot, Da>), anyhow::Error>,
     ) -> Option<ProverStatus<StateRoot, Witness, Da>> {
         match proof {
-            Ok(p) => self.prover_status.insert(hash, ProverStatus::Proved(p)),
+            Ok((p, outputs)) => self
+                .prover_status
+                .insert(hash, ProverStatus::Proved(p, outputs)),
             Err(e) => self.prover_status.insert(hash, ProverStatus::Err(e)),
         }
     }
@@ -106,14 +111,20 @@ where
     pub(crate) fn submit_witness(
         &self,
         state_transition_data: StateTransitionData<StateRoot, Witness, Da::Spec>,
+        public_outputs: SlotProofOutput<StateRoot, Da::Spec>,
     ) -> WitnessSubmissionStatus {
         let header_hash = state_transition_data.da_block_header.hash();
-        let data = ProverStatus::WitnessSubmitted(state_transition_data);
+        let data = ProverStatus::WitnessSubmitted(state_transition_data, public_outputs);
 
         let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
         let entry = prover_state.prover_status.entry(header_hash);
 
         match entry {
+            // The witness of a failed proof is replaced, so that the block can be proven again.
+            Entry::Occupied(mut o) if matches!(o.get(), ProverStatus::Err(_)) => {
+                o.insert(data);
+                WitnessSubmissionStatus::SubmittedForProving
+            }
             Entry::Occupied(_) => WitnessSubmissionStatus::WitnessExist,
             Entry::Vacant(v) => {
                 v.insert(data);
@@ -142,7 +153,7 @@ where
             .ok_or_else(|| anyhow::anyhow!("Missing witness for block: {:?}", block_header_hash))?;
 
         match prover_status {
-            ProverStatus::WitnessSubmitted(state_transition_data) => {
+            ProverStatus::WitnessSubmitted(state_transition_data, public_outputs) => {
                 let start_prover = prover_state.inc_task_count_if_not_busy(self.num_threads);
 
                 // Initiate a new proving job only if the prover is not busy.
@@ -152,7 +163,8 @@ where
 
                     self.pool.spawn(move || {
                         tracing::info_span!("guest_execution").in_scope(|| {
-                            let proof = make_proof(vm, config, zk_storage);
+                            let proof = make_proof(vm, config, zk_storage)
+                                .map(|proof| (proof, public_outputs));
 
                             let mut prover_state =
                                 prover_state_clone.write().expect("Lock was poisoned");
@@ -172,31 +184,31 @@ where
                 block_header_hash
             )
             .into()),
-            ProverStatus::Proved(_) => Err(anyhow::anyhow!(
-                "Witness for block_header_hash {:?}, submitted multiple times.",
-                block_header_hash,
-            )
-            .into()),
+            // The proof is kept until it has been posted to the DA, so proving the block
+            // again after a failed submission only retries the submission.
+            ProverStatus::Proved(proof, public_outputs) => {
+                prover_state.set_to_proved(block_header_hash, Ok((proof, public_outputs)));
+                Ok(ProofProcessingStatus::ProvingInProgress)
+            }
             ProverStatus::Err(e) => Err(e.into()),
         }
     }
 
-    pub(crate) fn get_proof_submission_status_and_remove_on_success(
+    /// Returns the proof for the given block together with its public outputs,
+    /// or `None` if the proof generation is still in progress.
+    pub(crate) fn get_proof(
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
-    ) -> Result<ProofSubmissionStatus, anyhow::Error> {
-        let mut prover_state = self.prover_state.write().unwrap();
+    ) -> Result<Option<(Proof, SlotProofOutput<StateRoot, Da::Spec>)>, anyhow::Error> {
+        let prover_state = self.prover_state.read().expect("Lock was poisoned");
         let status = prover_state.get_prover_status(block_header_hash.clone());
 
         match status {
-            Some(ProverStatus::ProvingInProgress) => {
-                Ok(ProofSubmissionStatus::ProofGenerationInProgress)
+            Some(ProverStatus::ProvingInProgress) => Ok(None),
+            Some(ProverStatus::Proved(proof, outputs)) => {
+                Ok(Some((proof.clone(), outputs.clone())))
             }
-            Some(ProverStatus::Proved(_)) => {
-                prover_state.remove(&block_header_hash);
-                Ok(ProofSubmissionStatus::Success)
-            }
-            Some(ProverStatus::WitnessSubmitted(_)) => Err(anyhow::anyhow!(
+            Some(ProverStatus::WitnessSubmitted(..)) => Err(anyhow::anyhow!(
                 "Witness for {:?} was submitted, but the proof generation is not triggered.",
                 block_header_hash
             )),
@@ -207,6 +219,12 @@ where
             )),
         }
     }
+
+    /// Forgets about the given block. Called once its proof has been posted to the DA.
+    pub(crate) fn remove(&self, block_header_hash: &<Da::Spec as DaSpec>::SlotHash) {
+        let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
+        prover_state.remove(block_header_hash);
+    }
 }
 
 fn make_proof<V, Vm, Da>(
@@ -1,18 +1,27 @@
+use std::collections::VecDeque;
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use anyhow::Context;
 use jsonrpsee::RpcModule;
 use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_db::schema::types::{DbHash, StoredProof, StoredProofStatus, StoredProverJob};
+use sov_db::sequencer_db::SequencerDB;
 use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::storage::StorageManager;
-use sov_rollup_interface::zk::ZkvmHost;
-use tokio::sync::oneshot;
-use tracing::{debug, info};
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_rollup_interface::zk::{Proof, ZkvmHost};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
+use crate::proof_pipeline::ProofPipeline;
+use crate::soft_confirmations::check_soft_confirmations;
 use crate::verifier::StateTransitionVerifier;
-use crate::{ProofSubmissionStatus, ProverService, RunnerConfig, StateTransitionData};
+use crate::{
+    ProverService, RunnerConfig, SlotProofOutput, StateTransitionData, StateTransitionProofEnvelope,
+};
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 type InitialState<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::GenesisParams;
 
@@ -21,18 +30,79 @@ pub struct StateTransitionRunner<Stf, Sm, Da, Vm, Ps>
 where
     Da: DaService,
     Vm: ZkvmHost,
-    Sm: StorageManager,
+    Sm: HierarchicalStorageManager<Da::Spec>,
     Stf: StateTransitionFunction<Vm, Da::Spec, Condition = <Da::Spec as DaSpec>::ValidityCondition>,
     Ps: ProverService,
 {
-    start_height: u64,
+    /// Height of the next DA block to be executed.
+    next_height: u64,
     da_service: Da,
     stf: Stf,
     storage_manager: Sm,
     ledger_db: LedgerDB,
-    state_root: StateRoot<Stf, Vm, Da::Spec>,
+    /// State root after the last executed block, `None` until the chain is initialized.
+    state_root: Option<StateRoot<Stf, Vm, Da::Spec>>,
+    /// Genesis parameters, if the chain has not been initialized yet.
+    /// Genesis is executed on top of the first processed DA block.
+    genesis_params: Option<InitialState<Stf, Vm, Da::Spec>>,
+    /// Executed slots which are not yet finalized on the DA layer, ordered by height.
+    unfinalized_slots: VecDeque<UnfinalizedSlot<Stf, Da, Vm>>,
+    /// Header of the last finalized slot processed by this runner, if any.
+    last_finalized_header: Option<<Da::Spec as DaSpec>::BlockHeader>,
     listen_address: SocketAddr,
-    prover_service: Ps,
+    /// Proves finalized slots. Moved to its own task once the runner starts.
+    proof_pipeline: Option<ProofPipeline<Ps>>,
+    /// Sends finalized slots to the proof pipeline. Bounded by the maximum proof backlog.
+    proof_sender: mpsc::Sender<DbHash>,
+    /// The task running the proof pipeline, once the runner has started.
+    proving_task: Option<JoinHandle<anyhow::Result<()>>>,
+    /// The database of the sequencer of this node, if it signs soft confirmations.
+    /// Finalized slots are checked against them.
+    sequencer_db: Option<SequencerDB>,
+    /// The commitment to the guest program proving the slots of this rollup.
+    /// Proofs published by other provers are only accepted if they verify against it.
+    code_commitment: Vm::CodeCommitment,
+}
+
+/// How long the runner waits before fetching again a DA block which conflicts with the
+/// finalized history.
+const CONFLICTING_BLOCK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How a DA block relates to the slots executed by the runner.
+enum ForkChoice {
+    /// The block is a child of the last executed slot.
+    Extends,
+    /// The block is on another fork, so the last unfinalized slot has to be reverted.
+    Reorg,
+    /// The block is on a fork which doesn't contain the last finalized slot.
+    ConflictsWithFinalized,
+}
+
+/// A slot that has been executed, but might still be reverted by the DA layer.
+struct UnfinalizedSlot<Stf, Da, Vm>
+where
+    Da: DaService,
+    Vm: ZkvmHost,
+    Stf: StateTransitionFunction<Vm, Da::Spec>,
+{
+    /// State root before this slot was applied. Used to roll back in case of a reorg.
+    pre_state_root: Stf::StateRoot,
+    /// The outputs which the proof of this slot commits to.
+    public_outputs: SlotProofOutput<Stf::StateRoot, Da::Spec>,
+    transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec>,
+    data_to_commit:
+        SlotCommit<Da::FilteredBlock, Stf::BatchReceiptContents, Stf::TxReceiptContents>,
+}
+
+impl<Stf, Da, Vm> UnfinalizedSlot<Stf, Da, Vm>
+where
+    Da: DaService,
+    Vm: ZkvmHost,
+    Stf: StateTransitionFunction<Vm, Da::Spec>,
+{
+    fn header(&self) -> &<Da::Spec as DaSpec>::BlockHeader {
+        &self.transition_data.da_block_header
+    }
 }
 
 /// Represents the possible modes of execution for a zkVM program
@@ -55,7 +125,7 @@ impl<Stf, Sm, Da, Vm, Ps> StateTransitionRunner<Stf, Sm, Da, Vm, Ps>
 where
     Da: DaService<Error = anyhow::Error> + Clone + Send + Sync + 'static,
     Vm: ZkvmHost,
-    Sm: StorageManager,
+    Sm: HierarchicalStorageManager<Da::Spec>,
     Stf: StateTransitionFunction<
         Vm,
         Da::Spec,
@@ -64,13 +134,17 @@ where
         ChangeSet = Sm::NativeChangeSet,
     >,
 
-    Ps: ProverService<StateRoot = Stf::StateRoot, Witness = Stf::Witness, DaService = Da>,
+    Ps: ProverService<StateRoot = Stf::StateRoot, Witness = Stf::Witness, DaService = Da>
+        + Send
+        + Sync
+        + 'static,
 {
     /// Creates a new `StateTransitionRunner`.
     ///
     /// If a previous state root is provided, uses that as the starting point
-    /// for execution. Otherwise, initializes the chain using the provided
-    /// genesis config.
+    /// for execution. Otherwise, the chain is initialized using the provided
+    /// genesis config on top of the first DA block processed by the runner.
+    /// Proofs posted to the DA layer by other provers are verified against `code_commitment`.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         runner_config: RunnerConfig,
@@ -81,43 +155,60 @@ where
         prev_state_root: Option<StateRoot<Stf, Vm, Da::Spec>>,
         genesis_config: InitialState<Stf, Vm, Da::Spec>,
         prover_service: Ps,
+        code_commitment: Vm::CodeCommitment,
     ) -> Result<Self, anyhow::Error> {
         let rpc_config = runner_config.rpc_config;
 
-        let prev_state_root = if let Some(prev_state_root) = prev_state_root {
+        let (state_root, genesis_params) = if let Some(prev_state_root) = prev_state_root {
             // Check if the rollup has previously been initialized
             debug!("Chain is already initialized. Skipping initialization.");
-            prev_state_root
+            (Some(prev_state_root), None)
         } else {
-            info!("No history detected. Initializing chain...");
-            let genesis_state = storage_manager.get_native_storage();
-            let (genesis_root, _) = stf.init_chain(genesis_state, genesis_config);
-            info!(
-                "Chain initialization is done. Genesis root: 0x{}",
-                hex::encode(genesis_root.as_ref())
-            );
-            genesis_root
+            info!("No history detected. Chain will be initialized on the first DA block.");
+            (None, Some(genesis_config))
         };
 
         let listen_address = SocketAddr::new(rpc_config.bind_host.parse()?, rpc_config.bind_port);
 
-        // Start the main rollup loop
+        anyhow::ensure!(
+            runner_config.max_proof_backlog > 0,
+            "The maximum proof backlog must be positive"
+        );
+        let (proof_sender, proof_receiver) = mpsc::channel(runner_config.max_proof_backlog);
+        let proof_pipeline = ProofPipeline::new(prover_service, ledger_db.clone(), proof_receiver);
+
+        // Slots which were not finalized before shutdown are reverted when the ledger is opened,
+        // so the rollup resumes from the first slot that has not been finalized.
         let item_numbers = ledger_db.get_next_items_numbers();
         let last_slot_processed_before_shutdown = item_numbers.slot_number - 1;
-        let start_height = runner_config.start_height + last_slot_processed_before_shutdown;
+        let next_height = runner_config.start_height + last_slot_processed_before_shutdown;
 
         Ok(Self {
-            start_height,
+            next_height,
             da_service,
             stf,
             storage_manager,
             ledger_db,
-            state_root: prev_state_root,
+            state_root,
+            genesis_params,
+            unfinalized_slots: VecDeque::new(),
+            last_finalized_header: None,
             listen_address,
-            prover_service,
+            proof_pipeline: Some(proof_pipeline),
+            proof_sender,
+            proving_task: None,
+            sequencer_db: None,
+            code_commitment,
         })
     }
 
+    /// Checks every finalized slot against the soft confirmations recorded in `sequencer_db`,
+    /// and reports the transactions whose inclusion differs from their confirmation.
+    pub fn with_soft_confirmations(mut self, sequencer_db: SequencerDB) -> Self {
+        self.sequencer_db = Some(sequencer_db);
+        self
+    }
+
     /// Starts a RPC server with provided rpc methods.
     pub async fn start_rpc_server(
         &self,
@@ -143,112 +234,417 @@ where
     }
 
     /// Runs the rollup.
+    ///
+    /// Blocks are executed as soon as they are available on the DA layer. If the DA layer
+    /// reorganizes, the runner discards the slots of the abandoned fork and re-executes the
+    /// new one, starting from the common ancestor. Slots are committed to the ledger as soon
+    /// as they are executed, and proven in the background once the DA layer reports them as
+    /// finalized. Prover jobs left unfinished by a previous run are resumed first.
     pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
-        for height in self.start_height.. {
-            debug!("Requesting data for height {}", height,);
-
-            loop {
-                match self.da_service.get_last_finalized_block_header().await {
-                    Ok(header) => {
-                        tracing::trace!("Last finalized height={}", header.height());
-                        if header.height() >= height {
-                            break;
-                        }
-                    }
-                    Err(err) => {
-                        tracing::info!("Error receiving last finalized block header: {:?}", err);
-                    }
-                }
+        if let Some(proof_pipeline) = self.proof_pipeline.take() {
+            self.proving_task = Some(tokio::spawn(proof_pipeline.run()));
+        }
+        self.resume_prover_jobs().await?;
 
-                tokio::time::sleep(Duration::from_millis(10)).await;
+        loop {
+            if self
+                .proving_task
+                .as_ref()
+                .map_or(false, |task| task.is_finished())
+            {
+                return Err(self.proving_task_error().await);
             }
-            // At this point we sure that the block request is finalized
 
-            // Assumes we are on chains with instant finality
+            let height = self.next_height;
+            debug!("Requesting data for height {}", height);
+
             let filtered_block = self.da_service.get_block_at(height).await?;
 
-            let mut blobs = self.da_service.extract_relevant_blobs(&filtered_block);
+            match self.fork_choice(filtered_block.header()) {
+                ForkChoice::Extends => {}
+                ForkChoice::Reorg => {
+                    self.revert_last_slot()?;
+                    continue;
+                }
+                ForkChoice::ConflictsWithFinalized => {
+                    // Finalized slots can't be reverted, so the block is fetched again
+                    // until the DA layer serves one extending the finalized history.
+                    warn!(
+                        "DA block at height {} does not extend the last finalized block {:?}, retrying in {:?}",
+                        height,
+                        self.last_finalized_header,
+                        CONFLICTING_BLOCK_RETRY_INTERVAL
+                    );
+                    tokio::time::sleep(CONFLICTING_BLOCK_RETRY_INTERVAL).await;
+                    continue;
+                }
+            }
+
+            self.process_block(filtered_block).await?;
+            self.next_height += 1;
+
+            self.finalize_slots().await?;
+        }
+    }
+
+    /// Checks whether given header is a child of the last processed block.
+    fn fork_choice(&self, header: &<Da::Spec as DaSpec>::BlockHeader) -> ForkChoice {
+        if let Some(last_slot) = self.unfinalized_slots.back() {
+            return if header.prev_hash() == last_slot.header().hash() {
+                ForkChoice::Extends
+            } else {
+                ForkChoice::Reorg
+            };
+        }
+
+        match &self.last_finalized_header {
+            Some(finalized_header) if header.prev_hash() != finalized_header.hash() => {
+                ForkChoice::ConflictsWithFinalized
+            }
+            _ => ForkChoice::Extends,
+        }
+    }
+
+    /// Discards the most recent unfinalized slot, so it is fetched and executed again.
+    fn revert_last_slot(&mut self) -> anyhow::Result<()> {
+        let reverted = self
+            .unfinalized_slots
+            .pop_back()
+            .ok_or_else(|| anyhow::anyhow!("Cannot revert: no unfinalized slots"))?;
+        let reverted_header = reverted.header();
+        warn!(
+            "DA reorg detected. Reverting slot at height {} with hash {:?}",
+            reverted_header.height(),
+            reverted_header.hash()
+        );
+        self.next_height = reverted_header.height();
+        self.state_root = Some(reverted.pre_state_root);
+        self.ledger_db.revert_executed_slot()
+    }
+
+    async fn process_block(&mut self, filtered_block: Da::FilteredBlock) -> anyhow::Result<()> {
+        let height = filtered_block.header().height();
 
+        let pre_state_root = if let Some(genesis_params) = self.genesis_params.take() {
             info!(
-                "Extracted {} relevant blobs at height {}: {:?}",
-                blobs.len(),
+                "Initializing chain on DA block at height {}: {:?}",
                 height,
-                blobs
-                    .iter()
-                    .map(|b| format!(
-                        "sequencer={} blob_hash=0x{}",
-                        b.sender(),
-                        hex::encode(b.hash())
-                    ))
-                    .collect::<Vec<_>>()
+                filtered_block.header()
+            );
+            let genesis_state = self
+                .storage_manager
+                .get_native_storage_on(filtered_block.header())?;
+            let (genesis_root, change_set) = self.stf.init_chain(genesis_state, genesis_params);
+            self.storage_manager
+                .save_change_set(filtered_block.header(), change_set)?;
+            // Genesis state does not depend on DA data, so it can be persisted right away.
+            self.storage_manager.finalize(filtered_block.header())?;
+            info!(
+                "Chain initialization is done. Genesis root: 0x{}",
+                hex::encode(genesis_root.as_ref())
             );
+            genesis_root
+        } else {
+            self.state_root
+                .take()
+                .expect("State root is set once the chain is initialized")
+        };
+
+        let mut blobs = self.da_service.extract_relevant_blobs(&filtered_block);
+
+        info!(
+            "Extracted {} relevant blobs at height {}: {:?}",
+            blobs.len(),
+            height,
+            blobs
+                .iter()
+                .map(|b| format!(
+                    "sequencer={} blob_hash=0x{}",
+                    b.sender(),
+                    hex::encode(b.hash())
+                ))
+                .collect::<Vec<_>>()
+        );
+
+        let mut data_to_commit = SlotCommit::new(filtered_block.clone());
+
+        let pre_state = self
+            .storage_manager
+            .get_native_storage_on(filtered_block.header())?;
+        let validity_condition = filtered_block.validity_condition();
+        let slot_result = self.stf.apply_slot(
+            &pre_state_root,
+            pre_state,
+            Default::default(),
+            filtered_block.header(),
+            &validity_condition,
+            &mut blobs,
+        );
 
-            let mut data_to_commit = SlotCommit::new(filtered_block.clone());
+        self.storage_manager
+            .save_change_set(filtered_block.header(), slot_result.change_set)?;
 
-            let pre_state = self.storage_manager.get_native_storage();
-            let slot_result = self.stf.apply_slot(
-                &self.state_root,
-                pre_state,
-                Default::default(),
-                filtered_block.header(),
-                &filtered_block.validity_condition(),
-                &mut blobs,
+        // Proofs are posted alongside batches and are not executed by the state transition function.
+        for blob in blobs.iter_mut() {
+            self.process_proof_blob(blob)?;
+        }
+
+        for receipt in slot_result.batch_receipts {
+            data_to_commit.add_batch(receipt);
+        }
+
+        let (inclusion_proof, completeness_proof) = self
+            .da_service
+            .get_extraction_proof(&filtered_block, &blobs)
+            .await;
+
+        let transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec> =
+            StateTransitionData {
+                pre_state_root: pre_state_root.clone(),
+                da_block_header: filtered_block.header().clone(),
+                inclusion_proof,
+                completeness_proof,
+                blobs,
+                state_transition_witness: slot_result.witness,
+            };
+
+        let public_outputs = SlotProofOutput::<Stf::StateRoot, Da::Spec> {
+            pre_state_root: pre_state_root.clone(),
+            post_state_root: slot_result.state_root.clone(),
+            da_block_hash: filtered_block.header().hash(),
+            prev_da_block_hash: filtered_block.header().prev_hash(),
+            validity_condition,
+        };
+
+        // The slot is committed to the ledger right away, and reverted if the DA layer reorganizes.
+        self.ledger_db.put_executed_slot(data_to_commit.clone())?;
+        self.state_root = Some(slot_result.state_root);
+        self.unfinalized_slots.push_back(UnfinalizedSlot {
+            pre_state_root,
+            public_outputs,
+            transition_data,
+            data_to_commit,
+        });
+
+        Ok(())
+    }
+
+    /// Records a proof published on the DA layer by another prover, if the blob contains one.
+    ///
+    /// The proof satisfies the local prover job of the slot it proves, as long as that job is
+    /// not done yet, the proof verifies against the code commitment of this rollup, and the
+    /// outputs it proves match the local execution. The outputs and the code commitment
+    /// claimed by the envelope are not trusted, since anyone can post a proof blob.
+    fn process_proof_blob(
+        &self,
+        blob: &mut <Da::Spec as DaSpec>::BlobTransaction,
+    ) -> anyhow::Result<()> {
+        let envelope = match StateTransitionProofEnvelope::<
+            Stf::StateRoot,
+            Da::Spec,
+            Vm::CodeCommitment,
+        >::decode(blob.full_data())
+        {
+            Ok(Some(envelope)) => envelope,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                warn!(
+                    "Failed to decode proof blob 0x{}: {:?}",
+                    hex::encode(blob.hash()),
+                    e
+                );
+                return Ok(());
+            }
+        };
+
+        let Proof::Data(proof) = envelope.proof() else {
+            debug!(
+                "Ignoring empty proof in blob 0x{}",
+                hex::encode(blob.hash())
+            );
+            return Ok(());
+        };
+        let outputs = match Vm::verify_and_deserialize_output::<
+            SlotProofOutput<Stf::StateRoot, Da::Spec>,
+        >(proof, &self.code_commitment)
+        {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                warn!(
+                    "Invalid proof in blob 0x{}: {:?}",
+                    hex::encode(blob.hash()),
+                    e
+                );
+                return Ok(());
+            }
+        };
+
+        let slot_hash: DbHash = outputs.da_block_hash.clone().into();
+        let Some(job) = self.ledger_db.get_prover_job(&slot_hash)? else {
+            debug!(
+                "Ignoring proof of unknown slot 0x{} in blob 0x{}",
+                hex::encode(slot_hash),
+                hex::encode(blob.hash())
+            );
+            return Ok(());
+        };
+        if job.status.is_done() {
+            debug!(
+                "Slot 0x{} has already been proven, ignoring proof blob 0x{}",
+                hex::encode(slot_hash),
+                hex::encode(blob.hash())
             );
+            return Ok(());
+        }
 
-            for receipt in slot_result.batch_receipts {
-                data_to_commit.add_batch(receipt);
+        let (_, local_outputs): (
+            StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec>,
+            SlotProofOutput<Stf::StateRoot, Da::Spec>,
+        ) = bincode::deserialize(job.witness.as_ref())
+            .context("Failed to deserialize prover job")?;
+        // The slot hash matches, since the job has been looked up by it.
+        let matches_local_execution = local_outputs.pre_state_root.as_ref()
+            == outputs.pre_state_root.as_ref()
+            && local_outputs.post_state_root.as_ref() == outputs.post_state_root.as_ref()
+            && local_outputs.prev_da_block_hash == outputs.prev_da_block_hash
+            && local_outputs.validity_condition == outputs.validity_condition;
+        if !matches_local_execution {
+            warn!(
+                "Proof blob 0x{} of slot 0x{} diverges from the local execution: post_state_root=0x{} expected=0x{}",
+                hex::encode(blob.hash()),
+                hex::encode(slot_hash),
+                hex::encode(outputs.post_state_root.as_ref()),
+                hex::encode(local_outputs.post_state_root.as_ref()),
+            );
+            return Ok(());
+        }
+
+        info!(
+            "Found proof of slot 0x{} in blob 0x{}",
+            hex::encode(slot_hash),
+            hex::encode(blob.hash())
+        );
+        self.ledger_db.put_proof(
+            &slot_hash,
+            &StoredProof {
+                da_transaction_id: blob.hash().to_vec().into(),
+                proof: blob.full_data().to_vec().into(),
+            },
+        )
+    }
+
+    /// Finalizes, proves and commits to the ledger all executed slots
+    /// that the DA layer reports as finalized.
+    async fn finalize_slots(&mut self) -> anyhow::Result<()> {
+        let last_finalized_header = match self.da_service.get_last_finalized_block_header().await {
+            Ok(header) => header,
+            Err(err) => {
+                tracing::info!("Error receiving last finalized block header: {:?}", err);
+                return Ok(());
             }
+        };
+        tracing::trace!("Last finalized height={}", last_finalized_header.height());
 
-            let (inclusion_proof, completeness_proof) = self
-                .da_service
-                .get_extraction_proof(&filtered_block, &blobs)
-                .await;
-
-            let transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec> =
-                StateTransitionData {
-                    pre_state_root: self.state_root.clone(),
-                    da_block_header: filtered_block.header().clone(),
-                    inclusion_proof,
-                    completeness_proof,
-                    blobs,
-                    state_transition_witness: slot_result.witness,
-                };
-
-            // Create ZKP proof.
+        while let Some(slot) = self.unfinalized_slots.front() {
+            let header = slot.header();
+            if header.height() > last_finalized_header.height() {
+                break;
+            }
+            if header.height() == last_finalized_header.height()
+                && header.hash() != last_finalized_header.hash()
             {
-                let header_hash = transition_data.da_block_header.hash();
-                self.prover_service.submit_witness(transition_data).await;
-                // TODO(#1185): This section will be moved and called upon block finalization once we have fork management ready.
-                self.prover_service
-                    .prove(header_hash.clone())
-                    .await
-                    .expect("The proof creation should succeed");
-
-                loop {
-                    let status = self
-                        .prover_service
-                        .send_proof_to_da(header_hash.clone())
-                        .await;
-
-                    match status {
-                        Ok(ProofSubmissionStatus::Success) => {
-                            break;
-                        }
-                        // TODO(#1185): Add timeout handling.
-                        Ok(ProofSubmissionStatus::ProofGenerationInProgress) => {
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
-                        }
-                        // TODO(#1185): Add handling for DA submission errors.
-                        Err(e) => panic!("{:?}", e),
-                    }
-                }
+                // Our slot lies on a fork which has been abandoned,
+                // it will be reverted when the next block is fetched.
+                warn!(
+                    "Executed slot at height {} is not on the finalized fork",
+                    header.height()
+                );
+                break;
             }
-            let next_state_root = slot_result.state_root;
 
-            self.ledger_db.commit_slot(data_to_commit)?;
-            self.state_root = next_state_root;
+            let slot = self
+                .unfinalized_slots
+                .pop_front()
+                .expect("Slot has been checked above");
+            self.finalize_slot(slot).await?;
         }
 
         Ok(())
     }
+
+    async fn finalize_slot(&mut self, slot: UnfinalizedSlot<Stf, Da, Vm>) -> anyhow::Result<()> {
+        let UnfinalizedSlot {
+            public_outputs,
+            transition_data,
+            data_to_commit,
+            ..
+        } = slot;
+        let header = transition_data.da_block_header.clone();
+        debug!("Finalizing slot at height {}", header.height());
+
+        self.storage_manager.finalize(&header)?;
+
+        // The prover job is persisted together with the slot, so it can be resumed after a restart.
+        let slot_hash: DbHash = header.hash().into();
+        let witness = bincode::serialize(&(&transition_data, &public_outputs))
+            .context("Failed to serialize prover job")?;
+        self.ledger_db.put_prover_job(
+            &slot_hash,
+            &StoredProverJob {
+                da_height: header.height(),
+                status: StoredProofStatus::Pending,
+                failed_attempts: 0,
+                witness: witness.into(),
+            },
+        )?;
+        if let Some(sequencer_db) = &self.sequencer_db {
+            for divergence in check_soft_confirmations(sequencer_db, &data_to_commit)? {
+                warn!(
+                    height = header.height(),
+                    ?divergence,
+                    "Finalized slot diverges from soft confirmation"
+                );
+            }
+        }
+        self.ledger_db.finalize_executed_slot(&slot_hash)?;
+        self.last_finalized_header = Some(header);
+
+        self.queue_for_proving(slot_hash).await
+    }
+
+    /// Queues the prover jobs left unfinished by a previous run, in DA order.
+    async fn resume_prover_jobs(&mut self) -> anyhow::Result<()> {
+        for (slot_hash, job) in self.ledger_db.get_unfinished_prover_jobs()? {
+            info!(
+                "Resuming prover job for slot 0x{} at height {}",
+                hex::encode(slot_hash),
+                job.da_height
+            );
+            self.queue_for_proving(slot_hash).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends the slot to the proof pipeline, waiting for room in the backlog if it is full.
+    async fn queue_for_proving(&mut self, slot_hash: DbHash) -> anyhow::Result<()> {
+        if self.proof_sender.capacity() == 0 {
+            info!("Proof backlog is full, waiting for the prover to catch up");
+        }
+        if self.proof_sender.send(slot_hash).await.is_err() {
+            return Err(self.proving_task_error().await);
+        }
+        Ok(())
+    }
+
+    /// Returns the reason why the proof pipeline stopped.
+    async fn proving_task_error(&mut self) -> anyhow::Error {
+        match self.proving_task.take() {
+            Some(task) => match task.await {
+                Ok(Err(e)) => e.context("Proof pipeline failed"),
+                Ok(Ok(())) => anyhow::anyhow!("Proof pipeline stopped unexpectedly"),
+                Err(e) => anyhow::anyhow!("Proof pipeline panicked: {:?}", e),
+            },
+            None => anyhow::anyhow!("Proof pipeline is not running"),
+        }
+    }
 }
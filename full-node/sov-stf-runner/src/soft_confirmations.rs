@@ -0,0 +1,134 @@
+use serde::Serialize;
+use sov_db::ledger_db::SlotCommit;
+use sov_db::schema::types::SoftConfirmationPosition;
+use sov_db::sequencer_db::SequencerDB;
+use sov_rollup_interface::services::da::SlotData;
+
+/// A difference between the soft confirmations of the sequencer and a finalized slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SoftConfirmationDivergence {
+    /// A transaction confirmed after this one has been included first.
+    NotIncludedInOrder {
+        /// The hash of the transaction.
+        tx_hash: [u8; 32],
+        /// The position the transaction was confirmed at.
+        position: u64,
+    },
+    /// The transaction has been included in order, but its receipt or events differ
+    /// from the confirmed ones.
+    EffectMismatch {
+        /// The hash of the transaction.
+        tx_hash: [u8; 32],
+        /// The position the transaction was confirmed at.
+        position: u64,
+    },
+}
+
+/// Checks the transactions of a finalized slot against the soft confirmations recorded
+/// in the [`SequencerDB`], and deletes the confirmations which have been checked.
+/// Transactions which haven't been confirmed, such as the ones of other sequencers, are ignored.
+pub fn check_soft_confirmations<S: SlotData, B, T: Serialize>(
+    sequencer_db: &SequencerDB,
+    slot: &SlotCommit<S, B, T>,
+) -> anyhow::Result<Vec<SoftConfirmationDivergence>> {
+    let mut divergences = Vec::new();
+    for tx in slot
+        .batch_receipts()
+        .iter()
+        .flat_map(|batch| batch.tx_receipts.iter())
+    {
+        let Some((position, _)) = sequencer_db.get_soft_confirmation(&tx.tx_hash)? else {
+            continue;
+        };
+
+        // The confirmations preceding this transaction which are still recorded
+        // belong to transactions which haven't been included yet.
+        let mut confirmations = sequencer_db.take_soft_confirmations_up_to(position)?;
+        let (_, confirmation) = confirmations
+            .pop()
+            .expect("The confirmation of the transaction has been recorded");
+        for (SoftConfirmationPosition(position), skipped) in confirmations {
+            divergences.push(SoftConfirmationDivergence::NotIncludedInOrder {
+                tx_hash: skipped.tx_hash,
+                position,
+            });
+        }
+
+        if !confirmation.matches(tx) {
+            divergences.push(SoftConfirmationDivergence::EffectMismatch {
+                tx_hash: tx.tx_hash,
+                position: position.0,
+            });
+        }
+    }
+    Ok(divergences)
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_db::schema::types::StoredSoftConfirmation;
+    use sov_mock_da::MockBlock;
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+
+    use super::*;
+
+    fn tx_receipt(tx_hash: [u8; 32], receipt: u64) -> TransactionReceipt<u64> {
+        TransactionReceipt {
+            tx_hash,
+            body_to_save: None,
+            events: vec![Event::new("key", "value")],
+            receipt,
+        }
+    }
+
+    #[test]
+    fn reports_divergences_from_soft_confirmations() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let sequencer_db = SequencerDB::with_path(tmpdir.path()).unwrap();
+        for i in 0..4u8 {
+            let confirmed = tx_receipt([i; 32], 0);
+            let confirmation =
+                StoredSoftConfirmation::new([i; 32], &confirmed.receipt, confirmed.events);
+            sequencer_db
+                .put_soft_confirmation(SoftConfirmationPosition(i.into()), &confirmation)
+                .unwrap();
+        }
+
+        // The transaction confirmed at position 1 is missing, the one at position 2 has
+        // another receipt, and the transaction of another sequencer is ignored.
+        let mut slot = SlotCommit::<_, (), u64>::new(MockBlock::default());
+        slot.add_batch(BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts: vec![
+                tx_receipt([0; 32], 0),
+                tx_receipt([9; 32], 0),
+                tx_receipt([2; 32], 1),
+            ],
+            inner: (),
+        });
+
+        assert_eq!(
+            check_soft_confirmations(&sequencer_db, &slot).unwrap(),
+            vec![
+                SoftConfirmationDivergence::NotIncludedInOrder {
+                    tx_hash: [1; 32],
+                    position: 1,
+                },
+                SoftConfirmationDivergence::EffectMismatch {
+                    tx_hash: [2; 32],
+                    position: 2,
+                },
+            ]
+        );
+
+        // Only the confirmation of the transaction which hasn't been included is left.
+        assert!(sequencer_db
+            .get_soft_confirmation(&[2; 32])
+            .unwrap()
+            .is_none());
+        assert!(sequencer_db
+            .get_soft_confirmation(&[3; 32])
+            .unwrap()
+            .is_some());
+    }
+}
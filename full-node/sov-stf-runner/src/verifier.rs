@@ -7,15 +7,19 @@ use sov_rollup_interface::zk::{Zkvm, ZkvmGuest};
 
 use crate::StateTransitionData;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 /// Output of the verifier.
-pub struct StateTransitionOutput<StateRoot, SlotHash> {
+pub struct StateTransitionOutput<StateRoot, SlotHash, Condition> {
     /// The state root before the state transition
     pub pre_state_root: StateRoot,
     /// The state root after the state transition
     pub post_state_root: StateRoot,
     /// Da block hash
     pub da_block_hash: SlotHash,
+    /// The hash of the Da block preceding this one
+    pub prev_da_block_hash: SlotHash,
+    /// The condition on the DA layer which must hold for the state transition to be valid
+    pub validity_condition: Condition,
 }
 
 /// Verifies a state transition
@@ -68,6 +72,8 @@ where
             pre_state_root: data.pre_state_root,
             post_state_root: result.state_root,
             da_block_hash: data.da_block_header.hash(),
+            prev_da_block_hash: data.da_block_header.prev_hash(),
+            validity_condition,
         };
 
         zkvm.commit(&out);
@@ -0,0 +1,139 @@
+use sha2::Sha256;
+use sov_mock_da::{MockAddress, MockDaSpec, MockHash, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkGuest, MockZkvm};
+use sov_rollup_interface::zk::{StateTransition, ZkvmHost};
+use sov_stf_runner::aggregator::{aggregate_outputs, AggregationData, StateTransitionAggregator};
+use sov_stf_runner::SlotProofOutput;
+
+type Aggregator = StateTransitionAggregator<MockZkGuest, MockDaSpec, MockAddress, [u8; 32], Sha256>;
+
+const SLOT_CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([1; 32]);
+
+#[test]
+fn test_aggregates_consecutive_slots() -> Result<(), anyhow::Error> {
+    let slots = vec![
+        make_slot_output(1, [0; 32], [1; 32], true),
+        make_slot_output(2, [1; 32], [2; 32], false),
+        make_slot_output(3, [2; 32], [3; 32], true),
+    ];
+
+    let transition = run_aggregation(slots.iter().map(prove_slot).collect())?;
+
+    assert_eq!(
+        StateTransition {
+            initial_state_root: [0; 32],
+            final_state_root: [3; 32],
+            initial_slot_hash: MockHash::from([1; 32]),
+            slot_hash: MockHash::from([3; 32]),
+            rewarded_address: rewarded_address(),
+            // A single invalid slot invalidates the whole range.
+            validity_condition: MockValidityCond { is_valid: false },
+        },
+        transition
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rejects_discontinuous_slots() {
+    let slots = vec![
+        make_slot_output(1, [0; 32], [1; 32], true),
+        make_slot_output(2, [2; 32], [3; 32], true),
+    ];
+
+    let err = run_aggregation(slots.iter().map(prove_slot).collect()).unwrap_err();
+    assert!(
+        err.to_string().contains("starts from state root"),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_rejects_gap_between_slots() {
+    let slots = vec![
+        make_slot_output(1, [0; 32], [1; 32], true),
+        make_slot_output(3, [1; 32], [2; 32], true),
+    ];
+
+    let err = run_aggregation(slots.iter().map(prove_slot).collect()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("does not follow the previous slot"),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_rejects_proof_of_another_program() {
+    let slot = make_slot_output(1, [0; 32], [1; 32], true);
+    let log = bincode::serialize(&slot).unwrap();
+    let proof = MockProof {
+        program_id: MockCodeCommitment([2; 32]),
+        is_valid: true,
+        log: &log,
+    }
+    .encode_to_vec();
+
+    let err = run_aggregation(vec![proof]).unwrap_err();
+    assert!(
+        err.to_string().contains("Slot proof failed to verify"),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_rejects_empty_range() {
+    let result = aggregate_outputs::<MockDaSpec, MockAddress, [u8; 32], Sha256>(
+        Vec::new(),
+        rewarded_address(),
+    );
+    assert!(result.is_err());
+}
+
+fn run_aggregation(
+    slot_proofs: Vec<Vec<u8>>,
+) -> Result<StateTransition<MockDaSpec, MockAddress, [u8; 32]>, anyhow::Error> {
+    let mut vm = MockZkvm::default();
+    vm.add_hint(AggregationData {
+        slot_code_commitment: SLOT_CODE_COMMITMENT,
+        slot_proofs,
+        rewarded_address: rewarded_address(),
+    });
+
+    let guest = vm.simulate_with_hints();
+    Aggregator::new().run_aggregation(&guest)?;
+
+    Ok(bincode::deserialize(&guest.committed_outputs())?)
+}
+
+fn make_slot_output(
+    height: u8,
+    pre_state_root: [u8; 32],
+    post_state_root: [u8; 32],
+    is_valid: bool,
+) -> SlotProofOutput<[u8; 32], MockDaSpec> {
+    SlotProofOutput {
+        pre_state_root,
+        post_state_root,
+        da_block_hash: MockHash::from([height; 32]),
+        prev_da_block_hash: MockHash::from([height - 1; 32]),
+        validity_condition: MockValidityCond { is_valid },
+    }
+}
+
+fn prove_slot(output: &SlotProofOutput<[u8; 32], MockDaSpec>) -> Vec<u8> {
+    let log = bincode::serialize(output).unwrap();
+    MockProof {
+        program_id: SLOT_CODE_COMMITMENT,
+        is_valid: true,
+        log: &log,
+    }
+    .encode_to_vec()
+}
+
+fn rewarded_address() -> MockAddress {
+    MockAddress::from([9; 32])
+}
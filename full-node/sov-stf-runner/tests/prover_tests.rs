@@ -1,11 +1,15 @@
 use sov_mock_da::{
-    MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash, MockValidityCond,
+    MockAddress, MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash,
+    MockValidityCond,
 };
-use sov_mock_zkvm::MockZkvm;
+use sov_mock_zkvm::{MockCodeCommitment, MockZkvm};
+use sov_rollup_interface::da::BlobReaderTrait;
+use sov_rollup_interface::services::da::DaService;
 use sov_stf_runner::mock::MockStf;
 use sov_stf_runner::{
     ParallelProverService, ProofProcessingStatus, ProofSubmissionStatus, ProverService,
-    ProverServiceError, RollupProverConfig, StateTransitionData, WitnessSubmissionStatus,
+    ProverServiceError, PublishedProof, RollupProverConfig, SlotProofOutput, StateTransitionData,
+    StateTransitionProofEnvelope, WitnessSubmissionStatus,
 };
 
 #[tokio::test]
@@ -16,7 +20,10 @@ async fn test_sucesfull_prover_execution() -> Result<(), ProverServiceError> {
 
     let header_hash = MockHash::from([0; 32]);
     prover_service
-        .submit_witness(make_transition_data(header_hash))
+        .submit_witness(
+            make_transition_data(header_hash),
+            make_public_outputs(header_hash),
+        )
         .await;
     prover_service.prove(header_hash).await?;
     vm.make_proof();
@@ -36,6 +43,43 @@ async fn test_sucesfull_prover_execution() -> Result<(), ProverServiceError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_proof_is_published_to_da() -> Result<(), anyhow::Error> {
+    let TestProver {
+        prover_service,
+        vm,
+        da_service,
+        ..
+    } = make_new_prover_with_config(RollupProverConfig::Prove);
+
+    let header_hash = MockHash::from([0; 32]);
+    prover_service
+        .submit_witness(
+            make_transition_data(header_hash),
+            make_public_outputs(header_hash),
+        )
+        .await;
+    prover_service.prove(header_hash).await?;
+    vm.make_proof();
+    let published = wait_for_proof_proof_da_submission(header_hash, &prover_service)
+        .await
+        .expect("Proof should be published");
+
+    let envelope = StateTransitionProofEnvelope::<[u8; 0], MockDaSpec, MockCodeCommitment>::decode(
+        &published.blob,
+    )?
+    .expect("Published blob should contain a proof");
+    assert_eq!(&make_public_outputs(header_hash), envelope.public_outputs());
+    assert_eq!(&MockCodeCommitment([0; 32]), envelope.code_commitment());
+
+    let block = da_service.get_block_at(0).await?;
+    let mut blobs = da_service.extract_relevant_blobs(&block);
+    assert_eq!(1, blobs.len());
+    assert_eq!(published.blob.as_slice(), blobs[0].full_data());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     let TestProver {
@@ -50,7 +94,10 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     // Saturate the prover.
     for header_hash in header_hashes.clone() {
         prover_service
-            .submit_witness(make_transition_data(header_hash))
+            .submit_witness(
+                make_transition_data(header_hash),
+                make_public_outputs(header_hash),
+            )
             .await;
 
         let poof_processing_status = prover_service.prove(header_hash).await?;
@@ -70,7 +117,10 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     {
         let header_hash = MockHash::from([0; 32]);
         prover_service
-            .submit_witness(make_transition_data(header_hash))
+            .submit_witness(
+                make_transition_data(header_hash),
+                make_public_outputs(header_hash),
+            )
             .await;
 
         let status = prover_service.prove(header_hash).await?;
@@ -97,7 +147,10 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     {
         let header_hash = MockHash::from([(num_worker_threads + 1) as u8; 32]);
         prover_service
-            .submit_witness(make_transition_data(header_hash))
+            .submit_witness(
+                make_transition_data(header_hash),
+                make_public_outputs(header_hash),
+            )
             .await;
 
         let status = prover_service.prove(header_hash).await?;
@@ -126,7 +179,10 @@ async fn test_multiple_witness_submissions() -> Result<(), anyhow::Error> {
 
     let header_hash = MockHash::from([0; 32]);
     let submission_status = prover_service
-        .submit_witness(make_transition_data(header_hash))
+        .submit_witness(
+            make_transition_data(header_hash),
+            make_public_outputs(header_hash),
+        )
         .await;
 
     assert_eq!(
@@ -135,7 +191,10 @@ async fn test_multiple_witness_submissions() -> Result<(), anyhow::Error> {
     );
 
     let submission_status = prover_service
-        .submit_witness(make_transition_data(header_hash))
+        .submit_witness(
+            make_transition_data(header_hash),
+            make_public_outputs(header_hash),
+        )
         .await;
 
     assert_eq!(WitnessSubmissionStatus::WitnessExist, submission_status);
@@ -149,7 +208,10 @@ async fn test_generate_multiple_proofs_for_the_same_witness() -> Result<(), anyh
 
     let header_hash = MockHash::from([0; 32]);
     prover_service
-        .submit_witness(make_transition_data(header_hash))
+        .submit_witness(
+            make_transition_data(header_hash),
+            make_public_outputs(header_hash),
+        )
         .await;
 
     let status = prover_service.prove(header_hash).await?;
@@ -164,6 +226,7 @@ struct TestProver {
     prover_service:
         ParallelProverService<[u8; 0], Vec<u8>, MockDaService, MockZkvm, MockStf<MockValidityCond>>,
     vm: MockZkvm,
+    da_service: MockDaService,
     num_worker_threads: usize,
 }
 
@@ -176,33 +239,41 @@ async fn wait_for_proof_proof_da_submission(
         MockZkvm,
         MockStf<MockValidityCond>,
     >,
-) {
+) -> Option<PublishedProof> {
     for _ in 0..10 {
         let status = prover_service.send_proof_to_da(header_hash).await;
-        if let Ok(ProofSubmissionStatus::Success) = status {
-            return;
+        if let Ok(ProofSubmissionStatus::Success(published)) = status {
+            return published;
         }
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
     }
+    None
 }
 
 fn make_new_prover() -> TestProver {
+    make_new_prover_with_config(RollupProverConfig::Execute)
+}
+
+fn make_new_prover_with_config(prover_config: RollupProverConfig) -> TestProver {
     let num_threads = num_cpus::get();
     let vm = MockZkvm::default();
 
-    let prover_config = RollupProverConfig::Execute;
     let zk_stf = MockStf::<MockValidityCond>::default();
+    let da_service = MockDaService::new(MockAddress::default());
     let da_verifier = MockDaVerifier::default();
     TestProver {
         prover_service: ParallelProverService::new(
             vm.clone(),
             zk_stf,
+            da_service.clone(),
             da_verifier,
+            MockCodeCommitment([0; 32]),
             prover_config,
             (),
             num_threads,
         ),
         vm,
+        da_service,
         num_worker_threads: num_threads,
     }
 }
@@ -223,3 +294,13 @@ fn make_transition_data(
         state_transition_witness: vec![],
     }
 }
+
+fn make_public_outputs(header_hash: MockHash) -> SlotProofOutput<[u8; 0], MockDaSpec> {
+    SlotProofOutput {
+        pre_state_root: [],
+        post_state_root: [],
+        da_block_hash: header_hash,
+        prev_da_block_hash: [0; 32].into(),
+        validity_condition: MockValidityCond::default(),
+    }
+}
@@ -0,0 +1,597 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::StoredProofStatus;
+use sov_mock_da::{
+    MockAddress, MockBlob, MockBlock, MockBlockHeader, MockDaSpec, MockDaVerifier, MockHash,
+    MockValidityCond,
+};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+use sov_rollup_interface::rpc::{LedgerRpcProvider, QueryMode};
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::stf::{BatchReceipt, SlotResult, StateTransitionFunction};
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_rollup_interface::zk::{Proof, ProofEnvelope};
+use sov_state::config::Config;
+use sov_state::storage::{StorageKey, StorageValue};
+use sov_state::{
+    ArrayWitness, DefaultStorageSpec, OrderedReadsAndWrites, ProverStorage, Storage,
+    StorageInternalCache,
+};
+use sov_stf_runner::{
+    ProofProcessingStatus, ProofSubmissionStatus, ProverService, ProverServiceError, RpcConfig,
+    RunnerConfig, SlotProofOutput, StateTransitionData, StateTransitionProofEnvelope,
+    StateTransitionRunner, WitnessSubmissionStatus,
+};
+
+type S = DefaultStorageSpec;
+
+/// The code commitment the runner verifies published proofs against.
+const CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([7; 32]);
+
+#[tokio::test]
+async fn test_reorg_onto_another_fork() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir()?;
+    let da_service = ForkingDaService::default();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let mut runner = new_runner(&da_service, &ledger_db, tmpdir.path(), true)?;
+
+    // 0    1    2    3    4
+    // A -> B -> C
+    // \ -> D -> E -> F -> G
+    let block_a = make_block(0, 0, 1, 1);
+    let block_b = make_block(1, 1, 2, 2);
+    let block_c = make_block(2, 2, 3, 3);
+    let block_d = make_block(1, 1, 12, 20);
+    let block_e = make_block(2, 12, 13, 30);
+    let block_f = make_block(3, 13, 14, 40);
+    let block_g = make_block(4, 14, 15, 50);
+
+    let scenario = async {
+        da_service.push_block(block_a.clone());
+        da_service.push_block(block_b.clone());
+        da_service.push_block(block_c.clone());
+        // Slot numbers start at 1.
+        wait_for_executed_slot(&ledger_db, 3, block_c.header.hash).await;
+
+        // The slots of B and C are reverted, and the slots of the new fork executed on top of A.
+        da_service.fork_at(1);
+        da_service.push_block(block_d.clone());
+        da_service.push_block(block_e.clone());
+        da_service.push_block(block_f.clone());
+        da_service.finalize(3);
+        da_service.push_block(block_g.clone());
+        wait_for_finalized_slot(&ledger_db, 4).await
+    };
+
+    let finalized_slot = tokio::select! {
+        result = runner.run_in_process() => panic!("Runner stopped: {:?}", result),
+        slot = scenario => slot,
+    };
+
+    assert_eq!(finalized_slot.hash, <[u8; 32]>::from(block_f.header.hash));
+    // The reverted slots of B and C have been replaced in the ledger by the new fork.
+    let slot_d = ledger_db
+        .get_slot_by_number::<[u8; 32], ()>(2, QueryMode::Compact)?
+        .expect("The slot of D should be in the ledger");
+    assert_eq!(slot_d.hash, <[u8; 32]>::from(block_d.header.hash));
+    let expected_root = *execute_chain(&[block_a, block_d, block_e, block_f])?
+        .last()
+        .unwrap();
+    let batch = ledger_db
+        .get_batch_by_number::<[u8; 32], ()>(finalized_slot.batch_range.start, QueryMode::Compact)?
+        .expect("The batch of the finalized slot should be in the ledger");
+    assert_eq!(batch.custom_receipt, expected_root);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_block_conflicting_with_finalized_history() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir()?;
+    let da_service = ForkingDaService::default();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let mut runner = new_runner(&da_service, &ledger_db, tmpdir.path(), true)?;
+
+    let block_a = make_block(0, 0, 1, 1);
+    let conflicting_block = make_block(1, 9, 10, 20);
+    let block_b = make_block(1, 1, 2, 2);
+
+    let scenario = async {
+        da_service.push_block(block_a.clone());
+        da_service.finalize(0);
+        wait_for_finalized_slot(&ledger_db, 1).await;
+
+        // The runner waits for the DA layer to settle instead of stopping.
+        da_service.push_block(conflicting_block.clone());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        da_service.fork_at(1);
+        da_service.push_block(block_b.clone());
+        wait_for_executed_slot(&ledger_db, 2, block_b.header.hash).await;
+    };
+
+    tokio::select! {
+        result = runner.run_in_process() => panic!("Runner stopped: {:?}", result),
+        _ = scenario => {},
+    };
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proof_published_by_another_prover() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir()?;
+    let da_service = ForkingDaService::default();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    // The local prover never finishes, so the slot can only be proven by another prover.
+    let mut runner = new_runner(&da_service, &ledger_db, tmpdir.path(), false)?;
+
+    let block_a = make_block(0, 0, 1, 1);
+    let roots = execute_chain(&[block_a.clone()])?;
+    let outputs = SlotProofOutput::<[u8; 32], MockDaSpec> {
+        pre_state_root: roots[0],
+        post_state_root: roots[1],
+        da_block_hash: block_a.header.hash,
+        prev_da_block_hash: block_a.header.prev_hash,
+        validity_condition: MockValidityCond::default(),
+    };
+    let empty_proof = make_proof_blob(outputs.clone(), Proof::Empty)?;
+    let wrong_commitment_proof = make_proof_blob(
+        outputs.clone(),
+        mock_proof(&outputs, MockCodeCommitment([0; 32]))?,
+    )?;
+    let divergent_outputs = SlotProofOutput {
+        post_state_root: [0; 32],
+        ..outputs.clone()
+    };
+    let divergent_proof = make_proof_blob(
+        divergent_outputs.clone(),
+        mock_proof(&divergent_outputs, CODE_COMMITMENT)?,
+    )?;
+    let proof = make_proof_blob(outputs.clone(), mock_proof(&outputs, CODE_COMMITMENT)?)?;
+
+    let mut block_b = make_block(1, 1, 2, 2);
+    block_b.blobs = vec![
+        MockBlob::new(empty_proof, MockAddress::default(), [3; 32]),
+        MockBlob::new(wrong_commitment_proof, MockAddress::default(), [4; 32]),
+        MockBlob::new(divergent_proof, MockAddress::default(), [5; 32]),
+        MockBlob::new(proof.clone(), MockAddress::default(), [6; 32]),
+    ];
+
+    let slot_hash: [u8; 32] = block_a.header.hash.into();
+    let scenario = async {
+        da_service.push_block(block_a.clone());
+        da_service.finalize(0);
+        wait_for_finalized_slot(&ledger_db, 1).await;
+
+        da_service.push_block(block_b.clone());
+        for _ in 0..100 {
+            if let Some(proof) = ledger_db.get_proof_by_slot_hash(&slot_hash).unwrap() {
+                return proof;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("The published proof has not been recorded in time");
+    };
+
+    let recorded_proof = tokio::select! {
+        result = runner.run_in_process() => panic!("Runner stopped: {:?}", result),
+        proof = scenario => proof,
+    };
+
+    // Empty proofs, proofs of another program and proofs diverging from the local execution
+    // are ignored. Only the valid proof is recorded, and it completes the prover job.
+    assert_eq!(recorded_proof.proof.as_ref(), proof.as_slice());
+    assert_eq!(recorded_proof.da_transaction_id.as_ref(), &[6; 32]);
+    let job = ledger_db
+        .get_prover_job(&slot_hash)?
+        .expect("The prover job of the slot should be in the ledger");
+    assert_eq!(job.status, StoredProofStatus::Submitted);
+
+    Ok(())
+}
+
+type TestRunner = StateTransitionRunner<
+    SumStf,
+    ProverStorageManager<MockDaSpec, S>,
+    ForkingDaService,
+    MockZkvm,
+    NoopProverService,
+>;
+
+fn new_runner(
+    da_service: &ForkingDaService,
+    ledger_db: &LedgerDB,
+    path: &std::path::Path,
+    finishes_proofs: bool,
+) -> Result<TestRunner, anyhow::Error> {
+    let storage_manager = ProverStorageManager::<MockDaSpec, S>::new(Config {
+        path: path.to_path_buf(),
+    })?;
+    StateTransitionRunner::new(
+        RunnerConfig {
+            start_height: 0,
+            rpc_config: RpcConfig {
+                bind_host: "127.0.0.1".to_string(),
+                bind_port: 0,
+            },
+            max_proof_backlog: 16,
+        },
+        da_service.clone(),
+        ledger_db.clone(),
+        SumStf,
+        storage_manager,
+        None,
+        (),
+        NoopProverService { finishes_proofs },
+        CODE_COMMITMENT,
+    )
+}
+
+fn make_proof_blob(
+    public_outputs: SlotProofOutput<[u8; 32], MockDaSpec>,
+    proof: Proof,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let envelope: StateTransitionProofEnvelope<[u8; 32], MockDaSpec, MockCodeCommitment> =
+        ProofEnvelope::V1 {
+            public_outputs,
+            code_commitment: match &proof {
+                Proof::Data(data) => MockProof::decode(data)?.program_id,
+                Proof::Empty => CODE_COMMITMENT,
+            },
+            proof,
+        };
+    envelope.encode()
+}
+
+/// A valid proof of the given program, committing to `public_outputs`.
+fn mock_proof(
+    public_outputs: &SlotProofOutput<[u8; 32], MockDaSpec>,
+    program_id: MockCodeCommitment,
+) -> Result<Proof, anyhow::Error> {
+    let log = bincode::serialize(public_outputs)?;
+    let proof = MockProof {
+        program_id,
+        is_valid: true,
+        log: &log,
+    };
+    Ok(Proof::Data(proof.encode_to_vec()))
+}
+
+/// Executes the given blocks with a fresh storage, and returns the genesis state root
+/// followed by the state root after each block.
+fn execute_chain(blocks: &[MockBlock]) -> Result<Vec<[u8; 32]>, anyhow::Error> {
+    let tmpdir = tempfile::tempdir()?;
+    let mut storage_manager = ProverStorageManager::<MockDaSpec, S>::new(Config {
+        path: tmpdir.path().to_path_buf(),
+    })?;
+    let stf = SumStf;
+
+    let genesis_header = &blocks[0].header;
+    let genesis_state = storage_manager.get_native_storage_on(genesis_header)?;
+    let (mut state_root, change_set) =
+        <SumStf as StateTransitionFunction<MockZkvm, MockDaSpec>>::init_chain(
+            &stf,
+            genesis_state,
+            (),
+        );
+    storage_manager.save_change_set(genesis_header, change_set)?;
+    storage_manager.finalize(genesis_header)?;
+    let mut roots = vec![state_root];
+
+    for block in blocks {
+        let mut blobs = block.blobs.clone();
+        let pre_state = storage_manager.get_native_storage_on(&block.header)?;
+        let result = <SumStf as StateTransitionFunction<MockZkvm, MockDaSpec>>::apply_slot(
+            &stf,
+            &state_root,
+            pre_state,
+            Default::default(),
+            &block.header,
+            &block.validity_cond,
+            &mut blobs,
+        );
+        storage_manager.save_change_set(&block.header, result.change_set)?;
+        storage_manager.finalize(&block.header)?;
+        state_root = result.state_root;
+        roots.push(state_root);
+    }
+    Ok(roots)
+}
+
+async fn wait_for_executed_slot(ledger_db: &LedgerDB, number: u64, hash: MockHash) {
+    for _ in 0..100 {
+        let slot = ledger_db
+            .get_executed_slot::<[u8; 32], ()>(number, QueryMode::Compact)
+            .unwrap();
+        if slot.map_or(false, |slot| slot.hash == <[u8; 32]>::from(hash)) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Slot {} has not been executed in time", number);
+}
+
+async fn wait_for_finalized_slot(
+    ledger_db: &LedgerDB,
+    number: u64,
+) -> sov_rollup_interface::rpc::SlotResponse<[u8; 32], ()> {
+    for _ in 0..100 {
+        // Slots are only served as executed until they are finalized.
+        let executed = ledger_db
+            .get_executed_slot::<[u8; 32], ()>(number, QueryMode::Compact)
+            .unwrap();
+        if let (None, Some(slot)) = (
+            executed,
+            ledger_db
+                .get_slot_by_number::<[u8; 32], ()>(number, QueryMode::Compact)
+                .unwrap(),
+        ) {
+            return slot;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Slot {} has not been finalized in time", number);
+}
+
+fn make_block(height: u64, prev_hash: u8, hash: u8, data: u8) -> MockBlock {
+    MockBlock {
+        header: MockBlockHeader {
+            prev_hash: MockHash::from([prev_hash; 32]),
+            hash: MockHash::from([hash; 32]),
+            height,
+        },
+        validity_cond: MockValidityCond::default(),
+        blobs: vec![MockBlob::new(
+            vec![data],
+            MockAddress::default(),
+            [data; 32],
+        )],
+    }
+}
+
+/// A state transition function which adds the bytes of all the blobs to a counter in the state.
+/// Its batch receipts hold the state root after the slot.
+struct SumStf;
+
+impl SumStf {
+    fn sum_key() -> StorageKey {
+        StorageKey::from("sum")
+    }
+
+    fn commit_sum(
+        storage: &ProverStorage<S>,
+        sum: Option<u64>,
+        witness: &ArrayWitness,
+    ) -> [u8; 32] {
+        let mut cache = StorageInternalCache::default();
+        if let Some(sum) = sum {
+            cache.set(
+                &Self::sum_key(),
+                StorageValue::from(sum.to_be_bytes().to_vec()),
+            );
+        }
+        let (root, state_update) = storage
+            .compute_state_update(cache.into(), witness)
+            .expect("State update must succeed");
+        storage.commit(&state_update, &OrderedReadsAndWrites::default());
+        root.0
+    }
+}
+
+impl StateTransitionFunction<MockZkvm, MockDaSpec> for SumStf {
+    type StateRoot = [u8; 32];
+    type GenesisParams = ();
+    type PreState = ProverStorage<S>;
+    type ChangeSet = ProverStorage<S>;
+    type TxReceiptContents = ();
+    type BatchReceiptContents = [u8; 32];
+    type Witness = ArrayWitness;
+    type Condition = MockValidityCond;
+
+    fn init_chain(
+        &self,
+        genesis_state: Self::PreState,
+        _params: Self::GenesisParams,
+    ) -> (Self::StateRoot, Self::ChangeSet) {
+        let root = Self::commit_sum(&genesis_state, None, &ArrayWitness::default());
+        (root, genesis_state)
+    }
+
+    fn apply_slot<'a, I>(
+        &self,
+        _pre_state_root: &Self::StateRoot,
+        pre_state: Self::PreState,
+        witness: Self::Witness,
+        _slot_header: &<MockDaSpec as DaSpec>::BlockHeader,
+        _validity_condition: &<MockDaSpec as DaSpec>::ValidityCondition,
+        blobs: I,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    >
+    where
+        I: IntoIterator<Item = &'a mut <MockDaSpec as DaSpec>::BlobTransaction>,
+    {
+        let mut sum = pre_state
+            .get(&Self::sum_key(), &witness)
+            .map(|value| u64::from_be_bytes(value.value().try_into().unwrap()))
+            .unwrap_or_default();
+        for blob in blobs {
+            sum += blob
+                .full_data()
+                .iter()
+                .map(|byte| *byte as u64)
+                .sum::<u64>();
+        }
+        let state_root = Self::commit_sum(&pre_state, Some(sum), &witness);
+
+        SlotResult {
+            state_root,
+            change_set: pre_state,
+            batch_receipts: vec![BatchReceipt {
+                batch_hash: [0; 32],
+                tx_receipts: vec![],
+                inner: state_root,
+            }],
+            witness,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Chain {
+    blocks: Vec<MockBlock>,
+    finalized_height: Option<u64>,
+}
+
+/// A DA service whose blocks are pushed by the test, and which can switch to another fork.
+#[derive(Clone, Default)]
+struct ForkingDaService {
+    chain: Arc<Mutex<Chain>>,
+}
+
+impl ForkingDaService {
+    fn push_block(&self, block: MockBlock) {
+        let mut chain = self.chain.lock().unwrap();
+        assert_eq!(block.header.height, chain.blocks.len() as u64);
+        chain.blocks.push(block);
+    }
+
+    /// Discards the blocks from the given height on, so that another fork can be pushed.
+    fn fork_at(&self, height: u64) {
+        let mut chain = self.chain.lock().unwrap();
+        assert!(
+            chain
+                .finalized_height
+                .map_or(true, |finalized| finalized < height),
+            "Cannot fork below the finalized height"
+        );
+        chain.blocks.truncate(height as usize);
+    }
+
+    fn finalize(&self, height: u64) {
+        self.chain.lock().unwrap().finalized_height = Some(height);
+    }
+}
+
+#[async_trait]
+impl DaService for ForkingDaService {
+    type Spec = MockDaSpec;
+    type Verifier = MockDaVerifier;
+    type FilteredBlock = MockBlock;
+    type HeaderStream =
+        futures::stream::Pending<Result<<MockDaSpec as DaSpec>::BlockHeader, anyhow::Error>>;
+    type TransactionId = ();
+    type Error = anyhow::Error;
+
+    async fn get_block_at(&self, height: u64) -> Result<Self::FilteredBlock, Self::Error> {
+        loop {
+            let block = self
+                .chain
+                .lock()
+                .unwrap()
+                .blocks
+                .get(height as usize)
+                .cloned();
+            if let Some(block) = block {
+                return Ok(block);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    async fn get_last_finalized_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let chain = self.chain.lock().unwrap();
+        chain
+            .finalized_height
+            .and_then(|height| chain.blocks.get(height as usize))
+            .map(|block| block.header)
+            .ok_or_else(|| anyhow::anyhow!("No block is finalized yet"))
+    }
+
+    async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
+        Ok(futures::stream::pending())
+    }
+
+    async fn get_head_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let chain = self.chain.lock().unwrap();
+        chain
+            .blocks
+            .last()
+            .map(|block| block.header)
+            .ok_or_else(|| anyhow::anyhow!("The chain is empty"))
+    }
+
+    fn extract_relevant_blobs(
+        &self,
+        block: &Self::FilteredBlock,
+    ) -> Vec<<Self::Spec as DaSpec>::BlobTransaction> {
+        block.blobs.clone()
+    }
+
+    async fn get_extraction_proof(
+        &self,
+        _block: &Self::FilteredBlock,
+        _blobs: &[<Self::Spec as DaSpec>::BlobTransaction],
+    ) -> (
+        <Self::Spec as DaSpec>::InclusionMultiProof,
+        <Self::Spec as DaSpec>::CompletenessProof,
+    ) {
+        ([0u8; 32], ())
+    }
+
+    async fn send_transaction(&self, _blob: &[u8]) -> Result<Self::TransactionId, Self::Error> {
+        anyhow::bail!("The chain of this DA service is driven by the test")
+    }
+}
+
+/// A prover service which doesn't publish proofs. It either reports every slot as proven
+/// right away, or never finishes proving.
+struct NoopProverService {
+    finishes_proofs: bool,
+}
+
+#[async_trait]
+impl ProverService for NoopProverService {
+    type StateRoot = [u8; 32];
+    type Witness = ArrayWitness;
+    type DaService = ForkingDaService;
+
+    async fn submit_witness(
+        &self,
+        _state_transition_data: StateTransitionData<Self::StateRoot, Self::Witness, MockDaSpec>,
+        _public_outputs: SlotProofOutput<Self::StateRoot, MockDaSpec>,
+    ) -> WitnessSubmissionStatus {
+        WitnessSubmissionStatus::SubmittedForProving
+    }
+
+    async fn prove(
+        &self,
+        _block_header_hash: MockHash,
+    ) -> Result<ProofProcessingStatus, ProverServiceError> {
+        Ok(ProofProcessingStatus::ProvingInProgress)
+    }
+
+    async fn send_proof_to_da(
+        &self,
+        _block_header_hash: MockHash,
+    ) -> Result<ProofSubmissionStatus, anyhow::Error> {
+        if self.finishes_proofs {
+            Ok(ProofSubmissionStatus::Success(None))
+        } else {
+            Ok(ProofSubmissionStatus::ProofGenerationInProgress)
+        }
+    }
+}
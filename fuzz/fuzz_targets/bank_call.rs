@@ -14,7 +14,7 @@ fuzz_target!(|input: (&[u8], [u8; 32])| {
     if let Ok(msgs) = serde_json::from_slice::<Vec<CallMessage<C>>>(data) {
         let tmpdir = tempfile::tempdir().unwrap();
         let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
-        let ctx = C::new(sender.into(), 1);
+        let ctx = C::new(sender.into(), Vec::new(), [0; 32], 1);
         let bank = Bank::default();
         for msg in msgs {
             bank.call(msg, &ctx, &mut working_set).ok();
@@ -1,10 +1,13 @@
 #![cfg(feature = "native")]
 
 use sov_accessory_state::{AccessorySetter, CallMessage};
+use sov_mock_da::{MockBlockHeader, MockDaSpec};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::prelude::*;
+use sov_modules_api::storage::HierarchicalStorageManager;
 use sov_modules_api::{Address, Context, Module, WorkingSet};
-use sov_state::{ProverStorage, Storage};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_state::{DefaultStorageSpec, Storage};
 
 #[test]
 /// Check that:
@@ -12,13 +15,20 @@ use sov_state::{ProverStorage, Storage};
 /// 2. Accessory state is saved to underlying the database
 fn test_accessory_value_setter() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        })
+        .unwrap();
+    let storage = storage_manager
+        .get_native_storage_on(&MockBlockHeader::default())
+        .unwrap();
     let mut working_set_for_state = WorkingSet::new(storage.clone());
     let mut working_set_for_accessory = WorkingSet::new(storage.clone());
     let mut working_set_for_check: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
 
     let admin = Address::from([1; 32]);
-    let context = DefaultContext::new(admin, 1);
+    let context = DefaultContext::new(admin, Vec::new(), [0; 32], 1);
 
     let module = AccessorySetter::<DefaultContext>::default();
 
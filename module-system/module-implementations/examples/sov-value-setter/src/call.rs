@@ -25,6 +25,22 @@ pub enum CallMessage {
     ),
 }
 
+/// This enumeration represents the events emitted by the `sov-value-setter` module.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum Event {
+    /// The value was set.
+    ValueSet {
+        /// new value
+        value: u32,
+    },
+}
+
 /// Example of a custom error.
 #[derive(Debug, Error)]
 enum SetValueError {
@@ -50,7 +66,7 @@ impl<C: sov_modules_api::Context> ValueSetter<C> {
 
         // This is how we set a new value:
         self.value.set(&new_value, working_set);
-        working_set.add_event("set", &format!("value_set: {new_value:?}"));
+        working_set.emit_event(self, Event::ValueSet { value: new_value });
 
         Ok(CallResponse::default())
     }
@@ -1,5 +1,5 @@
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
-use sov_modules_api::{Address, Context, Event, Module, WorkingSet};
+use sov_modules_api::{module_event, Address, Context, Module, WorkingSet};
 use sov_state::{ProverStorage, ZkStorage};
 
 use super::ValueSetter;
@@ -14,7 +14,7 @@ fn test_value_setter() {
     #[cfg(feature = "native")]
     {
         let config = ValueSetterConfig { admin };
-        let context = DefaultContext::new(admin, 1);
+        let context = DefaultContext::new(admin, Vec::new(), [0; 32], 1);
         test_value_setter_helper(context, &config, &mut working_set);
     }
 
@@ -23,7 +23,7 @@ fn test_value_setter() {
     // Test Zk-Context
     {
         let config = ValueSetterConfig { admin };
-        let zk_context = ZkDefaultContext::new(admin, 1);
+        let zk_context = ZkDefaultContext::new(admin, Vec::new(), [0; 32], 1);
         let mut zk_working_set = WorkingSet::with_witness(ZkStorage::new(), witness);
         test_value_setter_helper(zk_context, &config, &mut zk_working_set);
     }
@@ -44,7 +44,10 @@ fn test_value_setter_helper<C: Context>(
     {
         module.call(call_msg, &context, working_set).unwrap();
         let event = &working_set.events()[0];
-        assert_eq!(event, &Event::new("set", "value_set: 99"));
+        assert_eq!(
+            event,
+            &module_event(&module, &call::Event::ValueSet { value: 99 })
+        );
     }
 
     // Test query
@@ -75,7 +78,7 @@ fn test_err_on_sender_is_not_admin() {
         let config = ValueSetterConfig {
             admin: sender_not_admin,
         };
-        let context = DefaultContext::new(sender, 1);
+        let context = DefaultContext::new(sender, Vec::new(), [0; 32], 1);
         test_err_on_sender_is_not_admin_helper(context, &config, &mut native_working_set);
     }
     let (_, witness) = native_working_set.checkpoint().freeze();
@@ -86,7 +89,7 @@ fn test_err_on_sender_is_not_admin() {
             admin: sender_not_admin,
         };
         let zk_backing_store = ZkStorage::new();
-        let zk_context = ZkDefaultContext::new(sender, 1);
+        let zk_context = ZkDefaultContext::new(sender, Vec::new(), [0; 32], 1);
         let zk_working_set = &mut WorkingSet::with_witness(zk_backing_store, witness);
         test_err_on_sender_is_not_admin_helper(zk_context, &config, zk_working_set);
     }
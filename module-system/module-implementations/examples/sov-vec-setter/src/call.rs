@@ -34,6 +34,43 @@ pub enum CallMessage {
     PopValue,
 }
 
+/// This enumeration represents the events emitted by the `sov-vec-setter` module.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize),
+    derive(serde::Deserialize),
+    derive(schemars::JsonSchema)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum Event {
+    /// A value was pushed
+    ValuePushed {
+        /// pushed value
+        value: u32,
+        /// length of the vector after the push
+        new_length: usize,
+    },
+    /// A value was set
+    ValueSet {
+        /// index of the value
+        index: usize,
+        /// new value
+        value: u32,
+    },
+    /// All values were set
+    AllValuesSet {
+        /// length of the vector after the values were set
+        new_length: usize,
+    },
+    /// A value was popped
+    ValuePopped {
+        /// popped value, if the vector was not empty
+        value: Option<u32>,
+        /// length of the vector after the pop
+        new_length: usize,
+    },
+}
+
 /// Example of a custom error.
 #[derive(Debug, Error)]
 enum SetValueError {
@@ -62,9 +99,12 @@ impl<C: sov_modules_api::Context> VecSetter<C> {
 
         let new_length = self.vector.len(working_set);
 
-        working_set.add_event(
-            "push",
-            &format!("value_push: {new_value:?}, new length: {new_length:?}"),
+        working_set.emit_event(
+            self,
+            Event::ValuePushed {
+                value: new_value,
+                new_length,
+            },
         );
 
         Ok(CallResponse::default())
@@ -89,9 +129,12 @@ impl<C: sov_modules_api::Context> VecSetter<C> {
         // This is how we set a new value:
         self.vector.set(index, &new_value, working_set)?;
 
-        working_set.add_event(
-            "set",
-            &format!("value_set: {new_value:?} for index: {index:?}"),
+        working_set.emit_event(
+            self,
+            Event::ValueSet {
+                index,
+                value: new_value,
+            },
         );
 
         Ok(CallResponse::default())
@@ -117,7 +160,7 @@ impl<C: sov_modules_api::Context> VecSetter<C> {
 
         let new_length = self.vector.len(working_set);
 
-        working_set.add_event("set_all", &format!("new length: {new_length:?}"));
+        working_set.emit_event(self, Event::AllValuesSet { new_length });
 
         Ok(CallResponse::default())
     }
@@ -141,9 +184,12 @@ impl<C: sov_modules_api::Context> VecSetter<C> {
 
         let new_length = self.vector.len(working_set);
 
-        working_set.add_event(
-            "pop",
-            &format!("value_pop: {pop_value:?}, new length: {new_length:?}"),
+        working_set.emit_event(
+            self,
+            Event::ValuePopped {
+                value: pop_value,
+                new_length,
+            },
         );
 
         Ok(CallResponse::default())
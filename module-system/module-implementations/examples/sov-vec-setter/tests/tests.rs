@@ -40,7 +40,7 @@ fn test_vec_setter_calls() {
     vec_setter.genesis(&config, &mut working_set).unwrap();
 
     for (sender, call, expected_contents) in test_cases().iter().cloned() {
-        let context = DefaultContext::new(sender, 1);
+        let context = DefaultContext::new(sender, Vec::new(), [0; 32], 1);
 
         let call_result = vec_setter.call(call, &context, &mut working_set);
 
@@ -0,0 +1,69 @@
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::WorkingSet;
+use sov_modules_stf_blueprint::kernels::base_fee::{
+    next_base_fee, BaseFeeOracle, INITIAL_BASE_FEE, TARGET_GAS_PER_SLOT,
+};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
+use sov_state::{DefaultStorageSpec, ProverStorage};
+
+type C = DefaultContext;
+
+#[test]
+fn test_next_base_fee() {
+    // On target, or without any target, the base fee doesn't move.
+    assert_eq!(100, next_base_fee(100, 50, 50));
+    assert_eq!(100, next_base_fee(100, 50, 0));
+    // The change is proportional to the distance from the target, up to 1/8th of the base fee.
+    assert_eq!(112, next_base_fee(100, 100, 50));
+    assert_eq!(106, next_base_fee(100, 75, 50));
+    assert_eq!(88, next_base_fee(100, 0, 50));
+    // A congested slot always raises the base fee, even from zero.
+    assert_eq!(1, next_base_fee(0, 100, 50));
+    assert_eq!(u64::MAX, next_base_fee(u64::MAX, 100, 50));
+}
+
+/// Checks that the base fee of the kernel follows the gas used by every slot, dimension by dimension.
+#[test]
+fn test_base_fee_moves_toward_target() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage: ProverStorage<DefaultStorageSpec> =
+        ProverStorage::with_path(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    let kernel = BasicKernel::<C, MockDaSpec>::default();
+    let oracle = BaseFeeOracle::<C>::default();
+    assert_eq!(INITIAL_BASE_FEE, kernel.base_fee(&mut working_set));
+
+    // The first dimension is used twice as much as its target, the second one isn't used.
+    kernel.record_gas_used(&[TARGET_GAS_PER_SLOT[0], 0], &mut working_set);
+    kernel.record_gas_used(&[TARGET_GAS_PER_SLOT[0], 0], &mut working_set);
+    assert_eq!(
+        [2 * TARGET_GAS_PER_SLOT[0], 0],
+        oracle.get_gas_used_in_slot(&mut working_set)
+    );
+
+    kernel.end_slot_hook(&mut working_set);
+    let base_fee = [
+        next_base_fee(
+            INITIAL_BASE_FEE[0],
+            2 * TARGET_GAS_PER_SLOT[0],
+            TARGET_GAS_PER_SLOT[0],
+        ),
+        next_base_fee(INITIAL_BASE_FEE[1], 0, TARGET_GAS_PER_SLOT[1]),
+    ];
+    assert!(base_fee[0] > INITIAL_BASE_FEE[0]);
+    assert_eq!(base_fee, kernel.base_fee(&mut working_set));
+    assert_eq!([0, 0], oracle.get_gas_used_in_slot(&mut working_set));
+
+    // An empty slot lowers the base fee again.
+    kernel.end_slot_hook(&mut working_set);
+    assert_eq!(
+        [
+            next_base_fee(base_fee[0], 0, TARGET_GAS_PER_SLOT[0]),
+            next_base_fee(base_fee[1], 0, TARGET_GAS_PER_SLOT[1]),
+        ],
+        kernel.base_fee(&mut working_set)
+    );
+}
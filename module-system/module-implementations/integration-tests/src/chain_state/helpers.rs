@@ -4,18 +4,111 @@ use sov_modules_api::macros::DefaultRuntime;
 use sov_modules_api::runtime::capabilities::{BlobRefOrOwned, BlobSelector};
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    AccessoryWorkingSet, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, MessageCodec,
-    PublicKey, Spec,
+    AccessoryWorkingSet, BlobReaderTrait, CallResponse, Context, DaSpec, DispatchCall, Error,
+    Genesis, MessageCodec, Module, ModuleInfo, PublicKey, Spec, StateMap, StateMapAccessor,
+    StateValue, StateValueAccessor, WorkingSet,
 };
 use sov_modules_stf_blueprint::{Runtime, SequencerOutcome};
 use sov_state::Storage;
 use sov_value_setter::{ValueSetter, ValueSetterConfig};
 
+/// The context seen by the call handler of a module.
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub(crate) struct RecordedContext {
+    pub slot_height: u64,
+    pub tx_hash: [u8; 32],
+    pub sequencer: Vec<u8>,
+}
+
+/// A module which records the context of the last transaction calling it.
+#[derive(ModuleInfo)]
+pub(crate) struct ContextRecorder<C: Context> {
+    #[address]
+    pub address: C::Address,
+
+    #[state]
+    pub last_context: StateValue<RecordedContext>,
+}
+
+impl<C: Context> Module for ContextRecorder<C> {
+    type Context = C;
+    type Config = ();
+    type CallMessage = ();
+    type Event = ();
+
+    fn call(
+        &self,
+        _message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let recorded = RecordedContext {
+            slot_height: context.slot_height(),
+            tx_hash: *context.tx_hash(),
+            sequencer: context.sequencer().to_vec(),
+        };
+        self.last_context.set(&recorded, working_set);
+        Ok(CallResponse::default())
+    }
+}
+
+/// A module which keeps track of the fees reserved by the transactions and of the nonces of
+/// their senders, in place of the bank and accounts modules.
+#[derive(ModuleInfo)]
+pub(crate) struct FeeRecorder<C: Context> {
+    #[address]
+    pub address: C::Address,
+
+    /// The fees reserved before the dispatch which have been neither settled nor refunded.
+    #[state]
+    pub reserved_fees: StateMap<C::Address, u64>,
+
+    /// The number of transactions of each sender whose fee has been settled or refunded.
+    #[state]
+    pub nonces: StateMap<C::Address, u64>,
+}
+
+impl<C: Context> Module for FeeRecorder<C> {
+    type Context = C;
+    type Config = ();
+    type CallMessage = ();
+    type Event = ();
+
+    fn call(
+        &self,
+        _message: Self::CallMessage,
+        _context: &Self::Context,
+        _working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(CallResponse::default())
+    }
+}
+
+impl<C: Context> FeeRecorder<C> {
+    fn release_fee(
+        &self,
+        tx: &Transaction<C>,
+        sender: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let reserved = self
+            .reserved_fees
+            .get(sender, working_set)
+            .unwrap_or_default();
+        self.reserved_fees
+            .set(sender, &(reserved - tx.fee().max_fee), working_set);
+        let nonce = self.nonces.get(sender, working_set).unwrap_or_default();
+        self.nonces.set(sender, &(nonce + 1), working_set);
+    }
+}
+
 #[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
 #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
 pub(crate) struct TestRuntime<C: Context, Da: DaSpec> {
     pub value_setter: ValueSetter<C>,
     pub chain_state: ChainState<C, Da>,
+    pub context_recorder: ContextRecorder<C>,
+    pub fee_recorder: FeeRecorder<C>,
 }
 
 impl<C: Context, Da: DaSpec> TxHooks for TestRuntime<C, Da> {
@@ -24,16 +117,41 @@ impl<C: Context, Da: DaSpec> TxHooks for TestRuntime<C, Da> {
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
-        _working_set: &mut sov_modules_api::WorkingSet<C>,
+        _base_fee: &C::GasUnit,
+        working_set: &mut sov_modules_api::WorkingSet<C>,
     ) -> anyhow::Result<<Self::Context as Spec>::Address> {
-        Ok(tx.pub_key().to_address())
+        let sender = tx.pub_key().to_address();
+        let reserved = self
+            .fee_recorder
+            .reserved_fees
+            .get(&sender, working_set)
+            .unwrap_or_default();
+        self.fee_recorder
+            .reserved_fees
+            .set(&sender, &(reserved + tx.fee().max_fee), working_set);
+        Ok(sender)
     }
 
     fn post_dispatch_tx_hook(
         &self,
-        _tx: &Transaction<Self::Context>,
-        _working_set: &mut sov_modules_api::WorkingSet<C>,
+        tx: &Transaction<Self::Context>,
+        ctx: &C,
+        _gas_meter: &sov_modules_api::GasMeter<C::GasUnit>,
+        working_set: &mut sov_modules_api::WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        // There is no gas token in this runtime, so any fee offered by the sender can't be settled.
+        anyhow::ensure!(tx.fee().max_fee == 0, "The fee can't be settled");
+        self.fee_recorder.release_fee(tx, ctx.sender(), working_set);
+        Ok(())
+    }
+
+    fn refund_tx_hook(
+        &self,
+        tx: &Transaction<Self::Context>,
+        ctx: &C,
+        working_set: &mut sov_modules_api::WorkingSet<C>,
     ) -> anyhow::Result<()> {
+        self.fee_recorder.release_fee(tx, ctx.sender(), working_set);
         Ok(())
     }
 }
@@ -114,7 +232,10 @@ where
 impl<C: Context, Da: DaSpec> Runtime<C, Da> for TestRuntime<C, Da> {
     type GenesisConfig = GenesisConfig<C, Da>;
 
-    fn rpc_methods(_storage: <C as Spec>::Storage) -> jsonrpsee::RpcModule<()> {
+    fn rpc_methods(
+        _storage: <C as Spec>::Storage,
+        _slot_resolver: std::sync::Arc<dyn sov_modules_api::rpc::SlotResolver>,
+    ) -> jsonrpsee::RpcModule<()> {
         todo!()
     }
 
@@ -132,8 +253,9 @@ pub(crate) fn create_chain_state_genesis_config<C: Context, Da: DaSpec>(
 ) -> GenesisConfig<C, Da> {
     let value_setter_config = ValueSetterConfig { admin };
     let chain_state_config = ChainStateConfig {
+        chain_id: 0,
         initial_slot_height: 0,
         current_time: Default::default(),
     };
-    GenesisConfig::new(value_setter_config, chain_state_config)
+    GenesisConfig::new(value_setter_config, chain_state_config, (), ())
 }
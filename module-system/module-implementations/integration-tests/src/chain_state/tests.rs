@@ -1,18 +1,26 @@
+use borsh::BorshSerialize;
 use sov_chain_state::{StateTransitionId, TransitionInProgress};
 use sov_data_generators::value_setter_data::ValueSetterMessages;
 use sov_data_generators::{has_tx_events, new_test_blob_from_batch, MessageGenerator};
 use sov_mock_da::{MockBlock, MockBlockHeader, MockDaSpec, MockHash, MockValidityCond};
 use sov_mock_zkvm::MockZkvm;
 use sov_modules_api::default_context::DefaultContext;
-use sov_modules_api::storage::StorageManager;
-use sov_modules_api::{Spec, WorkingSet};
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::storage::{HierarchicalStorageManager, StorageManager};
+use sov_modules_api::transaction::{Transaction, TxFee};
+use sov_modules_api::{
+    EncodeCall, PrivateKey, Spec, StateMapAccessor, StateValueAccessor, WorkingSet,
+};
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
-use sov_modules_stf_blueprint::{SequencerOutcome, StfBlueprint};
+use sov_modules_stf_blueprint::{Batch, RawTx, SequencerOutcome, StfBlueprint, TxEffect};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::digest::Digest;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_state::storage_manager::ProverStorageManager;
-use sov_state::Storage;
+use sov_state::{DefaultStorageSpec, Storage};
 
-use crate::chain_state::helpers::{create_chain_state_genesis_config, TestRuntime};
+use crate::chain_state::helpers::{
+    create_chain_state_genesis_config, ContextRecorder, RecordedContext, TestRuntime,
+};
 
 type C = DefaultContext;
 
@@ -25,13 +33,19 @@ fn test_simple_value_setter_with_chain_state() {
 
     let tmpdir = tempfile::tempdir().unwrap();
 
-    let storage_manager = ProverStorageManager::new(sov_state::config::Config {
-        path: tmpdir.path().to_path_buf(),
-    })
-    .unwrap();
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        })
+        .unwrap();
 
-    let stf =
-        StfBlueprint::<C, MockDaSpec, MockZkvm, TestRuntime<C, MockDaSpec>, BasicKernel<C>>::new();
+    let stf = StfBlueprint::<
+        C,
+        MockDaSpec,
+        MockZkvm,
+        TestRuntime<C, MockDaSpec>,
+        BasicKernel<C, MockDaSpec>,
+    >::new();
     let test_runtime = TestRuntime::<C, MockDaSpec>::default();
 
     let value_setter_messages = ValueSetterMessages::default();
@@ -40,10 +54,21 @@ fn test_simple_value_setter_with_chain_state() {
     let admin_pub_key = value_setter_messages.messages[0].admin.default_address();
 
     // Genesis
-    let (init_root_hash, _) = stf.init_chain(
-        storage_manager.get_native_storage(),
+    let genesis_header = MockBlockHeader {
+        prev_hash: [1; 32].into(),
+        hash: [0; 32].into(),
+        height: 0,
+    };
+    let (init_root_hash, genesis_change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(&genesis_header)
+            .unwrap(),
         create_chain_state_genesis_config(admin_pub_key),
     );
+    storage_manager
+        .save_change_set(&genesis_header, genesis_change_set)
+        .unwrap();
+    storage_manager.finalize(&genesis_header).unwrap();
 
     const MOCK_SEQUENCER_DA_ADDRESS: [u8; 32] = [1_u8; 32];
 
@@ -73,12 +98,18 @@ fn test_simple_value_setter_with_chain_state() {
 
     let result = stf.apply_slot(
         &init_root_hash,
-        storage_manager.get_native_storage(),
+        storage_manager
+            .get_native_storage_on(&slot_data.header)
+            .unwrap(),
         Default::default(),
         &slot_data.header,
         &slot_data.validity_cond,
         &mut [blob.clone()],
     );
+    storage_manager
+        .save_change_set(&slot_data.header, result.change_set)
+        .unwrap();
+    storage_manager.finalize(&slot_data.header).unwrap();
 
     assert_eq!(1, result.batch_receipts.len());
     let apply_blob_outcome = result.batch_receipts[0].clone();
@@ -133,12 +164,18 @@ fn test_simple_value_setter_with_chain_state() {
 
     let result = stf.apply_slot(
         &result.state_root,
-        storage_manager.get_native_storage(),
+        storage_manager
+            .get_native_storage_on(&new_slot_data.header)
+            .unwrap(),
         Default::default(),
         &new_slot_data.header,
         &new_slot_data.validity_cond,
         &mut [blob],
     );
+    storage_manager
+        .save_change_set(&new_slot_data.header, result.change_set)
+        .unwrap();
+    storage_manager.finalize(&new_slot_data.header).unwrap();
 
     assert_eq!(1, result.batch_receipts.len());
     let apply_blob_outcome = result.batch_receipts[0].clone();
@@ -184,3 +221,203 @@ fn test_simple_value_setter_with_chain_state() {
         StateTransitionId::new([10; 32].into(), new_root_hash, MockValidityCond::default())
     );
 }
+
+/// Checks that call handlers see the slot height reported by the kernel, the hash of
+/// the raw transaction, and the DA address of the sequencer which posted the blob.
+#[test]
+fn test_call_handler_sees_transaction_context() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        })
+        .unwrap();
+    let stf = StfBlueprint::<
+        C,
+        MockDaSpec,
+        MockZkvm,
+        TestRuntime<C, MockDaSpec>,
+        BasicKernel<C, MockDaSpec>,
+    >::new();
+    let test_runtime = TestRuntime::<C, MockDaSpec>::default();
+    let sender_key = DefaultPrivateKey::generate();
+
+    let genesis_header = MockBlockHeader {
+        prev_hash: [1; 32].into(),
+        hash: [0; 32].into(),
+        height: 0,
+    };
+    let (mut state_root, genesis_change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(&genesis_header)
+            .unwrap(),
+        create_chain_state_genesis_config(sender_key.to_address()),
+    );
+    storage_manager
+        .save_change_set(&genesis_header, genesis_change_set)
+        .unwrap();
+    storage_manager.finalize(&genesis_header).unwrap();
+
+    const SEQUENCER_DA_ADDRESS: [u8; 32] = [7; 32];
+    let mut prev_hash = genesis_header.hash;
+    for slot in 1..=2u8 {
+        let tx = Transaction::<C>::new_signed_tx(
+            &sender_key,
+            <TestRuntime<C, MockDaSpec> as EncodeCall<ContextRecorder<C>>>::encode_call(()),
+            0,
+            None,
+            TxFee::zero(),
+            slot.into(),
+        );
+        let raw_tx = tx.try_to_vec().unwrap();
+        let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(&raw_tx).into();
+        let blob = new_test_blob_from_batch(
+            Batch {
+                txs: vec![RawTx { data: raw_tx }],
+            },
+            &SEQUENCER_DA_ADDRESS,
+            [slot; 32],
+        );
+
+        let header = MockBlockHeader {
+            prev_hash,
+            hash: [slot * 10; 32].into(),
+            height: slot.into(),
+        };
+        let result = stf.apply_slot(
+            &state_root,
+            storage_manager.get_native_storage_on(&header).unwrap(),
+            Default::default(),
+            &header,
+            &MockValidityCond::default(),
+            &mut [blob],
+        );
+        storage_manager
+            .save_change_set(&header, result.change_set)
+            .unwrap();
+        storage_manager.finalize(&header).unwrap();
+        state_root = result.state_root;
+        prev_hash = header.hash;
+
+        let mut working_set = WorkingSet::new(storage_manager.get_native_storage());
+        let recorded = test_runtime
+            .context_recorder
+            .last_context
+            .get(&mut working_set)
+            .expect("The call handler should have recorded its context");
+        assert_eq!(
+            recorded,
+            RecordedContext {
+                slot_height: slot.into(),
+                tx_hash,
+                sequencer: SEQUENCER_DA_ADDRESS.to_vec(),
+            }
+        );
+    }
+}
+
+/// Checks that a transaction is reverted, rather than panicking, when its fee can't be settled
+/// by the post dispatch hook, and that its fee is refunded and the nonce of its sender incremented.
+#[test]
+fn test_tx_is_reverted_if_fee_cannot_be_settled() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        })
+        .unwrap();
+    let stf = StfBlueprint::<
+        C,
+        MockDaSpec,
+        MockZkvm,
+        TestRuntime<C, MockDaSpec>,
+        BasicKernel<C, MockDaSpec>,
+    >::new();
+    let test_runtime = TestRuntime::<C, MockDaSpec>::default();
+    let sender_key = DefaultPrivateKey::generate();
+
+    let genesis_header = MockBlockHeader {
+        prev_hash: [1; 32].into(),
+        hash: [0; 32].into(),
+        height: 0,
+    };
+    let (state_root, genesis_change_set) = stf.init_chain(
+        storage_manager
+            .get_native_storage_on(&genesis_header)
+            .unwrap(),
+        create_chain_state_genesis_config(sender_key.to_address()),
+    );
+    storage_manager
+        .save_change_set(&genesis_header, genesis_change_set)
+        .unwrap();
+    storage_manager.finalize(&genesis_header).unwrap();
+
+    let tx = Transaction::<C>::new_signed_tx(
+        &sender_key,
+        <TestRuntime<C, MockDaSpec> as EncodeCall<ContextRecorder<C>>>::encode_call(()),
+        0,
+        None,
+        TxFee {
+            max_fee: 1,
+            ..TxFee::zero()
+        },
+        0,
+    );
+    let blob = new_test_blob_from_batch(
+        Batch {
+            txs: vec![RawTx {
+                data: tx.try_to_vec().unwrap(),
+            }],
+        },
+        &[7; 32],
+        [1; 32],
+    );
+
+    let header = MockBlockHeader {
+        prev_hash: genesis_header.hash,
+        hash: [10; 32].into(),
+        height: 1,
+    };
+    let result = stf.apply_slot(
+        &state_root,
+        storage_manager.get_native_storage_on(&header).unwrap(),
+        Default::default(),
+        &header,
+        &MockValidityCond::default(),
+        &mut [blob],
+    );
+    assert_eq!(1, result.batch_receipts.len());
+    let tx_receipts = &result.batch_receipts[0].tx_receipts;
+    assert_eq!(1, tx_receipts.len());
+    assert_eq!(tx_receipts[0].receipt, TxEffect::Reverted { fee: 0 });
+
+    storage_manager
+        .save_change_set(&header, result.change_set)
+        .unwrap();
+    storage_manager.finalize(&header).unwrap();
+
+    // The changes made by the call handler have been reverted along with the transaction.
+    let mut working_set = WorkingSet::new(storage_manager.get_native_storage());
+    assert!(test_runtime
+        .context_recorder
+        .last_context
+        .get(&mut working_set)
+        .is_none());
+
+    // The reserved fee has been refunded, and the transaction can't be replayed.
+    let sender: <C as Spec>::Address = sender_key.to_address();
+    assert_eq!(
+        test_runtime
+            .fee_recorder
+            .reserved_fees
+            .get(&sender, &mut working_set),
+        Some(0)
+    );
+    assert_eq!(
+        test_runtime
+            .fee_recorder
+            .nonces
+            .get(&sender, &mut working_set),
+        Some(1)
+    );
+}
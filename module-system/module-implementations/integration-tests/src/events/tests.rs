@@ -0,0 +1,140 @@
+use borsh::BorshDeserialize;
+use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_mock_da::MockBlock;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{Address, Context, Module, ModuleInfo, WorkingSet};
+use sov_rollup_interface::rpc::{EventFilter, LedgerRpcProvider};
+use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
+use sov_state::{DefaultStorageSpec, ProverStorage};
+use sov_value_setter::{ValueSetter, ValueSetterConfig};
+use sov_vec_setter::{VecSetter, VecSetterConfig};
+
+type C = DefaultContext;
+
+/// Returns a receipt for a transaction which emitted the events of the `working_set`.
+fn take_tx_receipt(working_set: &mut WorkingSet<C>) -> TransactionReceipt<()> {
+    TransactionReceipt {
+        tx_hash: [0; 32],
+        body_to_save: None,
+        events: working_set.take_events(),
+        receipt: (),
+    }
+}
+
+/// Returns the events matching `filter`, decoded as events of the module `M`.
+fn filtered_events<M: Module>(ledger_db: &LedgerDB, filter: EventFilter) -> Vec<M::Event> {
+    ledger_db
+        .get_events_by_filter(&filter, None, 10)
+        .unwrap()
+        .events
+        .into_iter()
+        .map(|event| M::Event::try_from_slice(event.event.value().inner()).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_filter_module_events() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage: ProverStorage<DefaultStorageSpec> =
+        ProverStorage::with_path(tmpdir.path()).unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+
+    let admin = Address::from([1; 32]);
+    let context = C::new(admin, Vec::new(), [0; 32], 1);
+    let vec_setter = VecSetter::<C>::default();
+    vec_setter
+        .genesis(&VecSetterConfig { admin }, &mut working_set)
+        .unwrap();
+    let value_setter = ValueSetter::<C>::default();
+    value_setter
+        .genesis(&ValueSetterConfig { admin }, &mut working_set)
+        .unwrap();
+
+    let mut tx_receipts = Vec::new();
+    vec_setter
+        .call(
+            sov_vec_setter::CallMessage::PushValue(1),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    tx_receipts.push(take_tx_receipt(&mut working_set));
+    vec_setter
+        .call(
+            sov_vec_setter::CallMessage::SetValue { index: 0, value: 2 },
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    value_setter
+        .call(
+            sov_value_setter::CallMessage::SetValue(3),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    tx_receipts.push(take_tx_receipt(&mut working_set));
+    vec_setter
+        .call(
+            sov_vec_setter::CallMessage::PushValue(4),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    tx_receipts.push(take_tx_receipt(&mut working_set));
+
+    let mut commit = SlotCommit::<_, (), ()>::new(MockBlock::default());
+    commit.add_batch(BatchReceipt {
+        batch_hash: [0; 32],
+        tx_receipts,
+        inner: (),
+    });
+    ledger_db.commit_slot(commit).unwrap();
+
+    let pushed = |value, new_length| sov_vec_setter::Event::ValuePushed { value, new_length };
+    let vec_setter_events = EventFilter {
+        module: Some(vec_setter.address().as_ref().to_vec()),
+        ..Default::default()
+    };
+    assert_eq!(
+        filtered_events::<VecSetter<C>>(&ledger_db, vec_setter_events.clone()),
+        vec![
+            pushed(1, 1),
+            sov_vec_setter::Event::ValueSet { index: 0, value: 2 },
+            pushed(4, 2),
+        ]
+    );
+    // `ValuePushed` is the first variant of the events of the vector setter.
+    assert_eq!(
+        filtered_events::<VecSetter<C>>(
+            &ledger_db,
+            EventFilter {
+                kind: Some(0),
+                ..vec_setter_events
+            }
+        ),
+        vec![pushed(1, 1), pushed(4, 2)]
+    );
+    assert_eq!(
+        filtered_events::<ValueSetter<C>>(
+            &ledger_db,
+            EventFilter {
+                module: Some(value_setter.address().as_ref().to_vec()),
+                ..Default::default()
+            }
+        ),
+        vec![sov_value_setter::Event::ValueSet { value: 3 }]
+    );
+    // Only the vector setter emitted events of kind 1.
+    assert_eq!(
+        filtered_events::<VecSetter<C>>(
+            &ledger_db,
+            EventFilter {
+                kind: Some(1),
+                ..Default::default()
+            }
+        ),
+        vec![sov_vec_setter::Event::ValueSet { index: 0, value: 2 }]
+    );
+}
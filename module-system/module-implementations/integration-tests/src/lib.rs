@@ -1,5 +1,11 @@
+#[cfg(test)]
+mod base_fee;
+
 #[cfg(test)]
 mod chain_state;
 
+#[cfg(test)]
+mod events;
+
 #[cfg(test)]
 mod nested_modules;
@@ -1,14 +1,24 @@
+use sov_mock_da::{MockBlockHeader, MockDaSpec};
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
 use sov_modules_api::prelude::*;
-use sov_modules_api::{Context, Event, ModulePrefix, StateMap, WorkingSet};
-use sov_state::{ProverStorage, Storage, ZkStorage};
+use sov_modules_api::storage::HierarchicalStorageManager;
+use sov_modules_api::{module_event, Context, ModulePrefix, StateMap, WorkingSet};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
 
-use super::helpers::module_c;
+use super::helpers::{module_a, module_b, module_c};
 
 #[test]
 fn nested_module_call_test() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let native_storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        })
+        .unwrap();
+    let native_storage = storage_manager
+        .get_native_storage_on(&MockBlockHeader::default())
+        .unwrap();
     let mut working_set = WorkingSet::new(native_storage.clone());
 
     // Test the `native` execution.
@@ -16,14 +26,24 @@ fn nested_module_call_test() {
         execute_module_logic::<DefaultContext>(&mut working_set);
         test_state_update::<DefaultContext>(&mut working_set);
     }
+    let module_a_updated = module_event(
+        &module_a::ModuleA::<DefaultContext>::default(),
+        &module_a::Event::Updated,
+    );
     assert_eq!(
         working_set.events(),
         &vec![
-            Event::new("module C", "execute"),
-            Event::new("module A", "update"),
-            Event::new("module B", "update"),
-            Event::new("module A", "update"),
-            Event::new("module A", "update"),
+            module_event(
+                &module_c::ModuleC::<DefaultContext>::default(),
+                &module_c::Event::Executed
+            ),
+            module_a_updated.clone(),
+            module_event(
+                &module_b::ModuleB::<DefaultContext>::default(),
+                &module_b::Event::Updated
+            ),
+            module_a_updated.clone(),
+            module_a_updated,
         ]
     );
 
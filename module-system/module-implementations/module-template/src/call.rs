@@ -21,6 +21,18 @@ pub enum CallMessage {
     SetValue(u32),
 }
 
+/// This enumeration represents the events emitted by the `ExampleModule` module.
+/// The `derive` for [`schemars::JsonSchema`] is a requirement of
+/// [`sov_modules_api::RuntimeEventJsonSchema`].
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq)]
+pub enum Event {
+    ValueSet { value: u32 },
+}
+
 /// Example of a custom error.
 #[derive(Debug, Error)]
 enum SetValueError {}
@@ -34,7 +46,7 @@ impl<C: sov_modules_api::Context> ExampleModule<C> {
         working_set: &mut WorkingSet<C>,
     ) -> Result<sov_modules_api::CallResponse> {
         self.value.set(&new_value, working_set);
-        working_set.add_event("set", &format!("value_set: {new_value:?}"));
+        working_set.emit_event(self, Event::ValueSet { value: new_value });
 
         Ok(CallResponse::default())
     }
@@ -1,8 +1,8 @@
-use module_template::{CallMessage, ExampleModule, ExampleModuleConfig, Response};
+use module_template::{CallMessage, Event, ExampleModule, ExampleModuleConfig, Response};
 #[cfg(feature = "native")]
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_context::ZkDefaultContext;
-use sov_modules_api::{Address, Context, Event, Module, WorkingSet};
+use sov_modules_api::{module_event, Address, Context, Module, WorkingSet};
 use sov_state::{DefaultStorageSpec, ProverStorage, ZkStorage};
 
 #[test]
@@ -18,7 +18,7 @@ fn test_value_setter() {
     #[cfg(feature = "native")]
     {
         let config = ExampleModuleConfig {};
-        let context = DefaultContext::new(admin, 1);
+        let context = DefaultContext::new(admin, Vec::new(), [0; 32], 1);
         test_value_setter_helper(context, &config, &mut working_set);
     }
 
@@ -27,7 +27,7 @@ fn test_value_setter() {
     // Test Zk-Context
     {
         let config = ExampleModuleConfig {};
-        let zk_context = ZkDefaultContext::new(admin, 1);
+        let zk_context = ZkDefaultContext::new(admin, Vec::new(), [0; 32], 1);
         let mut zk_working_set = WorkingSet::with_witness(ZkStorage::new(), witness);
         test_value_setter_helper(zk_context, &config, &mut zk_working_set);
     }
@@ -48,7 +48,10 @@ fn test_value_setter_helper<C: Context>(
     {
         module.call(call_msg, &context, working_set).unwrap();
         let event = &working_set.events()[0];
-        assert_eq!(event, &Event::new("set", "value_set: 99"));
+        assert_eq!(
+            event,
+            &module_event(&module, &Event::ValueSet { value: 99 })
+        );
     }
 
     // Test query
@@ -1,6 +1,6 @@
 use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, Spec, StateMapAccessor, WorkingSet};
+use sov_modules_api::{Context, GasMeter, Spec, StateMapAccessor, WorkingSet};
 
 use crate::Accounts;
 
@@ -10,6 +10,7 @@ impl<C: Context> TxHooks for Accounts<C> {
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<C>,
+        _base_fee: &C::GasUnit,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<<Self::Context as Spec>::Address> {
         let pub_key = tx.pub_key();
@@ -31,6 +32,8 @@ impl<C: Context> TxHooks for Accounts<C> {
     fn post_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
+        _ctx: &C,
+        _gas_meter: &GasMeter<C::GasUnit>,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         let mut account = self.accounts.get_or_err(tx.pub_key(), working_set)?;
@@ -38,4 +41,14 @@ impl<C: Context> TxHooks for Accounts<C> {
         self.accounts.set(tx.pub_key(), &account, working_set);
         Ok(())
     }
+
+    fn refund_tx_hook(
+        &self,
+        tx: &Transaction<Self::Context>,
+        ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        // Accounts don't hold fees, so only the nonce is incremented.
+        self.post_dispatch_tx_hook(tx, ctx, &GasMeter::default(), working_set)
+    }
 }
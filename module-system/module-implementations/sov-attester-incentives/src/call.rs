@@ -195,7 +195,10 @@ pub enum AttesterIncentiveErrors {
     MintFailure,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
 /// A role in the attestation process
 pub enum Role {
     /// A user who attests to new state transitions
@@ -204,6 +207,58 @@ pub enum Role {
     Challenger,
 }
 
+/// This enumeration represents the events emitted by the `AttesterIncentives` module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "Event")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum Event<C: sov_modules_api::Context> {
+    /// A user was slashed and removed from the bonded set of its role.
+    UserSlashed {
+        /// The address of the slashed user.
+        user: C::Address,
+        /// The role of the slashed user.
+        role: Role,
+    },
+    /// A user bonded coins.
+    BondedUser {
+        /// The address of the user.
+        user: C::Address,
+        /// The role the user bonded for.
+        role: Role,
+        /// The amount of coins that were bonded.
+        deposit: Amount,
+        /// The total bond of the user after the deposit.
+        total_bond: Amount,
+    },
+    /// A challenger unbonded all of its coins.
+    UnbondedChallenger {
+        /// The address of the challenger.
+        challenger: C::Address,
+        /// The amount of coins that were withdrawn.
+        amount_withdrawn: Amount,
+    },
+    /// An attester finished unbonding all of its coins.
+    UnbondedAttester {
+        /// The address of the attester.
+        attester: C::Address,
+        /// The amount of coins that were withdrawn.
+        amount_withdrawn: Amount,
+    },
+    /// An attester submitted a valid attestation.
+    ProcessedValidAttestation {
+        /// The address of the attester.
+        attester: C::Address,
+    },
+    /// A challenger submitted a valid challenge.
+    ProcessedValidProof {
+        /// The address of the challenger.
+        challenger: C::Address,
+    },
+}
+
 impl<C, Vm, Da, Checker> AttesterIncentives<C, Vm, Da, Checker>
 where
     C: sov_modules_api::Context,
@@ -257,7 +312,13 @@ where
         bonded_set.remove(user, working_set);
 
         // We raise an event
-        working_set.add_event("user_slashed", &format!("address {user:?}"));
+        working_set.emit_event(
+            self,
+            Event::UserSlashed {
+                user: user.clone(),
+                role,
+            },
+        );
 
         reward
     }
@@ -354,9 +415,9 @@ where
             .transfer_from(user_address, &self.address, coins, working_set)
             .map_err(|_err| AttesterIncentiveErrors::TransferFailure)?;
 
-        let (balances, event_key) = match role {
-            Role::Attester => (&self.bonded_attesters, "bonded_attester"),
-            Role::Challenger => (&self.bonded_challengers, "bonded_challenger"),
+        let balances = match role {
+            Role::Attester => &self.bonded_attesters,
+            Role::Challenger => &self.bonded_challengers,
         };
 
         // Update our record of the total bonded amount for the sender.
@@ -366,9 +427,14 @@ where
         balances.set(user_address, &total_balance, working_set);
 
         // Emit the bonding event
-        working_set.add_event(
-            event_key,
-            &format!("new_deposit: {bond_amount:?}. total_bond: {total_balance:?}"),
+        working_set.emit_event(
+            self,
+            Event::BondedUser {
+                user: user_address.clone(),
+                role,
+                deposit: bond_amount,
+                total_bond: total_balance,
+            },
         );
 
         Ok(CallResponse::default())
@@ -387,9 +453,12 @@ where
             self.reward_sender(context, old_balance, working_set)?;
 
             // Emit the unbonding event
-            working_set.add_event(
-                "unbonded_challenger",
-                &format!("amount_withdrawn: {old_balance:?}"),
+            working_set.emit_event(
+                self,
+                Event::UnbondedChallenger {
+                    challenger: context.sender().clone(),
+                    amount_withdrawn: old_balance,
+                },
             );
         }
 
@@ -466,10 +535,13 @@ where
                 .remove(context.sender(), working_set);
 
             // Emit the unbonding event
-            working_set.add_event("unbonded_challenger", {
-                let amount = unbonding_info.amount;
-                &format!("amount_withdrawn: {:?}", amount)
-            });
+            working_set.emit_event(
+                self,
+                Event::UnbondedAttester {
+                    attester: context.sender().clone(),
+                    amount_withdrawn: unbonding_info.amount,
+                },
+            );
         } else {
             return Err(AttesterIncentiveErrors::AttesterIsNotUnbonding);
         }
@@ -726,9 +798,11 @@ where
             working_set,
         )?;
 
-        working_set.add_event(
-            "processed_valid_attestation",
-            &format!("attester: {:?}", context.sender()),
+        working_set.emit_event(
+            self,
+            Event::ProcessedValidAttestation {
+                attester: context.sender().clone(),
+            },
         );
 
         // Now we have to check whether the claimed_transition_num is the max_attested_height.
@@ -874,9 +948,11 @@ where
                 // Now remove the bad transition from the pool
                 self.bad_transition_pool.remove(transition_num, working_set);
 
-                working_set.add_event(
-                    "processed_valid_proof",
-                    &format!("challenger: {:?}", context.sender()),
+                working_set.emit_event(
+                    self,
+                    Event::ProcessedValidProof {
+                        challenger: context.sender().clone(),
+                    },
                 );
             }
             Err(_err) => {
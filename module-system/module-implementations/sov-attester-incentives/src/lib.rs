@@ -132,7 +132,7 @@ where
 
     type CallMessage = call::CallMessage<C, Da>;
 
-    type Event = ();
+    type Event = call::Event<C>;
 
     fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
         // The initialization logic
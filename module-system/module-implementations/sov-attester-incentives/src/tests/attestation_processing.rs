@@ -1,18 +1,17 @@
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::optimistic::Attestation;
 use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
-use sov_state::ProverStorage;
 
 use crate::call::AttesterIncentiveErrors;
 use crate::tests::helpers::{
-    execution_simulation, setup, BOND_AMOUNT, INITIAL_BOND_AMOUNT, INIT_HEIGHT,
+    execution_simulation, new_storage, setup, BOND_AMOUNT, INITIAL_BOND_AMOUNT, INIT_HEIGHT,
 };
 
 /// Start by testing the positive case where the attestations are valid
 #[test]
 fn test_process_valid_attestation() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let storage = new_storage(tmpdir.path());
     let mut working_set = WorkingSet::new(storage.clone());
     let (module, token_address, attester_address, _) = setup(&mut working_set);
 
@@ -33,7 +32,7 @@ fn test_process_valid_attestation() {
     let (mut exec_vars, mut working_set) =
         execution_simulation(3, &module, &storage, attester_address, working_set);
 
-    let context = DefaultContext::new(attester_address, 1);
+    let context = DefaultContext::new(attester_address, Vec::new(), [0; 32], 1);
 
     let transition_2 = exec_vars.pop().unwrap();
     let transition_1 = exec_vars.pop().unwrap();
@@ -100,7 +99,7 @@ fn test_process_valid_attestation() {
 #[test]
 fn test_burn_on_invalid_attestation() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let storage = new_storage(tmpdir.path());
     let mut working_set = WorkingSet::new(storage.clone());
     let (module, _token_address, attester_address, _) = setup(&mut working_set);
 
@@ -125,7 +124,7 @@ fn test_burn_on_invalid_attestation() {
     let transition_1 = exec_vars.pop().unwrap();
     let initial_transition = exec_vars.pop().unwrap();
 
-    let context = DefaultContext::new(attester_address, 1);
+    let context = DefaultContext::new(attester_address, Vec::new(), [0; 32], 1);
 
     // Process an invalid proof for genesis: everything is correct except the storage proof.
     // Must simply return an error. Cannot burn the token at this point because we don't know if the
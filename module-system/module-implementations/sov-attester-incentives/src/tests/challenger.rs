@@ -5,19 +5,18 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{Context, WorkingSet};
 use sov_rollup_interface::zk::StateTransition;
-use sov_state::ProverStorage;
 
 use crate::call::{AttesterIncentiveErrors, SlashingReason};
 use crate::tests::helpers::{
-    commit_get_new_working_set, execution_simulation, setup, BOND_AMOUNT, INITIAL_BOND_AMOUNT,
-    INIT_HEIGHT,
+    commit_get_new_working_set, execution_simulation, new_storage, setup, BOND_AMOUNT,
+    INITIAL_BOND_AMOUNT, INIT_HEIGHT,
 };
 
 /// Test that given an invalid transition, a challenger can successfully challenge it and get rewarded
 #[test]
 fn test_valid_challenge() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let storage = new_storage(tmpdir.path());
     let mut working_set = WorkingSet::new(storage.clone());
     let (module, token_address, attester_address, challenger_address) = setup(&mut working_set);
 
@@ -58,7 +57,7 @@ fn test_valid_challenge() {
         .bad_transition_pool
         .set(&(INIT_HEIGHT + 1), &BOND_AMOUNT, &mut working_set);
 
-    let context = DefaultContext::new(challenger_address, INIT_HEIGHT + 2);
+    let context = DefaultContext::new(challenger_address, Vec::new(), [0; 32], INIT_HEIGHT + 2);
 
     {
         let transition = StateTransition::<MockDaSpec, _, _> {
@@ -168,7 +167,7 @@ fn invalid_proof_helper(
 #[test]
 fn test_invalid_challenge() {
     let tmpdir = tempfile::tempdir().unwrap();
-    let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+    let storage = new_storage(tmpdir.path());
     let mut working_set = WorkingSet::new(storage.clone());
     let (module, _token_address, attester_address, challenger_address) = setup(&mut working_set);
 
@@ -188,9 +187,10 @@ fn test_invalid_challenge() {
         .bad_transition_pool
         .set(&(INIT_HEIGHT + 1), &BOND_AMOUNT, &mut working_set);
 
-    let context = DefaultContext::new(challenger_address, INIT_HEIGHT + 2);
+    let context = DefaultContext::new(challenger_address, Vec::new(), [0; 32], INIT_HEIGHT + 2);
     let transition: StateTransition<MockDaSpec, _, _> = StateTransition {
         initial_state_root: initial_transition.state_root,
+        initial_slot_hash: [1; 32].into(),
         slot_hash: [1; 32].into(),
         final_state_root: transition_1.state_root,
         rewarded_address: challenger_address,
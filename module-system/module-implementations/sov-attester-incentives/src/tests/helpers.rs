@@ -6,8 +6,10 @@ use sov_mock_da::{
 use sov_mock_zkvm::{MockCodeCommitment, MockZkvm};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::hooks::SlotHooks;
+use sov_modules_api::storage::HierarchicalStorageManager;
 use sov_modules_api::utils::generate_address;
 use sov_modules_api::{Address, Genesis, Spec, ValidityConditionChecker, WorkingSet};
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_state::storage::{NativeStorage, Storage, StorageProof};
 use sov_state::{DefaultStorageSpec, ProverStorage};
 
@@ -22,6 +24,18 @@ pub const SALT: u64 = 5;
 pub const DEFAULT_ROLLUP_FINALITY: u64 = 3;
 pub const INIT_HEIGHT: u64 = 0;
 
+/// Creates a storage on top of the first block, so the changes of the tests can be committed to it.
+pub(crate) fn new_storage(path: &std::path::Path) -> ProverStorage<DefaultStorageSpec> {
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(sov_state::config::Config {
+            path: path.to_path_buf(),
+        })
+        .unwrap();
+    storage_manager
+        .get_native_storage_on(&MockBlockHeader::default())
+        .unwrap()
+}
+
 /// Consumes and commit the existing working set on the underlying storage
 /// `storage` must be the underlying storage defined on the working set for this method to work.
 pub(crate) fn commit_get_new_working_set(
@@ -61,6 +75,7 @@ pub(crate) fn create_bank_config_with_token(
     (
         BankConfig {
             tokens: vec![token_config],
+            gas_token_address: None,
         },
         address_and_balances
             .into_iter()
@@ -94,6 +109,7 @@ pub(crate) fn setup(
 
     // Initialize chain state
     let chain_state_config = sov_chain_state::ChainStateConfig {
+        chain_id: 0,
         initial_slot_height: INIT_HEIGHT,
         current_time: Default::default(),
     };
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use sov_modules_api::{StateValueAccessor, WorkingSet};
+
+use crate::{Bank, Coins};
+
+impl<C: sov_modules_api::Context> Bank<C> {
+    /// Returns the address of the token used to pay for gas, or [`None`] if gas is free.
+    pub fn get_gas_token_address(&self, working_set: &mut WorkingSet<C>) -> Option<C::Address> {
+        self.gas_token_address.get(working_set)
+    }
+
+    /// Reserves the maximum fee of a transaction by moving `max_fee` gas tokens from the balance
+    /// of `sender` to the bank module.
+    ///
+    /// Nothing is reserved if no gas token was configured at genesis.
+    pub fn reserve_gas(
+        &self,
+        max_fee: u64,
+        sender: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let Some(token_address) = self.gas_token_address.get(working_set) else {
+            return Ok(());
+        };
+
+        if max_fee > 0 {
+            let coins = Coins {
+                amount: max_fee,
+                token_address,
+            };
+            self.transfer_from(sender, &self.address, coins, working_set)?;
+        }
+
+        Ok(())
+    }
+
+    /// Settles the fee reserved with [`Bank::reserve_gas`] once the transaction has been executed.
+    ///
+    /// The `base_fee` part of the fee is burned. The `priority_fee` part is paid to
+    /// `reward_address`, or burned if there is none, and the `unused` part is refunded to `sender`.
+    pub fn settle_gas(
+        &self,
+        base_fee: u64,
+        priority_fee: u64,
+        unused: u64,
+        sender: &C::Address,
+        reward_address: Option<&C::Address>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let Some(token_address) = self.gas_token_address.get(working_set) else {
+            return Ok(());
+        };
+
+        let coins = |amount| Coins {
+            amount,
+            token_address: token_address.clone(),
+        };
+
+        let burned = match reward_address {
+            Some(reward_address) => {
+                if priority_fee > 0 {
+                    self.transfer_from(
+                        &self.address,
+                        reward_address,
+                        coins(priority_fee),
+                        working_set,
+                    )?;
+                }
+                base_fee
+            }
+            None => base_fee.saturating_add(priority_fee),
+        };
+
+        if burned > 0 {
+            self.burn(coins(burned), &self.address, working_set)?;
+        }
+
+        if unused > 0 {
+            self.transfer_from(&self.address, sender, coins(unused), working_set)?;
+        }
+
+        Ok(())
+    }
+}
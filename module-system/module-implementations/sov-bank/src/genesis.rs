@@ -1,7 +1,7 @@
 use anyhow::{bail, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_modules_api::{StateValueAccessor, WorkingSet};
 
 use crate::token::Token;
 use crate::Bank;
@@ -12,6 +12,10 @@ use crate::Bank;
 pub struct BankConfig<C: sov_modules_api::Context> {
     /// A list of configurations for the initial tokens.
     pub tokens: Vec<TokenConfig<C>>,
+    /// The address of the token used to pay for gas, which must be one of the initial tokens.
+    /// Gas is free if it is not set.
+    #[serde(default)]
+    pub gas_token_address: Option<C::Address>,
 }
 
 /// [`TokenConfig`] specifies a configuration used when generating a token for the bank
@@ -59,6 +63,13 @@ impl<C: sov_modules_api::Context> Bank<C> {
 
             self.tokens.set(&token_address, &token, working_set);
         }
+
+        if let Some(gas_token_address) = &config.gas_token_address {
+            if self.tokens.get(gas_token_address, working_set).is_none() {
+                bail!("Gas token {} is not an initial token", gas_token_address);
+            }
+            self.gas_token_address.set(gas_token_address, working_set);
+        }
         Ok(())
     }
 }
@@ -87,6 +98,7 @@ mod tests {
                 authorized_minters: vec![address],
                 salt: 0,
             }],
+            gas_token_address: None,
         };
 
         let data = r#"
@@ -1,6 +1,7 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 mod call;
+mod gas;
 mod genesis;
 #[cfg(feature = "native")]
 mod query;
@@ -54,7 +55,11 @@ pub struct Bank<C: sov_modules_api::Context> {
 
     /// A mapping of addresses to tokens in the sov-bank.
     #[state]
-    pub(crate) tokens: sov_modules_api::StateMap<C::Address, Token<C>>,
+    pub(crate) tokens: sov_modules_api::IterableStateMap<C::Address, Token<C>>,
+
+    /// The address of the token used to pay for gas. Gas is free if it is not set.
+    #[state]
+    pub(crate) gas_token_address: sov_modules_api::StateValue<C::Address>,
 }
 
 impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
@@ -1,7 +1,7 @@
 //! Defines rpc queries exposed by the bank module, along with the relevant types
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
-use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_modules_api::WorkingSet;
 
 use crate::{Amount, Bank};
 
@@ -19,6 +19,27 @@ pub struct TotalSupplyResponse {
     pub amount: Option<Amount>,
 }
 
+/// The maximum number of tokens returned by a single call to the `listTokens` rpc method.
+pub const MAX_TOKENS_PER_PAGE: usize = 100;
+
+/// Information about a token, as returned by the `listTokens` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct TokenInfo<C: sov_modules_api::Context> {
+    /// The address of the token.
+    pub token_address: C::Address,
+    /// The name of the token.
+    pub name: String,
+    /// The total supply of the token.
+    pub total_supply: Amount,
+}
+
+/// Structure returned by the `listTokens` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ListTokensResponse<C: sov_modules_api::Context> {
+    /// The tokens, ordered by address.
+    pub tokens: Vec<TokenInfo<C>>,
+}
+
 #[rpc_gen(client, server, namespace = "bank")]
 impl<C: sov_modules_api::Context> Bank<C> {
     #[rpc_method(name = "balanceOf")]
@@ -49,4 +70,30 @@ impl<C: sov_modules_api::Context> Bank<C> {
                 .map(|token| token.total_supply),
         })
     }
+
+    #[rpc_method(name = "listTokens")]
+    /// Rpc method that returns up to `limit` tokens ordered by address, starting right after
+    /// `start_after`. At most [`MAX_TOKENS_PER_PAGE`] tokens are returned, which is also the
+    /// default `limit`. Pass the address of the last returned token to get the next page.
+    pub fn list_tokens(
+        &self,
+        start_after: Option<C::Address>,
+        limit: Option<usize>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ListTokensResponse<C>> {
+        let limit = limit
+            .unwrap_or(MAX_TOKENS_PER_PAGE)
+            .min(MAX_TOKENS_PER_PAGE);
+        let tokens = self
+            .tokens
+            .entries(start_after.as_ref(), limit, working_set)
+            .into_iter()
+            .map(|(token_address, token)| TokenInfo {
+                token_address,
+                name: token.name,
+                total_supply: token.total_supply,
+            })
+            .collect();
+        Ok(ListTokensResponse { tokens })
+    }
 }
@@ -18,13 +18,16 @@ fn burn_deployed_tokens() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        gas_token_address: None,
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address("just_sender");
-    let sender_context = C::new(sender_address, 1);
+    let sender_context = C::new(sender_address, Vec::new(), [0; 32], 1);
     let minter_address = generate_address("minter");
-    let minter_context = C::new(minter_address, 1);
+    let minter_context = C::new(minter_address, Vec::new(), [0; 32], 1);
 
     let salt = 0;
     let token_name = "Token1".to_owned();
@@ -211,7 +214,7 @@ fn burn_initial_tokens() {
         },
     };
 
-    let context = C::new(sender_address, 1);
+    let context = C::new(sender_address, Vec::new(), [0; 32], 1);
     bank.call(burn_message, &context, &mut working_set)
         .expect("Failed to burn token");
     assert!(working_set.events().is_empty());
@@ -1,4 +1,4 @@
-use sov_bank::{get_token_address, Bank, CallMessage};
+use sov_bank::{get_genesis_token_address, get_token_address, Bank, CallMessage};
 use sov_modules_api::utils::generate_address;
 use sov_modules_api::{Context, Module, WorkingSet};
 use sov_state::ProverStorage;
@@ -16,7 +16,7 @@ fn initial_and_deployed_token() {
     bank.genesis(&bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address::<C>("sender");
-    let sender_context = C::new(sender_address, 1);
+    let sender_context = C::new(sender_address, Vec::new(), [0; 32], 1);
     let minter_address = generate_address::<C>("minter");
     let initial_balance = 500;
     let token_name = "Token1".to_owned();
@@ -65,3 +65,57 @@ fn overflow_max_supply() {
         genesis_result.unwrap_err().to_string()
     );
 }
+
+#[test]
+fn list_tokens() {
+    let bank_config = create_bank_config_with_token(1, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let sender_address = generate_address::<C>("sender");
+    let sender_context = C::new(sender_address, Vec::new(), [0; 32], 1);
+    for salt in 0..3 {
+        let create_token_message = CallMessage::CreateToken::<C> {
+            salt,
+            token_name: format!("Token{salt}"),
+            initial_balance: 500,
+            minter_address: sender_address,
+            authorized_minters: vec![],
+        };
+        bank.call(create_token_message, &sender_context, &mut working_set)
+            .expect("Failed to create token");
+    }
+
+    let genesis_token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let mut expected_addresses: Vec<_> = (0..3)
+        .map(|salt| get_token_address::<C>(&format!("Token{salt}"), sender_address.as_ref(), salt))
+        .chain([genesis_token_address])
+        .collect();
+    expected_addresses.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+
+    // Tokens are listed in order of their addresses, page by page.
+    let first_page = bank.list_tokens(None, Some(3), &mut working_set).unwrap();
+    assert_eq!(first_page.tokens.len(), 3);
+    let last_address = first_page.tokens[2].token_address;
+    let second_page = bank
+        .list_tokens(Some(last_address), None, &mut working_set)
+        .unwrap();
+    let listed_addresses: Vec<_> = first_page
+        .tokens
+        .iter()
+        .chain(second_page.tokens.iter())
+        .map(|token| token.token_address)
+        .collect();
+    assert_eq!(listed_addresses, expected_addresses);
+
+    let token = &second_page.tokens[0];
+    assert_eq!(
+        bank.get_token_name(&token.token_address, &mut working_set),
+        Some(token.name.clone())
+    );
+}
@@ -14,11 +14,14 @@ fn freeze_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        gas_token_address: None,
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address::<DefaultContext>("minter");
-    let minter_context = C::new(minter_address, 1);
+    let minter_context = C::new(minter_address, Vec::new(), [0; 32], 1);
 
     let salt = 0;
     let token_name = "Token1".to_owned();
@@ -91,7 +94,7 @@ fn freeze_token() {
 
     // Try to freeze with a non authorized minter
     let unauthorized_address = generate_address::<C>("unauthorized_address");
-    let unauthorized_context = C::new(unauthorized_address, 1);
+    let unauthorized_context = C::new(unauthorized_address, Vec::new(), [0; 32], 1);
     let freeze_message = CallMessage::Freeze {
         token_address: token_address_2,
     };
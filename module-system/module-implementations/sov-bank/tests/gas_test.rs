@@ -0,0 +1,89 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank, TotalSupplyResponse};
+use sov_modules_api::{Module, WorkingSet};
+use sov_state::ProverStorage;
+
+#[test]
+fn reserve_and_settle_gas() {
+    let initial_balance = 100;
+    let mut bank_config = create_bank_config_with_token(3, initial_balance);
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    bank_config.gas_token_address = Some(token_address);
+    let sender = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer = bank_config.tokens[0].address_and_balances[1].0;
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+    assert_eq!(
+        Some(token_address),
+        bank.get_gas_token_address(&mut working_set)
+    );
+
+    bank.reserve_gas(30, &sender, &mut working_set).unwrap();
+    assert_eq!(
+        Some(initial_balance - 30),
+        bank.get_balance_of(sender, token_address, &mut working_set)
+    );
+
+    // The sender can't reserve more than its balance.
+    assert!(bank
+        .reserve_gas(initial_balance, &sender, &mut working_set)
+        .is_err());
+
+    // The base fee is burned and the priority fee is paid to the sequencer.
+    bank.settle_gas(5, 15, 10, &sender, Some(&sequencer), &mut working_set)
+        .unwrap();
+    assert_eq!(
+        Some(initial_balance - 20),
+        bank.get_balance_of(sender, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 15),
+        bank.get_balance_of(sequencer, token_address, &mut working_set)
+    );
+    let total_supply: TotalSupplyResponse =
+        bank.supply_of(token_address, &mut working_set).unwrap();
+    assert_eq!(Some(initial_balance * 3 - 5), total_supply.amount);
+
+    // Without a reward address, the whole consumed fee is burned.
+    bank.reserve_gas(10, &sender, &mut working_set).unwrap();
+    bank.settle_gas(5, 5, 0, &sender, None, &mut working_set)
+        .unwrap();
+    let total_supply: TotalSupplyResponse =
+        bank.supply_of(token_address, &mut working_set).unwrap();
+    assert_eq!(Some(initial_balance * 3 - 15), total_supply.amount);
+}
+
+#[test]
+fn gas_is_free_without_gas_token() {
+    let bank_config = create_bank_config_with_token(1, 0);
+    let sender = bank_config.tokens[0].address_and_balances[0].0;
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    assert_eq!(None, bank.get_gas_token_address(&mut working_set));
+    bank.reserve_gas(30, &sender, &mut working_set).unwrap();
+    bank.settle_gas(10, 10, 10, &sender, None, &mut working_set)
+        .unwrap();
+}
+
+#[test]
+fn gas_token_must_be_an_initial_token() {
+    let mut bank_config = create_bank_config_with_token(1, 100);
+    bank_config.gas_token_address = Some(generate_address("unknown_token"));
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
+    let bank = Bank::<C>::default();
+    assert!(bank.genesis(&bank_config, &mut working_set).is_err());
+}
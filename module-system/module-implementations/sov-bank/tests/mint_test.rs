@@ -14,11 +14,14 @@ fn mint_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        gas_token_address: None,
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let minter_address = generate_address::<C>("minter");
-    let minter_context = C::new(minter_address, 1);
+    let minter_context = C::new(minter_address, Vec::new(), [0; 32], 1);
 
     let salt = 0;
     let token_name = "Token1".to_owned();
@@ -85,7 +88,7 @@ fn mint_token() {
 
     // Mint with an un-authorized user
     let unauthorized_address = generate_address::<C>("unauthorized_address");
-    let unauthorized_context = C::new(unauthorized_address, 1);
+    let unauthorized_context = C::new(unauthorized_address, Vec::new(), [0; 32], 1);
     let unauthorized_mint = bank.call(mint_message, &unauthorized_context, &mut working_set);
 
     assert!(unauthorized_mint.is_err());
@@ -168,7 +171,7 @@ fn mint_token() {
         message_2
     );
     // Try to mint new token with authorized sender 2
-    let authorized_minter_2_context = C::new(authorized_minter_address_2, 1);
+    let authorized_minter_2_context = C::new(authorized_minter_address_2, Vec::new(), [0; 32], 1);
     let mint_message = CallMessage::Mint {
         coins: Coins {
             amount: mint_amount,
@@ -185,7 +188,7 @@ fn mint_token() {
     assert_eq!(Some(110), supply);
 
     // Try to mint new token with authorized sender 1
-    let authorized_minter_1_context = C::new(authorized_minter_address_1, 1);
+    let authorized_minter_1_context = C::new(authorized_minter_address_1, Vec::new(), [0; 32], 1);
     let mint_message = CallMessage::Mint {
         coins: Coins {
             amount: mint_amount,
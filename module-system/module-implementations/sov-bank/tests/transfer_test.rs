@@ -49,7 +49,7 @@ fn transfer_initial_token() {
 
     assert_eq!(Some(initial_balance), sender_balance_before);
     assert_eq!(sender_balance_before, receiver_balance_before);
-    let sender_context = C::new(sender_address, 1);
+    let sender_context = C::new(sender_address, Vec::new(), [0; 32], 1);
 
     // Transfer happy test
     {
@@ -156,7 +156,7 @@ fn transfer_initial_token() {
     // Sender does not exist
     {
         let unknown_sender = generate_address::<C>("non_existing_sender");
-        let unknown_sender_context = C::new(unknown_sender, 1);
+        let unknown_sender_context = C::new(unknown_sender, Vec::new(), [0; 32], 1);
 
         let sender_balance = query_user_balance(unknown_sender, &mut working_set);
         assert!(sender_balance.is_none());
@@ -258,7 +258,10 @@ fn transfer_deployed_token() {
     let bank = Bank::<C>::default();
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
-    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    let empty_bank_config = BankConfig::<C> {
+        tokens: vec![],
+        gas_token_address: None,
+    };
     bank.genesis(&empty_bank_config, &mut working_set).unwrap();
 
     let sender_address = generate_address::<C>("just_sender");
@@ -289,7 +292,7 @@ fn transfer_deployed_token() {
 
     assert!(sender_balance_before.is_none());
     assert!(receiver_balance_before.is_none());
-    let sender_context = C::new(sender_address, 1);
+    let sender_context = C::new(sender_address, Vec::new(), [0; 32], 1);
 
     let mint_message = CallMessage::CreateToken {
         salt,
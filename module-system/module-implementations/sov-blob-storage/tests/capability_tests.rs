@@ -6,9 +6,11 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::hooks::SlotHooks;
 use sov_modules_api::macros::DefaultRuntime;
 use sov_modules_api::runtime::capabilities::{BlobRefOrOwned, BlobSelector};
+use sov_modules_api::storage::HierarchicalStorageManager;
 use sov_modules_api::{
     Address, BlobReaderTrait, Context, DaSpec, DispatchCall, MessageCodec, Module, Spec, WorkingSet,
 };
+use sov_prover_storage_manager::ProverStorageManager;
 use sov_sequencer_registry::SequencerConfig;
 use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
 
@@ -38,6 +40,7 @@ fn get_bank_config(
 
     sov_bank::BankConfig {
         tokens: vec![token_config],
+        gas_token_address: None,
     }
 }
 
@@ -166,7 +169,7 @@ fn do_deferred_blob_test(
                         .blob_storage
                         .call(
                             msg,
-                            &DefaultContext::new(sender, slot_number),
+                            &DefaultContext::new(sender, Vec::new(), [0; 32], slot_number),
                             &mut working_set,
                         )
                         .unwrap();
@@ -666,7 +669,15 @@ impl TestRuntime<DefaultContext, MockDaSpec> {
     ) -> (ProverStorage<DefaultStorageSpec>, Self, jmt::RootHash) {
         use sov_modules_api::Genesis;
         let tmpdir = tempfile::tempdir().unwrap();
-        let storage = ProverStorage::with_path(tmpdir.path()).unwrap();
+        let mut storage_manager = ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(
+            sov_state::config::Config {
+                path: tmpdir.path().to_path_buf(),
+            },
+        )
+        .unwrap();
+        let storage = storage_manager
+            .get_native_storage_on(&MockBlockHeader::default())
+            .unwrap();
 
         let genesis_config = Self::build_genesis_config(with_preferred_sequencer);
         let runtime: Self = Default::default();
@@ -682,7 +693,7 @@ impl TestRuntime<DefaultContext, MockDaSpec> {
             .sequencer_registry
             .call(
                 register_message,
-                &C::new(REGULAR_SEQUENCER_ROLLUP, 1),
+                &C::new(REGULAR_SEQUENCER_ROLLUP, Vec::new(), [0; 32], 1),
                 &mut working_set,
             )
             .unwrap();
@@ -716,6 +727,7 @@ impl TestRuntime<DefaultContext, MockDaSpec> {
 
         let initial_slot_height = 0;
         let chain_state_config = ChainStateConfig {
+            chain_id: 0,
             initial_slot_height,
             current_time: Default::default(),
         };
@@ -8,6 +8,8 @@ use crate::{ChainState, TransitionHeight};
 /// Initial configuration of the chain state
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct ChainStateConfig {
+    /// The id of the rollup, which every transaction must be signed for
+    pub chain_id: u64,
     /// Initial slot height
     pub initial_slot_height: TransitionHeight,
     /// The time at genesis
@@ -27,6 +29,7 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
             .set(&config.initial_slot_height, working_set);
 
         self.time.set(&config.current_time, working_set);
+        self.chain_id.set(&config.chain_id, working_set);
         Ok(())
     }
 }
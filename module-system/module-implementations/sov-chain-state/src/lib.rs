@@ -148,6 +148,10 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     /// The height of genesis
     #[state]
     genesis_height: sov_modules_api::StateValue<TransitionHeight>,
+
+    /// The id of the rollup, which every transaction must be signed for
+    #[state]
+    chain_id: sov_modules_api::StateValue<u64>,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -173,6 +177,13 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.genesis_hash.get(working_set)
     }
 
+    /// Returns the id of the rollup, which every transaction must be signed for.
+    pub fn get_chain_id(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.chain_id
+            .get(working_set)
+            .expect("Chain id must be set at initialization")
+    }
+
     /// Returns the genesis height of the module.
     pub fn get_genesis_height(&self, working_set: &mut WorkingSet<C>) -> Option<TransitionHeight> {
         self.genesis_height.get(working_set)
@@ -15,4 +15,10 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
     ) -> RpcResult<TransitionHeight> {
         Ok(self.get_slot_height(working_set))
     }
+
+    /// Get the id of the rollup, which every transaction must be signed for.
+    #[rpc_method(name = "getChainId")]
+    pub fn get_chain_id_rpc(&self, working_set: &mut WorkingSet<C>) -> RpcResult<u64> {
+        Ok(self.get_chain_id(working_set))
+    }
 }
@@ -6,12 +6,14 @@ use crate::ChainStateConfig;
 fn test_config_serialization() {
     let time = Time::new(2, NanoSeconds::new(3).unwrap());
     let config = ChainStateConfig {
+        chain_id: 0,
         initial_slot_height: 1,
         current_time: time,
     };
 
     let data = r#"
     {
+        "chain_id":0,
         "initial_slot_height":1,
         "current_time":{
             "secs":2,
@@ -42,7 +42,7 @@ fn call_test() {
     let set_arg = 999;
     {
         let sender_address = generate_address::<C>("sender");
-        let context = C::new(sender_address, 1);
+        let context = C::new(sender_address, Vec::new(), [0; 32], 1);
 
         let messages = vec![
             create_contract_message(&dev_signer, 0),
@@ -101,7 +101,7 @@ fn failed_transaction_test() {
     evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), working_set);
     {
         let sender_address = generate_address::<C>("sender");
-        let context = C::new(sender_address, 1);
+        let context = C::new(sender_address, Vec::new(), [0; 32], 1);
         let message = create_contract_message(&dev_signer, 0);
         evm.call(message, &context, working_set).unwrap();
     }
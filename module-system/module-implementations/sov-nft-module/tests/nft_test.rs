@@ -40,7 +40,7 @@ fn mints_and_transfers() {
         collection_uri: collection_uri.to_string(),
     };
 
-    let creator_context = DefaultContext::new(creator_address, 1);
+    let creator_context = DefaultContext::new(creator_address, Vec::new(), [0; 32], 1);
 
     // Create Collection
     nft.call(
@@ -122,7 +122,7 @@ fn mints_and_transfers() {
         collection_uri: new_collection_uri.to_string(),
     };
 
-    let creator_context = DefaultContext::new(creator_address, 1);
+    let creator_context = DefaultContext::new(creator_address, Vec::new(), [0; 32], 1);
 
     nft.call(
         create_collection_message,
@@ -145,7 +145,7 @@ fn mints_and_transfers() {
         collection_name: ne_collection_name.to_string(),
     };
 
-    let creator_context = DefaultContext::new(creator_address, 1);
+    let creator_context = DefaultContext::new(creator_address, Vec::new(), [0; 32], 1);
 
     let freeze_response = nft.call(
         freeze_collection_message,
@@ -172,7 +172,7 @@ fn mints_and_transfers() {
         collection_name: collection_name.to_string(),
     };
 
-    let creator_context = DefaultContext::new(creator_address, 1);
+    let creator_context = DefaultContext::new(creator_address, Vec::new(), [0; 32], 1);
     nft.call(
         freeze_collection_message,
         &creator_context,
@@ -193,7 +193,7 @@ fn mints_and_transfers() {
         collection_uri: un_updated_collection_uri.to_string(),
     };
 
-    let creator_context = DefaultContext::new(creator_address, 1);
+    let creator_context = DefaultContext::new(creator_address, Vec::new(), [0; 32], 1);
 
     let update_response = nft.call(
         create_collection_message,
@@ -287,7 +287,7 @@ fn mints_and_transfers() {
 
     // transfer NFT with non-existent token id
     let target_address = private_key_2.default_address();
-    let owner_context = DefaultContext::new(*owner.get_address(), 1);
+    let owner_context = DefaultContext::new(*owner.get_address(), Vec::new(), [0; 32], 1);
     let transfer_nft_message = CallMessage::TransferNft {
         collection_address: collection_address.clone(),
         token_id: 1000,
@@ -312,7 +312,7 @@ fn mints_and_transfers() {
 
     // transfer NFT by owner
     let target_address = private_key_2.default_address();
-    let owner_context = DefaultContext::new(*owner.get_address(), 1);
+    let owner_context = DefaultContext::new(*owner.get_address(), Vec::new(), [0; 32], 1);
     let transfer_nft_message = CallMessage::TransferNft {
         collection_address: collection_address.clone(),
         token_id,
@@ -422,7 +422,7 @@ fn mints_and_transfers() {
     // transfer NFT by owner
     let target_address = private_key_1.default_address();
     let owner = private_key_2.default_address();
-    let owner_context = DefaultContext::new(owner, 1);
+    let owner_context = DefaultContext::new(owner, Vec::new(), [0; 32], 1);
     let transfer_nft_message = CallMessage::TransferNft {
         collection_address: collection_address.clone(),
         token_id,
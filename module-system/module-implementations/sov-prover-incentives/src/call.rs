@@ -22,6 +22,42 @@ pub enum CallMessage {
     VerifyProof(Vec<u8>),
 }
 
+/// This enumeration represents the events emitted by the `sov-prover-incentives` module.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "Event")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Clone)]
+pub enum Event<C: sov_modules_api::Context> {
+    /// A prover bonded coins.
+    BondedProver {
+        /// The address of the prover.
+        prover: C::Address,
+        /// The amount of coins that were bonded.
+        deposit: u64,
+        /// The total bond of the prover after the deposit.
+        total_bond: u64,
+    },
+    /// A prover unbonded all of its coins.
+    UnbondedProver {
+        /// The address of the prover.
+        prover: C::Address,
+        /// The amount of coins that were withdrawn.
+        amount_withdrawn: u64,
+    },
+    /// A prover submitted a valid proof.
+    ProcessedValidProof {
+        /// The address of the prover.
+        prover: C::Address,
+    },
+    /// A prover submitted an invalid proof and was slashed.
+    ProcessedInvalidProof {
+        /// The address of the slashed prover.
+        prover: C::Address,
+    },
+}
+
 impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C, Vm> {
     /// A helper function for the `bond_prover` call. Also used to bond provers
     /// during genesis when no context is available.
@@ -53,9 +89,13 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
         self.bonded_provers.set(prover, &total_balance, working_set);
 
         // Emit the bonding event
-        working_set.add_event(
-            "bonded_prover",
-            &format!("new_deposit: {bond_amount:?}. total_bond: {total_balance:?}"),
+        working_set.emit_event(
+            self,
+            Event::BondedProver {
+                prover: prover.clone(),
+                deposit: bond_amount,
+                total_bond: total_balance,
+            },
         );
 
         Ok(CallResponse::default())
@@ -97,9 +137,12 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
             self.bonded_provers.set(context.sender(), &0, working_set);
 
             // Emit the unbonding event
-            working_set.add_event(
-                "unbonded_prover",
-                &format!("amount_withdrawn: {old_balance:?}"),
+            working_set.emit_event(
+                self,
+                Event::UnbondedProver {
+                    prover: context.sender().clone(),
+                    amount_withdrawn: old_balance,
+                },
             );
         }
 
@@ -144,14 +187,18 @@ impl<C: sov_modules_api::Context, Vm: sov_modules_api::Zkvm> ProverIncentives<C,
             self.bonded_provers
                 .set(context.sender(), &old_balance, working_set);
 
-            working_set.add_event(
-                "processed_valid_proof",
-                &format!("prover: {:?}", context.sender()),
+            working_set.emit_event(
+                self,
+                Event::ProcessedValidProof {
+                    prover: context.sender().clone(),
+                },
             );
         } else {
-            working_set.add_event(
-                "processed_invalid_proof",
-                &format!("slashed_prover: {:?}", context.sender()),
+            working_set.emit_event(
+                self,
+                Event::ProcessedInvalidProof {
+                    prover: context.sender().clone(),
+                },
             );
         }
 
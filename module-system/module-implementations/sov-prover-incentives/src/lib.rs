@@ -57,7 +57,7 @@ impl<C: Context, Vm: Zkvm> sov_modules_api::Module for ProverIncentives<C, Vm> {
 
     type CallMessage = call::CallMessage;
 
-    type Event = ();
+    type Event = call::Event<C>;
 
     fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
         // The initialization logic
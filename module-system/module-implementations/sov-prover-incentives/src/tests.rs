@@ -31,6 +31,7 @@ fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
     (
         sov_bank::BankConfig {
             tokens: vec![token_config],
+            gas_token_address: None,
         },
         prover_address,
     )
@@ -79,7 +80,7 @@ fn test_burn_on_invalid_proof() {
 
     // Process an invalid proof
     {
-        let context = DefaultContext::new(prover_address, 1);
+        let context = DefaultContext::new(prover_address, Vec::new(), [0; 32], 1);
         let proof = MockProof {
             program_id: MOCK_CODE_COMMITMENT,
             is_valid: false,
@@ -115,7 +116,7 @@ fn test_valid_proof() {
 
     // Process a valid proof
     {
-        let context = DefaultContext::new(prover_address, 1);
+        let context = DefaultContext::new(prover_address, Vec::new(), [0; 32], 1);
         let proof = MockProof {
             program_id: MOCK_CODE_COMMITMENT,
             is_valid: true,
@@ -140,7 +141,7 @@ fn test_unbonding() {
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
     let (module, prover_address) = setup(&mut working_set);
-    let context = DefaultContext::new(prover_address, 1);
+    let context = DefaultContext::new(prover_address, Vec::new(), [0; 32], 1);
     let token_address = module
         .bonding_token_address
         .get(&mut working_set)
@@ -191,7 +192,7 @@ fn test_prover_not_bonded() {
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(ProverStorage::with_path(tmpdir.path()).unwrap());
     let (module, prover_address) = setup(&mut working_set);
-    let context = DefaultContext::new(prover_address, 1);
+    let context = DefaultContext::new(prover_address, Vec::new(), [0; 32], 1);
 
     // Unbond the prover
     module
@@ -28,7 +28,7 @@ impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<SequencerAddressResponse<C>> {
         Ok(SequencerAddressResponse {
-            address: self.allowed_sequencers.get(&da_address, working_set),
+            address: self.get_sequencer_rollup_address(&da_address, working_set),
         })
     }
 }
@@ -81,6 +81,7 @@ pub fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
     (
         sov_bank::BankConfig {
             tokens: vec![token_config],
+            gas_token_address: None,
         },
         seq_address,
     )
@@ -5,13 +5,14 @@ use std::path::Path;
 use anyhow::Context;
 use borsh::BorshSerialize;
 use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::params::ArrayParams;
 use jsonrpsee::http_client::HttpClientBuilder;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_accounts::AccountsRpcClient;
 use sov_bank::{BalanceResponse, BankRpcClient};
-use sov_modules_api::clap;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, TxFee};
+use sov_modules_api::{clap, GasUnit};
 
 use crate::wallet_state::{AddressEntry, KeyIdentifier, WalletState};
 use crate::workflows::keys::load_key;
@@ -50,6 +51,21 @@ pub enum RpcWorkflows<C: sov_modules_api::Context> {
         /// (Optional) The nonce to use for the first transaction in the batch (default: the current nonce for the account). Any other transactions will
         /// be signed with sequential nonces starting from this value.
         nonce_override: Option<u64>,
+        /// The maximum amount of gas tokens to pay for each transaction of the batch
+        #[clap(long, default_value_t = 0)]
+        max_fee: u64,
+        /// The price to pay for each dimension of gas on top of the base fee, as a comma separated list (default: zero)
+        #[clap(long, value_delimiter = ',')]
+        priority_fee: Vec<u64>,
+        /// (Optional) The maximum gas each transaction can use per dimension, as a comma separated list
+        #[clap(long, value_delimiter = ',')]
+        gas_limit: Option<Vec<u64>>,
+        /// (Optional) The id of the rollup to sign the transactions for (default: the chain id reported by the rollup)
+        #[clap(long)]
+        chain_id: Option<u64>,
+        /// (Optional) The last slot height at which the transactions of the batch can be executed
+        #[clap(long)]
+        valid_until: Option<u64>,
     },
 }
 
@@ -134,9 +150,28 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
                     amount.unwrap_or_default()
                 );
             }
-            RpcWorkflows::SubmitBatch { nonce_override, .. } => {
+            RpcWorkflows::SubmitBatch {
+                nonce_override,
+                max_fee,
+                priority_fee,
+                gas_limit,
+                chain_id,
+                valid_until,
+                ..
+            } => {
                 let private_key = load_key::<C>(&account.location)?;
+                let mut fee = TxFee::new(
+                    *max_fee,
+                    C::GasUnit::from_arbitrary_dimensions(priority_fee),
+                );
+                if let Some(gas_limit) = gas_limit {
+                    fee = fee.with_gas_limit(C::GasUnit::from_arbitrary_dimensions(gas_limit));
+                }
 
+                let chain_id = match chain_id {
+                    Some(chain_id) => *chain_id,
+                    None => get_chain_id(&client).await?,
+                };
                 let nonce = match nonce_override {
                     Some(nonce) => *nonce,
                     None => get_nonce_for_account(&client, account).await?,
@@ -148,6 +183,9 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
                         Transaction::<C>::new_signed_tx(
                             &private_key,
                             tx.try_to_vec().unwrap(),
+                            chain_id,
+                            *valid_until,
+                            fee.clone(),
                             nonce + offset as u64,
                         )
                         .try_to_vec()
@@ -187,3 +225,12 @@ async fn get_nonce_for_account<C: sov_modules_api::Context + Send + Sync + Seria
         _ => 0,
     })
 }
+
+async fn get_chain_id(client: &(impl ClientT + Send + Sync)) -> Result<u64, anyhow::Error> {
+    client
+        .request("chainState_getChainId", ArrayParams::new())
+        .await
+        .context(
+            "Unable to fetch the chain id from the provided RPC. You can set it with the `--chain-id` option",
+        )
+}
@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use sov_modules_core::{
+    AccessoryWorkingSet, Context, Prefix, StateCodec, StateKeyCodec, StateValueCodec,
+};
+use sov_state::codec::BorshCodec;
+
+use super::traits::StateDequeAccessor;
+
+/// A variant of [`StateDeque`](crate::StateDeque) that stores its elements as
+/// "accessory" state, instead of in the JMT.
+///
+/// # Type parameters
+/// [`AccessoryStateDeque`] is generic over:
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct AccessoryStateDeque<V, Codec = BorshCodec> {
+    _phantom: PhantomData<V>,
+    codec: Codec,
+    prefix: Prefix,
+}
+
+impl<V> AccessoryStateDeque<V> {
+    /// Creates a new [`AccessoryStateDeque`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<V, Codec> AccessoryStateDeque<V, Codec> {
+    /// Creates a new [`AccessoryStateDeque`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        Self {
+            _phantom: PhantomData,
+            codec,
+            prefix,
+        }
+    }
+
+    /// Returns the prefix used when this [`AccessoryStateDeque`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl<'a, V, Codec, C> StateDequeAccessor<V, Codec, AccessoryWorkingSet<'a, C>>
+    for AccessoryStateDeque<V, Codec>
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<V> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    C: Context,
+{
+    /// Returns the prefix used when this [`AccessoryStateDeque`] was created.
+    fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    fn codec(&self) -> &Codec {
+        &self.codec
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_modules_core::WorkingSet;
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::containers::traits::deque_tests::Testable;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_accessory_state_deque() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new("test".as_bytes().to_vec());
+        let state_deque = AccessoryStateDeque::<u32>::new(prefix);
+        state_deque.run_tests(&mut working_set.accessory_state());
+    }
+}
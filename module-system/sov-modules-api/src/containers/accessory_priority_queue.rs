@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+
+use sov_modules_core::{
+    AccessoryWorkingSet, Context, Prefix, StateCodec, StateKeyCodec, StateValueCodec,
+};
+use sov_state::codec::BorshCodec;
+
+use super::traits::StatePriorityQueueAccessor;
+
+/// A variant of [`StatePriorityQueue`](crate::StatePriorityQueue) that stores its
+/// elements as "accessory" state, instead of in the JMT.
+///
+/// # Type parameters
+/// [`AccessoryStatePriorityQueue`] is generic over:
+/// - an ordered key type `K`;
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct AccessoryStatePriorityQueue<K, V, Codec = BorshCodec> {
+    _phantom: (PhantomData<K>, PhantomData<V>),
+    codec: Codec,
+    prefix: Prefix,
+}
+
+impl<K, V> AccessoryStatePriorityQueue<K, V> {
+    /// Creates a new [`AccessoryStatePriorityQueue`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<K, V, Codec> AccessoryStatePriorityQueue<K, V, Codec> {
+    /// Creates a new [`AccessoryStatePriorityQueue`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        Self {
+            _phantom: (PhantomData, PhantomData),
+            codec,
+            prefix,
+        }
+    }
+
+    /// Returns the prefix used when this [`AccessoryStatePriorityQueue`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl<'a, K, V, Codec, C> StatePriorityQueueAccessor<K, V, Codec, AccessoryWorkingSet<'a, C>>
+    for AccessoryStatePriorityQueue<K, V, Codec>
+where
+    K: Ord,
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<(K, u64, V)> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    C: Context,
+{
+    /// Returns the prefix used when this [`AccessoryStatePriorityQueue`] was created.
+    fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    fn codec(&self) -> &Codec {
+        &self.codec
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_modules_core::WorkingSet;
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::containers::traits::priority_queue_tests::Testable;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_accessory_state_priority_queue() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new("test".as_bytes().to_vec());
+        let state_queue = AccessoryStatePriorityQueue::<u32, String>::new(prefix);
+        state_queue.run_tests(&mut working_set.accessory_state());
+    }
+}
@@ -0,0 +1,374 @@
+#[cfg(feature = "native")]
+use sov_modules_core::AccessoryWorkingSet;
+use sov_modules_core::{
+    Context, EncodeKeyLike, Prefix, StateCodec, StateKeyCodec, StateValueCodec, WorkingSet,
+};
+use sov_state::codec::BorshCodec;
+
+use super::traits::{StateMapAccessor, StateMapError};
+use super::{AccessoryStateMap, AccessoryStateValue, StateMap};
+#[cfg(feature = "native")]
+use crate::StateValueAccessor;
+
+/// The maximum number of keys stored in a single page of the key index.
+#[cfg(feature = "native")]
+const KEY_INDEX_PAGE_SIZE: usize = 256;
+
+/// A [`StateMap`] which also maintains a sorted index of its keys, so that its
+/// entries can be listed in order, e.g. by RPC methods.
+///
+/// The entries are stored in the JMT exactly like the entries of a [`StateMap`]
+/// with the same prefix. The index is stored as accessory state and is only
+/// maintained in native mode, so it doesn't affect zk execution. It only covers
+/// the entries written through this container.
+///
+/// Keys are ordered by their encoding, i.e. in the same order as the raw storage
+/// keys of the entries.
+///
+/// # Type parameters
+/// [`IterableStateMap`] is generic over:
+/// - a key type `K`;
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`, used for the entries of the map.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct IterableStateMap<K, V, Codec = BorshCodec> {
+    map: StateMap<K, V, Codec>,
+    index_len: AccessoryStateValue<u64>,
+    // The first encoded key and the id of every page of the index, sorted by key.
+    index_pages: AccessoryStateValue<Vec<(Vec<u8>, u64)>>,
+    // The keys of every page, sorted by their encoding.
+    index_page_keys: AccessoryStateMap<u64, Vec<(Vec<u8>, K)>>,
+    next_index_page_id: AccessoryStateValue<u64>,
+}
+
+impl<K, V> IterableStateMap<K, V> {
+    /// Creates a new [`IterableStateMap`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<K, V, Codec> IterableStateMap<K, V, Codec> {
+    /// Creates a new [`IterableStateMap`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        // The index lives in the accessory state, so its prefixes can't collide
+        // with the entries of the map.
+        Self {
+            index_len: AccessoryStateValue::new(prefix.extended(b"l")),
+            index_pages: AccessoryStateValue::new(prefix.extended(b"d")),
+            index_page_keys: AccessoryStateMap::new(prefix.extended(b"p")),
+            next_index_page_id: AccessoryStateValue::new(prefix.extended(b"n")),
+            map: StateMap::with_codec(prefix, codec),
+        }
+    }
+
+    /// Returns a reference to the codec used by this [`IterableStateMap`].
+    pub fn codec(&self) -> &Codec {
+        self.map.codec()
+    }
+
+    /// Returns the prefix used when this [`IterableStateMap`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        self.map.prefix()
+    }
+}
+
+impl<K, V, Codec> IterableStateMap<K, V, Codec>
+where
+    K: Clone + borsh::BorshSerialize + borsh::BorshDeserialize,
+    Codec: StateCodec,
+    Codec::KeyCodec: StateKeyCodec<K>,
+    Codec::ValueCodec: StateValueCodec<V>,
+{
+    /// Inserts a key-value pair into the map, and adds the key to the index.
+    pub fn set<C: Context>(&self, key: &K, value: &V, working_set: &mut WorkingSet<C>) {
+        self.map.set(key, value, working_set);
+        #[cfg(feature = "native")]
+        self.index_key(key, &mut working_set.accessory_state());
+    }
+
+    /// Returns the value corresponding to the key, or [`None`] if the map
+    /// doesn't contain the key.
+    pub fn get<Q, C>(&self, key: &Q, working_set: &mut WorkingSet<C>) -> Option<V>
+    where
+        Codec::KeyCodec: EncodeKeyLike<Q, K>,
+        Q: ?Sized,
+        C: Context,
+    {
+        self.map.get(key, working_set)
+    }
+
+    /// Returns the value corresponding to the key or [`StateMapError`] if key is absent from
+    /// the map.
+    pub fn get_or_err<Q, C>(
+        &self,
+        key: &Q,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<V, StateMapError>
+    where
+        Codec::KeyCodec: EncodeKeyLike<Q, K>,
+        Q: ?Sized,
+        C: Context,
+    {
+        self.map.get_or_err(key, working_set)
+    }
+
+    /// Removes a key from the map and from the index, returning the corresponding
+    /// value (or [`None`] if the key is absent).
+    pub fn remove<C: Context>(&self, key: &K, working_set: &mut WorkingSet<C>) -> Option<V> {
+        let value = self.map.remove(key, working_set);
+        #[cfg(feature = "native")]
+        self.unindex_key(key, &mut working_set.accessory_state());
+        value
+    }
+
+    /// Deletes a key-value pair from the map and its key from the index.
+    ///
+    /// This is equivalent to [`IterableStateMap::remove`], but doesn't deserialize and
+    /// return the value before deletion.
+    pub fn delete<C: Context>(&self, key: &K, working_set: &mut WorkingSet<C>) {
+        self.map.delete(key, working_set);
+        #[cfg(feature = "native")]
+        self.unindex_key(key, &mut working_set.accessory_state());
+    }
+}
+
+#[cfg(feature = "native")]
+impl<K, V, Codec> IterableStateMap<K, V, Codec>
+where
+    K: Clone + borsh::BorshSerialize + borsh::BorshDeserialize,
+    Codec: StateCodec,
+    Codec::KeyCodec: StateKeyCodec<K>,
+    Codec::ValueCodec: StateValueCodec<V>,
+{
+    /// Returns the number of indexed keys.
+    pub fn len<C: Context>(&self, working_set: &mut WorkingSet<C>) -> usize {
+        self.index_len
+            .get(&mut working_set.accessory_state())
+            .unwrap_or_default() as usize
+    }
+
+    /// Returns `true` if no key is indexed.
+    pub fn is_empty<C: Context>(&self, working_set: &mut WorkingSet<C>) -> bool {
+        self.len(working_set) == 0
+    }
+
+    /// Returns up to `limit` indexed keys in ascending order, starting right after
+    /// `start_after`, or from the smallest key if it is [`None`].
+    ///
+    /// To list all the keys page by page, pass the last key of each page as the
+    /// `start_after` of the next one.
+    pub fn keys<C: Context>(
+        &self,
+        start_after: Option<&K>,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<K> {
+        let start_after = start_after.map(|key| self.encode_key(key));
+        let mut working_set = working_set.accessory_state();
+        let pages = self.index_pages.get(&mut working_set).unwrap_or_default();
+        let first_page = start_after
+            .as_ref()
+            .map_or(0, |start_after| page_position(&pages, start_after));
+
+        let mut keys = Vec::new();
+        for (_, page_id) in pages.iter().skip(first_page) {
+            let page_keys = self
+                .index_page_keys
+                .get(page_id, &mut working_set)
+                .unwrap_or_default();
+            for (encoded_key, key) in page_keys {
+                if keys.len() == limit {
+                    return keys;
+                }
+                if start_after
+                    .as_ref()
+                    .map_or(true, |start_after| &encoded_key > start_after)
+                {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// Returns up to `limit` entries in ascending order of their keys, starting right
+    /// after `start_after`, or from the smallest key if it is [`None`].
+    pub fn entries<C: Context>(
+        &self,
+        start_after: Option<&K>,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<(K, V)> {
+        self.keys(start_after, limit, working_set)
+            .into_iter()
+            .filter_map(|key| {
+                let value = self.map.get(&key, working_set)?;
+                Some((key, value))
+            })
+            .collect()
+    }
+
+    fn encode_key(&self, key: &K) -> Vec<u8> {
+        self.codec().key_codec().encode_key(key)
+    }
+
+    fn index_key<C: Context>(&self, key: &K, working_set: &mut AccessoryWorkingSet<C>) {
+        let encoded_key = self.encode_key(key);
+        let mut pages = self.index_pages.get(working_set).unwrap_or_default();
+        if pages.is_empty() {
+            let page_id = self.allocate_page_id(working_set);
+            self.index_page_keys.set(
+                &page_id,
+                &vec![(encoded_key.clone(), key.clone())],
+                working_set,
+            );
+            self.index_pages
+                .set(&vec![(encoded_key, page_id)], working_set);
+            self.index_len.set(&1, working_set);
+            return;
+        }
+
+        let position = page_position(&pages, &encoded_key);
+        let page_id = pages[position].1;
+        let mut page_keys = self
+            .index_page_keys
+            .get(&page_id, working_set)
+            .unwrap_or_default();
+        let Err(offset) = page_keys.binary_search_by(|(other, _)| other.cmp(&encoded_key)) else {
+            return;
+        };
+        page_keys.insert(offset, (encoded_key.clone(), key.clone()));
+
+        let mut pages_changed = false;
+        if offset == 0 {
+            // Only possible for a key smaller than all the others.
+            pages[position].0 = encoded_key;
+            pages_changed = true;
+        }
+        if page_keys.len() > KEY_INDEX_PAGE_SIZE {
+            let upper_keys = page_keys.split_off(page_keys.len() / 2);
+            let upper_page_id = self.allocate_page_id(working_set);
+            pages.insert(position + 1, (upper_keys[0].0.clone(), upper_page_id));
+            self.index_page_keys
+                .set(&upper_page_id, &upper_keys, working_set);
+            pages_changed = true;
+        }
+
+        self.index_page_keys.set(&page_id, &page_keys, working_set);
+        if pages_changed {
+            self.index_pages.set(&pages, working_set);
+        }
+        let len = self.index_len.get(working_set).unwrap_or_default();
+        self.index_len.set(&(len + 1), working_set);
+    }
+
+    fn unindex_key<C: Context>(&self, key: &K, working_set: &mut AccessoryWorkingSet<C>) {
+        let encoded_key = self.encode_key(key);
+        let mut pages = self.index_pages.get(working_set).unwrap_or_default();
+        if pages.is_empty() {
+            return;
+        }
+
+        let position = page_position(&pages, &encoded_key);
+        let page_id = pages[position].1;
+        let mut page_keys = self
+            .index_page_keys
+            .get(&page_id, working_set)
+            .unwrap_or_default();
+        let Ok(offset) = page_keys.binary_search_by(|(other, _)| other.cmp(&encoded_key)) else {
+            return;
+        };
+        page_keys.remove(offset);
+
+        // Pages are never merged, only emptied pages are dropped.
+        if page_keys.is_empty() {
+            self.index_page_keys.delete(&page_id, working_set);
+            pages.remove(position);
+            self.index_pages.set(&pages, working_set);
+        } else {
+            if offset == 0 {
+                pages[position].0 = page_keys[0].0.clone();
+                self.index_pages.set(&pages, working_set);
+            }
+            self.index_page_keys.set(&page_id, &page_keys, working_set);
+        }
+        let len = self.index_len.get(working_set).unwrap_or_default();
+        self.index_len.set(&(len - 1), working_set);
+    }
+
+    fn allocate_page_id<C: Context>(&self, working_set: &mut AccessoryWorkingSet<C>) -> u64 {
+        let page_id = self.next_index_page_id.get(working_set).unwrap_or_default();
+        self.next_index_page_id.set(&(page_id + 1), working_set);
+        page_id
+    }
+}
+
+/// Returns the position of the page which contains `encoded_key`, or would contain
+/// it if it was indexed.
+#[cfg(feature = "native")]
+fn page_position(pages: &[(Vec<u8>, u64)], encoded_key: &[u8]) -> usize {
+    pages
+        .partition_point(|(first_key, _)| first_key.as_slice() <= encoded_key)
+        .saturating_sub(1)
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_iterable_state_map() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        // Big-endian keys are encoded in the same order as the numbers they represent.
+        let map = IterableStateMap::<[u8; 4], u32>::new(Prefix::new(b"test".to_vec()));
+        let key = |n: u32| n.to_be_bytes();
+        // Insert enough keys in a scrambled order to split the index into several pages.
+        let count = 1000;
+        for i in 0..count {
+            let n = i * 7919 % count;
+            map.set(&key(n), &(n * 2), &mut working_set);
+        }
+        // Overwriting a key doesn't index it twice.
+        map.set(&key(0), &0, &mut working_set);
+        for n in (0..count).filter(|n| n % 3 == 0) {
+            assert_eq!(map.remove(&key(n), &mut working_set), Some(n * 2));
+        }
+        // Removing a missing key is a no-op.
+        assert_eq!(map.remove(&key(0), &mut working_set), None);
+
+        let expected_keys: Vec<[u8; 4]> = (0..count).filter(|n| n % 3 != 0).map(key).collect();
+        assert_eq!(map.len(&mut working_set), expected_keys.len());
+
+        let mut keys = Vec::new();
+        loop {
+            let page = map.keys(keys.last(), 100, &mut working_set);
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 100);
+            keys.extend(page);
+        }
+        assert_eq!(keys, expected_keys);
+
+        assert_eq!(
+            map.entries(Some(&key(1)), 3, &mut working_set),
+            vec![(key(2), 4), (key(4), 8), (key(5), 10)]
+        );
+    }
+}
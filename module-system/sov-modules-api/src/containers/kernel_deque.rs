@@ -0,0 +1,97 @@
+use std::marker::PhantomData;
+
+use sov_modules_core::{
+    Context, KernelWorkingSet, Prefix, StateCodec, StateKeyCodec, StateValueCodec,
+};
+use sov_state::codec::BorshCodec;
+
+use super::traits::StateDequeAccessor;
+
+/// A variant of [`StateDeque`](crate::StateDeque) that is stored in the JMT
+/// but can only be accessed from the kernel, through a [`KernelWorkingSet`].
+///
+/// # Type parameters
+/// [`KernelStateDeque`] is generic over:
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct KernelStateDeque<V, Codec = BorshCodec> {
+    _phantom: PhantomData<V>,
+    codec: Codec,
+    prefix: Prefix,
+}
+
+impl<V> KernelStateDeque<V> {
+    /// Creates a new [`KernelStateDeque`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<V, Codec> KernelStateDeque<V, Codec> {
+    /// Creates a new [`KernelStateDeque`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        Self {
+            _phantom: PhantomData,
+            codec,
+            prefix,
+        }
+    }
+
+    /// Returns the prefix used when this [`KernelStateDeque`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl<'a, V, Codec, C> StateDequeAccessor<V, Codec, KernelWorkingSet<'a, C>>
+    for KernelStateDeque<V, Codec>
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<V> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    C: Context,
+{
+    /// Returns the prefix used when this [`KernelStateDeque`] was created.
+    fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    fn codec(&self) -> &Codec {
+        &self.codec
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_mock_da::MockDaSpec;
+    use sov_modules_core::capabilities::mocks::MockKernel;
+    use sov_modules_core::WorkingSet;
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::containers::traits::deque_tests::Testable;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_kernel_state_deque() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new("test".as_bytes().to_vec());
+        let state_deque = KernelStateDeque::<u32>::new(prefix);
+        let kernel = MockKernel::<DefaultContext, MockDaSpec>::new(4, 1);
+        let mut kernel_state = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+        state_deque.run_tests(&mut kernel_state);
+    }
+}
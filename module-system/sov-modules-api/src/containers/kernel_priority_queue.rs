@@ -0,0 +1,99 @@
+use std::marker::PhantomData;
+
+use sov_modules_core::{
+    Context, KernelWorkingSet, Prefix, StateCodec, StateKeyCodec, StateValueCodec,
+};
+use sov_state::codec::BorshCodec;
+
+use super::traits::StatePriorityQueueAccessor;
+
+/// A variant of [`StatePriorityQueue`](crate::StatePriorityQueue) that is stored
+/// in the JMT but can only be accessed from the kernel, through a [`KernelWorkingSet`].
+///
+/// # Type parameters
+/// [`KernelStatePriorityQueue`] is generic over:
+/// - an ordered key type `K`;
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct KernelStatePriorityQueue<K, V, Codec = BorshCodec> {
+    _phantom: (PhantomData<K>, PhantomData<V>),
+    codec: Codec,
+    prefix: Prefix,
+}
+
+impl<K, V> KernelStatePriorityQueue<K, V> {
+    /// Creates a new [`KernelStatePriorityQueue`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<K, V, Codec> KernelStatePriorityQueue<K, V, Codec> {
+    /// Creates a new [`KernelStatePriorityQueue`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        Self {
+            _phantom: (PhantomData, PhantomData),
+            codec,
+            prefix,
+        }
+    }
+
+    /// Returns the prefix used when this [`KernelStatePriorityQueue`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl<'a, K, V, Codec, C> StatePriorityQueueAccessor<K, V, Codec, KernelWorkingSet<'a, C>>
+    for KernelStatePriorityQueue<K, V, Codec>
+where
+    K: Ord,
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<(K, u64, V)> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    C: Context,
+{
+    /// Returns the prefix used when this [`KernelStatePriorityQueue`] was created.
+    fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    fn codec(&self) -> &Codec {
+        &self.codec
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_mock_da::MockDaSpec;
+    use sov_modules_core::capabilities::mocks::MockKernel;
+    use sov_modules_core::WorkingSet;
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::containers::traits::priority_queue_tests::Testable;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_kernel_state_priority_queue() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new("test".as_bytes().to_vec());
+        let state_queue = KernelStatePriorityQueue::<u32, String>::new(prefix);
+        let kernel = MockKernel::<DefaultContext, MockDaSpec>::new(4, 1);
+        let mut kernel_state = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+        state_queue.run_tests(&mut kernel_state);
+    }
+}
@@ -347,14 +347,22 @@ mod tests {
 
         {
             {
-                let mut versioned_state =
-                    working_set.versioned_state(&DefaultContext::new(Address::from([1; 32]), 1));
+                let mut versioned_state = working_set.versioned_state(&DefaultContext::new(
+                    Address::from([1; 32]),
+                    Vec::new(),
+                    [0; 32],
+                    1,
+                ));
                 // Try to read the value from user space with the slot number set to 1. Should fail.
                 assert_eq!(value.get(&mut versioned_state), None);
             }
             // Try to read the value from user space with the slot number set to 4. Should succeed.
-            let mut versioned_state =
-                working_set.versioned_state(&DefaultContext::new(Address::from([1; 32]), 4));
+            let mut versioned_state = working_set.versioned_state(&DefaultContext::new(
+                Address::from([1; 32]),
+                Vec::new(),
+                [0; 32],
+                4,
+            ));
             // Try to read the value from user space with the slot number set to 1. Should fail.
             assert_eq!(value.get(&mut versioned_state), Some(100));
         }
@@ -381,14 +389,22 @@ mod tests {
         {
             use crate::StateValueAccessor;
             {
-                let mut versioned_state =
-                    working_set.versioned_state(&DefaultContext::new(Address::from([1; 32]), 1));
+                let mut versioned_state = working_set.versioned_state(&DefaultContext::new(
+                    Address::from([1; 32]),
+                    Vec::new(),
+                    [0; 32],
+                    1,
+                ));
                 // Try to read the value from user space with the slot number set to 1. Should fail.
                 assert_eq!(value.get(&mut versioned_state), None);
             }
             // Try to read the value from user space with the slot number set to 2. Should succeed.
-            let mut versioned_state =
-                working_set.versioned_state(&DefaultContext::new(Address::from([1; 32]), 2));
+            let mut versioned_state = working_set.versioned_state(&DefaultContext::new(
+                Address::from([1; 32]),
+                Vec::new(),
+                [0; 32],
+                2,
+            ));
 
             assert_eq!(value.get(&mut versioned_state), Some(100));
         }
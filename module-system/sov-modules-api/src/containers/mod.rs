@@ -1,22 +1,36 @@
+mod accessory_deque;
 mod accessory_map;
+mod accessory_priority_queue;
 mod accessory_value;
 mod accessory_vec;
+mod iterable_map;
 
+mod kernel_deque;
+mod kernel_priority_queue;
 mod kernel_value;
 
+mod deque;
 mod map;
+mod priority_queue;
 mod value;
 mod vec;
 
 mod traits;
+pub use accessory_deque::AccessoryStateDeque;
 pub use accessory_map::AccessoryStateMap;
+pub use accessory_priority_queue::AccessoryStatePriorityQueue;
 pub use accessory_value::AccessoryStateValue;
 pub use accessory_vec::AccessoryStateVec;
+pub use deque::StateDeque;
+pub use iterable_map::IterableStateMap;
+pub use kernel_deque::KernelStateDeque;
+pub use kernel_priority_queue::KernelStatePriorityQueue;
 pub use kernel_value::KernelStateValue;
 pub use map::StateMap;
+pub use priority_queue::StatePriorityQueue;
 pub use traits::{
-    StateMapAccessor, StateMapError, StateValueAccessor, StateValueError, StateVecAccessor,
-    StateVecError,
+    StateDequeAccessor, StateMapAccessor, StateMapError, StatePriorityQueueAccessor,
+    StateValueAccessor, StateValueError, StateVecAccessor, StateVecError,
 };
 pub use value::StateValue;
 pub use vec::StateVec;
@@ -24,11 +38,18 @@ pub use vec::StateVec;
 #[cfg(test)]
 mod test {
     use jmt::Version;
-    use sov_modules_core::{StateReaderAndWriter, Storage, StorageKey, StorageValue, WorkingSet};
-    use sov_state::{DefaultStorageSpec, ProverStorage};
+    use sov_mock_da::{MockBlockHeader, MockDaSpec};
+    use sov_modules_core::{
+        NativeStorage, StateReaderAndWriter, Storage, StorageKey, StorageValue, WorkingSet,
+    };
+    use sov_prover_storage_manager::ProverStorageManager;
+    use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
+    use sov_state::DefaultStorageSpec;
 
     use crate::default_context::DefaultContext;
 
+    type TestStorageManager = ProverStorageManager<MockDaSpec, DefaultStorageSpec>;
+
     #[derive(Clone)]
     struct TestCase {
         key: StorageKey,
@@ -37,8 +58,23 @@ mod test {
     }
 
     fn get_state_db_version(path: &std::path::Path) -> Version {
-        let state_db = sov_db::state_db::StateDB::with_path(path).unwrap();
-        state_db.get_next_version()
+        let storage = sov_state::ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+        storage.latest_version() + 1
+    }
+
+    fn create_storage_manager(path: &std::path::Path) -> TestStorageManager {
+        TestStorageManager::new(sov_state::config::Config {
+            path: path.to_path_buf(),
+        })
+        .unwrap()
+    }
+
+    fn block_header(height: u64) -> MockBlockHeader {
+        MockBlockHeader {
+            prev_hash: [height as u8; 32].into(),
+            hash: [height as u8 + 1; 32].into(),
+            height,
+        }
     }
 
     fn create_tests() -> Vec<TestCase> {
@@ -67,12 +103,13 @@ mod test {
         let path = tempdir.path();
         let tests = create_tests();
         {
-            for test in tests.clone() {
+            for (height, test) in tests.clone().into_iter().enumerate() {
                 let version_before = get_state_db_version(path);
                 assert_eq!(version_before, test.version);
                 {
-                    let prover_storage =
-                        ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+                    let mut storage_manager = create_storage_manager(path);
+                    let header = block_header(height as u64);
+                    let prover_storage = storage_manager.get_native_storage_on(&header).unwrap();
                     let mut working_set: WorkingSet<DefaultContext> =
                         WorkingSet::new(prover_storage.clone());
 
@@ -82,6 +119,10 @@ mod test {
                         .validate_and_commit(cache, &witness)
                         .expect("storage is valid");
                     assert_eq!(test.value, prover_storage.get(&test.key, &witness).unwrap());
+                    storage_manager
+                        .save_change_set(&header, prover_storage)
+                        .unwrap();
+                    storage_manager.finalize(&header).unwrap();
                 }
                 let version_after = get_state_db_version(path);
                 assert_eq!(version_after, test.version + 1)
@@ -90,7 +131,7 @@ mod test {
 
         {
             let version_from_db = get_state_db_version(path);
-            let storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+            let storage = create_storage_manager(path).get_native_storage();
             assert_eq!(version_from_db, (tests.len() + 1) as u64);
             for test in tests {
                 assert_eq!(
@@ -106,7 +147,7 @@ mod test {
         let tempdir = tempfile::tempdir().unwrap();
         let path = tempdir.path();
         {
-            let prover_storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+            let prover_storage = create_storage_manager(path).get_native_storage();
             assert!(prover_storage.is_empty());
         }
 
@@ -114,7 +155,9 @@ mod test {
         let value = StorageValue::from("some_value");
         // First restart
         {
-            let prover_storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+            let mut storage_manager = create_storage_manager(path);
+            let header = block_header(0);
+            let prover_storage = storage_manager.get_native_storage_on(&header).unwrap();
             assert!(prover_storage.is_empty());
             let mut storage: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
             storage.set(&key, value.clone());
@@ -122,11 +165,15 @@ mod test {
             prover_storage
                 .validate_and_commit(cache, &witness)
                 .expect("storage is valid");
+            storage_manager
+                .save_change_set(&header, prover_storage)
+                .unwrap();
+            storage_manager.finalize(&header).unwrap();
         }
 
         // Correctly restart from disk
         {
-            let prover_storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+            let prover_storage = create_storage_manager(path).get_native_storage();
             assert!(!prover_storage.is_empty());
             assert_eq!(
                 value,
@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use sov_modules_core::{Context, Prefix, StateCodec, StateKeyCodec, StateValueCodec, WorkingSet};
+use sov_state::codec::BorshCodec;
+
+use super::traits::StatePriorityQueueAccessor;
+
+/// A priority queue of values ordered by a key, stored as JMT-backed state.
+///
+/// See [`StatePriorityQueueAccessor`] for the available operations.
+///
+/// # Type parameters
+/// [`StatePriorityQueue`] is generic over:
+/// - an ordered key type `K`;
+/// - a value type `V`;
+/// - a [`StateValueCodec`] `Codec`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct StatePriorityQueue<K, V, Codec = BorshCodec> {
+    _phantom: (PhantomData<K>, PhantomData<V>),
+    codec: Codec,
+    prefix: Prefix,
+}
+
+impl<K, V> StatePriorityQueue<K, V> {
+    /// Creates a new [`StatePriorityQueue`] with the given prefix and the default
+    /// [`StateValueCodec`] (i.e. [`BorshCodec`]).
+    pub fn new(prefix: Prefix) -> Self {
+        Self::with_codec(prefix, BorshCodec)
+    }
+}
+
+impl<K, V, Codec> StatePriorityQueue<K, V, Codec> {
+    /// Creates a new [`StatePriorityQueue`] with the given prefix and [`StateValueCodec`].
+    pub fn with_codec(prefix: Prefix, codec: Codec) -> Self {
+        Self {
+            _phantom: (PhantomData, PhantomData),
+            codec,
+            prefix,
+        }
+    }
+
+    /// Returns the prefix used when this [`StatePriorityQueue`] was created.
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl<K, V, Codec, C> StatePriorityQueueAccessor<K, V, Codec, WorkingSet<C>>
+    for StatePriorityQueue<K, V, Codec>
+where
+    K: Ord,
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<(K, u64, V)> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    C: Context,
+{
+    /// Returns the prefix used when this [`StatePriorityQueue`] was created.
+    fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    fn codec(&self) -> &Codec {
+        &self.codec
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod test {
+    use sov_state::{DefaultStorageSpec, ProverStorage};
+
+    use super::*;
+    use crate::containers::traits::priority_queue_tests::Testable;
+    use crate::default_context::DefaultContext;
+
+    #[test]
+    fn test_state_priority_queue() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = ProverStorage::<DefaultStorageSpec>::with_path(tmpdir.path()).unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+
+        let prefix = Prefix::new("test".as_bytes().to_vec());
+        let state_queue = StatePriorityQueue::<u32, String>::new(prefix);
+        state_queue.run_tests(&mut working_set);
+    }
+}
@@ -0,0 +1,258 @@
+use sov_modules_core::{Prefix, StateCodec, StateKeyCodec, StateReaderAndWriter, StateValueCodec};
+
+/// Allows a type to access a double-ended queue of values in state.
+///
+/// The elements are stored in a map from a `u64` index to the element, along
+/// with the index of the front element and the length of the queue, so that
+/// elements can be pushed and popped at both ends in constant time. Indices
+/// wrap around, so the queue can be pushed to at either end indefinitely.
+pub trait StateDequeAccessor<V, Codec, W>
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<V> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    /// Returns the prefix used when this deque was created.
+    fn prefix(&self) -> &Prefix;
+
+    /// Returns the codec used for this deque.
+    fn codec(&self) -> &Codec;
+
+    /// Returns the number of elements in the deque.
+    fn len(&self, working_set: &mut W) -> usize {
+        get_len(self.prefix(), self.codec(), working_set) as usize
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    fn is_empty(&self, working_set: &mut W) -> bool {
+        get_len(self.prefix(), self.codec(), working_set) == 0
+    }
+
+    /// Returns the element at position `index` from the front, or [`None`] if
+    /// the index is out of bounds.
+    fn get(&self, index: usize, working_set: &mut W) -> Option<V> {
+        let index = index as u64;
+        if index >= get_len(self.prefix(), self.codec(), working_set) {
+            return None;
+        }
+        let front = get_front(self.prefix(), self.codec(), working_set);
+        working_set.get_value::<u64, u64, V, Codec>(
+            &elems_prefix(self.prefix()),
+            &front.wrapping_add(index),
+            self.codec(),
+        )
+    }
+
+    /// Returns the front element, or [`None`] if the deque is empty.
+    fn front(&self, working_set: &mut W) -> Option<V> {
+        self.get(0, working_set)
+    }
+
+    /// Returns the back element, or [`None`] if the deque is empty.
+    fn back(&self, working_set: &mut W) -> Option<V> {
+        let len = get_len(self.prefix(), self.codec(), working_set);
+        self.get(len.checked_sub(1)? as usize, working_set)
+    }
+
+    /// Appends an element to the back of the deque.
+    fn push_back(&self, value: &V, working_set: &mut W) {
+        let len = get_len(self.prefix(), self.codec(), working_set);
+        let front = get_front(self.prefix(), self.codec(), working_set);
+        working_set.set_value::<u64, u64, V, Codec>(
+            &elems_prefix(self.prefix()),
+            &front.wrapping_add(len),
+            value,
+            self.codec(),
+        );
+        set_len(self.prefix(), self.codec(), len + 1, working_set);
+    }
+
+    /// Prepends an element to the front of the deque.
+    fn push_front(&self, value: &V, working_set: &mut W) {
+        let len = get_len(self.prefix(), self.codec(), working_set);
+        let front = get_front(self.prefix(), self.codec(), working_set).wrapping_sub(1);
+        working_set.set_value::<u64, u64, V, Codec>(
+            &elems_prefix(self.prefix()),
+            &front,
+            value,
+            self.codec(),
+        );
+        set_front(self.prefix(), self.codec(), front, working_set);
+        set_len(self.prefix(), self.codec(), len + 1, working_set);
+    }
+
+    /// Removes the front element and returns it, or [`None`] if the deque is empty.
+    fn pop_front(&self, working_set: &mut W) -> Option<V> {
+        let len = get_len(self.prefix(), self.codec(), working_set).checked_sub(1)?;
+        let front = get_front(self.prefix(), self.codec(), working_set);
+        let value = working_set.remove_value::<u64, u64, V, Codec>(
+            &elems_prefix(self.prefix()),
+            &front,
+            self.codec(),
+        )?;
+        set_front(
+            self.prefix(),
+            self.codec(),
+            front.wrapping_add(1),
+            working_set,
+        );
+        set_len(self.prefix(), self.codec(), len, working_set);
+        Some(value)
+    }
+
+    /// Removes the back element and returns it, or [`None`] if the deque is empty.
+    fn pop_back(&self, working_set: &mut W) -> Option<V> {
+        let len = get_len(self.prefix(), self.codec(), working_set).checked_sub(1)?;
+        let front = get_front(self.prefix(), self.codec(), working_set);
+        let value = working_set.remove_value::<u64, u64, V, Codec>(
+            &elems_prefix(self.prefix()),
+            &front.wrapping_add(len),
+            self.codec(),
+        )?;
+        set_len(self.prefix(), self.codec(), len, working_set);
+        Some(value)
+    }
+
+    /// Removes all elements from the deque.
+    fn clear(&self, working_set: &mut W) {
+        let len = get_len(self.prefix(), self.codec(), working_set);
+        let front = get_front(self.prefix(), self.codec(), working_set);
+        for i in 0..len {
+            working_set.delete_value::<u64, u64, Codec>(
+                &elems_prefix(self.prefix()),
+                &front.wrapping_add(i),
+                self.codec(),
+            );
+        }
+        working_set.delete_singleton(&front_prefix(self.prefix()));
+        working_set.delete_singleton(&len_prefix(self.prefix()));
+    }
+}
+
+// Like for `StateVec`, the length, the front index and the elements get their
+// own prefixes, so as not to rely on the key layout of singletons and maps.
+fn len_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"l")
+}
+
+fn front_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"f")
+}
+
+fn elems_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"e")
+}
+
+fn get_len<Codec, W>(prefix: &Prefix, codec: &Codec, working_set: &mut W) -> u64
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set
+        .get_singleton(&len_prefix(prefix), codec)
+        .unwrap_or_default()
+}
+
+fn set_len<Codec, W>(prefix: &Prefix, codec: &Codec, len: u64, working_set: &mut W)
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.set_singleton(&len_prefix(prefix), &len, codec);
+}
+
+fn get_front<Codec, W>(prefix: &Prefix, codec: &Codec, working_set: &mut W) -> u64
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set
+        .get_singleton(&front_prefix(prefix), codec)
+        .unwrap_or_default()
+}
+
+fn set_front<Codec, W>(prefix: &Prefix, codec: &Codec, index: u64, working_set: &mut W)
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.set_singleton(&front_prefix(prefix), &index, codec);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::VecDeque;
+
+    use sov_state::codec::BorshCodec;
+
+    use super::*;
+
+    pub trait Testable<W>: StateDequeAccessor<u32, BorshCodec, W>
+    where
+        W: StateReaderAndWriter,
+    {
+        /// Applies the same operations to the deque and to a [`VecDeque`], and
+        /// checks that their contents always match.
+        fn run_tests(&self, working_set: &mut W) {
+            let mut expected = VecDeque::new();
+            for i in 0..10 {
+                if i % 2 == 0 {
+                    self.push_back(&i, working_set);
+                    expected.push_back(i);
+                } else {
+                    self.push_front(&i, working_set);
+                    expected.push_front(i);
+                }
+                check_contents(self, &expected, working_set);
+            }
+
+            assert_eq!(self.pop_front(working_set), expected.pop_front());
+            assert_eq!(self.pop_back(working_set), expected.pop_back());
+            check_contents(self, &expected, working_set);
+            assert_eq!(self.get(expected.len(), working_set), None);
+
+            // Drain from the front past the initial front index, then refill.
+            while let Some(value) = expected.pop_front() {
+                assert_eq!(self.pop_front(working_set), Some(value));
+            }
+            assert_eq!(self.pop_front(working_set), None);
+            assert_eq!(self.pop_back(working_set), None);
+            assert_eq!(self.front(working_set), None);
+            assert_eq!(self.back(working_set), None);
+            for i in 20..25 {
+                self.push_front(&i, working_set);
+                expected.push_front(i);
+            }
+            check_contents(self, &expected, working_set);
+
+            self.clear(working_set);
+            expected.clear();
+            check_contents(self, &expected, working_set);
+            self.push_back(&42, working_set);
+            expected.push_back(42);
+            check_contents(self, &expected, working_set);
+        }
+    }
+
+    impl<T: StateDequeAccessor<u32, BorshCodec, W>, W> Testable<W> for T where W: StateReaderAndWriter {}
+
+    fn check_contents<S, W>(deque: &S, expected: &VecDeque<u32>, working_set: &mut W)
+    where
+        S: StateDequeAccessor<u32, BorshCodec, W>,
+        W: StateReaderAndWriter,
+    {
+        assert_eq!(deque.len(working_set), expected.len());
+        assert_eq!(deque.is_empty(working_set), expected.is_empty());
+        assert_eq!(deque.front(working_set).as_ref(), expected.front());
+        assert_eq!(deque.back(working_set).as_ref(), expected.back());
+        let contents: Vec<u32> = (0..expected.len())
+            .map(|i| deque.get(i, working_set).unwrap())
+            .collect();
+        assert_eq!(contents, Vec::from(expected.clone()));
+    }
+}
@@ -26,6 +26,16 @@ where
     /// Returns the prefix used when this map was created.
     fn prefix(&self) -> &Prefix;
 
+    /// Returns the raw key under which the value of `key` is stored, e.g. to request a proof of
+    /// that value from the `state_getWithProof` RPC method.
+    fn storage_key<Q>(&self, key: &Q) -> StorageKey
+    where
+        Codec::KeyCodec: EncodeKeyLike<Q, K>,
+        Q: ?Sized,
+    {
+        StorageKey::new(self.prefix(), key, self.codec().key_codec())
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// The key may be any borrowed form of the
@@ -90,10 +100,7 @@ where
         Q: ?Sized,
     {
         self.get(key, working_set).ok_or_else(|| {
-            StateMapError::MissingValue(
-                self.prefix().clone(),
-                StorageKey::new(self.prefix(), key, self.codec().key_codec()),
-            )
+            StateMapError::MissingValue(self.prefix().clone(), self.storage_key(key))
         })
     }
 
@@ -121,10 +128,7 @@ where
         Q: ?Sized,
     {
         self.remove(key, working_set).ok_or_else(|| {
-            StateMapError::MissingValue(
-                self.prefix().clone(),
-                StorageKey::new(self.prefix(), key, self.codec().key_codec()),
-            )
+            StateMapError::MissingValue(self.prefix().clone(), self.storage_key(key))
         })
     }
 
@@ -1,7 +1,15 @@
+mod deque;
 mod map;
+mod priority_queue;
 mod value;
 mod vec;
+#[cfg(test)]
+pub use deque::tests as deque_tests;
+pub use deque::StateDequeAccessor;
 pub use map::{StateMapAccessor, StateMapError};
+#[cfg(test)]
+pub use priority_queue::tests as priority_queue_tests;
+pub use priority_queue::StatePriorityQueueAccessor;
 pub use value::{StateValueAccessor, StateValueError};
 #[cfg(test)]
 pub use vec::tests as vec_tests;
@@ -0,0 +1,274 @@
+use sov_modules_core::{Prefix, StateCodec, StateKeyCodec, StateReaderAndWriter, StateValueCodec};
+
+/// An element of a priority queue along with its key and its insertion sequence number,
+/// which orders the elements with equal keys.
+type Entry<K, V> = (K, u64, V);
+
+/// Allows a type to access a priority queue of values in state, ordered by a key.
+///
+/// The queue is a binary min-heap stored in a map from a `u64` index to the
+/// elements, so pushing and popping an element takes a logarithmic number of
+/// state accesses. Elements with the smallest key are popped first, and elements
+/// with equal keys are popped in insertion order. Each element is stored along
+/// with its key and its insertion sequence number, as a `(K, u64, V)` tuple.
+pub trait StatePriorityQueueAccessor<K, V, Codec, W>
+where
+    K: Ord,
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<Entry<K, V>> + StateValueCodec<u64>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    /// Returns the prefix used when this priority queue was created.
+    fn prefix(&self) -> &Prefix;
+
+    /// Returns the codec used for this priority queue.
+    fn codec(&self) -> &Codec;
+
+    /// Returns the number of elements in the priority queue.
+    fn len(&self, working_set: &mut W) -> usize {
+        get_counter(&len_prefix(self.prefix()), self.codec(), working_set) as usize
+    }
+
+    /// Returns `true` if the priority queue contains no elements.
+    fn is_empty(&self, working_set: &mut W) -> bool {
+        self.len(working_set) == 0
+    }
+
+    /// Returns the element with the smallest key along with its key, without
+    /// removing it, or [`None`] if the priority queue is empty.
+    fn peek(&self, working_set: &mut W) -> Option<(K, V)> {
+        let (key, _, value) = get_entry(self.prefix(), self.codec(), 0, working_set)?;
+        Some((key, value))
+    }
+
+    /// Inserts an element with the given key.
+    fn push(&self, key: K, value: V, working_set: &mut W) {
+        let len = get_counter(&len_prefix(self.prefix()), self.codec(), working_set);
+        let sequence_number =
+            get_counter(&sequence_prefix(self.prefix()), self.codec(), working_set);
+        set_counter(
+            &sequence_prefix(self.prefix()),
+            self.codec(),
+            sequence_number + 1,
+            working_set,
+        );
+        let entry = (key, sequence_number, value);
+
+        // Move the parents of the new entry down until its position is found.
+        let mut index = len;
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            let parent_entry = get_entry(self.prefix(), self.codec(), parent, working_set)
+                .expect("Priority queue entries must be contiguous");
+            if !precedes(&entry, &parent_entry) {
+                break;
+            }
+            set_entry(
+                self.prefix(),
+                self.codec(),
+                index,
+                &parent_entry,
+                working_set,
+            );
+            index = parent;
+        }
+        set_entry(self.prefix(), self.codec(), index, &entry, working_set);
+        set_counter(
+            &len_prefix(self.prefix()),
+            self.codec(),
+            len + 1,
+            working_set,
+        );
+    }
+
+    /// Removes the element with the smallest key and returns it along with its
+    /// key, or [`None`] if the priority queue is empty.
+    fn pop(&self, working_set: &mut W) -> Option<(K, V)> {
+        let len =
+            get_counter(&len_prefix(self.prefix()), self.codec(), working_set).checked_sub(1)?;
+        let last_entry = working_set.remove_value::<u64, u64, Entry<K, V>, Codec>(
+            &elems_prefix(self.prefix()),
+            &len,
+            self.codec(),
+        )?;
+        set_counter(&len_prefix(self.prefix()), self.codec(), len, working_set);
+        if len == 0 {
+            return Some((last_entry.0, last_entry.2));
+        }
+
+        let (key, _, value) = get_entry(self.prefix(), self.codec(), 0, working_set)
+            .expect("Priority queue entries must be contiguous");
+        // Move the smallest children of the last entry up until its position is found.
+        let mut index = 0;
+        loop {
+            let left = 2 * index + 1;
+            if left >= len {
+                break;
+            }
+            let mut child = left;
+            let mut child_entry = get_entry(self.prefix(), self.codec(), left, working_set)
+                .expect("Priority queue entries must be contiguous");
+            if left + 1 < len {
+                let right_entry = get_entry(self.prefix(), self.codec(), left + 1, working_set)
+                    .expect("Priority queue entries must be contiguous");
+                if precedes(&right_entry, &child_entry) {
+                    child = left + 1;
+                    child_entry = right_entry;
+                }
+            }
+            if !precedes(&child_entry, &last_entry) {
+                break;
+            }
+            set_entry(
+                self.prefix(),
+                self.codec(),
+                index,
+                &child_entry,
+                working_set,
+            );
+            index = child;
+        }
+        set_entry(self.prefix(), self.codec(), index, &last_entry, working_set);
+        Some((key, value))
+    }
+
+    /// Removes all elements from the priority queue.
+    fn clear(&self, working_set: &mut W) {
+        let len = get_counter(&len_prefix(self.prefix()), self.codec(), working_set);
+        for index in 0..len {
+            working_set.delete_value::<u64, u64, Codec>(
+                &elems_prefix(self.prefix()),
+                &index,
+                self.codec(),
+            );
+        }
+        working_set.delete_singleton(&len_prefix(self.prefix()));
+    }
+}
+
+/// Returns `true` if `entry` must be popped before `other`.
+fn precedes<K: Ord, V>(entry: &Entry<K, V>, other: &Entry<K, V>) -> bool {
+    (&entry.0, entry.1) < (&other.0, other.1)
+}
+
+// The length, the next sequence number and the elements get their own prefixes,
+// so as not to rely on the key layout of singletons and maps.
+fn len_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"l")
+}
+
+fn sequence_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"s")
+}
+
+fn elems_prefix(prefix: &Prefix) -> Prefix {
+    prefix.extended(b"e")
+}
+
+fn get_counter<Codec, W>(prefix: &Prefix, codec: &Codec, working_set: &mut W) -> u64
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.get_singleton(prefix, codec).unwrap_or_default()
+}
+
+fn set_counter<Codec, W>(prefix: &Prefix, codec: &Codec, value: u64, working_set: &mut W)
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.set_singleton(prefix, &value, codec);
+}
+
+fn get_entry<K, V, Codec, W>(
+    prefix: &Prefix,
+    codec: &Codec,
+    index: u64,
+    working_set: &mut W,
+) -> Option<Entry<K, V>>
+where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<Entry<K, V>>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.get_value::<u64, u64, Entry<K, V>, Codec>(&elems_prefix(prefix), &index, codec)
+}
+
+fn set_entry<K, V, Codec, W>(
+    prefix: &Prefix,
+    codec: &Codec,
+    index: u64,
+    entry: &Entry<K, V>,
+    working_set: &mut W,
+) where
+    Codec: StateCodec,
+    Codec::ValueCodec: StateValueCodec<Entry<K, V>>,
+    Codec::KeyCodec: StateKeyCodec<u64>,
+    W: StateReaderAndWriter,
+{
+    working_set.set_value::<u64, u64, Entry<K, V>, Codec>(
+        &elems_prefix(prefix),
+        &index,
+        entry,
+        codec,
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use sov_state::codec::BorshCodec;
+
+    use super::*;
+
+    pub trait Testable<W>: StatePriorityQueueAccessor<u32, String, BorshCodec, W>
+    where
+        W: StateReaderAndWriter,
+    {
+        fn run_tests(&self, working_set: &mut W) {
+            assert_eq!(self.pop(working_set), None);
+            assert_eq!(self.peek(working_set), None);
+
+            // Keys in a scrambled order, with duplicates.
+            let keys: Vec<u32> = (0..50).map(|i| i * 17 % 20).collect();
+            for (i, key) in keys.iter().enumerate() {
+                self.push(*key, format!("{key}-{i}"), working_set);
+            }
+            assert_eq!(self.len(working_set), keys.len());
+
+            let mut expected: Vec<(u32, String)> = keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (*key, format!("{key}-{i}")))
+                .collect();
+            // Elements with equal keys are popped in insertion order.
+            expected.sort_by_key(|(key, _)| *key);
+
+            for (i, expected) in expected.into_iter().enumerate() {
+                if i == 25 {
+                    // Pushing a key smaller than all the remaining ones makes it the next one.
+                    self.push(0, "first".to_string(), working_set);
+                    assert_eq!(self.pop(working_set), Some((0, "first".to_string())));
+                }
+                assert_eq!(self.peek(working_set), Some(expected.clone()));
+                assert_eq!(self.pop(working_set), Some(expected));
+            }
+            assert!(self.is_empty(working_set));
+
+            self.push(2, "two".to_string(), working_set);
+            self.push(1, "one".to_string(), working_set);
+            self.clear(working_set);
+            assert!(self.is_empty(working_set));
+            assert_eq!(self.pop(working_set), None);
+        }
+    }
+
+    impl<T: StatePriorityQueueAccessor<u32, String, BorshCodec, W>, W> Testable<W> for T where
+        W: StateReaderAndWriter
+    {
+    }
+}
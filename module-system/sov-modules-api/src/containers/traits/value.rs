@@ -1,4 +1,4 @@
-use sov_modules_core::{Prefix, StateCodec, StateReaderAndWriter, StateValueCodec};
+use sov_modules_core::{Prefix, StateCodec, StateReaderAndWriter, StateValueCodec, StorageKey};
 use thiserror::Error;
 
 /// Error type for getters from state values method.
@@ -21,6 +21,12 @@ where
     /// Returns the codec used for this value
     fn codec(&self) -> &Codec;
 
+    /// Returns the raw key under which the value is stored, e.g. to request a proof of the value
+    /// from the `state_getWithProof` RPC method.
+    fn storage_key(&self) -> StorageKey {
+        StorageKey::singleton(self.prefix())
+    }
+
     /// Sets the value.
     fn set(&self, value: &V, working_set: &mut W) {
         working_set.set_singleton(self.prefix(), value, self.codec())
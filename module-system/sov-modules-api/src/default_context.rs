@@ -16,6 +16,10 @@ use crate::default_signature::{DefaultPublicKey, DefaultSignature};
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DefaultContext {
     pub sender: Address,
+    /// The DA address of the sequencer which posted the transaction
+    sequencer: Vec<u8>,
+    /// The hash of the raw transaction
+    tx_hash: [u8; 32],
     /// The height to report. This is set by the kernel when the context is created
     visible_height: u64,
 }
@@ -39,9 +43,19 @@ impl Context for DefaultContext {
         &self.sender
     }
 
-    fn new(sender: Self::Address, height: u64) -> Self {
+    fn sequencer(&self) -> &[u8] {
+        &self.sequencer
+    }
+
+    fn tx_hash(&self) -> &[u8; 32] {
+        &self.tx_hash
+    }
+
+    fn new(sender: Self::Address, sequencer: Vec<u8>, tx_hash: [u8; 32], height: u64) -> Self {
         Self {
             sender,
+            sequencer,
+            tx_hash,
             visible_height: height,
         }
     }
@@ -55,6 +69,10 @@ impl Context for DefaultContext {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ZkDefaultContext {
     pub sender: Address,
+    /// The DA address of the sequencer which posted the transaction
+    sequencer: Vec<u8>,
+    /// The hash of the raw transaction
+    tx_hash: [u8; 32],
     /// The height to report. This is set by the kernel when the context is created
     visible_height: u64,
 }
@@ -77,9 +95,19 @@ impl Context for ZkDefaultContext {
         &self.sender
     }
 
-    fn new(sender: Self::Address, height: u64) -> Self {
+    fn sequencer(&self) -> &[u8] {
+        &self.sequencer
+    }
+
+    fn tx_hash(&self) -> &[u8; 32] {
+        &self.tx_hash
+    }
+
+    fn new(sender: Self::Address, sequencer: Vec<u8>, tx_hash: [u8; 32], height: u64) -> Self {
         Self {
             sender,
+            sequencer,
+            tx_hash,
             visible_height: height,
         }
     }
@@ -1,4 +1,4 @@
-use sov_modules_core::{AccessoryWorkingSet, Context, Spec, Storage, WorkingSet};
+use sov_modules_core::{AccessoryWorkingSet, Context, GasMeter, Spec, Storage, WorkingSet};
 use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 
 use crate::transaction::Transaction;
@@ -8,20 +8,39 @@ pub trait TxHooks {
     type Context: Context;
 
     /// Runs just before a transaction is dispatched to an appropriate module.
+    /// This is where the fee of the transaction is reserved and the gas meter of the
+    /// working set is initialized, pricing gas at the `base_fee` set by the kernel.
     /// TODO: Why does it return address?
     /// Does it implies that it should do signature verification.
     /// Can other code rely on that assumption?
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
+        base_fee: &<Self::Context as Context>::GasUnit,
         working_set: &mut WorkingSet<Self::Context>,
     ) -> anyhow::Result<<Self::Context as Spec>::Address>;
 
     /// Runs after the tx is dispatched to an appropriate module.
-    /// IF this hook returns error rollup panics
+    /// Receives the gas meter of the transaction, so that the consumed fee can be paid
+    /// and the unused part of the reserved fee can be refunded.
+    /// If this hook returns Err, the transaction is reverted, [`TxHooks::refund_tx_hook`] runs
+    /// instead and no fee is recorded in its receipt.
     fn post_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
+        ctx: &Self::Context,
+        gas_meter: &GasMeter<<Self::Context as Context>::GasUnit>,
+        working_set: &mut WorkingSet<Self::Context>,
+    ) -> anyhow::Result<()>;
+
+    /// Runs in place of [`TxHooks::post_dispatch_tx_hook`] when it fails, once the changes of the
+    /// transaction have been reverted. Refunds the whole fee reserved before the dispatch and
+    /// increments the nonce of the sender, so that the transaction can't be replayed.
+    /// If this hook returns Err, its changes are reverted too.
+    fn refund_tx_hook(
+        &self,
+        tx: &Transaction<Self::Context>,
+        ctx: &Self::Context,
         working_set: &mut WorkingSet<Self::Context>,
     ) -> anyhow::Result<()>;
 }
@@ -8,6 +8,8 @@ pub mod default_signature;
 mod encode;
 pub mod hooks;
 mod pub_key_hex;
+#[cfg(feature = "native")]
+pub mod rpc;
 
 #[cfg(feature = "macros")]
 mod reexport_macros;
@@ -30,13 +32,17 @@ use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "native")]
 pub use clap;
+#[cfg(feature = "native")]
+pub use schemars;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "native")]
 pub use sov_modules_core::PrivateKey;
 pub use sov_modules_core::{
-    runtime, AccessoryWorkingSet, Address, AddressBech32, CallResponse, Context, DispatchCall,
-    EncodeCall, GasUnit, Genesis, Module, ModuleCallJsonSchema, ModuleError, ModuleError as Error,
-    ModuleInfo, ModulePrefix, PublicKey, Signature, Spec, StateCheckpoint, WorkingSet,
+    module_event, module_event_kind, runtime, AccessoryWorkingSet, Address, AddressBech32,
+    CallResponse, Context, DispatchCall, EncodeCall, GasMeter, GasUnit, Genesis, Module,
+    ModuleCallJsonSchema, ModuleError, ModuleError as Error, ModuleInfo, ModulePrefix, PublicKey,
+    RuntimeEventJsonSchema, RuntimeEventProcessor, Signature, Spec, StateAccessGasConfig,
+    StateCheckpoint, WorkingSet,
 };
 pub use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 pub use sov_rollup_interface::services::da::SlotData;
@@ -47,7 +53,10 @@ pub use sov_rollup_interface::zk::{
 pub use sov_rollup_interface::{digest, BasicAddress, RollupAddress};
 
 pub mod prelude {
-    pub use super::{StateMapAccessor, StateValueAccessor, StateVecAccessor};
+    pub use super::{
+        StateDequeAccessor, StateMapAccessor, StatePriorityQueueAccessor, StateValueAccessor,
+        StateVecAccessor,
+    };
 }
 
 pub mod optimistic {
@@ -59,7 +68,7 @@ pub mod da {
 }
 
 pub mod storage {
-    pub use sov_rollup_interface::storage::StorageManager;
+    pub use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
 }
 
 /// A type that can't be instantiated.
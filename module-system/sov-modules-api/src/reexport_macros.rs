@@ -2,7 +2,10 @@
 /// type.
 #[cfg(feature = "macros")]
 pub use sov_modules_macros::DispatchCall;
-/// Derives the <runtime_name>Event enum for a given runtime.
+/// Derives the <runtime_name>Event enum for a given runtime, along with the
+/// [`RuntimeEventProcessor`](trait.RuntimeEventProcessor.html) trait and, with the
+/// `native` feature, the [`RuntimeEventJsonSchema`](trait.RuntimeEventJsonSchema.html)
+/// trait.
 #[cfg(feature = "macros")]
 pub use sov_modules_macros::Event;
 /// Derives the [`Genesis`](trait.Genesis.html) trait for the underlying runtime
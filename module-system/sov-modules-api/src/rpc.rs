@@ -0,0 +1,135 @@
+//! Historical queries for the RPC methods generated by `rpc_gen`, and proofs of the state
+//! served by the `state_getWithProof` method.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use sov_modules_core::{
+    CacheKey, Context, NativeStorage, Spec, Storage, StorageKey, StorageProof, Version, WorkingSet,
+};
+pub use sov_rollup_interface::rpc::SlotIdentifier;
+use sov_rollup_interface::rpc::{LedgerRpcProvider, QueryMode};
+pub use sov_state_verifier::{JmtProof, StateProof};
+
+use crate::utils::to_jsonrpsee_error_object;
+
+const HISTORICAL_QUERY_ERROR: &str = "HISTORICAL_QUERY_ERROR";
+const STATE_RPC_ERROR: &str = "STATE_RPC_ERROR";
+
+/// Resolves the slots targeted by historical RPC queries.
+pub trait SlotResolver: Send + Sync {
+    /// Returns the number of the slot, or [`None`] if the slot is unknown.
+    fn resolve_slot_number(&self, slot: &SlotIdentifier) -> anyhow::Result<Option<u64>>;
+}
+
+impl<L: LedgerRpcProvider + Send + Sync> SlotResolver for L {
+    fn resolve_slot_number(&self, slot: &SlotIdentifier) -> anyhow::Result<Option<u64>> {
+        match slot {
+            SlotIdentifier::Number(number) => Ok(Some(*number)),
+            SlotIdentifier::Hash(hash) => Ok(self
+                .get_slot_by_hash::<(), ()>(hash, QueryMode::Compact)?
+                .map(|slot| slot.number)),
+        }
+    }
+}
+
+/// Returns the version of the state right after the slot with the given number.
+///
+/// The genesis state is written at version 1 and every slot writes the next version,
+/// so slot 0 refers to the genesis state.
+pub fn state_version_after_slot(slot_number: u64) -> Version {
+    slot_number + 1
+}
+
+/// Returns the number of the slot right before the given version of the state, i.e. the inverse
+/// of [`state_version_after_slot`].
+pub fn slot_before_state_version(version: Version) -> u64 {
+    version.saturating_sub(1)
+}
+
+/// Returns a working set on top of the state right after `slot`.
+///
+/// The accessory state isn't versioned, so the working set reads its latest values.
+pub fn working_set_at_slot<C: Context>(
+    storage: &<C as Spec>::Storage,
+    slot_resolver: &Arc<dyn SlotResolver>,
+    slot: &SlotIdentifier,
+) -> Result<WorkingSet<C>, ErrorObjectOwned> {
+    let slot_number = slot_resolver
+        .resolve_slot_number(slot)
+        .map_err(|e| to_jsonrpsee_error_object(e, HISTORICAL_QUERY_ERROR))?
+        .ok_or_else(|| {
+            to_jsonrpsee_error_object(format!("Unknown slot {:?}", slot), HISTORICAL_QUERY_ERROR)
+        })?;
+    let storage = storage
+        .at_version(state_version_after_slot(slot_number))
+        .map_err(|e| to_jsonrpsee_error_object(e, HISTORICAL_QUERY_ERROR))?;
+    Ok(WorkingSet::new(storage))
+}
+
+/// A raw storage key, passed as a `0x`-prefixed hex string.
+#[derive(serde::Deserialize)]
+struct HexKey(#[serde(with = "sov_rollup_interface::rpc::utils::rpc_hex")] Vec<u8>);
+
+/// Creates a new [`RpcModule`] exposing the `state_getWithProof` method.
+///
+/// The method takes a raw storage key and an optional [`SlotIdentifier`], and returns a
+/// [`StateProof`] of the value of the key right after that slot, or after the latest slot if
+/// none is given. The storage keys of module state items are returned by
+/// [`StateMapAccessor::storage_key`](crate::StateMapAccessor::storage_key) and
+/// [`StateValueAccessor::storage_key`](crate::StateValueAccessor::storage_key).
+pub fn get_state_rpc_methods<S>(
+    storage: S,
+    slot_resolver: Arc<dyn SlotResolver>,
+) -> anyhow::Result<RpcModule<()>>
+where
+    S: NativeStorage + Send + Sync + 'static,
+    S::Proof: Into<JmtProof>,
+{
+    let mut rpc = RpcModule::new(());
+
+    rpc.register_method("state_getWithProof", move |params, _| {
+        let mut params = params.sequence();
+        let HexKey(key) = params.next()?;
+        let slot: Option<SlotIdentifier> = params.optional_next()?;
+        get_state_proof(&storage, slot_resolver.as_ref(), key, slot.as_ref())
+            .map_err(|e| to_jsonrpsee_error_object(e, STATE_RPC_ERROR))
+    })?;
+
+    Ok(rpc)
+}
+
+fn get_state_proof<S>(
+    storage: &S,
+    slot_resolver: &dyn SlotResolver,
+    key: Vec<u8>,
+    slot: Option<&SlotIdentifier>,
+) -> anyhow::Result<StateProof>
+where
+    S: NativeStorage,
+    S::Proof: Into<JmtProof>,
+{
+    let version = match slot {
+        Some(slot) => state_version_after_slot(
+            slot_resolver
+                .resolve_slot_number(slot)?
+                .ok_or_else(|| anyhow!("Unknown slot {:?}", slot))?,
+        ),
+        None => storage.latest_version(),
+    };
+    let storage = storage.at_version(version)?;
+    let state_root = storage.get_root_hash(version)?;
+
+    let key = StorageKey::from(CacheKey { key: Arc::new(key) });
+    let StorageProof { key, value, proof } = storage.get_with_proof(key);
+
+    Ok(StateProof {
+        key: key.as_ref().clone(),
+        value: value.map(|value| value.value().to_vec()),
+        proof: proof.into(),
+        state_root: state_root.into(),
+        slot_number: slot_before_state_version(version),
+    })
+}
@@ -180,3 +180,35 @@ fn test_default_signature_roundtrip() {
     sig.verify(&key.pub_key(), msg)
         .expect("Roundtrip verification failed");
 }
+
+#[test]
+fn test_transaction_signs_chain_id_and_expiry() {
+    use crate::transaction::{Transaction, TxFee};
+
+    let key = DefaultPrivateKey::generate();
+    let tx = Transaction::<DefaultContext>::new_signed_tx(
+        &key,
+        vec![1, 2, 3],
+        7,
+        Some(10),
+        TxFee::zero(),
+        0,
+    );
+    tx.verify().unwrap();
+
+    // The signature doesn't cover a transaction replayed on another chain.
+    let replayed = Transaction::<DefaultContext>::new(
+        key.pub_key(),
+        vec![1, 2, 3],
+        tx.signature().clone(),
+        8,
+        Some(10),
+        TxFee::zero(),
+        0,
+    );
+    assert!(replayed.verify().is_err());
+
+    tx.check_validity(7, 10).unwrap();
+    assert!(tx.check_validity(8, 10).is_err());
+    assert!(tx.check_validity(7, 11).is_err());
+}
@@ -1,9 +1,51 @@
+use borsh::BorshSerialize;
 #[cfg(feature = "native")]
 use sov_modules_core::PrivateKey;
-use sov_modules_core::{Context, Signature};
+use sov_modules_core::{Context, GasUnit, Signature};
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 
+/// The fee the sender of a [`Transaction`] is willing to pay for its execution.
+#[derive(
+    Debug, PartialEq, Eq, Clone, borsh::BorshDeserialize, borsh::BorshSerialize, serde::Serialize,
+)]
+pub struct TxFee<GU> {
+    /// The maximum amount of gas tokens the sender is willing to pay. It is reserved from the
+    /// balance of the sender before the transaction is executed, and the unused part is refunded.
+    pub max_fee: u64,
+    /// The price the sender is willing to pay for each dimension of gas on top of the base fee
+    /// of the rollup. It rewards the sequencer which included the transaction.
+    pub priority_fee: GU,
+    /// An optional limit of the gas the transaction can use, per dimension.
+    pub gas_limit: Option<GU>,
+}
+
+impl<GU: GasUnit> TxFee<GU> {
+    /// Creates a fee without any gas limit.
+    pub fn new(max_fee: u64, priority_fee: GU) -> Self {
+        Self {
+            max_fee,
+            priority_fee,
+            gas_limit: None,
+        }
+    }
+
+    /// A fee which doesn't pay for any gas.
+    pub const fn zero() -> Self {
+        Self {
+            max_fee: 0,
+            priority_fee: GU::ZEROED,
+            gas_limit: None,
+        }
+    }
+
+    /// Limits the gas the transaction can use, per dimension.
+    pub fn with_gas_limit(mut self, gas_limit: GU) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+}
+
 /// A Transaction object that is compatible with the module-system/sov-default-stf.
 #[derive(
     Debug, PartialEq, Eq, Clone, borsh::BorshDeserialize, borsh::BorshSerialize, serde::Serialize,
@@ -12,6 +54,9 @@ pub struct Transaction<C: Context> {
     signature: C::Signature,
     pub_key: C::PublicKey,
     runtime_msg: Vec<u8>,
+    chain_id: u64,
+    valid_until: Option<u64>,
+    fee: TxFee<C::GasUnit>,
     nonce: u64,
 }
 
@@ -32,13 +77,55 @@ impl<C: Context> Transaction<C> {
         self.nonce
     }
 
+    /// The fee the sender offers to pay for the execution of the transaction.
+    pub fn fee(&self) -> &TxFee<C::GasUnit> {
+        &self.fee
+    }
+
+    /// The id of the rollup the transaction was signed for.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The last slot height at which the transaction can be executed, if any.
+    pub fn valid_until(&self) -> Option<u64> {
+        self.valid_until
+    }
+
+    /// Check that the transaction was signed for the rollup with the given `chain_id` and has not
+    /// expired at `slot_height`.
+    pub fn check_validity(&self, chain_id: u64, slot_height: u64) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.chain_id == chain_id,
+            "Transaction was signed for chain id {}, but the chain id of the rollup is {}",
+            self.chain_id,
+            chain_id
+        );
+        if let Some(valid_until) = self.valid_until {
+            anyhow::ensure!(
+                slot_height <= valid_until,
+                "Transaction expired at slot height {}, current slot height is {}",
+                valid_until,
+                slot_height
+            );
+        }
+
+        Ok(())
+    }
+
     /// Check whether the transaction has been signed correctly.
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     pub fn verify(&self) -> anyhow::Result<()> {
         let mut serialized_tx =
             Vec::with_capacity(self.runtime_msg().len() + std::mem::size_of::<u64>());
         serialized_tx.extend_from_slice(self.runtime_msg());
-        serialized_tx.extend_from_slice(&self.nonce().to_le_bytes());
+        append_signed_fields(
+            &mut serialized_tx,
+            self.chain_id,
+            self.valid_until,
+            &self.fee,
+            self.nonce,
+        );
         self.signature().verify(&self.pub_key, &serialized_tx)?;
 
         Ok(())
@@ -49,25 +136,55 @@ impl<C: Context> Transaction<C> {
         pub_key: C::PublicKey,
         message: Vec<u8>,
         signature: C::Signature,
+        chain_id: u64,
+        valid_until: Option<u64>,
+        fee: TxFee<C::GasUnit>,
         nonce: u64,
     ) -> Self {
         Self {
             signature,
             runtime_msg: message,
             pub_key,
+            chain_id,
+            valid_until,
+            fee,
             nonce,
         }
     }
 }
 
+/// Appends the fields signed along with the runtime message.
+fn append_signed_fields<GU: GasUnit>(
+    message: &mut Vec<u8>,
+    chain_id: u64,
+    valid_until: Option<u64>,
+    fee: &TxFee<GU>,
+    nonce: u64,
+) {
+    message.extend_from_slice(&chain_id.to_le_bytes());
+    valid_until
+        .serialize(message)
+        .expect("Serialization to a vector is infallible");
+    message.extend_from_slice(&nonce.to_le_bytes());
+    fee.serialize(message)
+        .expect("Serialization to a vector is infallible");
+}
+
 #[cfg(feature = "native")]
 impl<C: Context> Transaction<C> {
     /// New signed transaction.
-    pub fn new_signed_tx(priv_key: &C::PrivateKey, mut message: Vec<u8>, nonce: u64) -> Self {
-        // Since we own the message already, try to add the serialized nonce in-place.
-        // This lets us avoid a copy if the message vec has at least 8 bytes of extra capacity.
+    pub fn new_signed_tx(
+        priv_key: &C::PrivateKey,
+        mut message: Vec<u8>,
+        chain_id: u64,
+        valid_until: Option<u64>,
+        fee: TxFee<C::GasUnit>,
+        nonce: u64,
+    ) -> Self {
+        // Since we own the message already, try to add the signed fields in-place.
+        // This lets us avoid a copy if the message vec has enough extra capacity.
         let original_length = message.len();
-        message.extend_from_slice(&nonce.to_le_bytes());
+        append_signed_fields(&mut message, chain_id, valid_until, &fee, nonce);
 
         let pub_key = priv_key.pub_key();
         let signature = priv_key.sign(&message);
@@ -79,6 +196,9 @@ impl<C: Context> Transaction<C> {
             signature,
             runtime_msg: message,
             pub_key,
+            chain_id,
+            valid_until,
+            fee,
             nonce,
         }
     }
@@ -1,4 +1,8 @@
 use jsonrpsee::types::ErrorObjectOwned;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, RootSchema, Schema, SchemaObject, SubschemaValidation,
+};
 use sov_modules_core::{Context, Spec};
 use sov_rollup_interface::digest::Digest;
 
@@ -14,3 +18,46 @@ pub fn to_jsonrpsee_error_object(err: impl ToString, message: &str) -> ErrorObje
         Some(err.to_string()),
     )
 }
+
+/// Builds the JSON schema of a runtime event enum, with one variant per module.
+/// Each variant is an object with a single property named after the module,
+/// which matches the default externally tagged serde representation of enums.
+pub fn runtime_event_json_schema(
+    title: &str,
+    module_events: impl FnOnce(&mut SchemaGenerator) -> Vec<(&'static str, Schema)>,
+) -> String {
+    let mut generator = SchemaGenerator::default();
+    let variants = module_events(&mut generator)
+        .into_iter()
+        .map(|(module, event_schema)| {
+            let mut variant = SchemaObject {
+                instance_type: Some(InstanceType::Object.into()),
+                ..Default::default()
+            };
+            let object = variant.object();
+            object.properties.insert(module.to_owned(), event_schema);
+            object.required.insert(module.to_owned());
+            object.additional_properties = Some(Box::new(Schema::Bool(false)));
+            Schema::Object(variant)
+        })
+        .collect();
+
+    let schema = SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            title: Some(title.to_owned()),
+            ..Default::default()
+        })),
+        subschemas: Some(Box::new(SubschemaValidation {
+            one_of: Some(variants),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    let root = RootSchema {
+        meta_schema: generator.settings().meta_schema.clone(),
+        definitions: generator.take_definitions(),
+        schema,
+    };
+    serde_json::to_string_pretty(&root)
+        .expect("Failed to serialize JSON schema; this is a bug in the runtime")
+}
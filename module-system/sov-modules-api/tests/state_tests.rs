@@ -1,8 +1,24 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use sov_mock_da::{MockBlockHeader, MockDaSpec};
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
 use sov_modules_api::*;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::config::Config;
+use sov_state::storage::{NativeStorage, StorageProof};
 use sov_state::{ArrayWitness, DefaultStorageSpec, Prefix, ProverStorage, Storage, ZkStorage};
 
+/// Creates a storage on top of the first block, which accepts commits.
+fn new_storage(path: &std::path::Path) -> ProverStorage<DefaultStorageSpec> {
+    let mut storage_manager = ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(Config {
+        path: path.to_path_buf(),
+    })
+    .unwrap();
+    storage_manager
+        .get_native_storage_on(&MockBlockHeader::default())
+        .unwrap()
+}
+
 enum Operation {
     Merge,
     Finalize,
@@ -91,7 +107,7 @@ fn create_state_map(
 fn test_state_map_with_remove() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_remove, after_remove) in create_storage_operations() {
         let key = 1;
         let value = 11;
@@ -110,7 +126,7 @@ fn test_state_map_with_remove() {
 fn test_state_map_with_delete() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_delete, after_delete) in create_storage_operations() {
         let key = 1;
         let value = 11;
@@ -135,7 +151,7 @@ fn create_state_value(value: u32, working_set: &mut WorkingSet<DefaultContext>)
 fn test_state_value_with_remove() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_remove, after_remove) in create_storage_operations() {
         let value = 11;
         let mut working_set = WorkingSet::new(storage.clone());
@@ -153,7 +169,7 @@ fn test_state_value_with_remove() {
 fn test_state_value_with_delete() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_delete, after_delete) in create_storage_operations() {
         let value = 11;
         let mut working_set = WorkingSet::new(storage.clone());
@@ -175,7 +191,7 @@ fn test_witness_round_trip() {
 
     // Native execution
     let witness: ArrayWitness = {
-        let storage = ProverStorage::<DefaultStorageSpec>::with_path(path).unwrap();
+        let storage = new_storage(path);
         let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
         state_value.set(&11, &mut working_set);
         let _ = state_value.get(&mut working_set);
@@ -203,6 +219,157 @@ fn test_witness_round_trip() {
     };
 }
 
+#[test]
+fn test_witness_round_trip_with_many_reads() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+    let storage = new_storage(path);
+
+    // Half of the keys read below are in the state, the other half are missing.
+    {
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        for key in (0..100).step_by(2) {
+            state_map.set(&key, &key, &mut working_set);
+        }
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+    }
+
+    fn read_and_update<C: Context>(
+        state_map: &StateMap<u32, u32>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        for key in 0..100 {
+            let value = state_map.get(&key, working_set);
+            assert_eq!(value.is_some(), key % 2 == 0);
+            state_map.set(&key, &(key + 1), working_set);
+        }
+    }
+
+    // Native execution
+    let witness: ArrayWitness = {
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        read_and_update(&state_map, &mut working_set);
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+        witness
+    };
+
+    {
+        let storage = ZkStorage::<DefaultStorageSpec>::new();
+        let mut working_set: WorkingSet<ZkDefaultContext> =
+            WorkingSet::with_witness(storage.clone(), witness);
+        read_and_update(&state_map, &mut working_set);
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("ZK validation should succeed");
+    };
+}
+
+#[test]
+fn test_storage_opened_on_path_is_read_only() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let state_value = StateValue::new(Prefix::new(vec![0]));
+    {
+        let mut storage_manager =
+            ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(Config {
+                path: tempdir.path().to_path_buf(),
+            })
+            .unwrap();
+        let block_header = MockBlockHeader::default();
+        let storage = storage_manager
+            .get_native_storage_on(&block_header)
+            .unwrap();
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        state_value.set(&11, &mut working_set);
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+        storage.validate_and_commit(cache_log, &witness).unwrap();
+        storage_manager
+            .save_change_set(&block_header, storage)
+            .unwrap();
+        storage_manager.finalize(&block_header).unwrap();
+    }
+
+    // The finalized state can be read, but changes can't be committed, as they would be lost.
+    let storage = ProverStorage::<DefaultStorageSpec>::with_path(tempdir.path()).unwrap();
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+    assert_eq!(state_value.get(&mut working_set), Some(11));
+    state_value.set(&22, &mut working_set);
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    assert!(storage.validate_and_commit(cache_log, &witness).is_err());
+}
+
+#[test]
+fn test_read_at_past_version() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let storage = new_storage(tempdir.path());
+    let state_value = StateValue::new(Prefix::new(vec![0]));
+
+    // The first commit writes version 1, the second one version 2.
+    for value in [11, 22] {
+        let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+        state_value.set(&value, &mut working_set);
+        let (cache_log, witness) = working_set.checkpoint().freeze();
+        storage
+            .validate_and_commit(cache_log, &witness)
+            .expect("Native jmt validation should succeed");
+    }
+
+    let read_at = |version| {
+        let mut working_set: WorkingSet<DefaultContext> =
+            WorkingSet::new(storage.at_version(version).unwrap());
+        state_value.get(&mut working_set)
+    };
+    assert_eq!(read_at(1), Some(11));
+    assert_eq!(read_at(2), Some(22));
+    assert!(storage.at_version(3).is_err());
+
+    // A storage reading a past version is read-only.
+    let past_storage = storage.at_version(1).unwrap();
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(past_storage.clone());
+    state_value.set(&33, &mut working_set);
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    assert!(past_storage
+        .validate_and_commit(cache_log, &witness)
+        .is_err());
+}
+
+#[test]
+fn test_verify_state_proof() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let storage = new_storage(tempdir.path());
+    let state_map = StateMap::<u32, u32>::new(Prefix::new(vec![0]));
+
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+    state_map.set(&1, &11, &mut working_set);
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("Native jmt validation should succeed");
+    let state_root: [u8; 32] = storage.get_root_hash(1).unwrap().into();
+
+    for key in [1, 2] {
+        let StorageProof { key, value, proof } =
+            storage.get_with_proof(state_map.storage_key(&key));
+        let value = value.map(|value| value.value().to_vec());
+        let proof: rpc::JmtProof = proof.into();
+        proof
+            .verify::<sha2::Sha256>(&state_root, key.as_ref(), value.as_deref())
+            .expect("The proof should be valid");
+        assert!(proof
+            .verify::<sha2::Sha256>(&[0; 32], key.as_ref(), value.as_deref())
+            .is_err());
+    }
+}
+
 fn create_state_vec<T: BorshDeserialize + BorshSerialize>(
     values: Vec<T>,
     working_set: &mut WorkingSet<DefaultContext>,
@@ -216,7 +383,7 @@ fn create_state_vec<T: BorshDeserialize + BorshSerialize>(
 fn test_state_vec_len() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_len, after_len) in create_storage_operations() {
         let values = vec![11, 22, 33];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -234,7 +401,7 @@ fn test_state_vec_len() {
 fn test_state_vec_get() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_get, after_get) in create_storage_operations() {
         let values = vec![56, 55, 54];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -265,7 +432,7 @@ fn test_state_vec_get() {
 fn test_state_vec_set() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_set, after_set) in create_storage_operations() {
         let values = vec![56, 55, 54];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -295,7 +462,7 @@ fn test_state_vec_set() {
 fn test_state_vec_push() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_push, after_push) in create_storage_operations() {
         let values = vec![56, 55, 54];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -322,7 +489,7 @@ fn test_state_vec_push() {
 fn test_state_vec_pop() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_pop, after_pop) in create_storage_operations() {
         let values = vec![56, 55, 54];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -351,7 +518,7 @@ fn test_state_vec_pop() {
 fn test_state_vec_set_all() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_set_all, after_set_all) in create_storage_operations() {
         let values = vec![56, 55, 54];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -384,7 +551,7 @@ fn test_state_vec_set_all() {
 fn test_state_vec_diff_type() {
     let tempdir = tempfile::tempdir().unwrap();
     let path = tempdir.path();
-    let storage = ProverStorage::with_path(path).unwrap();
+    let storage = new_storage(path);
     for (before_ops, after_ops) in create_storage_operations() {
         let values = vec![String::from("Hello"), String::from("World")];
         let mut working_set = WorkingSet::new(storage.clone());
@@ -3,17 +3,27 @@
 use core::fmt;
 
 use anyhow::Result;
+use borsh::{BorshDeserialize, BorshSerialize};
 
 /// A gas unit that provides scalar conversion from complex, multi-dimensional types.
-pub trait GasUnit: fmt::Debug + Clone + Send + Sync {
+pub trait GasUnit: fmt::Debug + Clone + Send + Sync + BorshSerialize + BorshDeserialize {
     /// A zeroed instance of the unit.
     const ZEROED: Self;
 
     /// Creates a unit from a multi-dimensional unit with arbitrary dimension.
     fn from_arbitrary_dimensions(dimensions: &[u64]) -> Self;
 
+    /// Returns the value of each dimension of the unit.
+    fn dimensions(&self) -> &[u64];
+
     /// Converts the unit into a scalar value, given a price.
     fn value(&self, price: &Self) -> u64;
+
+    /// Adds the provided unit to this one, dimension by dimension, saturating on overflow.
+    fn saturating_add(&self, rhs: &Self) -> Self;
+
+    /// Returns `true` if none of the dimensions of the unit exceed the ones of `limit`.
+    fn fits_within(&self, limit: &Self) -> bool;
 }
 
 /// A multi-dimensional gas unit.
@@ -34,21 +44,61 @@ impl<const N: usize> GasUnit for TupleGasUnit<N> {
         unit
     }
 
+    fn dimensions(&self) -> &[u64] {
+        self.as_slice()
+    }
+
     fn value(&self, price: &Self) -> u64 {
         self.iter()
             .zip(price.iter().copied())
             .map(|(a, b)| a.saturating_mul(b))
             .fold(0, |a, b| a.saturating_add(b))
     }
+
+    fn saturating_add(&self, rhs: &Self) -> Self {
+        let mut unit = *self;
+        unit.iter_mut()
+            .zip(rhs.iter().copied())
+            .for_each(|(a, b)| *a = a.saturating_add(b));
+        unit
+    }
+
+    fn fits_within(&self, limit: &Self) -> bool {
+        self.iter().zip(limit.iter()).all(|(a, b)| a <= b)
+    }
+}
+
+/// The gas charged for the accesses to the state made through a [`crate::WorkingSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateAccessGasConfig<GU: GasUnit> {
+    /// Gas charged for every read of a value from the state.
+    pub read: GU,
+    /// Gas charged for every write or deletion of a value in the state.
+    pub write: GU,
+}
+
+impl<GU: GasUnit> Default for StateAccessGasConfig<GU> {
+    fn default() -> Self {
+        Self {
+            read: GU::ZEROED,
+            write: GU::ZEROED,
+        }
+    }
 }
 
 /// A gas meter.
+#[derive(Debug, Clone)]
 pub struct GasMeter<GU>
 where
     GU: GasUnit,
 {
     remaining_funds: u64,
+    consumed_funds: u64,
     gas_price: GU,
+    gas_used: GU,
+    gas_limit: Option<GU>,
+    state_access: StateAccessGasConfig<GU>,
+    out_of_gas: bool,
 }
 
 impl<GU> Default for GasMeter<GU>
@@ -56,10 +106,7 @@ where
     GU: GasUnit,
 {
     fn default() -> Self {
-        Self {
-            remaining_funds: 0,
-            gas_price: GU::ZEROED,
-        }
+        Self::new(0, GU::ZEROED)
     }
 }
 
@@ -71,24 +118,87 @@ where
     pub fn new(remaining_funds: u64, gas_price: GU) -> Self {
         Self {
             remaining_funds,
+            consumed_funds: 0,
             gas_price,
+            gas_used: GU::ZEROED,
+            gas_limit: None,
+            state_access: StateAccessGasConfig::default(),
+            out_of_gas: false,
         }
     }
 
+    /// Limits the gas that can be used, per dimension.
+    pub fn with_gas_limit(mut self, gas_limit: Option<GU>) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the gas charged for the accesses to the state.
+    pub fn with_state_access_config(mut self, state_access: StateAccessGasConfig<GU>) -> Self {
+        self.state_access = state_access;
+        self
+    }
+
     /// Returns the remaining gas funds.
     pub const fn remaining_funds(&self) -> u64 {
         self.remaining_funds
     }
 
+    /// Returns the gas funds consumed so far.
+    pub const fn consumed_funds(&self) -> u64 {
+        self.consumed_funds
+    }
+
+    /// Returns the gas used so far, per dimension.
+    pub const fn gas_used(&self) -> &GU {
+        &self.gas_used
+    }
+
+    /// Returns `true` if a state access could not be paid for. Any further charge will fail.
+    pub const fn is_out_of_gas(&self) -> bool {
+        self.out_of_gas
+    }
+
     /// Deducts the provided gas unit from the remaining funds, computing the scalar value of the
     /// funds from the price of the instance.
     pub fn charge_gas(&mut self, gas: &GU) -> Result<()> {
-        let gas = gas.value(&self.gas_price);
+        anyhow::ensure!(!self.out_of_gas, "Not enough gas");
+
+        let gas_used = self.gas_used.saturating_add(gas);
+        if let Some(limit) = &self.gas_limit {
+            anyhow::ensure!(gas_used.fits_within(limit), "Gas limit exceeded");
+        }
+
+        let value = gas.value(&self.gas_price);
         self.remaining_funds = self
             .remaining_funds
-            .checked_sub(gas)
+            .checked_sub(value)
             .ok_or_else(|| anyhow::anyhow!("Not enough gas"))?;
+        self.consumed_funds = self.consumed_funds.saturating_add(value);
+        self.gas_used = gas_used;
 
         Ok(())
     }
+
+    /// Charges the gas of a read from the state.
+    pub fn charge_state_read(&mut self) {
+        let gas = self.state_access.read.clone();
+        self.charge_state_access(&gas);
+    }
+
+    /// Charges the gas of a write to the state.
+    pub fn charge_state_write(&mut self) {
+        let gas = self.state_access.write.clone();
+        self.charge_state_access(&gas);
+    }
+
+    // State accesses are infallible, so running out of gas consumes all the remaining funds and
+    // marks the meter as exhausted instead.
+    fn charge_state_access(&mut self, gas: &GU) {
+        if self.charge_gas(gas).is_err() {
+            self.consumed_funds = self.consumed_funds.saturating_add(self.remaining_funds);
+            self.remaining_funds = 0;
+            self.out_of_gas = true;
+        }
+    }
 }
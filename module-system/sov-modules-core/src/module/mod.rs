@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use sov_rollup_interface::stf::{Event, EventKey};
 
 use crate::common::{ModuleError, ModulePrefix};
 use crate::storage::WorkingSet;
@@ -76,6 +77,53 @@ pub trait ModuleCallJsonSchema: Module {
     fn json_schema() -> String;
 }
 
+/// Builds the [`Event`] stored by [`WorkingSet::emit_event`] for an event
+/// emitted by `module`: the value is the borsh-encoded event, and the key is
+/// the address of the module followed by the kind of the event, see
+/// [`module_event_kind`].
+pub fn module_event<M>(module: &M, event: &M::Event) -> Event
+where
+    M: Module + ModuleInfo<Context = <M as Module>::Context>,
+{
+    let value = event
+        .try_to_vec()
+        .expect("Serialization to vec is infallible");
+    let key = EventKey::module_event(
+        <_ as AsRef<[u8]>>::as_ref(module.address()),
+        module_event_kind(&value),
+    );
+    Event::from_bytes(key.inner().clone(), value)
+}
+
+/// Returns the kind of a module event from its borsh encoding. Module events
+/// are enums, which borsh encodes starting with the index of their variant, so
+/// the kind is that index. Events with an empty encoding, like `()`, are of
+/// kind `0`.
+pub fn module_event_kind(encoded_event: &[u8]) -> u8 {
+    encoded_event.first().copied().unwrap_or_default()
+}
+
+/// A runtime that can decode the events emitted by its modules into its
+/// runtime event enum. You can derive it with `#[derive(Event)]`.
+pub trait RuntimeEventProcessor {
+    /// The runtime event enum, with one variant per module.
+    type RuntimeEvent;
+
+    /// Decodes an event emitted with [`WorkingSet::emit_event`]. Returns
+    /// [`None`] if the event was not emitted by a module of this runtime, or
+    /// if its value is not a valid event of that module of the kind recorded in
+    /// its key.
+    fn decode_event(&self, event: &Event) -> Option<Self::RuntimeEvent>;
+}
+
+/// A runtime that has a well-defined and known [JSON
+/// Schema](https://json-schema.org/) for its runtime event enum. It is
+/// derived with `#[derive(Event)]` when the `native` feature is enabled.
+pub trait RuntimeEventJsonSchema: RuntimeEventProcessor {
+    /// Returns the JSON schema for [`RuntimeEventProcessor::RuntimeEvent`].
+    fn event_json_schema() -> String;
+}
+
 /// Every module has to implement this trait.
 pub trait ModuleInfo {
     /// Execution context.
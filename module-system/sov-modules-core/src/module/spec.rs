@@ -1,5 +1,6 @@
 //! Module specification definitions.
 
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -92,7 +93,8 @@ pub trait Spec {
 
 /// A context contains information which is passed to modules during
 /// transaction execution. Currently, context includes the sender of the transaction
-/// as recovered from its signature.
+/// as recovered from its signature, the hash of the transaction, the DA address of the
+/// sequencer which posted it, and the slot height reported by the kernel.
 ///
 /// Context objects also implement the [`Spec`] trait, which specifies the types to be used in this
 /// instance of the state transition function. By making modules generic over a `Context`, developers
@@ -104,8 +106,16 @@ pub trait Context: Spec + Clone + Debug + PartialEq + 'static {
     /// Sender of the transaction.
     fn sender(&self) -> &Self::Address;
 
+    /// The DA address of the sequencer which posted the transaction.
+    /// Empty if the transaction is executed before being posted, for example
+    /// when a sequencer builds a batch or speculatively executes the transaction.
+    fn sequencer(&self) -> &[u8];
+
+    /// The hash of the raw transaction.
+    fn tx_hash(&self) -> &[u8; 32];
+
     /// Constructor for the Context.
-    fn new(sender: Self::Address, height: u64) -> Self;
+    fn new(sender: Self::Address, sequencer: Vec<u8>, tx_hash: [u8; 32], height: u64) -> Self;
 
     /// Returns the height of the current slot as reported by the kernel. This value is
     /// non-decreasing and is guaranteed to be less than or equal to the actual "objective" height of the rollup.
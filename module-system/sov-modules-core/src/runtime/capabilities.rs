@@ -16,9 +16,17 @@ use crate::{Context, WorkingSet};
 /// in order to give out soft confirmations.
 pub trait Kernel<C: Context, Da: DaSpec>: BlobSelector<Da, Context = C> + Default {
     /// Return the current slot height
-    fn true_height(&self) -> u64;
+    fn true_height(&self, working_set: &mut WorkingSet<C>) -> u64;
     /// Return the height at which transactions currently *appear* to be executing.
-    fn visible_height(&self) -> u64;
+    fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64;
+    /// Return the id of the rollup, which every transaction must be signed for.
+    fn chain_id(&self, working_set: &mut WorkingSet<C>) -> u64;
+    /// Return the price of each dimension of gas in the current slot.
+    fn base_fee(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit;
+    /// Record the gas used by a transaction of the current slot.
+    fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>);
+    /// Runs once all the blobs of the current slot have been applied.
+    fn end_slot_hook(&self, working_set: &mut WorkingSet<C>);
 }
 
 /// BlobSelector decides which blobs to process in a current slot.
@@ -86,7 +94,7 @@ pub mod mocks {
     use sov_rollup_interface::da::DaSpec;
 
     use super::{BlobRefOrOwned, BlobSelector, Kernel};
-    use crate::Context;
+    use crate::{Context, GasUnit};
 
     /// A mock kernel for use in tests
     #[derive(Debug, Clone)]
@@ -95,6 +103,8 @@ pub mod mocks {
         pub true_height: u64,
         /// The height at which transactions appear to be executing
         pub visible_height: u64,
+        /// The id of the rollup
+        pub chain_id: u64,
         phantom: core::marker::PhantomData<(C, Da)>,
     }
 
@@ -103,6 +113,7 @@ pub mod mocks {
             Self {
                 true_height: 0,
                 visible_height: 0,
+                chain_id: 0,
                 phantom: Default::default(),
             }
         }
@@ -114,18 +125,28 @@ pub mod mocks {
             Self {
                 true_height,
                 visible_height,
+                chain_id: 0,
                 phantom: core::marker::PhantomData,
             }
         }
     }
 
     impl<C: Context, Da: DaSpec> Kernel<C, Da> for MockKernel<C, Da> {
-        fn true_height(&self) -> u64 {
+        fn true_height(&self, _working_set: &mut crate::WorkingSet<C>) -> u64 {
             self.true_height
         }
-        fn visible_height(&self) -> u64 {
+        fn visible_height(&self, _working_set: &mut crate::WorkingSet<C>) -> u64 {
             self.visible_height
         }
+        fn chain_id(&self, _working_set: &mut crate::WorkingSet<C>) -> u64 {
+            self.chain_id
+        }
+        fn base_fee(&self, _working_set: &mut crate::WorkingSet<C>) -> C::GasUnit {
+            C::GasUnit::ZEROED
+        }
+        fn record_gas_used(&self, _gas_used: &C::GasUnit, _working_set: &mut crate::WorkingSet<C>) {
+        }
+        fn end_slot_hook(&self, _working_set: &mut crate::WorkingSet<C>) {}
     }
 
     impl<C: Context, Da: DaSpec> BlobSelector<Da> for MockKernel<C, Da> {
@@ -164,9 +164,6 @@ pub trait Storage: Clone {
     /// The witness type for this storage instance.
     type Witness: Witness + Send + Sync;
 
-    /// The runtime config for this storage instance.
-    type RuntimeConfig;
-
     /// A cryptographic proof that a particular key has a particular value, or is absent.
     type Proof: Serialize
         + DeserializeOwned
@@ -192,10 +189,6 @@ pub trait Storage: Clone {
     /// State update that will be committed to the database.
     type StateUpdate;
 
-    /// Creates a new instance of this [`Storage`] type, with some configuration
-    /// options.
-    fn with_config(config: Self::RuntimeConfig) -> anyhow::Result<Self>;
-
     /// Returns the value corresponding to the key or None if key is absent.
     fn get(&self, key: &StorageKey, witness: &Self::Witness) -> Option<StorageValue>;
 
@@ -210,6 +203,18 @@ pub trait Storage: Clone {
         None
     }
 
+    /// Returns a read-only view of the storage, as it was right after `version`
+    /// was committed.
+    ///
+    /// The accessory state isn't versioned: the view always reads its latest values.
+    ///
+    /// # About historical state
+    /// This method is blanket-implemented to return an error. Only storages
+    /// which keep the values of past versions **SHOULD** override it.
+    fn at_version(&self, version: Version) -> Result<Self, anyhow::Error> {
+        anyhow::bail!("Version {version} of the state is not available from this storage")
+    }
+
     /// Calculates new state root but does not commit any changes to the database.
     fn compute_state_update(
         &self,
@@ -289,4 +294,7 @@ pub trait NativeStorage: Storage {
 
     /// Get the root hash of the tree at the requested version
     fn get_root_hash(&self, version: Version) -> Result<Self::Root, anyhow::Error>;
+
+    /// Returns the latest version of the state visible from this storage.
+    fn latest_version(&self) -> Version;
 }
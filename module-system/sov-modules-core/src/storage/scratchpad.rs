@@ -8,7 +8,7 @@ use sov_rollup_interface::maybestd::collections::HashMap;
 use sov_rollup_interface::stf::Event;
 
 use crate::common::{GasMeter, Prefix};
-use crate::module::{Context, Spec};
+use crate::module::{module_event, Context, Module, ModuleInfo, Spec};
 use crate::storage::{
     CacheKey, CacheValue, EncodeKeyLike, NativeStorage, OrderedReadsAndWrites, StateCodec,
     StateValueCodec, Storage, StorageInternalCache, StorageKey, StorageProof, StorageValue,
@@ -251,6 +251,7 @@ impl<S: Storage> StateReaderAndWriter for AccessoryDelta<S> {
 pub struct StateCheckpoint<C: Context> {
     delta: Delta<C::Storage>,
     accessory_delta: AccessoryDelta<C::Storage>,
+    gas_meter: GasMeter<C::GasUnit>,
 }
 
 impl<C: Context> StateCheckpoint<C> {
@@ -260,6 +261,7 @@ impl<C: Context> StateCheckpoint<C> {
         Self {
             delta: Delta::new(inner.clone()),
             accessory_delta: AccessoryDelta::new(inner),
+            gas_meter: GasMeter::default(),
         }
     }
 
@@ -272,16 +274,20 @@ impl<C: Context> StateCheckpoint<C> {
         Self {
             delta: Delta::with_witness(inner.clone(), witness),
             accessory_delta: AccessoryDelta::new(inner),
+            gas_meter: GasMeter::default(),
         }
     }
 
     /// Transforms this [`StateCheckpoint`] back into a [`WorkingSet`].
+    ///
+    /// The gas meter is carried over, so the gas of a transaction keeps being metered across
+    /// checkpoints and reverts.
     pub fn to_revertable(self) -> WorkingSet<C> {
         WorkingSet {
             delta: RevertableWriter::new(self.delta),
             accessory_delta: RevertableWriter::new(self.accessory_delta),
             events: Default::default(),
-            gas_meter: GasMeter::default(),
+            gas_meter: self.gas_meter,
         }
     }
 
@@ -365,6 +371,7 @@ impl<C: Context> WorkingSet<C> {
         StateCheckpoint {
             delta: self.delta.commit(),
             accessory_delta: self.accessory_delta.commit(),
+            gas_meter: self.gas_meter,
         }
     }
 
@@ -374,12 +381,19 @@ impl<C: Context> WorkingSet<C> {
         StateCheckpoint {
             delta: self.delta.revert(),
             accessory_delta: self.accessory_delta.revert(),
+            gas_meter: self.gas_meter,
         }
     }
 
-    /// Adds an event to the working set.
-    pub fn add_event(&mut self, key: &str, value: &str) {
-        self.events.push(Event::new(key, value));
+    /// Adds an event emitted by the given module to the working set. The event
+    /// is stored borsh-encoded, keyed by the address of the module and the kind
+    /// of the event (see [`module_event`]), so that it can be decoded into the runtime event enum with
+    /// [`RuntimeEventProcessor::decode_event`](crate::module::RuntimeEventProcessor::decode_event).
+    pub fn emit_event<M>(&mut self, module: &M, event: M::Event)
+    where
+        M: Module<Context = C> + ModuleInfo<Context = C>,
+    {
+        self.events.push(module_event(module, &event));
     }
 
     /// Extracts all events from this working set.
@@ -403,6 +417,22 @@ impl<C: Context> WorkingSet<C> {
         self.gas_meter = GasMeter::new(funds, gas_price);
     }
 
+    /// Replaces the current gas meter with the provided one.
+    pub fn set_gas_meter(&mut self, gas_meter: GasMeter<C::GasUnit>) {
+        self.gas_meter = gas_meter;
+    }
+
+    /// Returns the current gas meter.
+    pub const fn gas_meter(&self) -> &GasMeter<C::GasUnit> {
+        &self.gas_meter
+    }
+
+    /// Removes the current gas meter, leaving a meter without funds and with a zero price in its
+    /// place.
+    pub fn take_gas_meter(&mut self) -> GasMeter<C::GasUnit> {
+        mem::take(&mut self.gas_meter)
+    }
+
     /// Attempts to charge the provided gas unit from the gas meter, using the internal price to
     /// compute the scalar value.
     pub fn charge_gas(&mut self, gas: &C::GasUnit) -> anyhow::Result<()> {
@@ -424,14 +454,17 @@ impl<C: Context> WorkingSet<C> {
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        self.gas_meter.charge_state_read();
         self.delta.get(key)
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
+        self.gas_meter.charge_state_write();
         self.delta.set(key, value)
     }
 
     fn delete(&mut self, key: &StorageKey) {
+        self.gas_meter.charge_state_write();
         self.delta.delete(key)
     }
 }
@@ -482,15 +515,15 @@ pub mod kernel_state {
 
     impl<'a, C: Context> StateReaderAndWriter for VersionedWorkingSet<'a, C> {
         fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
-            self.ws.delta.get(key)
+            self.ws.get(key)
         }
 
         fn set(&mut self, key: &StorageKey, value: StorageValue) {
-            self.ws.delta.set(key, value)
+            self.ws.set(key, value)
         }
 
         fn delete(&mut self, key: &StorageKey) {
-            self.ws.delta.delete(key)
+            self.ws.delete(key)
         }
     }
 
@@ -509,10 +542,12 @@ pub mod kernel_state {
             kernel: &K,
             ws: &'a mut WorkingSet<C>,
         ) -> Self {
+            let true_slot_num = kernel.true_height(ws);
+            let virtual_slot_num = kernel.visible_height(ws);
             Self {
                 ws,
-                true_slot_num: kernel.true_height(),
-                virtual_slot_num: kernel.visible_height(),
+                true_slot_num,
+                virtual_slot_num,
             }
         }
 
@@ -2,7 +2,8 @@ use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_core::capabilities::mocks::MockKernel;
 use sov_modules_core::{
-    Address, Context, KernelWorkingSet, StateReaderAndWriter, StorageKey, StorageValue, WorkingSet,
+    Address, Context, GasMeter, KernelWorkingSet, StateAccessGasConfig, StateReaderAndWriter,
+    StorageKey, StorageValue, WorkingSet,
 };
 use sov_state::codec::BcsCodec;
 use sov_state::ProverStorage;
@@ -36,8 +37,12 @@ fn test_versioned_workingset_get() {
     let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
 
     let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
-    let mut working_set =
-        working_set.versioned_state(&DefaultContext::new(Address::from([1; 32]), 1));
+    let mut working_set = working_set.versioned_state(&DefaultContext::new(
+        Address::from([1; 32]),
+        Vec::new(),
+        [0; 32],
+        1,
+    ));
     working_set.set(&storage_key, storage_value.clone());
 
     assert_eq!(Some(storage_value), working_set.get(&storage_key));
@@ -61,3 +66,39 @@ fn test_kernel_workingset_get() {
 
     assert_eq!(Some(storage_value), working_set.get(&storage_key));
 }
+
+#[test]
+fn test_workingset_charges_state_accesses() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+    let codec = BcsCodec {};
+    let storage = ProverStorage::<sov_state::DefaultStorageSpec>::with_path(path).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let storage_key = StorageKey::new(&prefix, &vec![4, 5, 6], &codec);
+    let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
+    working_set.set_gas_meter(GasMeter::new(5, [1, 2]).with_state_access_config(
+        StateAccessGasConfig {
+            read: [1, 0],
+            write: [1, 1],
+        },
+    ));
+
+    working_set.set(&storage_key, storage_value.clone());
+    assert_eq!(3, working_set.gas_meter().consumed_funds());
+
+    // The gas meter survives checkpoints.
+    let mut working_set = working_set.checkpoint().to_revertable();
+    assert_eq!(Some(storage_value.clone()), working_set.get(&storage_key));
+    assert_eq!(4, working_set.gas_meter().consumed_funds());
+    assert_eq!(&[2, 1], working_set.gas_meter().gas_used());
+
+    // Running out of gas doesn't prevent the access, but consumes all the funds.
+    working_set.delete(&storage_key);
+    assert!(working_set.gas_meter().is_out_of_gas());
+    assert_eq!(0, working_set.gas_remaining_funds());
+    assert_eq!(5, working_set.gas_meter().consumed_funds());
+    assert!(working_set.charge_gas(&[0, 0]).is_err());
+}
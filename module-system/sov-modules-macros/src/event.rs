@@ -26,6 +26,85 @@ impl<'a> StructDef<'a> {
             })
             .collect()
     }
+
+    fn create_event_processor(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let impl_generics = &self.impl_generics;
+        let type_generics = &self.type_generics;
+        let where_clause = self.where_clause;
+        let event_enum = self.enum_ident(EVENT);
+
+        let decode_legs = self.fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+
+            quote::quote!(
+                if module == <_ as ::core::convert::AsRef<[u8]>>::as_ref(
+                    <#ty as ::sov_modules_api::ModuleInfo>::address(&self.#name),
+                ) {
+                    if kind != ::sov_modules_api::module_event_kind(value) {
+                        return ::core::option::Option::None;
+                    }
+                    return <<#ty as ::sov_modules_api::Module>::Event as ::borsh::BorshDeserialize>::try_from_slice(value)
+                        .ok()
+                        .map(#event_enum::#name);
+                }
+            )
+        });
+
+        let schema_legs = self.fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+
+            quote::quote!(
+                (
+                    stringify!(#name),
+                    generator.subschema_for::<<#ty as ::sov_modules_api::Module>::Event>(),
+                ),
+            )
+        });
+
+        let schema_bounds = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+
+            quote::quote!(
+                <#ty as ::sov_modules_api::Module>::Event: ::sov_modules_api::schemars::JsonSchema,
+            )
+        });
+        let where_predicates = where_clause
+            .into_iter()
+            .flat_map(|where_clause| where_clause.predicates.iter());
+
+        quote::quote! {
+            impl #impl_generics ::sov_modules_api::RuntimeEventProcessor for #ident #type_generics #where_clause {
+                type RuntimeEvent = #event_enum #type_generics;
+
+                fn decode_event(
+                    &self,
+                    event: &::sov_modules_api::Event,
+                ) -> ::core::option::Option<Self::RuntimeEvent> {
+                    let (module, kind) = event.key().module_and_kind()?;
+                    let value = event.value().inner();
+                    #(#decode_legs)*
+                    ::core::option::Option::None
+                }
+            }
+
+            #[cfg(feature = "native")]
+            impl #impl_generics ::sov_modules_api::RuntimeEventJsonSchema for #ident #type_generics
+            where
+                #(#where_predicates,)*
+                #(#schema_bounds)*
+            {
+                fn event_json_schema() -> ::std::string::String {
+                    ::sov_modules_api::utils::runtime_event_json_schema(
+                        stringify!(#event_enum),
+                        |generator| ::std::vec![#(#schema_legs)*],
+                    )
+                }
+            }
+        }
+    }
 }
 
 impl EventMacro {
@@ -64,10 +143,13 @@ impl EventMacro {
 
         let event_enum_legs = struct_def.create_event_enum_legs();
         let event_enum = struct_def.create_enum(&event_enum_legs, EVENT, &serialization_methods);
+        let event_processor = struct_def.create_event_processor();
 
         Ok(quote::quote! {
             #[doc="This enum is generated from the underlying Runtime, the variants correspond to events from the relevant modules"]
             #event_enum
+
+            #event_processor
         }
             .into())
     }
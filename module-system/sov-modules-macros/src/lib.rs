@@ -184,6 +184,13 @@ pub fn config_constant(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// Every method taking a `WorkingSet` can also be queried at a past slot, through a variant
+/// suffixed with `AtSlot` which takes an extra trailing `sov_modules_api::rpc::SlotIdentifier`.
+/// For example, if `my_method` took a `WorkingSet`, `myMethod` would come with `myMethodAtSlot`,
+/// i.e. `fn my_method_at_slot(&self, param: u32, at_slot: SlotIdentifier)`.
+/// The variant reads the state through `MyModuleRpcImpl::get_working_set_at`, which fails unless
+/// overridden by the implementer.
 #[proc_macro_attribute]
 #[cfg(feature = "native")]
 pub fn rpc_gen(attr: TokenStream, item: TokenStream) -> TokenStream {
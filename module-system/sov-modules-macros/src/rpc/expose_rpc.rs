@@ -48,6 +48,7 @@ impl ExposeRpcMacro {
         let rpc_storage_struct = quote! {
             struct RpcStorage #impl_generics #where_clause {
                 storage: #context_type::Storage,
+                slot_resolver: ::std::sync::Arc<dyn ::sov_modules_api::rpc::SlotResolver>,
                 // Function pointers are always Send + Sync, regardless of
                 // whether the return type is. The alternative would be to
                 // `unsafe impl Send/Sync` for `RpcStorage`, but this seems
@@ -60,6 +61,7 @@ impl ExposeRpcMacro {
                 fn clone(&self) -> Self {
                     Self {
                         storage: self.storage.clone(),
+                        slot_resolver: self.slot_resolver.clone(),
                         _phantom: ::std::marker::PhantomData,
                     }
                  }
@@ -108,6 +110,15 @@ impl ExposeRpcMacro {
                     {
                         ::sov_modules_api::WorkingSet::new(self.storage.clone())
                     }
+
+                    /// Get a working set on top of the state right after the given slot
+                    fn get_working_set_at(
+                        &self,
+                        slot: &::sov_modules_api::rpc::SlotIdentifier,
+                    ) -> ::core::result::Result<::sov_modules_api::WorkingSet<#context_type>, ::jsonrpsee::types::ErrorObjectOwned>
+                    {
+                        ::sov_modules_api::rpc::working_set_at_slot::<#context_type>(&self.storage, &self.slot_resolver, slot)
+                    }
                 }
             };
 
@@ -115,11 +126,16 @@ impl ExposeRpcMacro {
         }
 
         let get_rpc_methods: proc_macro2::TokenStream = quote! {
-            /// Returns a [`jsonrpsee::RpcModule`] with all the rpc methods exposed by the module
-            pub fn get_rpc_methods #impl_generics (storage: <#context_type as ::sov_modules_api::Spec>::Storage) -> ::jsonrpsee::RpcModule<()> #where_clause {
+            /// Returns a [`jsonrpsee::RpcModule`] with all the rpc methods exposed by the module.
+            /// The slots targeted by historical queries are looked up with the `slot_resolver`.
+            pub fn get_rpc_methods #impl_generics (
+                storage: <#context_type as ::sov_modules_api::Spec>::Storage,
+                slot_resolver: ::std::sync::Arc<dyn ::sov_modules_api::rpc::SlotResolver>,
+            ) -> ::jsonrpsee::RpcModule<()> #where_clause {
                 let mut module = ::jsonrpsee::RpcModule::new(());
                 let r = RpcStorage:: #ty_generics  {
                     storage: storage.clone(),
+                    slot_resolver,
                     _phantom: ::std::marker::PhantomData
                 };
 
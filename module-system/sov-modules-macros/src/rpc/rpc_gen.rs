@@ -43,6 +43,76 @@ fn jsonrpsee_rpc_macro_path() -> Path {
     }
 }
 
+/// Returns the `method` attribute of the historical variant of an RPC method reading the state,
+/// which is named after the original method with an `AtSlot` suffix. The aliases of the original
+/// method are not carried over, to avoid registering the same name twice.
+fn get_at_slot_method_attribute(attribute: &Attribute) -> Result<Attribute, syn::Error> {
+    let Meta::List(MetaList { path, nested, .. }) = attribute.parse_meta()? else {
+        return Err(syn::Error::new_spanned(
+            attribute,
+            "Expected a list of arguments",
+        ));
+    };
+    let mut has_name = false;
+    let nested = nested
+        .into_iter()
+        .filter(
+            |arg| !matches!(arg, syn::NestedMeta::Meta(meta) if meta.path().is_ident("aliases")),
+        )
+        .map(|arg| match arg {
+            syn::NestedMeta::Meta(Meta::NameValue(mut name_value))
+                if name_value.path.is_ident("name") =>
+            {
+                let syn::Lit::Str(name) = &name_value.lit else {
+                    return Err(syn::Error::new_spanned(
+                        &name_value.lit,
+                        "Expected a string literal",
+                    ));
+                };
+                name_value.lit = syn::Lit::Str(syn::LitStr::new(
+                    &format!("{}AtSlot", name.value()),
+                    name.span(),
+                ));
+                has_name = true;
+                Ok(syn::NestedMeta::Meta(Meta::NameValue(name_value)))
+            }
+            arg => Ok(arg),
+        })
+        .collect::<Result<Vec<_>, syn::Error>>()?;
+    if !has_name {
+        return Err(syn::Error::new_spanned(
+            attribute,
+            "Expected the name of the RPC method",
+        ));
+    }
+
+    Ok(syn::parse_quote! { #[#path( #(#nested),* )] })
+}
+
+/// Returns the signature of the historical variant of an RPC method reading the state: the
+/// working set argument is replaced with the slot to query the state at, in the last position.
+fn at_slot_signature(signature: &Signature, idx_of_working_set_arg: usize) -> Signature {
+    let mut signature = signature.clone();
+    let mut inputs: Vec<syn::FnArg> = signature.inputs.into_iter().collect();
+    inputs.remove(idx_of_working_set_arg);
+    inputs.push(syn::parse_quote! { at_slot: ::sov_modules_api::rpc::SlotIdentifier });
+    signature.inputs = inputs.into_iter().collect();
+    signature.ident = format_ident!("{}_at_slot", signature.ident);
+    signature
+}
+
+/// Returns the docs of the historical variant of an RPC method reading the state.
+fn at_slot_docs(docs: &[Attribute]) -> Vec<Attribute> {
+    let mut docs = docs.to_vec();
+    if !docs.is_empty() {
+        docs.push(syn::parse_quote! { #[doc = ""] });
+    }
+    docs.push(syn::parse_quote! {
+        #[doc = " Reads the state as it was right after the `at_slot` slot. The accessory state isn't versioned, so it is always read at the latest slot."]
+    });
+    docs
+}
+
 fn find_working_set_argument(sig: &Signature) -> Option<(usize, syn::Type)> {
     for (idx, input) in sig.inputs.iter().enumerate() {
         if let FnArg::Typed(PatType { ty, .. }) = input {
@@ -115,11 +185,15 @@ impl RpcImplBlock {
                 let pre_working_set_args = arg_values
                     .clone()
                     .take(idx)
-                    .filter(|arg| arg.to_string() != quote! { self }.to_string());
+                    .filter(|arg| arg.to_string() != quote! { self }.to_string())
+                    .collect::<Vec<_>>();
                 let post_working_set_args = arg_values
                     .clone()
                     .skip(idx + 1)
-                    .filter(|arg| arg.to_string() != quote! { self }.to_string());
+                    .filter(|arg| arg.to_string() != quote! { self }.to_string())
+                    .collect::<Vec<_>>();
+                let at_slot_signature = at_slot_signature(&signature, idx);
+                let at_slot_docs = at_slot_docs(docs);
                 let mut inputs: Vec<syn::FnArg> = signature.inputs.clone().into_iter().collect();
                 inputs.remove(idx);
 
@@ -130,6 +204,12 @@ impl RpcImplBlock {
                     #signature {
                         <#type_name #ty_generics as ::std::default::Default>::default().#method_name(#(#pre_working_set_args,)* &mut Self::get_working_set(self), #(#post_working_set_args),* )
                     }
+
+                    #( #at_slot_docs )*
+                    #at_slot_signature {
+                        let mut working_set = Self::get_working_set_at(self, &at_slot)?;
+                        <#type_name #ty_generics as ::std::default::Default>::default().#method_name(#(#pre_working_set_args,)* &mut working_set, #(#post_working_set_args),* )
+                    }
                 }
             } else {
                 // Remove the "self" argument, since the method is invoked on `self` using dot notation
@@ -147,13 +227,21 @@ impl RpcImplBlock {
 
             let blanket_impl_method = if let Some(idx) = method.idx_of_working_set_arg {
                 // If necessary, adjust the signature to remove the working set argument.
-                let pre_working_set_args = arg_values.clone().take(idx);
-                let post_working_set_args = arg_values.clone().skip(idx + 1);
+                let pre_working_set_args = arg_values.clone().take(idx).collect::<Vec<_>>();
+                let post_working_set_args = arg_values.clone().skip(idx + 1).collect::<Vec<_>>();
+                let at_slot_signature = at_slot_signature(&method.method_signature, idx);
+                let at_slot_method_name = &at_slot_signature.ident;
+                let at_slot_docs = at_slot_docs(docs);
                 quote! {
                     #( #docs )*
                     #signature {
                         <Self as #impl_trait_name #ty_generics >::#method_name(#(#pre_working_set_args,)* #(#post_working_set_args),* )
                     }
+
+                    #( #at_slot_docs )*
+                    #at_slot_signature {
+                        <Self as #impl_trait_name #ty_generics >::#at_slot_method_name(#(#pre_working_set_args,)* #(#post_working_set_args,)* at_slot)
+                    }
                 }
             } else {
                 quote! {
@@ -174,6 +262,20 @@ impl RpcImplBlock {
                 pub trait #impl_trait_name #generics #where_clause {
                     /// Get a clean working set on top of the latest state
                     fn get_working_set(&self) -> #working_set_type;
+
+                    /// Get a clean working set on top of the state right after the given slot.
+                    /// Historical queries are not supported unless this method is overridden.
+                    fn get_working_set_at(
+                        &self,
+                        slot: &::sov_modules_api::rpc::SlotIdentifier,
+                    ) -> ::core::result::Result<#working_set_type, ::jsonrpsee::types::ErrorObjectOwned> {
+                        ::core::result::Result::Err(::jsonrpsee::types::ErrorObjectOwned::owned(
+                            ::jsonrpsee::types::error::UNKNOWN_ERROR_CODE,
+                            ::std::format!("Historical queries are not supported, can't query slot {:?}", slot),
+                            ::core::option::Option::None::<()>,
+                        ))
+                    }
+
                     #(#impl_trait_methods)*
                 }
             }
@@ -304,6 +406,19 @@ fn build_rpc_trait(
                 };
                 intermediate_trait_items.push(annotated_signature);
 
+                // Methods reading the state can also be queried at any past slot, through a
+                // variant named after them
+                if let Some(idx) = idx_of_working_set_arg {
+                    let at_slot_attr = get_at_slot_method_attribute(&attr)?;
+                    let at_slot_signature = at_slot_signature(&method.sig, idx);
+                    let at_slot_docs = at_slot_docs(&docs);
+                    intermediate_trait_items.push(quote! {
+                        #( #at_slot_docs )*
+                        #at_slot_attr
+                        #at_slot_signature;
+                    });
+                }
+
                 let mut original_method = method.clone();
                 original_method.attrs.remove(idx_of_rpc_attr);
                 simplified_impl_items.push(ImplItem::Method(original_method));
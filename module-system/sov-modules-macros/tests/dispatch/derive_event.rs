@@ -2,7 +2,12 @@ mod modules;
 use modules::{first_test_module, second_test_module};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::macros::DefaultRuntime;
-use sov_modules_api::{Context, DispatchCall, Event, Genesis, MessageCodec};
+#[cfg(feature = "native")]
+use sov_modules_api::RuntimeEventJsonSchema;
+use sov_modules_api::{
+    module_event, Context, DispatchCall, Event, Genesis, MessageCodec, ModuleInfo,
+    RuntimeEventProcessor,
+};
 
 #[derive(Genesis, DispatchCall, Event, MessageCodec, DefaultRuntime)]
 #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
@@ -23,4 +28,47 @@ fn main() {
         ]));
     let _event =
         RuntimeEvent::<DefaultContext>::second(second_test_module::Event::SecondModuleEnum);
+
+    // Check that emitted events are decoded according to the module that emitted them
+    let runtime = &Runtime::<DefaultContext>::default();
+    let event = module_event(
+        &runtime.first,
+        &first_test_module::Event::FirstModuleEnum1(10),
+    );
+    assert_eq!(
+        runtime.decode_event(&event),
+        Some(RuntimeEvent::first(
+            first_test_module::Event::FirstModuleEnum1(10)
+        ))
+    );
+    let event = module_event(
+        &runtime.second,
+        &second_test_module::Event::SecondModuleEnum,
+    );
+    assert_eq!(
+        runtime.decode_event(&event),
+        Some(RuntimeEvent::second(
+            second_test_module::Event::SecondModuleEnum
+        ))
+    );
+    assert_eq!(runtime.decode_event(&Event::new("key", "value")), None);
+
+    // Check that the key identifies both the module and the kind of the event
+    let event = module_event(&runtime.first, &first_test_module::Event::FirstModuleEnum2);
+    assert_eq!(
+        event.key().module_and_kind(),
+        Some((runtime.first.address().as_ref(), 1))
+    );
+    let mislabeled = Event::from_bytes(
+        event.key().inner().clone(),
+        borsh::BorshSerialize::try_to_vec(&first_test_module::Event::FirstModuleEnum1(10)).unwrap(),
+    );
+    assert_eq!(runtime.decode_event(&mislabeled), None);
+
+    #[cfg(feature = "native")]
+    {
+        let schema = Runtime::<DefaultContext>::event_json_schema();
+        assert!(schema.contains("FirstModuleEnum3"));
+        assert!(schema.contains("SecondModuleEnum"));
+    }
 }
@@ -20,7 +20,15 @@ pub mod first_test_module {
         }
     }
 
-    #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq)]
+    #[derive(
+        borsh::BorshDeserialize,
+        borsh::BorshSerialize,
+        serde::Serialize,
+        serde::Deserialize,
+        schemars::JsonSchema,
+        Debug,
+        PartialEq,
+    )]
     pub enum Event {
         FirstModuleEnum1(u64),
         FirstModuleEnum2,
@@ -72,7 +80,15 @@ pub mod second_test_module {
         }
     }
 
-    #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq)]
+    #[derive(
+        borsh::BorshDeserialize,
+        borsh::BorshSerialize,
+        serde::Serialize,
+        serde::Deserialize,
+        schemars::JsonSchema,
+        Debug,
+        PartialEq,
+    )]
     pub enum Event {
         SecondModuleEnum,
     }
@@ -1,6 +1,7 @@
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::default_context::ZkDefaultContext;
 use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::rpc::SlotIdentifier;
 use sov_modules_api::{Context, ModuleInfo, WorkingSet};
 use sov_state::ZkStorage;
 
@@ -100,6 +101,15 @@ fn main() {
         assert_eq!(result, 44);
     }
 
+    {
+        // Historical queries fail unless `get_working_set_at` is implemented.
+        let result =
+            <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::second_method_at_slot(
+                &r, 22, SlotIdentifier::Number(1),
+            );
+        assert!(result.is_err());
+    }
+
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::health(&r)
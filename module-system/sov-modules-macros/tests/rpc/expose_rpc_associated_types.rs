@@ -121,7 +121,12 @@ fn main() {
     let serialized_message =
         <RT as EncodeCall<my_module::QueryModule<C, u32>>>::encode_call(message);
     let module = RT::decode_call(&serialized_message).unwrap();
-    let context = C::new(Address::try_from([11; 32].as_ref()).unwrap(), 1);
+    let context = C::new(
+        Address::try_from([11; 32].as_ref()).unwrap(),
+        Vec::new(),
+        [0; 32],
+        1,
+    );
 
     let _ = runtime
         .dispatch_call(module, working_set, &context)
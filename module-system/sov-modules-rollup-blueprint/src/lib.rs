@@ -8,12 +8,16 @@ use std::net::SocketAddr;
 use async_trait::async_trait;
 pub use runtime_rpc::*;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::PrunerHandle;
+use sov_db::sequencer_db::SequencerDB;
+use sov_modules_api::rpc::state_version_after_slot;
 use sov_modules_api::runtime::capabilities::Kernel;
 use sov_modules_api::{Context, DaSpec, Spec};
 use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, StfBlueprint};
 use sov_rollup_interface::services::da::DaService;
-use sov_rollup_interface::storage::StorageManager;
-use sov_rollup_interface::zk::ZkvmHost;
+use sov_rollup_interface::storage::{HierarchicalStorageManager, StorageManager};
+use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
+use sov_sequencer::BatchProducerHandle;
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{ProverService, RollupConfig, RollupProverConfig, StateTransitionRunner};
@@ -39,27 +43,40 @@ pub trait RollupBlueprint: Sized + Send + Sync {
     type NativeContext: Context;
 
     /// Manager for the native storage lifecycle.
+    /// The runner keeps one storage per DA block to handle forks, while RPC requests are served
+    /// from [`StorageManager::get_native_storage`]. Module RPC methods therefore only see the
+    /// finalized state, whereas the ledger RPC also serves executed slots which aren't finalized yet.
     type StorageManager: StorageManager<
-        NativeStorage = <Self::NativeContext as Spec>::Storage,
-        NativeChangeSet = (),
-    >;
+            NativeStorage = <Self::NativeContext as Spec>::Storage,
+            NativeChangeSet = <Self::NativeContext as Spec>::Storage,
+        > + HierarchicalStorageManager<
+            Self::DaSpec,
+            NativeStorage = <Self::NativeContext as Spec>::Storage,
+            NativeChangeSet = <Self::NativeContext as Spec>::Storage,
+        >;
 
     /// Runtime for the Zero Knowledge environment.
     type ZkRuntime: RuntimeTrait<Self::ZkContext, Self::DaSpec> + Default;
     /// Runtime for the Native environment.
-    type NativeRuntime: RuntimeTrait<Self::NativeContext, Self::DaSpec> + Default + Send + Sync;
+    type NativeRuntime: RuntimeTrait<Self::NativeContext, Self::DaSpec>
+        + Default
+        + Send
+        + Sync
+        + 'static;
 
     /// The kernel for the native environment.
-    type NativeKernel: Kernel<Self::NativeContext, Self::DaSpec> + Default + Send + Sync;
+    type NativeKernel: Kernel<Self::NativeContext, Self::DaSpec> + Default + Send + Sync + 'static;
     /// The kernel for the Zero Knowledge environment.
     type ZkKernel: Kernel<Self::ZkContext, Self::DaSpec> + Default;
 
     /// Prover service.
     type ProverService: ProverService<
-        StateRoot = <<Self::NativeContext as Spec>::Storage as Storage>::Root,
-        Witness = <<Self::NativeContext as Spec>::Storage as Storage>::Witness,
-        DaService = Self::DaService,
-    >;
+            StateRoot = <<Self::NativeContext as Spec>::Storage as Storage>::Root,
+            Witness = <<Self::NativeContext as Spec>::Storage as Storage>::Witness,
+            DaService = Self::DaService,
+        > + Send
+        + Sync
+        + 'static;
 
     /// Creates RPC methods for the rollup.
     fn create_rpc_methods(
@@ -95,6 +112,10 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         da_service: &Self::DaService,
     ) -> Self::ProverService;
 
+    /// The commitment to the guest program proving the slots of the rollup.
+    /// Proofs posted to the DA layer by other provers are only accepted if they verify against it.
+    fn code_commitment(&self) -> <Self::Vm as Zkvm>::CodeCommitment;
+
     /// Creates instance of [`StorageManager`].
     /// Panics if initialization fails.
     fn create_storage_manager(
@@ -121,21 +142,44 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         let prover_service = self.create_prover_service(prover_config, &da_service).await;
 
         let ledger_db = self.create_ledger_db(&rollup_config);
+        let pruning = &rollup_config.storage.pruning;
+        let ledger_pruner = pruning
+            .ledger
+            .as_ref()
+            .map(|ledger_pruning| {
+                ledger_db.spawn_pruner(ledger_pruning.clone(), pruning.interval())
+            })
+            .transpose()?;
         let genesis_config = self.create_genesis_config(genesis_paths, &rollup_config)?;
 
         let storage_manager = self.create_storage_manager(&rollup_config)?;
         let native_storage = storage_manager.get_native_storage();
 
-        let prev_root = ledger_db
-            .get_head_slot()?
-            .map(|(number, _)| native_storage.get_root_hash(number.0))
-            .transpose()?;
+        let prev_root = get_resume_state_root(&ledger_db, &native_storage)?;
 
-        let rpc_methods = self.create_rpc_methods(&native_storage, &ledger_db, &da_service)?;
+        // The runner checks the finalized slots against the soft confirmations of the sequencer,
+        // so both share the same database.
+        let sequencer_db = SequencerDB::with_path(&rollup_config.storage.path)?;
+        let soft_confirmations = rollup_config.sequencer.soft_confirmations.is_some();
+
+        let mut rpc_methods = self.create_rpc_methods(&native_storage, &ledger_db, &da_service)?;
+        let batch_producer = register_sequencer::<
+            Self::NativeRuntime,
+            Self::NativeKernel,
+            Self::NativeContext,
+            Self::DaService,
+        >(
+            &native_storage,
+            &da_service,
+            sequencer_db.clone(),
+            &ledger_db,
+            rollup_config.sequencer,
+            &mut rpc_methods,
+        )?;
 
         let native_stf = StfBlueprint::new();
 
-        let runner = StateTransitionRunner::new(
+        let mut runner = StateTransitionRunner::new(
             rollup_config.runner,
             da_service,
             ledger_db,
@@ -144,15 +188,37 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             prev_root,
             genesis_config,
             prover_service,
+            self.code_commitment(),
         )?;
+        if soft_confirmations {
+            runner = runner.with_soft_confirmations(sequencer_db);
+        }
 
         Ok(Rollup {
             runner,
             rpc_methods,
+            batch_producer,
+            ledger_pruner,
         })
     }
 }
 
+/// Returns the state root right after the latest slot committed to the ledger, from which a
+/// restarted rollup resumes, or [`None`] if no slot has been committed yet.
+///
+/// The state after slot `n` is version `n + 1` of the storage, since the genesis state is
+/// version 1, see [`state_version_after_slot`]. Looking up version `n` would resume from the
+/// root before the latest slot instead.
+pub fn get_resume_state_root<S: NativeStorage>(
+    ledger_db: &LedgerDB,
+    native_storage: &S,
+) -> anyhow::Result<Option<S::Root>> {
+    ledger_db
+        .get_head_slot()?
+        .map(|(number, _)| native_storage.get_root_hash(state_version_after_slot(number.0)))
+        .transpose()
+}
+
 /// Dependencies needed to run the rollup.
 pub struct Rollup<S: RollupBlueprint> {
     /// The State Transition Runner.
@@ -166,6 +232,10 @@ pub struct Rollup<S: RollupBlueprint> {
     >,
     /// Rpc methods for the rollup.
     pub rpc_methods: jsonrpsee::RpcModule<()>,
+    /// The background task publishing the sequencer's batches.
+    pub batch_producer: BatchProducerHandle,
+    /// The background thread pruning the ledger, if enabled.
+    pub ledger_pruner: Option<PrunerHandle>,
 }
 
 impl<S: RollupBlueprint> Rollup<S> {
@@ -181,7 +251,12 @@ impl<S: RollupBlueprint> Rollup<S> {
     ) -> Result<(), anyhow::Error> {
         let mut runner = self.runner;
         runner.start_rpc_server(self.rpc_methods, channel).await;
-        runner.run_in_process().await?;
-        Ok(())
+        let result = runner.run_in_process().await;
+        // Publish the transactions left in the mempool before exiting.
+        self.batch_producer.shutdown().await?;
+        if let Some(ledger_pruner) = self.ledger_pruner {
+            tokio::task::spawn_blocking(move || ledger_pruner.stop()).await?;
+        }
+        result
     }
 }
@@ -1,23 +1,48 @@
+use std::sync::Arc;
+
 use anyhow::Context as _;
+use sov_cli::wallet_state::PrivateKeyAndAddress;
 use sov_db::ledger_db::LedgerDB;
-use sov_modules_api::{Context, Spec};
+use sov_db::sequencer_db::SequencerDB;
+use sov_modules_api::rpc::JmtProof;
+use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::{Context, RuntimeEventJsonSchema, Spec};
 use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, SequencerOutcome, TxEffect};
+use sov_rollup_interface::services::batch_builder::BatchBuilder;
 use sov_rollup_interface::services::da::DaService;
-use sov_sequencer::batch_builder::FiFoStrictBatchBuilder;
+use sov_sequencer::batch_builder::{FiFoStrictBatchBuilder, PriorityBatchBuilder};
+use sov_sequencer::soft_confirmation::{SoftConfirmer, SpeculativeExecutor};
+use sov_sequencer::BatchProducerHandle;
+use sov_state::storage::{NativeStorage, Storage};
+use sov_stf_runner::{read_json_file, BatchBuilderKind, SequencerConfig};
 
 /// Register rollup's default rpc methods.
+/// The sequencer's methods are registered separately by [`register_sequencer`].
 pub fn register_rpc<RT, C, Da>(
     storage: &<C as Spec>::Storage,
     ledger_db: &LedgerDB,
-    da_service: &Da,
 ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error>
 where
-    RT: RuntimeTrait<C, <Da as DaService>::Spec> + Send + Sync + 'static,
+    RT: RuntimeTrait<C, <Da as DaService>::Spec> + RuntimeEventJsonSchema + Send + Sync + 'static,
+    RT::RuntimeEvent: serde::Serialize,
     C: Context,
-    Da: DaService + Clone,
+    <C as Spec>::Storage: NativeStorage + 'static,
+    <<C as Spec>::Storage as Storage>::Proof: Into<JmtProof>,
+    Da: DaService,
 {
     // runtime rpc.
-    let mut rpc_methods = RT::rpc_methods(storage.clone());
+    let mut rpc_methods = RT::rpc_methods(storage.clone(), Arc::new(ledger_db.clone()));
+
+    // state rpc.
+    {
+        let state_rpc = sov_modules_api::rpc::get_state_rpc_methods(
+            storage.clone(),
+            Arc::new(ledger_db.clone()),
+        )?;
+        rpc_methods
+            .merge(state_rpc)
+            .context("Failed to merge state RPC modules")?;
+    }
 
     // ledger rpc.
     {
@@ -26,22 +51,86 @@ where
             SequencerOutcome<<C as Spec>::Address>,
             TxEffect,
         >(ledger_db.clone())?)?;
-    }
-
-    // sequencer rpc.
-    {
-        let batch_builder = FiFoStrictBatchBuilder::new(
-            1024 * 100,
-            u32::MAX as usize,
+        rpc_methods.merge(sov_ledger_rpc::server::decoded_events_rpc_module(
+            ledger_db.clone(),
             RT::default(),
-            storage.clone(),
-        );
-
-        let sequencer_rpc = sov_sequencer::get_sequencer_rpc(batch_builder, da_service.clone());
-        rpc_methods
-            .merge(sequencer_rpc)
-            .context("Failed to merge Txs RPC modules")?;
+        )?)?;
     }
 
     Ok(rpc_methods)
 }
+
+/// Registers the sequencer's rpc methods and spawns its batch producer,
+/// which publishes the accepted transactions according to `config`.
+/// The mempool is ordered by the batch builder selected in `config`, and persisted in
+/// `sequencer_db` until its transactions are observed in a slot committed to `ledger_db`.
+/// If soft confirmations are enabled, the accepted transactions are executed speculatively
+/// and confirmed with the key configured in `config`.
+pub fn register_sequencer<RT, K, C, Da>(
+    storage: &<C as Spec>::Storage,
+    da_service: &Da,
+    sequencer_db: SequencerDB,
+    ledger_db: &LedgerDB,
+    config: SequencerConfig,
+    rpc_methods: &mut jsonrpsee::RpcModule<()>,
+) -> Result<BatchProducerHandle, anyhow::Error>
+where
+    RT: RuntimeTrait<C, <Da as DaService>::Spec> + Send + Sync + 'static,
+    K: Kernel<C, <Da as DaService>::Spec> + Send + Sync + 'static,
+    C: Context,
+    Da: DaService + Clone,
+{
+    let mempool = &config.mempool;
+    let batch_builder: Box<dyn BatchBuilder + Send + Sync> = match mempool.kind {
+        BatchBuilderKind::Fifo => {
+            Box::new(
+                FiFoStrictBatchBuilder::<C, <Da as DaService>::Spec, RT, K>::new(
+                    mempool.max_batch_size_bytes,
+                    mempool.max_txs,
+                    RT::default(),
+                    storage.clone(),
+                ),
+            )
+        }
+        BatchBuilderKind::Priority => {
+            Box::new(
+                PriorityBatchBuilder::<C, <Da as DaService>::Spec, RT, K>::new(
+                    mempool.max_batch_size_bytes,
+                    mempool.max_txs,
+                    mempool.max_tx_age(),
+                    RT::default(),
+                    storage.clone(),
+                ),
+            )
+        }
+    };
+
+    let soft_confirmer: Option<Box<dyn SoftConfirmer + Send>> = match &config.soft_confirmations {
+        Some(soft_confirmations) => {
+            let key_and_address: PrivateKeyAndAddress<C> =
+                read_json_file(&soft_confirmations.private_key_path)
+                    .context("Failed to read the soft confirmation key")?;
+            let executor = SpeculativeExecutor::<C, <Da as DaService>::Spec, RT, K>::new(
+                RT::default(),
+                storage.clone(),
+                key_and_address.private_key,
+            );
+            Some(Box::new(executor))
+        }
+        None => None,
+    };
+
+    let (sequencer_rpc, batch_producer) = sov_sequencer::start_sequencer_with_wal(
+        batch_builder,
+        da_service.clone(),
+        config.batch_production,
+        sequencer_db,
+        ledger_db.clone(),
+        soft_confirmer,
+    )?;
+    rpc_methods
+        .merge(sequencer_rpc)
+        .context("Failed to merge Txs RPC modules")?;
+
+    Ok(batch_producer)
+}
@@ -0,0 +1,66 @@
+use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader, MockDaSpec};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{StateValue, WorkingSet};
+use sov_modules_rollup_blueprint::get_resume_state_root;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::config::Config;
+use sov_state::{DefaultStorageSpec, Prefix, ProverStorage, Storage};
+
+type TestStorage = ProverStorage<DefaultStorageSpec>;
+
+/// Commits the next version of the state, which sets the value to `value`, and returns its root.
+fn commit_version(storage: &TestStorage, value: u32) -> <TestStorage as Storage>::Root {
+    let state_value = StateValue::new(Prefix::new(vec![0]));
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+    state_value.set(&value, &mut working_set);
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    storage.validate_and_commit(cache_log, &witness).unwrap()
+}
+
+#[test]
+fn test_resume_from_root_of_head_slot() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let slot_roots = {
+        let mut storage_manager =
+            ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(Config {
+                path: tempdir.path().to_path_buf(),
+            })
+            .unwrap();
+        let block_header = MockBlockHeader::default();
+        let storage = storage_manager
+            .get_native_storage_on(&block_header)
+            .unwrap();
+        let ledger_db = LedgerDB::with_path(tempdir.path()).unwrap();
+        // Nothing has been committed yet, so the rollup starts from genesis.
+        assert_eq!(get_resume_state_root(&ledger_db, &storage).unwrap(), None);
+
+        let genesis_root = commit_version(&storage, 0);
+        let slot_roots = [
+            genesis_root,
+            commit_version(&storage, 1),
+            commit_version(&storage, 2),
+        ];
+        storage_manager
+            .save_change_set(&block_header, storage)
+            .unwrap();
+        storage_manager.finalize(&block_header).unwrap();
+        for _ in 1..slot_roots.len() {
+            ledger_db
+                .commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(MockBlock::default()))
+                .unwrap();
+        }
+        slot_roots
+    };
+
+    // Restart the node on top of the same databases. It resumes from the root after the second
+    // slot, not from the one before it. Resuming only reads the state.
+    let storage = TestStorage::with_path(tempdir.path()).unwrap();
+    let ledger_db = LedgerDB::with_path(tempdir.path()).unwrap();
+    assert_eq!(
+        get_resume_state_root(&ledger_db, &storage).unwrap(),
+        Some(slot_roots[2])
+    );
+}
@@ -0,0 +1,129 @@
+//! A kernel module adjusting the base fee of the rollup after every slot, EIP-1559 style.
+use sov_modules_api::macros::config_constant;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, GasUnit, ModuleInfo, WorkingSet};
+
+/// The price of each dimension of gas before the first slot.
+#[config_constant]
+pub const INITIAL_BASE_FEE: [u64; 2];
+
+/// The gas every slot should use, per dimension. The base fee rises after a slot using more gas
+/// than its target, and falls after a slot using less. The base fee of a dimension with a zero
+/// target never changes.
+#[config_constant]
+pub const TARGET_GAS_PER_SLOT: [u64; 2];
+
+/// Bounds the change of the base fee between two consecutive slots, EIP-1559 style: a slot which
+/// uses twice its gas target raises the base fee by `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR`.
+#[config_constant]
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64;
+
+/// Tracks the gas used by every slot and prices the gas of the next one.
+///
+/// The module is owned by the kernel rather than by the runtime, so that the runtime can't
+/// tamper with the price of gas. Its parameters are the config constants of this file.
+#[derive(ModuleInfo)]
+pub struct BaseFeeOracle<C: Context> {
+    /// The address of the module.
+    #[address]
+    address: C::Address,
+
+    /// The price of each dimension of gas in the current slot
+    #[state]
+    base_fee: sov_modules_api::StateValue<C::GasUnit>,
+
+    /// The gas used so far by the transactions of the current slot
+    #[state]
+    gas_used_in_slot: sov_modules_api::StateValue<C::GasUnit>,
+}
+
+impl<C: Context> BaseFeeOracle<C> {
+    /// Returns the price of each dimension of gas for the current slot.
+    pub fn get_base_fee(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        self.base_fee
+            .get(working_set)
+            .unwrap_or_else(|| C::GasUnit::from_arbitrary_dimensions(&INITIAL_BASE_FEE))
+    }
+
+    /// Returns the gas used so far by the transactions of the current slot.
+    pub fn get_gas_used_in_slot(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        self.gas_used_in_slot
+            .get(working_set)
+            .unwrap_or(C::GasUnit::ZEROED)
+    }
+
+    /// Records the gas used by a transaction of the current slot.
+    pub fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>) {
+        let total = self
+            .get_gas_used_in_slot(working_set)
+            .saturating_add(gas_used);
+        self.gas_used_in_slot.set(&total, working_set);
+    }
+
+    /// Moves the base fee toward the gas target, according to the gas used by the slot which just
+    /// ended, and starts metering the next slot.
+    pub fn update_base_fee(&self, working_set: &mut WorkingSet<C>) {
+        let base_fee = self.get_base_fee(working_set);
+        let gas_used = self.get_gas_used_in_slot(working_set);
+        let gas_target = C::GasUnit::from_arbitrary_dimensions(&TARGET_GAS_PER_SLOT);
+
+        let next_base_fee = base_fee
+            .dimensions()
+            .iter()
+            .zip(gas_used.dimensions())
+            .zip(gas_target.dimensions())
+            .map(|((base_fee, gas_used), gas_target)| {
+                next_base_fee(*base_fee, *gas_used, *gas_target)
+            })
+            .collect::<Vec<_>>();
+
+        self.base_fee.set(
+            &C::GasUnit::from_arbitrary_dimensions(&next_base_fee),
+            working_set,
+        );
+        self.gas_used_in_slot.set(&C::GasUnit::ZEROED, working_set);
+    }
+}
+
+/// Computes the base fee of a single gas dimension for the next slot, given the gas used by the
+/// current one.
+pub fn next_base_fee(base_fee: u64, gas_used: u64, gas_target: u64) -> u64 {
+    if gas_target == 0 || gas_used == gas_target {
+        return base_fee;
+    }
+
+    let delta = |gas_delta: u64| {
+        u128::from(base_fee) * u128::from(gas_delta)
+            / u128::from(gas_target)
+            / u128::from(BASE_FEE_MAX_CHANGE_DENOMINATOR)
+    };
+
+    if gas_used > gas_target {
+        // Always raise the base fee of a congested slot, even if it is too low to be scaled.
+        let delta = delta(gas_used - gas_target).max(1);
+        base_fee.saturating_add(u64::try_from(delta).unwrap_or(u64::MAX))
+    } else {
+        // The delta is lower than the base fee, so it fits in a `u64`.
+        base_fee.saturating_sub(delta(gas_target - gas_used) as u64)
+    }
+}
+
+/// Registers the `baseFee_getBaseFee` RPC method, which returns the price of each dimension of
+/// gas in the current slot.
+#[cfg(feature = "native")]
+pub fn get_base_fee_rpc_methods<C: Context>(
+    storage: C::Storage,
+) -> anyhow::Result<jsonrpsee::RpcModule<()>>
+where
+    C::Storage: 'static,
+{
+    let mut rpc = jsonrpsee::RpcModule::new(());
+
+    rpc.register_method("baseFee_getBaseFee", move |_, _| {
+        let mut working_set = WorkingSet::<C>::new(storage.clone());
+        let base_fee = BaseFeeOracle::<C>::default().get_base_fee(&mut working_set);
+        Ok::<_, jsonrpsee::types::ErrorObjectOwned>(base_fee.dimensions().to_vec())
+    })?;
+
+    Ok(rpc)
+}
@@ -1,30 +1,56 @@
 //! The basic kernel provides censorship resistance by processing all blobs immediately in the order they appear on DA
+use sov_chain_state::ChainState;
 use sov_modules_api::runtime::capabilities::{BlobRefOrOwned, BlobSelector, Kernel};
-use sov_modules_api::{Context, DaSpec};
+use sov_modules_api::{Context, DaSpec, WorkingSet};
+
+use crate::kernels::base_fee::BaseFeeOracle;
 
 /// The simplest imaginable kernel. It does not do any batching or reordering of blobs.
-pub struct BasicKernel<C> {
-    phantom: std::marker::PhantomData<C>,
+///
+/// The slot height and the chain id are read from the [`ChainState`] module, which must be part of the runtime.
+/// The gas is priced by its own [`BaseFeeOracle`].
+pub struct BasicKernel<C: Context, Da: DaSpec> {
+    chain_state: ChainState<C, Da>,
+    base_fee: BaseFeeOracle<C>,
 }
 
-impl<C: Context> Default for BasicKernel<C> {
+impl<C: Context, Da: DaSpec> Default for BasicKernel<C, Da> {
     fn default() -> Self {
         Self {
-            phantom: std::marker::PhantomData,
+            chain_state: Default::default(),
+            base_fee: Default::default(),
         }
     }
 }
 
-impl<C: Context, Da: DaSpec> Kernel<C, Da> for BasicKernel<C> {
-    fn true_height(&self) -> u64 {
-        todo!()
+impl<C: Context, Da: DaSpec> Kernel<C, Da> for BasicKernel<C, Da> {
+    fn true_height(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.chain_state.get_slot_height(working_set)
+    }
+
+    // Blobs are executed as soon as they appear on DA, so transactions always see the true height.
+    fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.true_height(working_set)
+    }
+
+    fn chain_id(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.chain_state.get_chain_id(working_set)
     }
-    fn visible_height(&self) -> u64 {
-        todo!()
+
+    fn base_fee(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        self.base_fee.get_base_fee(working_set)
+    }
+
+    fn record_gas_used(&self, gas_used: &C::GasUnit, working_set: &mut WorkingSet<C>) {
+        self.base_fee.record_gas_used(gas_used, working_set);
+    }
+
+    fn end_slot_hook(&self, working_set: &mut WorkingSet<C>) {
+        self.base_fee.update_base_fee(working_set);
     }
 }
 
-impl<C: Context, Da: DaSpec> BlobSelector<Da> for BasicKernel<C> {
+impl<C: Context, Da: DaSpec> BlobSelector<Da> for BasicKernel<C, Da> {
     type Context = C;
 
     fn get_blobs_for_this_slot<'a, I>(
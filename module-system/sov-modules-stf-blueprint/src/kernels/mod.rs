@@ -1,2 +1,3 @@
 //! Defines different kernels responsible for ensuring censorship resistance and light client security.
+pub mod base_fee;
 pub mod basic;
@@ -17,7 +17,7 @@ use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
 use sov_state::Storage;
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
-pub use stf_blueprint::StfBlueprint;
+pub use stf_blueprint::{apply_tx, StfBlueprint, TxEnvironment};
 use tracing::info;
 pub use tx_verifier::RawTx;
 
@@ -44,8 +44,12 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     type GenesisPaths: Send + Sync;
 
     #[cfg(feature = "native")]
-    /// Default rpc methods.
-    fn rpc_methods(storage: <C as Spec>::Storage) -> jsonrpsee::RpcModule<()>;
+    /// Default rpc methods. The slots targeted by historical queries are looked up with the
+    /// `slot_resolver`.
+    fn rpc_methods(
+        storage: <C as Spec>::Storage,
+        slot_resolver: std::sync::Arc<dyn sov_modules_api::rpc::SlotResolver>,
+    ) -> jsonrpsee::RpcModule<()>;
 
     #[cfg(feature = "native")]
     /// Reads genesis configs.
@@ -55,12 +59,38 @@ pub trait Runtime<C: Context, Da: DaSpec>:
 }
 
 /// The receipts of all the transactions in a batch.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    borsh::BorshSerialize,
+    borsh::BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum TxEffect {
     /// Batch was reverted.
-    Reverted,
+    Reverted {
+        /// The gas fee paid for the transaction, in gas tokens.
+        fee: u64,
+    },
     /// Batch was processed successfully.
-    Successful,
+    Successful {
+        /// The gas fee paid for the transaction, in gas tokens.
+        fee: u64,
+    },
+}
+
+impl TxEffect {
+    /// Returns the gas fee paid for the transaction, in gas tokens. This is the price of the
+    /// gas used by the transaction, not the amount of gas itself.
+    pub fn fee(&self) -> u64 {
+        match self {
+            TxEffect::Reverted { fee } | TxEffect::Successful { fee } => *fee,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -127,10 +157,13 @@ where
     ) -> (
         <<C as Spec>::Storage as Storage>::Root,
         <<C as Spec>::Storage as Storage>::Witness,
+        C::Storage,
     ) {
         // Run end end_slot_hook
         let mut working_set = checkpoint.to_revertable();
         self.runtime.end_slot_hook(&mut working_set);
+        // The kernel prices the gas of the next slot, once all the transactions of this one have been applied
+        self.kernel.end_slot_hook(&mut working_set);
         // Save checkpoint
         let mut checkpoint = working_set.checkpoint();
 
@@ -150,7 +183,7 @@ where
 
         storage.commit(&state_update, &accessory_log);
 
-        (root_hash, witness)
+        (root_hash, witness, storage)
     }
 }
 
@@ -166,7 +199,7 @@ where
 
     type GenesisParams = <RT as Genesis>::Config;
     type PreState = C::Storage;
-    type ChangeSet = ();
+    type ChangeSet = C::Storage;
 
     type TxReceiptContents = TxEffect;
 
@@ -201,10 +234,11 @@ where
 
         let accessory_log = working_set.checkpoint().freeze_non_provable();
 
-        // TODO: Commit here for now, but probably this can be done outside of STF
+        // Changes are committed to the storage itself,
+        // which is then returned as the change set to be saved by the storage manager
         pre_state.commit(&state_update, &accessory_log);
 
-        (genesis_hash, ())
+        (genesis_hash, pre_state)
     }
 
     fn apply_slot<'a, I>(
@@ -269,10 +303,10 @@ where
             batch_receipts.push(batch_receipt);
         }
 
-        let (state_root, witness) = self.end_slot(pre_state, checkpoint);
+        let (state_root, witness, storage) = self.end_slot(pre_state, checkpoint);
         SlotResult {
             state_root,
-            change_set: (),
+            change_set: storage,
             batch_receipts,
             witness,
         }
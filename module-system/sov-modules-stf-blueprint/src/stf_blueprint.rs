@@ -1,11 +1,14 @@
 use std::marker::PhantomData;
 
 use borsh::BorshDeserialize;
+use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::runtime::capabilities::Kernel;
+use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, StateCheckpoint,
+    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, StateCheckpoint, WorkingSet,
 };
 use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
+use sov_rollup_interface::zk::is_proof_blob;
 use tracing::{debug, error};
 
 use crate::tx_verifier::{verify_txs_stateless, TransactionAndRawHash};
@@ -131,6 +134,27 @@ where
         // TODO: don't ignore these events: https://github.com/Sovereign-Labs/sovereign/issues/350
         let _ = batch_workspace.take_events();
 
+        // Proofs are posted to the same namespace as batches, but are not executed by the rollup.
+        // Their senders are still checked by the `begin_blob_hook`, like the senders of batches.
+        if is_proof_blob(data_for_deserialization(blob)) {
+            debug!("Skipping proof blob 0x{}", hex::encode(blob.hash()));
+            let sequencer_outcome = SequencerOutcome::Rewarded(0);
+            if let Err(e) = self
+                .runtime
+                .end_blob_hook(sequencer_outcome.clone(), &mut batch_workspace)
+            {
+                error!("Failed on `end_blob_hook`: {}", e);
+            };
+            return (
+                Ok(BatchReceipt {
+                    batch_hash: blob.hash(),
+                    tx_receipts: Vec::new(),
+                    inner: sequencer_outcome,
+                }),
+                batch_workspace.checkpoint(),
+            );
+        }
+
         let (txs, messages) = match self.pre_process_batch(blob) {
             Ok((txs, messages)) => (txs, messages),
             Err(reason) => {
@@ -173,72 +197,35 @@ where
             "Error in preprocessing batch, there should be same number of txs and messages"
         );
 
+        // All the transactions of the blob see the same slot height and sequencer.
+        let env = TxEnvironment {
+            sequencer: blob.sender().as_ref().to_vec(),
+            visible_height: self.kernel.visible_height(&mut batch_workspace),
+            chain_id: self.kernel.chain_id(&mut batch_workspace),
+        };
+
         // Dispatching transactions
         let mut tx_receipts = Vec::with_capacity(txs.len());
+        let mut sequencer_reward = 0u64;
         for (TransactionAndRawHash { tx, raw_tx_hash }, msg) in
             txs.into_iter().zip(messages.into_iter())
         {
-            // Pre dispatch hook
-            let sender_address = match self.runtime.pre_dispatch_tx_hook(&tx, &mut batch_workspace)
-            {
-                Ok(verified_tx) => verified_tx,
-                Err(e) => {
-                    // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
-                    // For example nonce for the relevant account is incremented.
-                    error!("Stateful verification error - the sequencer included an invalid transaction: {}", e);
-                    let receipt = TransactionReceipt {
-                        tx_hash: raw_tx_hash,
-                        body_to_save: None,
-                        events: batch_workspace.take_events(),
-                        receipt: TxEffect::Reverted,
-                    };
-
-                    tx_receipts.push(receipt);
-                    continue;
-                }
-            };
-            // Commit changes after pre_dispatch_tx_hook
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
-
-            let ctx = C::new(sender_address.clone(), 1);
-            let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
-
-            let events = batch_workspace.take_events();
-            let tx_effect = match tx_result {
-                Ok(_) => TxEffect::Successful,
-                Err(e) => {
-                    error!(
-                        "Tx 0x{} was reverted error: {}",
-                        hex::encode(raw_tx_hash),
-                        e
-                    );
-                    // The transaction causing invalid state transition is reverted
-                    // but we don't slash and we continue processing remaining transactions.
-                    batch_workspace = batch_workspace.revert().to_revertable();
-                    TxEffect::Reverted
-                }
-            };
-            debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
-
-            let receipt = TransactionReceipt {
-                tx_hash: raw_tx_hash,
-                body_to_save: None,
-                events,
-                receipt: tx_effect,
-            };
-
+            let (receipt, workspace) = apply_tx(
+                &self.runtime,
+                &self.kernel,
+                &env,
+                &tx,
+                raw_tx_hash,
+                msg,
+                batch_workspace,
+            );
+            batch_workspace = workspace;
+            sequencer_reward = sequencer_reward.saturating_add(receipt.receipt.fee());
             tx_receipts.push(receipt);
-            // We commit after events have been extracted into receipt.
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
-
-            // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            self.runtime
-                .post_dispatch_tx_hook(&tx, &mut batch_workspace)
-                .expect("Impossible happened: error in post_dispatch_tx_hook");
         }
 
-        // TODO: calculate the amount based of gas and fees
-        let sequencer_outcome = SequencerOutcome::Rewarded(0);
+        // The fees consumed by the batch have already been settled by the post dispatch hook of each transaction.
+        let sequencer_outcome = SequencerOutcome::Rewarded(sequencer_reward);
 
         if let Err(e) = self
             .runtime
@@ -335,6 +322,142 @@ where
     }
 }
 
+/// The environment shared by all the transactions of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxEnvironment {
+    /// The DA address of the sequencer which published the batch.
+    /// Empty if the batch hasn't been published yet.
+    pub sequencer: Vec<u8>,
+    /// The slot height visible to the transactions.
+    pub visible_height: u64,
+    /// The chain id of the rollup.
+    pub chain_id: u64,
+}
+
+/// Executes a transaction which has passed stateless verification on top of `working_set`,
+/// surrounded by the transaction hooks of the runtime. This is how every transaction of a
+/// batch is applied, so it can also be used to execute transactions speculatively.
+///
+/// Returns the receipt of the transaction, along with the working set. The changes of a
+/// reverted transaction are discarded, except for the ones made by the pre dispatch hook.
+/// A transaction is also reverted if the post dispatch hook fails to settle its fee, in which
+/// case the fee is refunded and the nonce of the sender is still incremented.
+///
+/// The gas of the transaction is priced at the base fee of the `kernel`, which records the gas used.
+pub fn apply_tx<C, Da, RT, K>(
+    runtime: &RT,
+    kernel: &K,
+    env: &TxEnvironment,
+    tx: &Transaction<C>,
+    raw_tx_hash: [u8; 32],
+    msg: RT::Decodable,
+    mut batch_workspace: WorkingSet<C>,
+) -> (TransactionReceipt<TxEffect>, WorkingSet<C>)
+where
+    C: Context,
+    Da: DaSpec,
+    RT: DispatchCall<Context = C> + TxHooks<Context = C>,
+    K: Kernel<C, Da>,
+{
+    let base_fee = kernel.base_fee(&mut batch_workspace);
+    // Pre dispatch hook, once the transaction is known to be signed for this rollup and not expired
+    let sender_address = match tx
+        .check_validity(env.chain_id, env.visible_height)
+        .and_then(|()| runtime.pre_dispatch_tx_hook(tx, &base_fee, &mut batch_workspace))
+    {
+        Ok(verified_tx) => verified_tx,
+        Err(e) => {
+            // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
+            // For example nonce for the relevant account is incremented.
+            error!(
+                "Stateful verification error - the sequencer included an invalid transaction: {}",
+                e
+            );
+            // No gas is charged for a rejected transaction.
+            batch_workspace.take_gas_meter();
+            let receipt = TransactionReceipt {
+                tx_hash: raw_tx_hash,
+                body_to_save: None,
+                events: batch_workspace.take_events(),
+                receipt: TxEffect::Reverted { fee: 0 },
+            };
+
+            return (receipt, batch_workspace);
+        }
+    };
+    // Commit changes after pre_dispatch_tx_hook
+    batch_workspace = batch_workspace.checkpoint().to_revertable();
+
+    let ctx = C::new(
+        sender_address,
+        env.sequencer.clone(),
+        raw_tx_hash,
+        env.visible_height,
+    );
+    let mut tx_result = runtime
+        .dispatch_call(msg, &mut batch_workspace, &ctx)
+        .map(|_| ());
+    // State accesses can't fail, so running out of gas is only detected once the call completes.
+    if tx_result.is_ok() && batch_workspace.gas_meter().is_out_of_gas() {
+        tx_result = Err(anyhow::anyhow!("Not enough gas to access the state").into());
+    }
+
+    // Stop metering: the gas consumed by the transaction is paid in the post dispatch hook.
+    let gas_meter = batch_workspace.take_gas_meter();
+    let fee = gas_meter.consumed_funds();
+
+    let events = batch_workspace.take_events();
+    let mut tx_effect = match tx_result {
+        Ok(()) => TxEffect::Successful { fee },
+        Err(e) => {
+            error!(
+                "Tx 0x{} was reverted error: {}",
+                hex::encode(raw_tx_hash),
+                e
+            );
+            // The transaction causing invalid state transition is reverted
+            // but we don't slash and we continue processing remaining transactions.
+            batch_workspace = batch_workspace.revert().to_revertable();
+            TxEffect::Reverted { fee }
+        }
+    };
+
+    // The changes of the transaction are only committed along with the settlement of its fee,
+    // so that they are reverted together if the post dispatch hook fails.
+    if let Err(e) = runtime.post_dispatch_tx_hook(tx, &ctx, &gas_meter, &mut batch_workspace) {
+        error!(
+            "Tx 0x{} was reverted, error in post_dispatch_tx_hook: {}",
+            hex::encode(raw_tx_hash),
+            e
+        );
+        batch_workspace = batch_workspace.revert().to_revertable();
+        // The fee couldn't be settled, so the reserved fee is refunded, and the nonce is
+        // incremented anyway so that the transaction can't be replayed.
+        if let Err(e) = runtime.refund_tx_hook(tx, &ctx, &mut batch_workspace) {
+            error!(
+                "Failed to refund the fee of tx 0x{}, error in refund_tx_hook: {}",
+                hex::encode(raw_tx_hash),
+                e
+            );
+            batch_workspace = batch_workspace.revert().to_revertable();
+        }
+        tx_effect = TxEffect::Reverted { fee: 0 };
+    }
+    // The gas used by the transaction counts toward the base fee of the next slot, even if its fee couldn't be settled.
+    kernel.record_gas_used(gas_meter.gas_used(), &mut batch_workspace);
+    batch_workspace = batch_workspace.checkpoint().to_revertable();
+    debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
+
+    let receipt = TransactionReceipt {
+        tx_hash: raw_tx_hash,
+        body_to_save: None,
+        events,
+        receipt: tx_effect,
+    };
+
+    (receipt, batch_workspace)
+}
+
 #[cfg(feature = "native")]
 fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
     blob.full_data()
@@ -0,0 +1,421 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use digest::typenum::U32;
+use digest::Digest;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::rpc::utils::rpc_hex;
+
+/// The hash of an empty subtree.
+pub const SPARSE_MERKLE_PLACEHOLDER_HASH: [u8; 32] = *b"SPARSE_MERKLE_PLACEHOLDER_HASH__";
+/// The domain separator of the hash of a leaf, as defined by the JMT.
+const LEAF_DOMAIN_SEPARATOR: &[u8] = b"JMT::LeafNode";
+/// The domain separator of the hash of an internal node, as defined by the JMT.
+const INTERNAL_DOMAIN_SEPARATOR: &[u8] = b"JMT::IntrnalNode";
+/// The maximum depth of a path in the tree, in bits.
+pub const MAX_DEPTH: usize = 256;
+
+/// The value of a single storage key, proven against the state root right after a slot.
+///
+/// This is the response of the `state_getWithProof` RPC method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProof {
+    /// The raw storage key, i.e. the prefix of the module state item followed by the encoded key.
+    #[serde(with = "rpc_hex")]
+    pub key: Vec<u8>,
+    /// The value of the key, or [`None`] if the key is absent from the state.
+    #[serde(with = "rpc_hex_option")]
+    pub value: Option<Vec<u8>>,
+    /// The proof of the value against `state_root`.
+    pub proof: JmtProof,
+    /// The root of the state the proof was produced against.
+    #[serde(with = "rpc_hex")]
+    pub state_root: [u8; 32],
+    /// The number of the slot after which the state had this root.
+    pub slot_number: u64,
+}
+
+impl StateProof {
+    /// Verifies the proof against a state root trusted by the caller, and returns the proven
+    /// value of the key.
+    pub fn verify<H: Digest<OutputSize = U32>>(
+        &self,
+        trusted_state_root: &[u8; 32],
+    ) -> Result<Option<&[u8]>, VerificationError> {
+        if &self.state_root != trusted_state_root {
+            return Err(VerificationError::UntrustedStateRoot);
+        }
+        let value = self.value.as_deref();
+        self.proof
+            .verify::<H>(trusted_state_root, &self.key, value)?;
+        Ok(value)
+    }
+}
+
+/// The leaf of the tree where the lookup of a key ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JmtLeaf {
+    /// The hash of the key stored in the leaf.
+    #[serde(with = "rpc_hex")]
+    pub key_hash: [u8; 32],
+    /// The hash of the value stored in the leaf.
+    #[serde(with = "rpc_hex")]
+    pub value_hash: [u8; 32],
+}
+
+impl JmtLeaf {
+    /// Returns the hash of the leaf, as computed by the JMT.
+    pub fn hash<H: Digest<OutputSize = U32>>(&self) -> [u8; 32] {
+        H::new()
+            .chain_update(LEAF_DOMAIN_SEPARATOR)
+            .chain_update(self.key_hash)
+            .chain_update(self.value_hash)
+            .finalize()
+            .into()
+    }
+}
+
+/// A proof of the value of a single key in a Jellyfish Merkle Tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JmtProof {
+    /// The leaf where the lookup of the key ends. It only matches the key if the key is in the
+    /// tree. If the lookup ends in an empty subtree, this is [`None`].
+    pub leaf: Option<JmtLeaf>,
+    /// The hashes of the siblings of the path of the key, ordered from the leaf to the root.
+    pub siblings: Vec<HexHash>,
+}
+
+/// A 32-byte hash, serialized as a `0x`-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HexHash(#[serde(with = "rpc_hex")] pub [u8; 32]);
+
+impl JmtProof {
+    /// Verifies that `key` has the given value in the tree with root `state_root`, or that the
+    /// key is absent from the tree if the value is [`None`].
+    pub fn verify<H: Digest<OutputSize = U32>>(
+        &self,
+        state_root: &[u8; 32],
+        key: &[u8],
+        value: Option<&[u8]>,
+    ) -> Result<(), VerificationError> {
+        if self.siblings.len() > MAX_DEPTH {
+            return Err(VerificationError::TooManySiblings(self.siblings.len()));
+        }
+        let key_hash: [u8; 32] = H::digest(key).into();
+
+        match (value, self.leaf) {
+            (Some(value), Some(leaf)) => {
+                if leaf.key_hash != key_hash {
+                    return Err(VerificationError::LeafKeyMismatch);
+                }
+                let value_hash: [u8; 32] = H::digest(value).into();
+                if leaf.value_hash != value_hash {
+                    return Err(VerificationError::LeafValueMismatch);
+                }
+            }
+            (Some(_), None) => return Err(VerificationError::MissingLeaf),
+            (None, Some(leaf)) => {
+                if leaf.key_hash == key_hash {
+                    return Err(VerificationError::LeafKeyMismatch);
+                }
+                // The leaf of another key must be the only leaf of the subtree the lookup ended in.
+                if common_prefix_len(&leaf.key_hash, &key_hash) < self.siblings.len() {
+                    return Err(VerificationError::LeafNotOnPath);
+                }
+            }
+            (None, None) => {}
+        }
+
+        let leaf_hash = self
+            .leaf
+            .map_or(SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash::<H>());
+        let depth = self.siblings.len();
+        let root =
+            self.siblings
+                .iter()
+                .enumerate()
+                .fold(leaf_hash, |node, (i, HexHash(sibling))| {
+                    if bit(&key_hash, depth - 1 - i) {
+                        internal_hash::<H>(sibling, &node)
+                    } else {
+                        internal_hash::<H>(&node, sibling)
+                    }
+                });
+
+        if &root != state_root {
+            return Err(VerificationError::RootMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "jmt")]
+impl<H: Digest<OutputSize = U32>> From<jmt::proof::SparseMerkleProof<H>> for JmtProof {
+    fn from(proof: jmt::proof::SparseMerkleProof<H>) -> Self {
+        Self {
+            leaf: proof.leaf().map(|leaf| JmtLeaf {
+                key_hash: leaf.key_hash().0,
+                value_hash: leaf.value_hash().0,
+            }),
+            // The siblings of a jmt proof are already ordered from the leaf to the root.
+            siblings: proof
+                .siblings()
+                .iter()
+                .map(|sibling| HexHash(sibling.hash::<H>()))
+                .collect(),
+        }
+    }
+}
+
+/// The reasons a [`StateProof`] can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The proof was produced against a different state root than the trusted one.
+    UntrustedStateRoot,
+    /// The proof has more siblings than the depth of the tree.
+    TooManySiblings(usize),
+    /// The proof of a present key doesn't contain a leaf.
+    MissingLeaf,
+    /// The leaf of the proof doesn't belong to the proven key, or the proof of an absent key
+    /// contains the leaf of that key.
+    LeafKeyMismatch,
+    /// The leaf of the proof doesn't contain the proven value.
+    LeafValueMismatch,
+    /// The leaf of the proof isn't on the path of the proven key.
+    LeafNotOnPath,
+    /// The root computed from the proof doesn't match the state root.
+    RootMismatch,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UntrustedStateRoot => {
+                write!(f, "The proof was produced against an untrusted state root")
+            }
+            Self::TooManySiblings(siblings) => write!(
+                f,
+                "The proof has {siblings} siblings, but the tree is at most {MAX_DEPTH} levels deep"
+            ),
+            Self::MissingLeaf => write!(f, "The proof of a present key must contain its leaf"),
+            Self::LeafKeyMismatch => write!(f, "The leaf of the proof doesn't match the key"),
+            Self::LeafValueMismatch => write!(f, "The leaf of the proof doesn't match the value"),
+            Self::LeafNotOnPath => write!(f, "The leaf of the proof isn't on the path of the key"),
+            Self::RootMismatch => write!(f, "The proof doesn't match the state root"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {}
+
+/// Returns the hash of an internal node of the tree, as computed by the JMT.
+pub fn internal_hash<H: Digest<OutputSize = U32>>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    H::new()
+        .chain_update(INTERNAL_DOMAIN_SEPARATOR)
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Returns the bit of `hash` at `index`, counting from the most significant bit of the first byte.
+pub fn bit(hash: &[u8; 32], index: usize) -> bool {
+    hash[index / 8] & (0x80 >> (index % 8)) != 0
+}
+
+/// Returns the number of leading bits shared by two hashes.
+pub fn common_prefix_len(left: &[u8; 32], right: &[u8; 32]) -> usize {
+    (0..MAX_DEPTH)
+        .find(|&index| bit(left, index) != bit(right, index))
+        .unwrap_or(MAX_DEPTH)
+}
+
+/// Serialization of optional byte vectors as `0x`-prefixed hex strings, or `null`.
+mod rpc_hex_option {
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::rpc_hex;
+
+    pub fn serialize<S: Serializer>(
+        data: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match data {
+            Some(data) => rpc_hex::serialize(data, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        #[derive(Deserialize)]
+        struct HexBytes(#[serde(with = "rpc_hex")] Vec<u8>);
+
+        Ok(Option::<HexBytes>::deserialize(deserializer)?.map(|HexBytes(data)| data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+
+    fn key_hash(key: &[u8]) -> [u8; 32] {
+        Sha256::digest(key).into()
+    }
+
+    fn leaf(key: &[u8], value: &[u8]) -> JmtLeaf {
+        JmtLeaf {
+            key_hash: key_hash(key),
+            value_hash: Sha256::digest(value).into(),
+        }
+    }
+
+    /// Returns two keys whose hashes differ in their first bit, ordered by that bit.
+    fn keys_in_different_halves() -> (Vec<u8>, Vec<u8>) {
+        let first = b"key-0".to_vec();
+        let second = (1u8..)
+            .map(|i| alloc::format!("key-{i}").into_bytes())
+            .find(|key| bit(&key_hash(key), 0) != bit(&key_hash(&first), 0))
+            .unwrap();
+        if bit(&key_hash(&first), 0) {
+            (second, first)
+        } else {
+            (first, second)
+        }
+    }
+
+    /// Builds a tree of two leaves, one in each half, and returns its root with the proof of the
+    /// left key.
+    fn two_leaves_tree() -> (Vec<u8>, Vec<u8>, [u8; 32], JmtProof) {
+        let (left, right) = keys_in_different_halves();
+        let left_leaf = leaf(&left, b"left");
+        let right_leaf = leaf(&right, b"right");
+        let root =
+            internal_hash::<Sha256>(&left_leaf.hash::<Sha256>(), &right_leaf.hash::<Sha256>());
+        let proof = JmtProof {
+            leaf: Some(left_leaf),
+            siblings: vec![HexHash(right_leaf.hash::<Sha256>())],
+        };
+        (left, right, root, proof)
+    }
+
+    #[test]
+    fn test_verify_present_key() {
+        let (left, _, root, proof) = two_leaves_tree();
+        let state_proof = StateProof {
+            key: left,
+            value: Some(b"left".to_vec()),
+            proof,
+            state_root: root,
+            slot_number: 3,
+        };
+        assert_eq!(
+            state_proof.verify::<Sha256>(&root),
+            Ok(Some(b"left".as_slice()))
+        );
+    }
+
+    #[test]
+    fn test_reject_wrong_value_and_root() {
+        let (left, right, root, proof) = two_leaves_tree();
+        assert_eq!(
+            proof.verify::<Sha256>(&root, &left, Some(b"right")),
+            Err(VerificationError::LeafValueMismatch)
+        );
+        assert_eq!(
+            proof.verify::<Sha256>(&root, &right, Some(b"right")),
+            Err(VerificationError::LeafKeyMismatch)
+        );
+        assert_eq!(
+            proof.verify::<Sha256>(&[0; 32], &left, Some(b"left")),
+            Err(VerificationError::RootMismatch)
+        );
+
+        let state_proof = StateProof {
+            key: left,
+            value: Some(b"left".to_vec()),
+            proof,
+            state_root: root,
+            slot_number: 3,
+        };
+        assert_eq!(
+            state_proof.verify::<Sha256>(&[0; 32]),
+            Err(VerificationError::UntrustedStateRoot)
+        );
+    }
+
+    #[test]
+    fn test_verify_absent_key() {
+        let single_leaf = leaf(b"present", b"value");
+        let proof = JmtProof {
+            leaf: Some(single_leaf),
+            siblings: vec![],
+        };
+        let root = single_leaf.hash::<Sha256>();
+        assert_eq!(proof.verify::<Sha256>(&root, b"absent", None), Ok(()));
+        assert_eq!(
+            proof.verify::<Sha256>(&root, b"present", None),
+            Err(VerificationError::LeafKeyMismatch)
+        );
+
+        let empty = JmtProof {
+            leaf: None,
+            siblings: vec![],
+        };
+        assert_eq!(
+            empty.verify::<Sha256>(&SPARSE_MERKLE_PLACEHOLDER_HASH, b"absent", None),
+            Ok(())
+        );
+        assert_eq!(
+            empty.verify::<Sha256>(&SPARSE_MERKLE_PLACEHOLDER_HASH, b"absent", Some(b"value")),
+            Err(VerificationError::MissingLeaf)
+        );
+    }
+
+    #[test]
+    fn test_reject_leaf_off_path() {
+        let (left, right, root, proof) = two_leaves_tree();
+        // The leaf of the left key can't prove the absence of a key of the right half.
+        let absent = (0u8..)
+            .map(|i| alloc::format!("absent-{i}").into_bytes())
+            .find(|key| bit(&key_hash(key), 0) == bit(&key_hash(&right), 0))
+            .unwrap();
+        assert_eq!(
+            proof.verify::<Sha256>(&root, &absent, None),
+            Err(VerificationError::LeafNotOnPath)
+        );
+        assert_eq!(proof.verify::<Sha256>(&root, &left, Some(b"left")), Ok(()));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let (left, _, root, proof) = two_leaves_tree();
+        for value in [Some(b"left".to_vec()), None] {
+            let state_proof = StateProof {
+                key: left.clone(),
+                value,
+                proof: proof.clone(),
+                state_root: root,
+                slot_number: 3,
+            };
+            let json = serde_json::to_string(&state_proof).unwrap();
+            assert_eq!(
+                serde_json::from_str::<StateProof>(&json).unwrap(),
+                state_proof
+            );
+        }
+    }
+}
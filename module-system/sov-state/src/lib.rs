@@ -7,16 +7,16 @@ pub mod codec;
 #[cfg(feature = "native")]
 mod prover_storage;
 
+mod multiproof;
 mod witness;
 mod zk_storage;
 
+pub use multiproof::SparseMerkleMultiProof;
 #[cfg(feature = "native")]
 pub use prover_storage::ProverStorage;
 pub use zk_storage::ZkStorage;
 
 pub mod config;
-#[cfg(feature = "native")]
-pub mod storage_manager;
 
 pub use sov_modules_core::{
     storage, AlignedVec, CacheLog, OrderedReadsAndWrites, Prefix, Storage, StorageInternalCache,
@@ -0,0 +1,454 @@
+//! Deduplicated proofs of many reads from the same version of the JMT.
+//!
+//! Proving every read with its own [`jmt::proof::SparseMerkleProof`] repeats the upper levels of
+//! the tree once per key, and verifying them hashes those levels once per key as well. A
+//! [`SparseMerkleMultiProof`] stores the path of every key once, along with the siblings which
+//! can't be computed from the other paths, so the root is recomputed in a single pass.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, ensure};
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::{KeyHash, RootHash, ValueHash};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::digest::Digest;
+use sov_state_verifier::{
+    bit, common_prefix_len, internal_hash, JmtLeaf, MAX_DEPTH, SPARSE_MERKLE_PLACEHOLDER_HASH,
+};
+
+/// A leaf of the tree.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+struct Leaf {
+    key_hash: [u8; 32],
+    value_hash: [u8; 32],
+}
+
+impl Leaf {
+    fn hash<H: Digest<OutputSize = sha2::digest::typenum::U32>>(&self) -> [u8; 32] {
+        JmtLeaf {
+            key_hash: self.key_hash,
+            value_hash: self.value_hash,
+        }
+        .hash::<H>()
+    }
+}
+
+/// The path from the root of the tree to the node where the lookup of a key ends.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+struct Path {
+    key_hash: [u8; 32],
+    /// The number of levels between the root and the node where the lookup ends.
+    depth: u16,
+    /// The leaf where the lookup ends. It only matches the key if the key is in the tree.
+    /// If the lookup ends in an empty subtree, this is [`None`].
+    leaf: Option<Leaf>,
+}
+
+impl Path {
+    fn terminal_hash<H: Digest<OutputSize = sha2::digest::typenum::U32>>(&self) -> [u8; 32] {
+        self.leaf
+            .map_or(SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash::<H>())
+    }
+}
+
+/// A proof of the values of many keys, all read from the same root.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SparseMerkleMultiProof {
+    /// The path of every proven key, sorted by key hash.
+    paths: Vec<Path>,
+    /// The hashes of the subtrees which don't contain any proven key, in the order they are
+    /// needed to recompute the root.
+    siblings: Vec<[u8; 32]>,
+}
+
+impl SparseMerkleMultiProof {
+    /// Merges the proofs of the provided keys, all generated against the same root.
+    /// Proofs of the same key are only included once.
+    ///
+    /// Fails if the proofs don't describe the same tree, e.g. if a proof is missing the sibling
+    /// of a subtree holding none of the other keys.
+    pub fn from_proofs<H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+        proofs: impl IntoIterator<Item = (KeyHash, jmt::proof::SparseMerkleProof<H>)>,
+    ) -> anyhow::Result<Self> {
+        let proofs = proofs
+            .into_iter()
+            .map(|(key_hash, proof)| {
+                let path = Path {
+                    key_hash: key_hash.0,
+                    depth: proof.siblings().len() as u16,
+                    leaf: proof.leaf().map(|leaf| Leaf {
+                        key_hash: leaf.key_hash().0,
+                        value_hash: leaf.value_hash().0,
+                    }),
+                };
+                // The siblings of a proof are ordered from the bottom of the tree to the root.
+                let siblings = proof
+                    .siblings()
+                    .iter()
+                    .rev()
+                    .map(|sibling| sibling.hash::<H>())
+                    .collect::<Vec<_>>();
+                (key_hash.0, (path, siblings))
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect::<Vec<_>>();
+
+        let mut siblings = Vec::new();
+        if !proofs.is_empty() {
+            collect_siblings(&proofs, 0, &mut siblings)?;
+        }
+
+        Ok(Self {
+            paths: proofs.into_iter().map(|(path, _)| path).collect(),
+            siblings,
+        })
+    }
+
+    /// Returns the number of keys proven by the multiproof.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns `true` if the multiproof doesn't prove any key.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Verifies that every `(key, value)` pair of `reads` is in the tree with the given root, or
+    /// that the key is absent from the tree if the value is [`None`].
+    ///
+    /// Every key must be proven by the multiproof, which must not prove any other key.
+    pub fn verify<'a, H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+        &self,
+        expected_root_hash: RootHash,
+        reads: impl IntoIterator<Item = (KeyHash, Option<&'a [u8]>)>,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.paths
+                .windows(2)
+                .all(|paths| paths[0].key_hash < paths[1].key_hash),
+            "The paths of the multiproof must be sorted by key hash"
+        );
+
+        let mut proven = vec![false; self.paths.len()];
+        for (key_hash, value) in reads {
+            let idx = self
+                .paths
+                .binary_search_by(|path| path.key_hash.cmp(&key_hash.0))
+                .map_err(|_| {
+                    anyhow::anyhow!("Key {:?} is not proven by the multiproof", key_hash)
+                })?;
+            verify_read::<H>(&self.paths[idx], value)?;
+            proven[idx] = true;
+        }
+        ensure!(
+            proven.into_iter().all(|proven| proven),
+            "The multiproof proves keys which were not read"
+        );
+
+        let root_hash = if self.paths.is_empty() {
+            ensure!(
+                self.siblings.is_empty(),
+                "An empty multiproof can't have siblings"
+            );
+            expected_root_hash.0
+        } else {
+            let mut siblings = self.siblings.iter();
+            let root_hash = subtree_hash::<H>(&self.paths, 0, &mut siblings)?;
+            ensure!(
+                siblings.next().is_none(),
+                "The multiproof has unused siblings"
+            );
+            root_hash
+        };
+
+        ensure!(
+            root_hash == expected_root_hash.0,
+            "Root hashes do not match. Expected: {:?}, actual: {:?}",
+            expected_root_hash,
+            RootHash(root_hash)
+        );
+        Ok(())
+    }
+}
+
+fn verify_read<H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+    path: &Path,
+    value: Option<&[u8]>,
+) -> anyhow::Result<()> {
+    ensure!(
+        path.depth as usize <= MAX_DEPTH,
+        "The path of key {:?} is too deep",
+        KeyHash(path.key_hash)
+    );
+
+    match (value, path.leaf) {
+        (Some(value), Some(leaf)) => {
+            ensure!(
+                leaf.key_hash == path.key_hash,
+                "Expected an inclusion proof for key {:?}, found a proof of its absence",
+                KeyHash(path.key_hash)
+            );
+            ensure!(
+                leaf.value_hash == ValueHash::with::<H>(value).0,
+                "Value hashes do not match for key {:?}",
+                KeyHash(path.key_hash)
+            );
+        }
+        (Some(_), None) => bail!(
+            "Expected an inclusion proof for key {:?}, found a proof of its absence",
+            KeyHash(path.key_hash)
+        ),
+        (None, Some(leaf)) => {
+            // The lookup ended in a subtree holding a single leaf, which must be another key.
+            ensure!(
+                leaf.key_hash != path.key_hash,
+                "Expected a proof of absence for key {:?}, found an inclusion proof",
+                KeyHash(path.key_hash)
+            );
+            ensure!(
+                common_prefix_len(&leaf.key_hash, &path.key_hash) >= path.depth as usize,
+                "The leaf of the proof of absence of key {:?} is not in its subtree",
+                KeyHash(path.key_hash)
+            );
+        }
+        (None, None) => {}
+    }
+
+    Ok(())
+}
+
+/// Recomputes the hash of the subtree at `depth` holding all the `paths`, which share their
+/// first `depth` bits.
+fn subtree_hash<'a, H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+    paths: &[Path],
+    depth: usize,
+    siblings: &mut impl Iterator<Item = &'a [u8; 32]>,
+) -> anyhow::Result<[u8; 32]> {
+    let terminal_paths = paths
+        .iter()
+        .filter(|path| path.depth as usize == depth)
+        .count();
+    if terminal_paths > 0 {
+        // A lookup which ends here ends in the same node for all the keys of the subtree.
+        ensure!(
+            terminal_paths == paths.len() && paths.iter().all(|path| path.leaf == paths[0].leaf),
+            "Inconsistent paths in the multiproof at depth {}",
+            depth
+        );
+        return Ok(paths[0].terminal_hash::<H>());
+    }
+    ensure!(depth < MAX_DEPTH, "The multiproof is too deep");
+
+    let (left, right) = paths.split_at(paths.partition_point(|path| !bit(&path.key_hash, depth)));
+    let left_hash = child_hash::<H>(left, depth, siblings)?;
+    let right_hash = child_hash::<H>(right, depth, siblings)?;
+
+    Ok(internal_hash::<H>(&left_hash, &right_hash))
+}
+
+/// Returns the hash of a child of the subtree at `depth`. It is only provided by the multiproof
+/// if none of the paths go through it.
+fn child_hash<'a, H: Digest<OutputSize = sha2::digest::typenum::U32>>(
+    paths: &[Path],
+    depth: usize,
+    siblings: &mut impl Iterator<Item = &'a [u8; 32]>,
+) -> anyhow::Result<[u8; 32]> {
+    if paths.is_empty() {
+        siblings
+            .next()
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("The multiproof is missing siblings"))
+    } else {
+        subtree_hash::<H>(paths, depth + 1, siblings)
+    }
+}
+
+/// Pushes the siblings needed to recompute the hash of the subtree at `depth`, in the order in
+/// which [`SparseMerkleMultiProof::verify`] consumes them.
+fn collect_siblings(
+    proofs: &[(Path, Vec<[u8; 32]>)],
+    depth: usize,
+    out: &mut Vec<[u8; 32]>,
+) -> anyhow::Result<()> {
+    if proofs.iter().any(|(path, _)| path.depth as usize <= depth) {
+        return Ok(());
+    }
+
+    let (left, right) =
+        proofs.split_at(proofs.partition_point(|(path, _)| !bit(&path.key_hash, depth)));
+    for (side, other_side) in [(left, right), (right, left)] {
+        if side.is_empty() {
+            // All the keys are on the other side, which knows the hash of this one.
+            let (path, siblings) = &other_side[0];
+            let sibling = siblings.get(depth).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The proof of key {:?} has no sibling at depth {}",
+                    KeyHash(path.key_hash),
+                    depth
+                )
+            })?;
+            out.push(*sibling);
+        } else {
+            collect_siblings(side, depth + 1, out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+
+    fn key(first_byte: u8) -> [u8; 32] {
+        let mut key = [0; 32];
+        key[0] = first_byte;
+        key
+    }
+
+    fn leaf(key_hash: [u8; 32], value: &[u8]) -> Leaf {
+        Leaf {
+            key_hash,
+            value_hash: ValueHash::with::<Sha256>(value).0,
+        }
+    }
+
+    fn internal(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+        internal_hash::<Sha256>(&left, &right)
+    }
+
+    /// A tree holding `a` and `b` on the left of the root, and `c` alone on its right.
+    fn tree() -> (RootHash, Leaf, Leaf, Leaf) {
+        let a = leaf(key(0b0000_0000), b"a");
+        let b = leaf(key(0b0100_0000), b"b");
+        let c = leaf(key(0b1000_0000), b"c");
+        let root = internal(
+            internal(a.hash::<Sha256>(), b.hash::<Sha256>()),
+            c.hash::<Sha256>(),
+        );
+        (RootHash(root), a, b, c)
+    }
+
+    #[test]
+    fn verifies_reads_with_shared_paths() {
+        let (root, a, b, c) = tree();
+        let proof = SparseMerkleMultiProof {
+            paths: vec![
+                Path {
+                    key_hash: a.key_hash,
+                    depth: 2,
+                    leaf: Some(a),
+                },
+                Path {
+                    key_hash: b.key_hash,
+                    depth: 2,
+                    leaf: Some(b),
+                },
+            ],
+            siblings: vec![c.hash::<Sha256>()],
+        };
+
+        proof
+            .verify::<Sha256>(
+                root,
+                [
+                    (KeyHash(a.key_hash), Some(&b"a"[..])),
+                    (KeyHash(b.key_hash), Some(&b"b"[..])),
+                ],
+            )
+            .unwrap();
+        assert!(proof
+            .verify::<Sha256>(
+                root,
+                [
+                    (KeyHash(a.key_hash), Some(&b"a"[..])),
+                    (KeyHash(b.key_hash), Some(&b"not b"[..])),
+                ],
+            )
+            .is_err());
+        // Every proven key must be read.
+        assert!(proof
+            .verify::<Sha256>(root, [(KeyHash(a.key_hash), Some(&b"a"[..]))])
+            .is_err());
+    }
+
+    #[test]
+    fn verifies_absent_keys() {
+        let (root, a, b, c) = tree();
+        // Shares its first bit with `c`, which is alone in the right subtree.
+        let missing = key(0b1100_0000);
+        let proof = SparseMerkleMultiProof {
+            paths: vec![
+                Path {
+                    key_hash: c.key_hash,
+                    depth: 1,
+                    leaf: Some(c),
+                },
+                Path {
+                    key_hash: missing,
+                    depth: 1,
+                    leaf: Some(c),
+                },
+            ],
+            siblings: vec![internal(a.hash::<Sha256>(), b.hash::<Sha256>())],
+        };
+
+        proof
+            .verify::<Sha256>(
+                root,
+                [
+                    (KeyHash(c.key_hash), Some(&b"c"[..])),
+                    (KeyHash(missing), None),
+                ],
+            )
+            .unwrap();
+        assert!(proof
+            .verify::<Sha256>(
+                root,
+                [
+                    (KeyHash(c.key_hash), Some(&b"c"[..])),
+                    (KeyHash(missing), Some(&b"c"[..])),
+                ],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_siblings() {
+        let (root, a, _, c) = tree();
+        let proof = SparseMerkleMultiProof {
+            paths: vec![Path {
+                key_hash: c.key_hash,
+                depth: 1,
+                leaf: Some(c),
+            }],
+            siblings: vec![a.hash::<Sha256>()],
+        };
+
+        assert!(proof
+            .verify::<Sha256>(root, [(KeyHash(c.key_hash), Some(&b"c"[..]))])
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_proofs_missing_siblings() {
+        let (_, a, _, c) = tree();
+        // `a` claims to be two levels deep, but its proof only knows the sibling of the root.
+        let proofs = vec![(
+            Path {
+                key_hash: a.key_hash,
+                depth: 2,
+                leaf: Some(a),
+            },
+            vec![c.hash::<Sha256>()],
+        )];
+
+        let mut siblings = Vec::new();
+        assert!(collect_siblings(&proofs, 0, &mut siblings).is_err());
+    }
+}
@@ -1,24 +1,33 @@
 use std::marker::PhantomData;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-use jmt::storage::{NodeBatch, TreeWriter};
+use jmt::storage::{NodeBatch, StaleNodeIndexBatch, TreeWriter};
 use jmt::{JellyfishMerkleTree, KeyHash, Version};
 use sov_db::native_db::NativeDB;
+use sov_db::snapshot_manager::SnapshotManager;
 use sov_db::state_db::StateDB;
 use sov_modules_core::{
     CacheKey, NativeStorage, OrderedReadsAndWrites, Storage, StorageKey, StorageProof,
     StorageValue, Witness,
 };
+use sov_schema_db::snapshot::{DbSnapshot, FrozenDbSnapshot, ReadOnlyLock};
 
-use crate::config::Config;
-use crate::MerkleProofSpec;
+use crate::{MerkleProofSpec, SparseMerkleMultiProof};
 
 /// A [`Storage`] implementation to be used by the prover in a native execution
 /// environment (outside of the zkVM).
+///
+/// Reads and writes go through a pair of database snapshots (state and native),
+/// so all changes stay isolated until the snapshots are committed to the database
+/// by their [`SnapshotManager`]s.
 pub struct ProverStorage<S: MerkleProofSpec> {
-    db: StateDB,
-    native_db: NativeDB,
+    db: StateDB<SnapshotManager>,
+    native_db: NativeDB<SnapshotManager>,
+    /// The version of the state read by this storage, or [`None`] to read the latest one.
+    version: Option<Version>,
+    /// Storage that is not backed by a storage manager can't commit changes, since they would never be persisted.
+    read_only: bool,
     _phantom_hasher: PhantomData<S::Hasher>,
 }
 
@@ -27,38 +36,57 @@ impl<S: MerkleProofSpec> Clone for ProverStorage<S> {
         Self {
             db: self.db.clone(),
             native_db: self.native_db.clone(),
+            version: self.version,
+            read_only: self.read_only,
             _phantom_hasher: Default::default(),
         }
     }
 }
 
 impl<S: MerkleProofSpec> ProverStorage<S> {
-    /// Creates a new [`ProverStorage`] instance at the specified path, opening
+    /// Creates a new read-only [`ProverStorage`] instance at the specified path, opening
     /// or creating the necessary RocksDB database(s) at the specified path.
+    /// The returned storage reads the latest data from the databases, but committing changes through it fails:
+    /// use a storage manager to obtain a storage whose changes can be saved.
     pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
-        let state_db = StateDB::with_path(&path)?;
-        let native_db = NativeDB::with_path(&path)?;
+        let state_db = StateDB::setup_schema_db(&path)?;
+        let native_db = NativeDB::setup_schema_db(&path)?;
 
         Ok(Self {
-            db: state_db,
-            native_db,
+            db: StateDB::with_db_snapshot(orphan_snapshot(state_db)),
+            native_db: NativeDB::with_db_snapshot(orphan_snapshot(native_db)),
+            version: None,
+            read_only: true,
             _phantom_hasher: Default::default(),
         })
     }
 
-    pub(crate) fn with_db_handles(db: StateDB, native_db: NativeDB) -> Self {
+    /// Creates a new [`ProverStorage`] instance from the given state and native database handles.
+    pub fn with_db_handles(
+        db: StateDB<SnapshotManager>,
+        native_db: NativeDB<SnapshotManager>,
+    ) -> Self {
         Self {
             db,
             native_db,
+            version: None,
+            read_only: false,
             _phantom_hasher: Default::default(),
         }
     }
 
+    /// Consumes the [`ProverStorage`] and returns the state and native snapshots with all writes made to them.
+    /// Fails if any clones of this storage are still alive.
+    pub fn freeze(self) -> Result<(FrozenDbSnapshot, FrozenDbSnapshot), anyhow::Error> {
+        let ProverStorage { db, native_db, .. } = self;
+        let state_snapshot = db.freeze()?;
+        let native_snapshot = native_db.freeze()?;
+        Ok((state_snapshot, native_snapshot))
+    }
+
     fn read_value(&self, key: &StorageKey) -> Option<StorageValue> {
-        match self
-            .db
-            .get_value_option_by_key(self.db.get_next_version(), key.as_ref())
-        {
+        let version = self.version.unwrap_or_else(|| self.db.get_next_version());
+        match self.db.get_value_option_by_key(version, key.as_ref()) {
             Ok(value) => value.map(Into::into),
             // It is ok to panic here, we assume the db is available and consistent.
             Err(e) => panic!("Unable to read value from db: {e}"),
@@ -66,23 +94,27 @@ impl<S: MerkleProofSpec> ProverStorage<S> {
     }
 }
 
+/// Wraps the database into a snapshot which has no parents, so it reads directly from the database.
+fn orphan_snapshot(db: sov_schema_db::DB) -> DbSnapshot<SnapshotManager> {
+    DbSnapshot::new(
+        0,
+        ReadOnlyLock::new(Arc::new(RwLock::new(SnapshotManager::orphan(db)))),
+    )
+}
+
 pub struct ProverStateUpdate {
     pub(crate) node_batch: NodeBatch,
+    pub(crate) stale_node_indices: StaleNodeIndexBatch,
     pub key_preimages: Vec<(KeyHash, CacheKey)>,
     // pub accessory_update: OrderedReadsAndWrites,
 }
 
 impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
     type Witness = S::Witness;
-    type RuntimeConfig = Config;
     type Proof = jmt::proof::SparseMerkleProof<S::Hasher>;
     type Root = jmt::RootHash;
     type StateUpdate = ProverStateUpdate;
 
-    fn with_config(config: Self::RuntimeConfig) -> Result<Self, anyhow::Error> {
-        Self::with_path(config.path.as_path())
-    }
-
     fn get(&self, key: &StorageKey, witness: &Self::Witness) -> Option<StorageValue> {
         let val = self.read_value(key);
         witness.add_hint(val.clone());
@@ -91,22 +123,52 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
 
     #[cfg(feature = "native")]
     fn get_accessory(&self, key: &StorageKey) -> Option<StorageValue> {
+        // The accessory state isn't versioned, so even a view of a past version reads the
+        // latest values.
         self.native_db
             .get_value_option(key.as_ref())
             .unwrap()
             .map(Into::into)
     }
 
+    fn at_version(&self, version: Version) -> Result<Self, anyhow::Error> {
+        let latest_version = self.latest_version();
+        anyhow::ensure!(
+            version <= latest_version,
+            "Version {version} of the state is not committed yet, the latest version is {latest_version}"
+        );
+        if let Some(pruned_versions) = self.db.get_pruned_versions()? {
+            anyhow::ensure!(
+                !pruned_versions.contains(version),
+                "Version {version} of the state has been pruned"
+            );
+        }
+        Ok(Self {
+            version: Some(version),
+            ..self.clone()
+        })
+    }
+
     fn compute_state_update(
         &self,
         state_accesses: OrderedReadsAndWrites,
         witness: &Self::Witness,
     ) -> Result<(Self::Root, Self::StateUpdate), anyhow::Error> {
+        anyhow::ensure!(
+            self.version.is_none(),
+            "A storage reading version {:?} of the state is read-only",
+            self.version
+        );
+        anyhow::ensure!(
+            !self.read_only,
+            "A storage opened directly on the database is read-only, use a storage manager to commit changes"
+        );
         let latest_version = self.db.get_next_version() - 1;
         let jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&self.db);
 
         // Handle empty jmt
-        // TODO: Fix this before introducing snapshots!
+        // The empty tree is written to the current snapshot,
+        // so it is persisted together with the rest of the changes.
         if jmt.get_root_hash_option(latest_version)?.is_none() {
             assert_eq!(latest_version, 0);
             let empty_batch = Vec::default().into_iter();
@@ -123,16 +185,18 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
             .expect("Previous root hash was just populated");
         witness.add_hint(prev_root.0);
 
-        // For each value that's been read from the tree, read it from the logged JMT to populate hints
+        // For each value that's been read from the tree, read it from the logged JMT and prove
+        // all the reads at once
+        let mut proofs = Vec::with_capacity(state_accesses.ordered_reads.len());
         for (key, read_value) in state_accesses.ordered_reads {
             let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            // TODO: Switch to the batch read API once it becomes available
             let (result, proof) = jmt.get_with_proof(key_hash, latest_version)?;
             if result.as_ref() != read_value.as_ref().map(|f| f.value.as_ref()) {
                 anyhow::bail!("Bug! Incorrect value read from jmt");
             }
-            witness.add_hint(proof);
+            proofs.push((key_hash, proof));
         }
+        witness.add_hint(SparseMerkleMultiProof::from_proofs(proofs)?);
 
         let mut key_preimages = Vec::with_capacity(state_accesses.ordered_writes.len());
 
@@ -160,6 +224,7 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
 
         let state_update = ProverStateUpdate {
             node_batch: tree_update.node_batch,
+            stale_node_indices: tree_update.stale_node_index_batch,
             key_preimages,
         };
 
@@ -167,6 +232,10 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
     }
 
     fn commit(&self, state_update: &Self::StateUpdate, accessory_writes: &OrderedReadsAndWrites) {
+        assert!(
+            !self.read_only,
+            "A storage opened directly on the database is read-only, use a storage manager to commit changes"
+        );
         self.db
             .put_preimages(
                 state_update
@@ -186,14 +255,13 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
             .expect("native db write must succeed");
 
         // Write the state values last, since we base our view of what has been touched
-        // on state. If the node crashes between the `native_db` update and this update,
-        // then the whole `commit` will be re-run later so no data can be lost.
+        // on state: the next version is derived from the latest written node.
+        self.db
+            .put_stale_node_indices(&state_update.stale_node_indices)
+            .expect("db write must succeed");
         self.db
             .write_node_batch(&state_update.node_batch)
             .expect("db write must succeed");
-
-        // Finally, update our in-memory view of the current item numbers
-        self.db.inc_next_version();
     }
 
     fn open_proof(
@@ -207,7 +275,7 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
         Ok((key, value))
     }
 
-    // Based on assumption `validate_and_commit` increments version.
+    // Based on assumption that `commit` writes nodes with the next version.
     fn is_empty(&self) -> bool {
         self.db.get_next_version() <= 1
     }
@@ -215,11 +283,11 @@ impl<S: MerkleProofSpec> Storage for ProverStorage<S> {
 
 impl<S: MerkleProofSpec> NativeStorage for ProverStorage<S> {
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof> {
-        let merkle = JellyfishMerkleTree::<StateDB, S::Hasher>::new(&self.db);
+        let merkle = JellyfishMerkleTree::<StateDB<SnapshotManager>, S::Hasher>::new(&self.db);
         let (val_opt, proof) = merkle
             .get_with_proof(
                 KeyHash::with::<S::Hasher>(key.as_ref()),
-                self.db.get_next_version() - 1,
+                self.latest_version(),
             )
             .unwrap();
         StorageProof {
@@ -230,8 +298,13 @@ impl<S: MerkleProofSpec> NativeStorage for ProverStorage<S> {
     }
 
     fn get_root_hash(&self, version: Version) -> Result<jmt::RootHash, anyhow::Error> {
-        let temp_merkle: JellyfishMerkleTree<'_, StateDB, S::Hasher> =
+        let temp_merkle: JellyfishMerkleTree<'_, StateDB<SnapshotManager>, S::Hasher> =
             JellyfishMerkleTree::new(&self.db);
         temp_merkle.get_root_hash(version)
     }
+
+    fn latest_version(&self) -> Version {
+        self.version
+            .unwrap_or_else(|| self.db.get_next_version() - 1)
+    }
 }
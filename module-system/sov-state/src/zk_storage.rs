@@ -8,7 +8,7 @@ use sov_modules_core::{
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 
-use crate::MerkleProofSpec;
+use crate::{MerkleProofSpec, SparseMerkleMultiProof};
 
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 extern crate risc0_zkvm;
@@ -38,15 +38,10 @@ impl<S: MerkleProofSpec> ZkStorage<S> {
 
 impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
     type Witness = S::Witness;
-    type RuntimeConfig = ();
     type Proof = jmt::proof::SparseMerkleProof<S::Hasher>;
     type Root = jmt::RootHash;
     type StateUpdate = ();
 
-    fn with_config(_config: Self::RuntimeConfig) -> Result<Self, anyhow::Error> {
-        Ok(Self::new())
-    }
-
     fn get(&self, _key: &StorageKey, witness: &Self::Witness) -> Option<StorageValue> {
         witness.get_hint()
     }
@@ -59,20 +54,20 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
     ) -> Result<(Self::Root, Self::StateUpdate), anyhow::Error> {
         let prev_state_root = witness.get_hint();
 
-        // For each value that's been read from the tree, verify the provided smt proof
-        for (key, read_value) in state_accesses.ordered_reads {
-            let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            // TODO: Switch to the batch read API once it becomes available
-            let proof: jmt::proof::SparseMerkleProof<S::Hasher> = witness.get_hint();
-            match read_value {
-                Some(val) => proof.verify_existence(
-                    jmt::RootHash(prev_state_root),
-                    key_hash,
-                    val.value.as_ref(),
-                )?,
-                None => proof.verify_nonexistence(jmt::RootHash(prev_state_root), key_hash)?,
-            }
-        }
+        // Verify the values read from the tree against a single multiproof
+        let proof: SparseMerkleMultiProof = witness.get_hint();
+        proof.verify::<S::Hasher>(
+            jmt::RootHash(prev_state_root),
+            state_accesses
+                .ordered_reads
+                .iter()
+                .map(|(key, read_value)| {
+                    (
+                        KeyHash::with::<S::Hasher>(key.key.as_ref()),
+                        read_value.as_ref().map(|val| val.value()),
+                    )
+                }),
+        )?;
 
         // Compute the jmt update from the write batch
         let batch = state_accesses
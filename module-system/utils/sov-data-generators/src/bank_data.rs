@@ -3,7 +3,7 @@ use std::rc::Rc;
 use sov_bank::{get_token_address, Bank, CallMessage, Coins};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, TxFee};
 use sov_modules_api::utils::generate_address;
 use sov_modules_api::{Context, EncodeCall, Module, PrivateKey, Spec};
 
@@ -177,7 +177,7 @@ impl<C: Context> MessageGenerator for BankMessageGenerator<C> {
         _is_last: bool,
     ) -> sov_modules_api::transaction::Transaction<C> {
         let message = Encoder::encode_call(message);
-        Transaction::<C>::new_signed_tx(sender, message, nonce)
+        Transaction::<C>::new_signed_tx(sender, message, 0, None, TxFee::zero(), nonce)
     }
 }
 
@@ -244,7 +244,14 @@ impl MessageGenerator for BadSerializationBankCallMessages {
             Encoder::encode_call(message)
         };
 
-        Transaction::<DefaultContext>::new_signed_tx(sender, call_data, nonce)
+        Transaction::<DefaultContext>::new_signed_tx(
+            sender,
+            call_data,
+            0,
+            None,
+            TxFee::zero(),
+            nonce,
+        )
     }
 }
 
@@ -296,15 +303,32 @@ impl MessageGenerator for BadSignatureBankCallMessages {
         let call_data = Encoder::encode_call(message);
 
         if is_last {
-            let tx = Transaction::<DefaultContext>::new_signed_tx(sender, call_data.clone(), nonce);
+            let tx = Transaction::<DefaultContext>::new_signed_tx(
+                sender,
+                call_data.clone(),
+                0,
+                None,
+                TxFee::zero(),
+                nonce,
+            );
             Transaction::new(
                 DefaultPrivateKey::generate().pub_key(),
                 call_data,
                 tx.signature().clone(),
+                0,
+                None,
+                TxFee::zero(),
                 nonce,
             )
         } else {
-            Transaction::<DefaultContext>::new_signed_tx(sender, call_data, nonce)
+            Transaction::<DefaultContext>::new_signed_tx(
+                sender,
+                call_data,
+                0,
+                None,
+                TxFee::zero(),
+                nonce,
+            )
         }
     }
 }
@@ -356,6 +380,6 @@ impl MessageGenerator for BadNonceBankCallMessages {
     ) -> Transaction<DefaultContext> {
         let message = Encoder::encode_call(message);
         // hard-coding the nonce to 1000
-        Transaction::<DefaultContext>::new_signed_tx(sender, message, 1000)
+        Transaction::<DefaultContext>::new_signed_tx(sender, message, 0, None, TxFee::zero(), 1000)
     }
 }
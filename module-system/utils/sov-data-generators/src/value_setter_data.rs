@@ -66,6 +66,6 @@ impl<C: Context> MessageGenerator for ValueSetterMessages<C> {
         _is_last: bool,
     ) -> Transaction<C> {
         let message = Encoder::encode_call(message);
-        Transaction::<C>::new_signed_tx(sender, message, nonce)
+        Transaction::<C>::new_signed_tx(sender, message, 0, None, TxFee::zero(), nonce)
     }
 }
@@ -4,10 +4,9 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
-#[cfg(feature = "native")]
-use crate::stf::Event;
-use crate::stf::EventKey;
+use crate::stf::{Event, EventKey};
 
 /// A struct containing enough information to uniquely specify single batch.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -100,6 +99,66 @@ pub enum EventGroupIdentifier {
     Key(Vec<u8>),
 }
 
+/// A filter selecting the events returned by
+/// [`LedgerRpcProvider::get_events_by_filter`]. Fields which are not set match
+/// all events.
+///
+/// The `module` and `kind` fields select module events by the two parts of
+/// their key, see [`EventKey::module_event`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFilter {
+    /// The address of the module which emitted the events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<Vec<u8>>,
+    /// The kind of the events, which is the index of their variant in the
+    /// event enum of their module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<u8>,
+    /// A prefix of the event key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<Vec<u8>>,
+    /// The range of slots in which the events were emitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot_range: Option<core::ops::Range<u64>>,
+    /// The range of transactions which emitted the events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_range: Option<core::ops::Range<u64>>,
+}
+
+impl EventFilter {
+    /// Returns `true` if an event with the given key passes the `module`,
+    /// `kind` and `key_prefix` filters. Slot and transaction ranges are not
+    /// checked.
+    pub fn matches_key(&self, key: &EventKey) -> bool {
+        if self.module.is_some() || self.kind.is_some() {
+            let Some((module, kind)) = key.module_and_kind() else {
+                return false;
+            };
+            if matches!(&self.module, Some(expected) if expected.as_slice() != module) {
+                return false;
+            }
+            if matches!(self.kind, Some(expected) if expected != kind) {
+                return false;
+            }
+        }
+        match &self.key_prefix {
+            Some(prefix) => key.inner().starts_with(prefix),
+            None => true,
+        }
+    }
+}
+
+/// A position in the event history, used to page through the results of
+/// [`LedgerRpcProvider::get_events_by_filter`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    /// The number of the transaction from which to resume.
+    pub tx_number: u64,
+    /// The number of the first event which may be returned.
+    pub event_number: u64,
+}
+
 /// An identifier that specifies a single slot.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -175,11 +234,36 @@ pub struct TxResponse<Tx> {
     /// The transaction body, if stored by the rollup.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<Vec<u8>>,
+    /// The events emitted by this transaction, if the [`QueryMode`] of the request is `Full`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<Event>>,
     /// The custom receipt specified by the rollup. This typically contains
     /// information about the outcome of the transaction.
     pub custom_receipt: Tx,
 }
 
+/// An event returned by [`LedgerRpcProvider::get_events_by_filter`], along
+/// with its position in the ledger.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct EventResponse {
+    /// The event number.
+    pub number: u64,
+    /// The number of the transaction which emitted the event.
+    pub tx_number: u64,
+    /// The event itself.
+    pub event: Event,
+}
+
+/// A page of events matching an [`EventFilter`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct EventsPage {
+    /// The matching events, in the order in which they were emitted.
+    pub events: Vec<EventResponse>,
+    /// The cursor from which to request the next page, if this page was
+    /// truncated. `None` if all matching events have been returned.
+    pub next_cursor: Option<EventCursor>,
+}
+
 /// An RPC response which might contain a full item or just its hash.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -190,6 +274,33 @@ pub enum ItemOrHash<T> {
     Full(T),
 }
 
+/// The status of the proof of a single slot.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofStatus {
+    /// The slot is waiting to be proven.
+    Pending,
+    /// The proof of the slot is being generated.
+    Proving,
+    /// The slot has been proven, but the prover produced no proof to be posted
+    /// on the DA layer (for example, because proving is simulated).
+    Proved,
+    /// Proof generation failed. The slot is proven again when the node restarts,
+    /// unless proving it has failed too many times.
+    Failed {
+        /// A description of the error.
+        reason: String,
+        /// Whether proving the slot has been given up on.
+        abandoned: bool,
+    },
+    /// The proof has been posted to the DA layer.
+    Submitted {
+        /// The hex encoded id of the DA transaction which carries the proof.
+        #[serde(with = "utils::rpc_hex")]
+        da_transaction_id: Vec<u8>,
+    },
+}
+
 /// A LedgerRpcProvider provides a way to query the ledger for information about slots, batches, transactions, and events.
 #[cfg(feature = "native")]
 pub trait LedgerRpcProvider {
@@ -226,6 +337,16 @@ pub trait LedgerRpcProvider {
         event_ids: &[EventIdentifier],
     ) -> Result<Vec<Option<Event>>, anyhow::Error>;
 
+    /// Get the events matching `filter`, in the order in which they were emitted.
+    /// Starts from `cursor` if set, and returns at most `limit` events along
+    /// with the cursor of the next page.
+    fn get_events_by_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventCursor>,
+        limit: u64,
+    ) -> Result<EventsPage, anyhow::Error>;
+
     /// Get a single slot by hash.
     fn get_slot_by_hash<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
@@ -301,8 +422,37 @@ pub trait LedgerRpcProvider {
         query_mode: QueryMode,
     ) -> Result<Vec<Option<TxResponse<T>>>, anyhow::Error>;
 
-    /// Get a notification each time a slot is processed
+    /// Get the status of the proof of a single slot, if the slot has been submitted for proving.
+    fn get_proof_status(
+        &self,
+        slot_id: &SlotIdentifier,
+    ) -> Result<Option<ProofStatus>, anyhow::Error>;
+
+    /// Get a notification each time a slot is finalized on the DA layer
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
+
+    /// Get a slot which has been executed, but is not finalized on the DA layer yet.
+    /// Executed slots are served by the other queries as well, but they are reverted
+    /// if the DA layer reorganizes.
+    fn get_executed_slot<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        number: u64,
+        query_mode: QueryMode,
+    ) -> Result<Option<SlotResponse<B, T>>, anyhow::Error>;
+
+    /// Get a transaction from a slot which has been executed, but is not finalized
+    /// on the DA layer yet.
+    fn get_executed_tx_by_hash<T: DeserializeOwned>(
+        &self,
+        hash: &[u8; 32],
+        query_mode: QueryMode,
+    ) -> Result<Option<TxResponse<T>>, anyhow::Error>;
+
+    /// Get a notification each time a slot is executed, before it is finalized. A slot
+    /// number is notified again if the slot is re-executed after a DA reorganization.
+    fn subscribe_executed_slots(
+        &self,
+    ) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 }
 
 /// JSON-RPC -related utilities. Occasionally useful but unimportant for most
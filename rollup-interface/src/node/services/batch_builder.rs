@@ -1,14 +1,160 @@
 //! This module defines the trait that is used to build batches of transactions.
 
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::maybestd::boxed::Box;
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
 
+/// A raw transaction along with its hash, as reported by the rollup once it has been executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxWithHash {
+    /// The serialized transaction.
+    pub raw_tx: Vec<u8>,
+    /// The hash of the transaction.
+    pub hash: [u8; 32],
+}
+
 /// BlockBuilder trait is responsible for managing mempool and building batches.
 pub trait BatchBuilder {
-    /// Accept a new transaction.
+    /// Accept a new transaction and return its hash.
     /// Can return error if transaction is invalid or mempool is full.
-    fn accept_tx(&mut self, tx: Vec<u8>) -> anyhow::Result<()>;
+    fn accept_tx(&mut self, tx: Vec<u8>) -> anyhow::Result<[u8; 32]>;
 
     /// Builds a new batch out of transactions in mempool.
     /// Logic of which transactions and how many of them is included in batch is up to implementation.
-    fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>>;
+    fn get_next_blob(&mut self) -> anyhow::Result<Vec<TxWithHash>>;
+
+    /// Returns the number and the total size of the transactions waiting in the mempool.
+    fn mempool_size(&self) -> MempoolSize;
+
+    /// Returns the transactions waiting in the mempool.
+    fn pending_txs(&self) -> Vec<PendingTx>;
+
+    /// Returns the transactions dropped from the mempool since the last call,
+    /// whether they were evicted, replaced, or failed to be included in a batch.
+    fn take_dropped_txs(&mut self) -> Vec<DroppedTx>;
+
+    /// Takes back transactions returned by [`BatchBuilder::get_next_blob`] which won't be
+    /// included in the rollup, because their blob failed to be sent or was dropped after too many
+    /// resubmissions. Lets the batch builder release the nonces they used.
+    fn revert_batched_txs(&mut self, _txs: &[TxWithHash]) {}
+}
+
+/// Lets the batch builder of a sequencer be chosen at runtime.
+impl<B: BatchBuilder + ?Sized> BatchBuilder for Box<B> {
+    fn accept_tx(&mut self, tx: Vec<u8>) -> anyhow::Result<[u8; 32]> {
+        (**self).accept_tx(tx)
+    }
+
+    fn get_next_blob(&mut self) -> anyhow::Result<Vec<TxWithHash>> {
+        (**self).get_next_blob()
+    }
+
+    fn mempool_size(&self) -> MempoolSize {
+        (**self).mempool_size()
+    }
+
+    fn pending_txs(&self) -> Vec<PendingTx> {
+        (**self).pending_txs()
+    }
+
+    fn take_dropped_txs(&mut self) -> Vec<DroppedTx> {
+        (**self).take_dropped_txs()
+    }
+
+    fn revert_batched_txs(&mut self, txs: &[TxWithHash]) {
+        (**self).revert_batched_txs(txs)
+    }
+}
+
+/// A transaction waiting in a mempool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTx {
+    /// The hash of the transaction.
+    #[serde(with = "crate::rpc::utils::rpc_hex")]
+    pub hash: [u8; 32],
+    /// The address of the sender, as displayed by the rollup.
+    pub sender: String,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The size of the serialized transaction, in bytes.
+    pub size: usize,
+}
+
+/// A transaction which has left a mempool without being included in a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedTx {
+    /// The hash of the transaction.
+    pub hash: [u8; 32],
+    /// Why the transaction has been dropped.
+    pub reason: String,
+}
+
+/// The number and the total size of the transactions waiting in a mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MempoolSize {
+    /// The number of transactions.
+    pub txs: usize,
+    /// The total size of the transactions, in bytes.
+    pub bytes: usize,
+}
+
+/// When a sequencer publishes the transactions waiting in its mempool.
+/// A batch is published as soon as any of the conditions is met.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchProductionConfig {
+    /// Publish a batch once this many transactions are waiting in the mempool.
+    pub max_pending_txs: usize,
+    /// Publish a batch once the transactions waiting in the mempool take up this many bytes.
+    pub max_pending_bytes: usize,
+    /// The longest time in milliseconds between two batches while the mempool is not empty.
+    pub max_batch_interval_ms: u64,
+    /// Publish a batch on every new finalized DA block.
+    pub publish_on_da_head: bool,
+    /// How long in milliseconds a published blob can go unobserved in a committed slot
+    /// before it is resubmitted. Doubles after every resubmission.
+    /// Only used when the mempool is persisted.
+    pub blob_resubmission_backoff_ms: u64,
+    /// How many times a blob is resubmitted before its transactions are dropped.
+    pub max_blob_resubmissions: u32,
+}
+
+impl Default for BatchProductionConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_txs: 100,
+            max_pending_bytes: 100 * 1024,
+            max_batch_interval_ms: 5_000,
+            publish_on_da_head: true,
+            blob_resubmission_backoff_ms: 30_000,
+            max_blob_resubmissions: 5,
+        }
+    }
+}
+
+impl BatchProductionConfig {
+    /// Returns true if a mempool of the given size has reached one of the thresholds.
+    pub fn is_batch_ready(&self, mempool_size: MempoolSize) -> bool {
+        mempool_size.txs > 0
+            && (mempool_size.txs >= self.max_pending_txs
+                || mempool_size.bytes >= self.max_pending_bytes)
+    }
+
+    /// The longest time between two batches while the mempool is not empty.
+    pub fn max_batch_interval(&self) -> Duration {
+        Duration::from_millis(self.max_batch_interval_ms)
+    }
+
+    /// How long to wait for a blob which has been submitted `attempts` times to be observed
+    /// in a committed slot, before resubmitting it.
+    pub fn blob_resubmission_backoff(&self, attempts: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempts.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(self.blob_resubmission_backoff_ms.saturating_mul(factor))
+    }
 }
@@ -31,12 +31,19 @@ pub trait DaService: Send + Sync + 'static {
     >;
 
     /// Type that allow to consume [`futures::Stream`] of BlockHeaders.
-    type HeaderStream: futures::Stream<
-        Item = Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error>,
-    >;
+    type HeaderStream: futures::Stream<Item = Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error>>
+        + Send;
 
     /// A transaction ID, used to identify the transaction in the DA layer.
-    type TransactionId: PartialEq + Eq + PartialOrd + Ord + core::hash::Hash;
+    type TransactionId: PartialEq
+        + Eq
+        + PartialOrd
+        + Ord
+        + core::hash::Hash
+        + Clone
+        + Serialize
+        + Send
+        + Sync;
 
     /// The error type for fallible methods.
     type Error: core::fmt::Debug + Send + Sync + core::fmt::Display;
@@ -165,7 +165,7 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
 }
 
 /// A key-value pair representing a change to the rollup state
-#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
 pub struct Event {
     key: EventKey,
@@ -181,6 +181,16 @@ impl Event {
         }
     }
 
+    /// Create a new event with the given raw key and value bytes. Events
+    /// emitted by modules use the address of the emitting module as the key and
+    /// the borsh-encoded module event as the value.
+    pub fn from_bytes(key: Vec<u8>, value: Vec<u8>) -> Self {
+        Self {
+            key: EventKey(key),
+            value: EventValue(value),
+        }
+    }
+
     /// Get the event key
     pub fn key(&self) -> &EventKey {
         &self.key
@@ -214,6 +224,29 @@ impl EventKey {
     pub fn inner(&self) -> &Vec<u8> {
         &self.0
     }
+
+    /// Builds the key of an event of kind `kind` emitted by a module: the
+    /// address of the module followed by the kind.
+    pub fn module_event(module: &[u8], kind: u8) -> Self {
+        let mut key = Vec::with_capacity(module.len() + 1);
+        key.extend_from_slice(module);
+        key.push(kind);
+        Self(key)
+    }
+
+    /// Splits the key of a module event, built with [`EventKey::module_event`],
+    /// into the address of the module and the kind of the event. Returns
+    /// [`None`] if the key is empty.
+    pub fn module_and_kind(&self) -> Option<(&[u8], u8)> {
+        let (kind, module) = self.0.split_last()?;
+        Some((module, *kind))
+    }
+}
+
+impl From<Vec<u8>> for EventKey {
+    fn from(key: Vec<u8>) -> Self {
+        Self(key)
+    }
 }
 
 /// The value of an event. This is a wrapper around a `Vec<u8>`.
@@ -18,7 +18,7 @@ use crate::da::DaSpec;
 use crate::RollupAddress;
 
 /// The ZK proof generated by the [`ZkvmHost::run`] method.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum Proof {
     /// Proof generation was skipped.
     Empty,
@@ -26,6 +26,79 @@ pub enum Proof {
     Data(Vec<u8>),
 }
 
+/// The prefix of every [`ProofEnvelope`] blob posted to the DA layer.
+///
+/// Proofs share the rollup's namespace with batches of transactions, so the prefix
+/// lets the state transition function skip proof blobs instead of treating them as malformed batches.
+pub const PROOF_BLOB_PREFIX: &[u8] = b"sov-proof";
+
+/// Returns `true` if the provided DA blob contains a [`ProofEnvelope`].
+pub fn is_proof_blob(blob: &[u8]) -> bool {
+    blob.starts_with(PROOF_BLOB_PREFIX)
+}
+
+/// A versioned envelope around a [`Proof`] which is posted to the DA layer.
+/// Besides the proof itself, the envelope carries everything needed to interpret it:
+/// the public outputs of the proven state transition and the commitment to the zkVM program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofEnvelope<Output, CodeCommitment> {
+    /// The first version of the envelope.
+    V1 {
+        /// The public outputs of the proven state transition.
+        public_outputs: Output,
+        /// The commitment to the zkVM program which produced the proof.
+        code_commitment: CodeCommitment,
+        /// The proof of the state transition.
+        proof: Proof,
+    },
+}
+
+#[cfg(feature = "native")]
+impl<Output, CodeCommitment> ProofEnvelope<Output, CodeCommitment>
+where
+    Output: Serialize + DeserializeOwned,
+    CodeCommitment: Serialize + DeserializeOwned,
+{
+    /// Serializes the envelope into a blob which can be posted to the DA layer.
+    pub fn encode(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let mut blob = PROOF_BLOB_PREFIX.to_vec();
+        bincode::serialize_into(&mut blob, self)?;
+        Ok(blob)
+    }
+
+    /// Deserializes an envelope from a DA blob.
+    /// Returns `None` if the blob does not contain a proof.
+    pub fn decode(blob: &[u8]) -> Result<Option<Self>, anyhow::Error> {
+        match blob.strip_prefix(PROOF_BLOB_PREFIX) {
+            Some(envelope) => Ok(Some(bincode::deserialize(envelope)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the proof contained in the envelope.
+    pub fn proof(&self) -> &Proof {
+        match self {
+            ProofEnvelope::V1 { proof, .. } => proof,
+        }
+    }
+
+    /// Returns the public outputs of the proven state transition.
+    pub fn public_outputs(&self) -> &Output {
+        match self {
+            ProofEnvelope::V1 { public_outputs, .. } => public_outputs,
+        }
+    }
+
+    /// Returns the commitment to the zkVM program which produced the proof.
+    pub fn code_commitment(&self) -> &CodeCommitment {
+        match self {
+            ProofEnvelope::V1 {
+                code_commitment, ..
+            } => code_commitment,
+        }
+    }
+}
+
 /// A trait implemented by the prover ("host") of a zkVM program.
 pub trait ZkvmHost: Zkvm + Clone {
     /// The associated guest type
@@ -67,9 +140,15 @@ pub trait Zkvm: Send + Sync {
         code_commitment: &Self::CodeCommitment,
     ) -> Result<&'a [u8], Self::Error>;
 
+    /// Same as [`verify`](Zkvm::verify), except that the output is deserialized
+    /// using the serialization format of the zkVM.
+    fn verify_and_deserialize_output<T: DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<T, Self::Error>;
+
     /// Same as [`verify`](Zkvm::verify), except that instead of returning the output
     /// as a serialized array, it returns a state transition structure.
-    /// TODO: specify a deserializer for the output
     fn verify_and_extract_output<
         Add: RollupAddress,
         Da: DaSpec,
@@ -120,7 +199,9 @@ pub struct StateTransition<Da: DaSpec, Address, Root> {
     pub initial_state_root: Root,
     /// The state of the rollup after the transition
     pub final_state_root: Root,
-    /// The slot hash of the state transition
+    /// The hash of the first slot covered by the state transition
+    pub initial_slot_hash: Da::SlotHash,
+    /// The hash of the last slot covered by the state transition
     pub slot_hash: Da::SlotHash,
 
     /// Rewarded address: the account that has produced the transition proof.
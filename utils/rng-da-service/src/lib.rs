@@ -10,7 +10,7 @@ use sov_mock_da::{
 };
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, TxFee};
 use sov_modules_api::{Address, AddressBech32, EncodeCall, PrivateKey, PublicKey, Spec};
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, DaVerifier};
 use sov_rollup_interface::services::da::{DaService, SlotData};
@@ -194,8 +194,14 @@ pub fn generate_transfers(n: usize, start_nonce: u64) -> Vec<u8> {
             <Runtime<DefaultContext, RngDaSpec> as EncodeCall<Bank<DefaultContext>>>::encode_call(
                 msg,
             );
-        let tx =
-            Transaction::<DefaultContext>::new_signed_tx(&pk, enc_msg, start_nonce + (i as u64));
+        let tx = Transaction::<DefaultContext>::new_signed_tx(
+            &pk,
+            enc_msg,
+            0,
+            None,
+            TxFee::zero(),
+            start_nonce + (i as u64),
+        );
         let ser_tx = tx.try_to_vec().unwrap();
         message_vec.push(ser_tx)
     }
@@ -216,7 +222,14 @@ pub fn generate_create(start_nonce: u64) -> Vec<u8> {
         };
     let enc_msg =
         <Runtime<DefaultContext, RngDaSpec> as EncodeCall<Bank<DefaultContext>>>::encode_call(msg);
-    let tx = Transaction::<DefaultContext>::new_signed_tx(&pk, enc_msg, start_nonce);
+    let tx = Transaction::<DefaultContext>::new_signed_tx(
+        &pk,
+        enc_msg,
+        0,
+        None,
+        TxFee::zero(),
+        start_nonce,
+    );
     let ser_tx = tx.try_to_vec().unwrap();
     message_vec.push(ser_tx);
     message_vec.try_to_vec().unwrap()